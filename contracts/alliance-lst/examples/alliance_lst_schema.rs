@@ -1,10 +1,11 @@
 use cosmwasm_schema::write_api;
-use eris::alliance_lst::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use eris::alliance_lst::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 
 fn main() {
     write_api! {
         instantiate: InstantiateMsg,
         query: QueryMsg,
-        execute: ExecuteMsg
+        execute: ExecuteMsg,
+        migrate: MigrateMsg
     }
 }
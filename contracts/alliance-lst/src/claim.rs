@@ -1,13 +1,19 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{
-    to_json_binary, CosmosMsg, DepsMut, Env, MessageInfo, Response, StdResult, WasmMsg,
+    to_json_binary, Addr, Coin, CosmosMsg, DepsMut, Env, Event, MessageInfo, QuerierWrapper,
+    Response, StdResult, Uint128, WasmMsg,
 };
-use eris::hub::ClaimType;
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg};
+use eris::hub::{CallbackMsg, ClaimType};
+use eris::CustomResponse;
 use eris_chain_adapter::types::{CustomMsgType, CustomQueryType};
+use itertools::Itertools;
 
 use crate::{
     error::{ContractError, ContractResult},
+    execute::check_received_coin_msg,
     state::State,
+    types::{Coins, SendFee},
 };
 
 #[cw_serde]
@@ -25,11 +31,33 @@ impl ClaimExecuteMsg {
     }
 }
 
+/// Claim message of cw20-merkle-airdrop compatible contracts
+#[cw_serde]
+pub enum MerkleAirdropExecuteMsg {
+    Claim {
+        stage: u8,
+        amount: Uint128,
+        proof: Vec<String>,
+    },
+}
+
+impl MerkleAirdropExecuteMsg {
+    pub fn into_msg(&self, contract_addr: String) -> StdResult<CosmosMsg<CustomMsgType>> {
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr,
+            msg: to_json_binary(&self)?,
+            funds: vec![],
+        }))
+    }
+}
+
 pub fn exec_claim(
     deps: DepsMut<CustomQueryType>,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     claims: Vec<ClaimType>,
+    route_denoms: Option<Vec<String>>,
+    route_cw20s: Option<Vec<String>>,
 ) -> ContractResult {
     let state = State::default();
     state.assert_owner(deps.storage, &info.sender)?;
@@ -38,6 +66,8 @@ pub fn exec_claim(
         return Err(ContractError::NoClaimsProvided {});
     }
 
+    let claim_contracts = state.claim_contracts.may_load(deps.storage)?.unwrap_or_default();
+
     let claim_msgs = claims
         .into_iter()
         .map(|claim| {
@@ -46,9 +76,174 @@ pub fn exec_claim(
                     deps.api.addr_validate(&contract_addr)?;
                     ClaimExecuteMsg::Claim {}.into_msg(contract_addr)?
                 },
+                ClaimType::MerkleAirdrop {
+                    contract,
+                    stage,
+                    amount,
+                    proof,
+                } => {
+                    deps.api.addr_validate(&contract)?;
+                    MerkleAirdropExecuteMsg::Claim {
+                        stage,
+                        amount,
+                        proof,
+                    }
+                    .into_msg(contract)?
+                },
+                ClaimType::Custom {
+                    contract,
+                    msg,
+                } => {
+                    if !claim_contracts.contains(&deps.api.addr_validate(&contract)?) {
+                        return Err(ContractError::ClaimContractNotWhitelisted(contract));
+                    }
+                    CosmosMsg::Wasm(WasmMsg::Execute {
+                        contract_addr: contract,
+                        msg,
+                        funds: vec![],
+                    })
+                },
             })
         })
-        .collect::<StdResult<Vec<CosmosMsg<CustomMsgType>>>>()?;
+        .collect::<Result<Vec<CosmosMsg<CustomMsgType>>, ContractError>>()?;
+
+    // snapshot the balances, so that only the claimed amounts are routed afterwards
+    let snapshots = route_denoms
+        .unwrap_or_default()
+        .into_iter()
+        .unique()
+        .map(|denom| deps.querier.query_balance(env.contract.address.to_string(), denom))
+        .collect::<StdResult<Vec<Coin>>>()?;
+    let cw20_snapshots = route_cw20s
+        .unwrap_or_default()
+        .into_iter()
+        .unique()
+        .map(|token| {
+            let token = deps.api.addr_validate(&token)?;
+            let balance = query_cw20_balance(&deps.querier, &token, &env.contract.address)?;
+            Ok((token, balance))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut response = Response::new().add_messages(claim_msgs);
+    if !snapshots.is_empty() || !cw20_snapshots.is_empty() {
+        response = response.add_callback(
+            &env,
+            CallbackMsg::RouteClaimed {
+                snapshots,
+                cw20_snapshots,
+            },
+        )?;
+    }
+
+    Ok(response.add_attribute("action", "erishub/exec_claim"))
+}
+
+fn query_cw20_balance(
+    querier: &QuerierWrapper<CustomQueryType>,
+    token: &Addr,
+    address: &Addr,
+) -> StdResult<Uint128> {
+    let res: BalanceResponse = querier.query_wasm_smart(
+        token,
+        &Cw20QueryMsg::Balance {
+            address: address.to_string(),
+        },
+    )?;
+    Ok(res.balance)
+}
+
+/// Compounds claimed utoken, stake and reward tokens. Other tokens, including all cw20 tokens, can't
+/// be compounded and are sent to the protocol fee contract.
+pub fn route_claimed(
+    deps: DepsMut<CustomQueryType>,
+    env: Env,
+    snapshots: Vec<Coin>,
+    cw20_snapshots: Vec<(Addr, Uint128)>,
+) -> ContractResult {
+    let state = State::default();
+    let stake = state.stake_token.load(deps.storage)?;
+    let whale_denom = state.whale_denom.load(deps.storage)?;
+    let fee_config = state.fee_config.load(deps.storage)?;
+
+    let mut unlocked_coins = Coins(vec![]);
+    let mut received_rewards = false;
+    let mut fee_msgs: Vec<CosmosMsg<CustomMsgType>> = vec![];
+    let mut event = Event::new("erishub/claimed");
+
+    for snapshot in snapshots {
+        let balance = deps
+            .querier
+            .query_balance(env.contract.address.to_string(), snapshot.denom.clone())?
+            .amount;
+        let received = balance.saturating_sub(snapshot.amount);
+        if received.is_zero() {
+            continue;
+        }
+        let received = Coin::new(received.u128(), snapshot.denom);
+
+        if received.denom == stake.utoken || received.denom == stake.denom {
+            event = event.add_attribute("compounded", received.to_string());
+            unlocked_coins.add(&received)?;
+        } else if received.denom == whale_denom {
+            event = event.add_attribute("compounded", received.to_string());
+            received_rewards = true;
+        } else {
+            event = event.add_attribute("protocol_fee", received.to_string());
+            fee_msgs.push(
+                SendFee::new(
+                    fee_config.protocol_fee_contract.clone(),
+                    received.amount.u128(),
+                    received.denom,
+                )
+                .to_cosmos_msg(),
+            );
+        }
+    }
+
+    for (token, snapshot) in cw20_snapshots {
+        let balance = query_cw20_balance(&deps.querier, &token, &env.contract.address)?;
+        let received = balance.saturating_sub(snapshot);
+        if received.is_zero() {
+            continue;
+        }
+
+        event = event.add_attribute("protocol_fee", format!("{0}{1}", received, token));
+        fee_msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: token.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: fee_config.protocol_fee_contract.to_string(),
+                amount: received,
+            })?,
+            funds: vec![],
+        }));
+    }
+
+    let compound = received_rewards || !unlocked_coins.0.is_empty();
+    if !unlocked_coins.0.is_empty() {
+        state.unlocked_coins.update(deps.storage, |coins| -> StdResult<_> {
+            let mut coins = Coins(coins);
+            coins.add_many(&unlocked_coins)?;
+            Ok(coins.0)
+        })?;
+    }
+
+    let mut response = Response::new().add_messages(fee_msgs);
+    if received_rewards {
+        // same as harvesting: half of the rewards are swapped and provided as liquidity
+        response = response
+            .add_callback(&env, CallbackMsg::HalfSwapReward {})?
+            .add_callback(&env, CallbackMsg::ProvideLiquidity {})?
+            .add_message(check_received_coin_msg(&deps, &env, stake, None)?);
+    }
+    if compound {
+        response = response.add_callback(
+            &env,
+            CallbackMsg::Reinvest {
+                skip_fee: false,
+            },
+        )?;
+    }
 
-    Ok(Response::new().add_messages(claim_msgs).add_attribute("action", "erishub/exec_claim"))
+    Ok(response.add_event(event).add_attribute("action", "erishub/route_claimed"))
 }
@@ -1,8 +1,20 @@
 use cosmwasm_std::Decimal;
 
-pub const CONTRACT_NAME: &str = "eris-staking-hub-tokenfactory";
+pub const CONTRACT_NAME: &str = "eris-alliance-lst";
+/// Names that were stored by previous versions of the contract (migrate used the hub name)
+pub const LEGACY_CONTRACT_NAMES: &[&str] = &["eris-staking-hub-tokenfactory"];
 pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 pub const DAY: u64 = 24 * 60 * 60;
+/// Max amount of concurrent unbonding entries per delegator-validator pair (cosmos-sdk default)
+pub const MAX_UNBONDING_ENTRIES: usize = 7;
+/// Default allowed deviation from the delegation goal for operator provided undelegations
+pub const DEFAULT_UNDELEGATION_TOLERANCE_BPS: u16 = 500;
+/// Default delay until proposed config changes can be executed
+pub const DEFAULT_TIMELOCK_DELAY: u64 = 3 * DAY;
+/// Max fee for unbonding through a priority batch
+pub const MAX_PRIORITY_UNBOND_FEE_BPS: u16 = 1000;
+/// Unbonding entries per validator that priority unbonds leave free for the regular batches
+pub const PRIORITY_UNBOND_RESERVED_ENTRIES: usize = 1;
 
 pub fn get_reward_fee_cap() -> Decimal {
     // 10% max reward fee
@@ -1,17 +1,15 @@
 use cosmwasm_std::{
-    entry_point, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+    entry_point, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, StdResult, Uint128,
 };
-use cw2::set_contract_version;
 
-use eris::alliance_lst::{ExecuteMsg, InstantiateMsg, QueryMsg};
-use eris::hub::{CallbackMsg, MigrateMsg};
+use eris::alliance_lst::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use eris::hub::CallbackMsg;
 use eris_chain_adapter::types::CustomQueryType;
 
-use crate::claim::exec_claim;
-use crate::constants::{CONTRACT_NAME, CONTRACT_VERSION};
+use crate::claim::{exec_claim, route_claimed};
 use crate::error::{ContractError, ContractResult};
 use crate::state::State;
-use crate::{execute, queries};
+use crate::{execute, gov, migrations, queries};
 
 #[entry_point]
 pub fn instantiate(
@@ -61,6 +59,7 @@ pub fn execute(
             stages,
         } => execute::harvest(deps, env, validators, withdrawals, stages, info.sender),
         ExecuteMsg::TuneDelegations {} => execute::tune_delegations(deps, env, info.sender),
+        ExecuteMsg::FundTuneTip {} => execute::fund_tune_tip(deps, info.funds),
         ExecuteMsg::Rebalance {
             min_redelegation,
         } => execute::rebalance(deps, env, info.sender, min_redelegation),
@@ -74,11 +73,20 @@ pub fn execute(
         ExecuteMsg::SubmitBatch {
             undelegations,
         } => execute::submit_batch(deps, env, info.sender, undelegations),
+        ExecuteMsg::Vote {
+            proposal_id,
+            vote,
+        } => gov::vote(deps, env, info, proposal_id, vote),
+        ExecuteMsg::VoteWeighted {
+            proposal_id,
+            votes,
+        } => gov::vote_weighted(deps, env, info, proposal_id, votes),
         ExecuteMsg::Callback(callback_msg) => callback(deps, env, info, callback_msg),
         ExecuteMsg::UpdateConfig {
             protocol_fee_contract,
             protocol_reward_fee,
             operator,
+            vote_operator,
             stages_preset,
             allow_donations,
             delegation_strategy,
@@ -90,12 +98,21 @@ pub fn execute(
             whale_denom,
             btc_denom,
             whale_btc_pool,
+            undelegation_tolerance_bps,
+            guardian,
+            timelock_delay,
+            priority_unbond_fee_bps,
+            priority_unbond_min_fee,
+            claim_contracts,
+            tune_tip,
         } => execute::update_config(
             deps,
+            env,
             info.sender,
             protocol_fee_contract,
             protocol_reward_fee,
             operator,
+            vote_operator,
             stages_preset,
             withdrawals_preset,
             allow_donations,
@@ -107,36 +124,72 @@ pub fn execute(
             whale_denom,
             btc_denom,
             whale_btc_pool,
+            undelegation_tolerance_bps,
+            guardian,
+            timelock_delay,
+            priority_unbond_fee_bps,
+            priority_unbond_min_fee,
+            claim_contracts,
+            tune_tip,
         ),
+        ExecuteMsg::UpdateDenomMetadata {
+            metadata,
+        } => execute::update_denom_metadata(deps, env, info.sender, metadata),
+        ExecuteMsg::ExecuteConfigChange {
+            id,
+        } => execute::execute_config_change(deps, env, info.sender, id),
+        ExecuteMsg::VetoConfigChange {
+            id,
+        } => execute::veto_config_change(deps, info.sender, id),
         ExecuteMsg::QueueUnbond {
             receiver,
         } => {
-            let state = State::default();
-            let stake_token = state.stake_token.load(deps.storage)?;
-
-            if info.funds.len() != 1 {
-                return Err(ContractError::ExpectingSingleCoin {});
-            }
-
-            if info.funds[0].denom != stake_token.denom {
-                return Err(ContractError::ExpectingAllianceStakeToken(
-                    info.funds[0].denom.to_string(),
-                ));
-            }
-
+            let ustake_amount = received_stake_token(deps.as_ref(), &info)?;
             execute::queue_unbond(
                 deps,
                 env,
                 api.addr_validate(&receiver.unwrap_or_else(|| info.sender.to_string()))?,
-                info.funds[0].amount,
+                ustake_amount,
+            )
+        },
+        ExecuteMsg::PriorityUnbond {
+            receiver,
+        } => {
+            let ustake_amount = received_stake_token(deps.as_ref(), &info)?;
+            execute::priority_unbond(
+                deps,
+                env,
+                api.addr_validate(&receiver.unwrap_or_else(|| info.sender.to_string()))?,
+                ustake_amount,
             )
         },
         ExecuteMsg::Claim {
             claims,
-        } => exec_claim(deps, env, info, claims),
+            route_denoms,
+            route_cw20s,
+        } => exec_claim(deps, env, info, claims, route_denoms, route_cw20s),
     }
 }
 
+/// Returns the amount of stake tokens sent with the message, which must be the only funds
+fn received_stake_token(
+    deps: Deps<CustomQueryType>,
+    info: &MessageInfo,
+) -> Result<Uint128, ContractError> {
+    let state = State::default();
+    let stake_token = state.stake_token.load(deps.storage)?;
+
+    if info.funds.len() != 1 {
+        return Err(ContractError::ExpectingSingleCoin {});
+    }
+
+    if info.funds[0].denom != stake_token.denom {
+        return Err(ContractError::ExpectingAllianceStakeToken(info.funds[0].denom.to_string()));
+    }
+
+    Ok(info.funds[0].amount)
+}
+
 fn callback(
     deps: DepsMut<CustomQueryType>,
     env: Env,
@@ -164,6 +217,11 @@ fn callback(
         } => execute::callback_received_coins(deps, env, snapshot, snapshot_stake),
         CallbackMsg::ProvideLiquidity {} => execute::provide_liquidity_msg(&deps, &env),
         CallbackMsg::HalfSwapReward {} => execute::half_swap_reward_msg(&deps, &env),
+        CallbackMsg::RouteClaimed {
+            snapshots,
+            cw20_snapshots,
+        } => route_claimed(deps, env, snapshots, cw20_snapshots),
+        CallbackMsg::TuneDelegations {} => execute::callback_tune_delegations(deps, env),
     }
 }
 
@@ -213,14 +271,19 @@ pub fn query(deps: Deps<CustomQueryType>, env: Env, msg: QueryMsg) -> StdResult<
         QueryMsg::SimulateUndelegations {} => {
             to_json_binary(&queries::simulate_undelegations(deps, env)?)
         },
+        QueryMsg::UnbondingEntries {} => to_json_binary(&queries::unbonding_entries(deps, env)?),
+        QueryMsg::PendingConfigChanges {
+            start_after,
+            limit,
+        } => to_json_binary(&queries::pending_config_changes(deps, start_after, limit)?),
+        QueryMsg::UndelegationOverrides {
+            start_after,
+            limit,
+        } => to_json_binary(&queries::undelegation_overrides(deps, start_after, limit)?),
     }
 }
 
 #[entry_point]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> ContractResult {
-    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-
-    Ok(Response::new()
-        .add_attribute("new_contract_name", CONTRACT_NAME)
-        .add_attribute("new_contract_version", CONTRACT_VERSION))
+pub fn migrate(deps: DepsMut<CustomQueryType>, env: Env, msg: MigrateMsg) -> ContractResult {
+    migrations::migrate(deps, env, msg)
 }
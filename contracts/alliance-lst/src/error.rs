@@ -25,6 +25,9 @@ pub enum ContractError {
     #[error("Unauthorized: sender is not operator")]
     UnauthorizedSenderNotOperator {},
 
+    #[error("Unauthorized: sender is not guardian or owner")]
+    UnauthorizedSenderNotGuardian {},
+
     #[error("Expecting only single coin")]
     ExpectingSingleCoin {},
 
@@ -79,6 +82,15 @@ pub enum ContractError {
     #[error("Contract can't be migrated!")]
     MigrationError {},
 
+    #[error("Contract {0} can't be migrated to {1}")]
+    MigrationContractName(String, String),
+
+    #[error("Contract can't be downgraded from version {0} to {1}")]
+    MigrationDowngrade(String, String),
+
+    #[error("Invalid contract version: {0}")]
+    InvalidVersion(String),
+
     #[error("No claims provided.")]
     NoClaimsProvided {},
 
@@ -96,4 +108,37 @@ pub enum ContractError {
 
     #[error("No reward")]
     NoReward {},
+
+    #[error("Config change {0} can only be executed after {1}")]
+    ConfigChangeTimelocked(u64, u64),
+
+    #[error("A guardian is required to veto pending config changes")]
+    GuardianRequired {},
+
+    #[error("Priority unbonding is disabled")]
+    PriorityUnbondDisabled {},
+
+    #[error("Priority unbond fee greater than max")]
+    PriorityUnbondFeeTooHigh {},
+
+    #[error("Priority unbond amount too small to pay a fee")]
+    PriorityUnbondFeeZero {},
+
+    #[error("Contract {0} is not whitelisted for custom claims")]
+    ClaimContractNotWhitelisted(String),
+
+    #[error("Delegations were already tuned in period {0}")]
+    TuneCooldown(u64),
+
+    #[error("Tip for tuning is disabled")]
+    TuneTipDisabled {},
+
+    #[error("Tip budget can only be funded with {0}")]
+    InvalidTuneTipDenom(String),
+
+    #[error("Denom {0} is used by the hub and can't be the tip denom")]
+    TuneTipDenomNotAllowed(String),
+
+    #[error("Tip denom can only be changed when the tip budget is empty")]
+    TuneTipBudgetNotEmpty {},
 }
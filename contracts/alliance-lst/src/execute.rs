@@ -1,29 +1,38 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
 use std::{cmp, vec};
 
-use astroport::asset::{Asset, AssetInfo};
 use cosmwasm_std::{
-    attr, to_json_binary, Addr, Attribute, BankMsg, Coin, CosmosMsg, Decimal, DepsMut, Env, Event,
-    Order, Response, StdResult, Uint128, WasmMsg,
+    attr, to_json_binary, Addr, Attribute, BankMsg, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env,
+    Event, Order, QuerierWrapper, Response, StdResult, Storage, Uint128, WasmMsg,
 };
 use cw2::set_contract_version;
-use eris::alliance_lst::{AllianceStakeToken, InstantiateMsg, Undelegation};
+use eris::alliance_lst::{
+    AllianceStakeToken, InstantiateMsg, PendingConfigChange, TimelockedConfig, Undelegation,
+    UndelegationOverride,
+};
+use eris::amp_gauges::get_amp_tune_info;
+use eris::emp_gauges::get_emp_tune_info;
+use eris::governance_helper::get_period;
 use eris::helper::validate_received_funds;
+use eris::helpers::bps::BasicPoints;
 use eris::{CustomEvent, CustomMsgExt, CustomResponse, DecimalCheckedOps};
 
-use eris::adapters::pair::Pair;
 use eris::hub::{
     Batch, CallbackMsg, DelegationStrategy, ExecuteMsg, FeeConfig, PendingBatch, SingleSwapConfig,
     UnbondRequest,
 };
 use eris_chain_adapter::types::{
-    chain, get_balances_hashmap, AssetExt, AssetInfoExt, CustomMsgType, CustomQueryType, DenomType,
-    HubChainConfig, StageType, WithdrawType,
+    chain, get_balances_hashmap, native_denom, AssetExt, AssetInfoExt, CustomMsgType,
+    CustomQueryType, DenomType, HubChainConfig, StageType, WithdrawType,
 };
 
 use itertools::Itertools;
 
-use crate::constants::get_reward_fee_cap;
+use crate::constants::{
+    get_reward_fee_cap, CONTRACT_NAME, CONTRACT_VERSION, MAX_PRIORITY_UNBOND_FEE_BPS,
+    PRIORITY_UNBOND_RESERVED_ENTRIES,
+};
 use crate::error::{ContractError, ContractResult};
 use crate::helpers::{get_wanted_delegations, query_all_delegations, query_delegations};
 use crate::math::{
@@ -35,10 +44,7 @@ use crate::types::alliance_delegations::AllianceDelegations;
 use crate::types::gauges::TuneInfoGaugeLoader;
 use crate::types::{withdraw_delegator_reward_msg, Coins, Delegation, SendFee, UndelegationExt};
 
-use eris_chain_shared::chain_trait::ChainInterface;
-
-const CONTRACT_NAME: &str = "eris-alliance-lst";
-const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+use eris_chain_shared::chain_trait::{ChainInterface, DenomMetadata};
 
 //--------------------------------------------------------------------------------------------------
 // Instantiation
@@ -80,6 +86,7 @@ pub fn instantiate(
     state.whale_btc_pool.save(deps.storage, &deps.api.addr_validate(&msg.whale_btc_pool)?)?;
     state.btc_denom.save(deps.storage, &msg.btc_denom)?;
     state.whale_denom.save(deps.storage, &msg.whale_denom)?;
+    state.guardian.save(deps.storage, &deps.api.addr_validate(&msg.guardian)?)?;
 
     // by default donations are set to false
     state.allow_donations.save(deps.storage, &false)?;
@@ -121,7 +128,18 @@ pub fn instantiate(
         },
     )?;
 
-    Ok(Response::new().add_message(chain.create_denom_msg(full_denom, sub_denom)))
+    // the metadata can also be set later through `UpdateDenomMetadata`
+    let set_metadata_msg = match msg.denom_metadata {
+        Some(denom_metadata) => {
+            denom_metadata.validate(&full_denom)?;
+            chain.create_set_metadata_msg(full_denom.clone(), denom_metadata)
+        },
+        None => None,
+    };
+
+    Ok(Response::new()
+        .add_message(chain.create_denom_msg(full_denom, sub_denom))
+        .add_messages(set_metadata_msg))
 }
 
 //--------------------------------------------------------------------------------------------------
@@ -243,8 +261,30 @@ pub fn harvest(
         })
         .collect::<Vec<_>>()
     };
+
+    // 2. Prepare swap stages of the other reward tokens into the whale denom
+    let stages = state.stages_preset.may_load(deps.storage)?;
+    validate_no_utoken_or_ustake_swap(&stages, &stake)?;
+    let mut skip_fee = false;
+    let swap_msgs = stages.map(|stages| {
+        stages
+            .into_iter()
+            .enumerate()
+            .map(|(index, stage)| {
+                skip_fee = skip_fee
+                    || stage.iter().any(|(_, _, _, _, pay_fee)| pay_fee.unwrap_or_default());
+
+                CallbackMsg::SingleStageSwap {
+                    stage,
+                    index,
+                }
+            })
+            .collect_vec()
+    });
+
     Ok(Response::new()
         .add_messages(withdraw_submsgs)
+        .add_optional_callbacks(&env, swap_msgs)?
         .add_callback(&env, CallbackMsg::HalfSwapReward {})?
         .add_callback(&env, CallbackMsg::ProvideLiquidity {})?
         .add_message(check_received_coin_msg(
@@ -256,7 +296,7 @@ pub fn harvest(
         .add_callback(
             &env,
             CallbackMsg::Reinvest {
-                skip_fee: false,
+                skip_fee,
             },
         )?
         .add_attribute("action", "erishub/harvest"))
@@ -340,6 +380,17 @@ pub fn single_stage_swap(
                     None => available,
                 };
 
+                // stages without a belief price can still limit their slippage by a simulation
+                let belief_price = match belief_price {
+                    Some(belief_price) => Some(belief_price),
+                    None => chain.simulate_belief_price(
+                        &deps.querier.into_empty(),
+                        &stage_type,
+                        &denom,
+                        used_amount,
+                    )?,
+                };
+
                 // create a single swap message add add to submsgs
                 let msg = chain.create_single_stage_swap_msgs(
                     get_chain_config,
@@ -402,7 +453,7 @@ pub fn half_swap_reward_msg(deps: &DepsMut<CustomQueryType>, env: &Env) -> Contr
         StageType::Dex {
             addr: pool,
         },
-        DenomType::native(whale_denom),
+        native_denom(whale_denom),
         None, // price
         Some(amount),
         None,
@@ -430,45 +481,28 @@ pub fn provide_liquidity_msg(deps: &DepsMut<CustomQueryType>, env: &Env) -> Cont
     let btc_amount =
         deps.querier.query_balance(env.contract.address.to_string(), &btc_denom)?.amount;
 
-    let mut assets: Vec<Asset> = vec![];
-    let mut funds: Vec<Coin> = vec![];
-
     if whale_amount.is_zero() || btc_amount.is_zero() {
         return Err(ContractError::NoReward {});
     }
 
-    assets.push(Asset {
-        info: AssetInfo::NativeToken {
-            denom: whale_denom.clone(),
-        },
-        amount: whale_amount,
-    });
-    assets.push(Asset {
-        info: AssetInfo::NativeToken {
-            denom: btc_denom.clone(),
+    let provide_liquidity_msgs = chain(env).create_provide_liquidity_msg(
+        &deps.querier.into_empty(),
+        || Ok(HubChainConfig {}),
+        WithdrawType::Dex {
+            addr: whale_btc_pool,
         },
-        amount: btc_amount,
-    });
-    funds.push(Coin {
-        denom: whale_denom,
-        amount: whale_amount,
-    });
-    funds.push(Coin {
-        denom: btc_denom,
-        amount: btc_amount,
-    });
+        vec![(native_denom(whale_denom), whale_amount), (native_denom(btc_denom), btc_amount)],
+        None,
+    )?;
 
-    let mut response = Response::new().add_attribute("action", "erishub/add_liquidity");
-    response = response.add_message(
-        Pair(whale_btc_pool)
-            .provide_liquidity_msg(assets, None, Some(env.contract.address.to_string()), funds)?
-            .to_specific()?,
-    );
+    let response = Response::new()
+        .add_messages(provide_liquidity_msgs)
+        .add_attribute("action", "erishub/add_liquidity");
     Ok(response)
 }
 
 /// This callback is used to take a current snapshot of the balance and add the received balance to the unlocked_coins state after the execution
-fn check_received_coin_msg(
+pub(crate) fn check_received_coin_msg(
     deps: &DepsMut<CustomQueryType>,
     env: &Env,
     stake: AllianceStakeToken,
@@ -834,6 +868,16 @@ pub fn submit_batch(
         stake.total_utoken_bonded,
     );
 
+    let unbonding_entries = state.get_unbonding_entries(deps.storage, current_time)?;
+    let validators = state.get_validators(deps.storage, &deps.querier)?;
+    let delegations = query_all_delegations(
+        &alliance_delegations,
+        &deps.querier,
+        &env.contract.address,
+        &stake.utoken,
+    )?;
+
+    let is_override = undelegations.is_some();
     let new_undelegations = if let Some(undelegations) = undelegations {
         state.assert_operator(deps.storage, &sender)?;
 
@@ -845,24 +889,50 @@ pub fn submit_batch(
             )));
         }
 
-        undelegations
-    } else {
-        let validators = state.get_validators(deps.storage, &deps.querier)?;
-        let delegations = query_all_delegations(
-            &alliance_delegations,
-            &deps.querier,
-            &env.contract.address,
-            &stake.utoken,
+        if let Some(undelegation) =
+            undelegations.iter().find(|u| !unbonding_entries.has_free_slot(&u.validator))
+        {
+            return Err(ContractError::SubmitBatchFailure(format!(
+                "validator {0} has no free unbonding entry",
+                undelegation.validator
+            )));
+        }
+
+        let tolerance =
+            BasicPoints::try_from(state.get_undelegation_tolerance_bps(deps.storage))?.decimal();
+        validate_undelegations(
+            &state,
+            deps.storage,
+            &delegations,
+            &validators,
+            &undelegations,
+            tolerance,
         )?;
 
-        compute_undelegations(
+        state.undelegation_overrides.save(
+            deps.storage,
+            pending_batch.id,
+            &UndelegationOverride {
+                batch_id: pending_batch.id,
+                operator: sender,
+                time: current_time,
+                undelegations: undelegations.clone(),
+            },
+        )?;
+
+        undelegations
+    } else {
+        let computed_undelegations = compute_undelegations(
             &state,
             deps.storage,
             utoken_to_unbond,
             &delegations,
             validators,
             &stake.utoken,
-        )?
+            &unbonding_entries.saturated_validators(),
+        )?;
+        assert_undelegations_complete(&computed_undelegations, utoken_to_unbond)?;
+        computed_undelegations
     };
 
     state.previous_batches.save(
@@ -878,10 +948,11 @@ pub fn submit_batch(
     )?;
 
     let epoch_period = state.epoch_period.load(deps.storage)?;
+    let next_batch_id = state.next_batch_id(deps.storage, pending_batch.id)?;
     state.pending_batch.save(
         deps.storage,
         &PendingBatch {
-            id: pending_batch.id + 1,
+            id: next_batch_id,
             ustake_to_burn: Uint128::zero(),
             est_unbond_start_time: current_time + epoch_period,
         },
@@ -889,6 +960,9 @@ pub fn submit_batch(
 
     // validates that the amount is available and validator delegation exists
     alliance_delegations.undelegate(&new_undelegations)?.save(&state, deps.storage)?;
+    unbonding_entries
+        .add(&new_undelegations, current_time + unbond_period)
+        .save(&state, deps.storage)?;
     let undelegate_msgs = new_undelegations
         .into_iter()
         .map(|d| d.to_cosmos_msg(env.contract.address.to_string(), stake.utoken.clone()))
@@ -905,7 +979,8 @@ pub fn submit_batch(
     let event = Event::new("erishub/unbond_submitted")
         .add_attribute("id", pending_batch.id.to_string())
         .add_attribute("utoken_unbonded", utoken_to_unbond)
-        .add_attribute("ustake_burned", pending_batch.ustake_to_burn);
+        .add_attribute("ustake_burned", pending_batch.ustake_to_burn)
+        .add_optional_attribute(is_override.then(|| attr("operator_override", "true")));
 
     Ok(Response::new()
         .add_messages(undelegate_msgs)
@@ -915,6 +990,201 @@ pub fn submit_batch(
         .add_attribute("action", "erishub/unbond"))
 }
 
+/// Unbonds the stake tokens of a single user in a batch of their own. Instead of waiting for the
+/// pending batch, the batch is submitted right away and the user pays a fee in stake tokens for the
+/// additional unbonding entry.
+pub fn priority_unbond(
+    deps: DepsMut<CustomQueryType>,
+    env: Env,
+    receiver: Addr,
+    ustake_amount: Uint128,
+) -> ContractResult {
+    let state = State::default();
+    let fee_bps = state.get_priority_unbond_fee_bps(deps.storage);
+    if fee_bps == 0 {
+        return Err(ContractError::PriorityUnbondDisabled {});
+    }
+
+    let mut stake = state.stake_token.load(deps.storage)?;
+    let unbond_period = state.unbond_period.load(deps.storage)?;
+    let pending_batch = state.pending_batch.load(deps.storage)?;
+    let alliance_delegations = state.alliance_delegations.load(deps.storage)?;
+    let fee_config = state.fee_config.load(deps.storage)?;
+    let current_time = env.block.time.seconds();
+
+    // the fee pays for an unbonding entry, it must not round down to zero for small amounts
+    let ustake_fee = BasicPoints::try_from(fee_bps)?
+        .decimal()
+        .checked_mul_uint(ustake_amount)?
+        .max(state.get_priority_unbond_min_fee(deps.storage));
+    if ustake_fee.is_zero() {
+        return Err(ContractError::PriorityUnbondFeeZero {});
+    }
+    let ustake_to_burn = ustake_amount.checked_sub(ustake_fee)?;
+    if ustake_to_burn.is_zero() {
+        return Err(ContractError::CantBeZero("ustake_to_burn".into()));
+    }
+
+    let utoken_to_unbond =
+        compute_unbond_amount(stake.total_supply, ustake_to_burn, stake.total_utoken_bonded);
+
+    let unbonding_entries = state.get_unbonding_entries(deps.storage, current_time)?;
+    let validators = state.get_validators(deps.storage, &deps.querier)?;
+    let delegations = query_all_delegations(
+        &alliance_delegations,
+        &deps.querier,
+        &env.contract.address,
+        &stake.utoken,
+    )?;
+
+    let new_undelegations = compute_undelegations(
+        &state,
+        deps.storage,
+        utoken_to_unbond,
+        &delegations,
+        validators,
+        &stake.utoken,
+        &unbonding_entries.saturated_validators_reserving(PRIORITY_UNBOND_RESERVED_ENTRIES),
+    )?;
+    assert_undelegations_complete(&new_undelegations, utoken_to_unbond)?;
+
+    let id = state.next_batch_id(deps.storage, pending_batch.id)?;
+    let est_unbond_end_time = current_time + unbond_period;
+    state.priority_batch_id.save(deps.storage, &id)?;
+    state.previous_batches.save(
+        deps.storage,
+        id,
+        &Batch {
+            id,
+            reconciled: false,
+            total_shares: ustake_to_burn,
+            utoken_unclaimed: utoken_to_unbond,
+            est_unbond_end_time,
+        },
+    )?;
+    state.unbond_requests.save(
+        deps.storage,
+        (id, &receiver),
+        &UnbondRequest {
+            id,
+            user: receiver.clone(),
+            shares: ustake_to_burn,
+        },
+    )?;
+
+    alliance_delegations.undelegate(&new_undelegations)?.save(&state, deps.storage)?;
+    unbonding_entries.add(&new_undelegations, est_unbond_end_time).save(&state, deps.storage)?;
+    let undelegate_msgs = new_undelegations
+        .into_iter()
+        .map(|d| d.to_cosmos_msg(env.contract.address.to_string(), stake.utoken.clone()))
+        .collect::<Vec<_>>();
+
+    stake.total_utoken_bonded = stake.total_utoken_bonded.checked_sub(utoken_to_unbond)?;
+    stake.total_supply = stake.total_supply.checked_sub(ustake_to_burn)?;
+    state.stake_token.save(deps.storage, &stake)?;
+
+    let burn_msg: CosmosMsg<CustomMsgType> =
+        chain(&env).create_burn_msg(stake.denom.clone(), ustake_to_burn);
+
+    let fee_msg = CosmosMsg::Bank(BankMsg::Send {
+        to_address: fee_config.protocol_fee_contract.to_string(),
+        amount: vec![Coin::new(ustake_fee.u128(), stake.denom.clone())],
+    });
+
+    let event = Event::new("erishub/priority_unbond_submitted")
+        .add_attribute("id", id.to_string())
+        .add_attribute("receiver", receiver)
+        .add_attribute("est_unbond_end_time", est_unbond_end_time.to_string())
+        .add_attribute("utoken_unbonded", utoken_to_unbond)
+        .add_attribute("ustake_burned", ustake_to_burn)
+        .add_attribute("ustake_fee", ustake_fee);
+
+    Ok(Response::new()
+        .add_messages(undelegate_msgs)
+        .add_message(burn_msg)
+        .add_message(fee_msg)
+        .add_message(check_received_coin_msg(&deps, &env, stake, None)?)
+        .add_event(event)
+        .add_attribute("action", "erishub/priority_unbond"))
+}
+
+/// Checks that the planned undelegations cover the full amount to unbond. If not, there are not
+/// enough validators with free unbonding entries and the batch needs to wait for entries to mature.
+pub(crate) fn assert_undelegations_complete(
+    undelegations: &[Undelegation],
+    utoken_to_unbond: Uint128,
+) -> Result<(), ContractError> {
+    let planned_amount: Uint128 = undelegations.iter().map(|u| u.amount).sum();
+    if planned_amount < utoken_to_unbond {
+        return Err(ContractError::SubmitBatchFailure(format!(
+            "not enough free unbonding entries, missing {0}",
+            utoken_to_unbond - planned_amount
+        )));
+    }
+    Ok(())
+}
+
+/// Validates undelegations provided by the operator. Undelegating is only allowed from validators of
+/// the current set, as long as any of them has stake. After undelegating, each validator needs to be
+/// within `tolerance` of its own delegation goal, or at least not further away from it than before.
+fn validate_undelegations(
+    state: &State,
+    storage: &dyn Storage,
+    current_delegations: &[Delegation],
+    validators: &[String],
+    undelegations: &[Undelegation],
+    tolerance: Decimal,
+) -> Result<(), ContractError> {
+    let current: BTreeMap<String, u128> =
+        current_delegations.iter().map(|d| (d.validator.clone(), d.amount)).collect();
+    let set_has_stake = validators.iter().any(|v| current.get(v).copied().unwrap_or_default() > 0);
+
+    let mut after = current.clone();
+    for undelegation in undelegations {
+        if set_has_stake && !validators.contains(&undelegation.validator) {
+            return Err(ContractError::SubmitBatchFailure(format!(
+                "validator {0} is not in the validator set",
+                undelegation.validator
+            )));
+        }
+
+        let amount = after.get(&undelegation.validator).copied().unwrap_or_default();
+        let new_amount = amount.checked_sub(undelegation.amount.u128()).ok_or_else(|| {
+            ContractError::SubmitBatchFailure(format!(
+                "undelegation exceeds delegation of validator {0}",
+                undelegation.validator
+            ))
+        })?;
+        after.insert(undelegation.validator.clone(), new_amount);
+    }
+
+    let utoken_before: u128 = current.values().sum();
+    let utoken_after: u128 = after.values().sum();
+    let (goal_before, _, _, _) =
+        get_utoken_per_validator(state, storage, utoken_before, validators, None)?;
+    let (goal_after, _, _, _) =
+        get_utoken_per_validator(state, storage, utoken_after, validators, None)?;
+
+    for (validator, amount_after) in after.iter() {
+        let goal = |goals: &HashMap<String, Uint128>| {
+            goals.get(validator).map(|a| a.u128()).unwrap_or_default()
+        };
+        let deviation_before =
+            current.get(validator).copied().unwrap_or_default().abs_diff(goal(&goal_before));
+        let deviation_after = amount_after.abs_diff(goal(&goal_after));
+        let max_deviation = tolerance.checked_mul_uint(Uint128::new(goal(&goal_after)))?.u128();
+
+        if deviation_after > max_deviation && deviation_after > deviation_before {
+            return Err(ContractError::SubmitBatchFailure(format!(
+                "undelegations move validator {0} outside of the delegation goal tolerance",
+                validator
+            )));
+        }
+    }
+
+    Ok(())
+}
+
 pub fn reconcile(deps: DepsMut<CustomQueryType>, env: Env) -> ContractResult {
     let state = State::default();
     let stake = state.stake_token.load(deps.storage)?;
@@ -1101,9 +1371,76 @@ pub fn withdraw_unbonded(
 
 pub fn tune_delegations(deps: DepsMut<CustomQueryType>, env: Env, sender: Addr) -> ContractResult {
     let state = State::default();
-    state.assert_owner(deps.storage, &sender)?;
+    let period = get_period(env.block.time.seconds())?;
+
+    // anyone can tune once per period, the owner at any time
+    let is_keeper = state.assert_owner(deps.storage, &sender).is_err();
+    if is_keeper && state.tune_period.may_load(deps.storage)?.is_some_and(|last| last >= period) {
+        return Err(ContractError::TuneCooldown(period));
+    }
+    state.tune_period.save(deps.storage, &period)?;
+
+    let tip_msg = if is_keeper {
+        tune_tip_msg(&state, deps.storage, &sender)?
+    } else {
+        None
+    };
+
+    let gauge_msgs = gauge_tune_msgs(&state, deps.storage, &deps.querier, period)?;
+    let response = if gauge_msgs.is_empty() {
+        Response::new().add_attributes(save_delegation_goal(deps, &env, &state)?)
+    } else {
+        // the goal is computed after the gauges applied the latest closed period
+        Response::new()
+            .add_messages(gauge_msgs)
+            .add_message(CallbackMsg::TuneDelegations {}.into_cosmos_msg(&env.contract.address)?)
+    };
+
+    Ok(response
+        .add_attribute("action", "erishub/tune_delegations")
+        .add_attribute("keeper_tip", tip_msg.is_some().to_string())
+        .add_optional_message(tip_msg))
+}
+
+pub fn callback_tune_delegations(deps: DepsMut<CustomQueryType>, env: Env) -> ContractResult {
+    let state = State::default();
+    let attributes = save_delegation_goal(deps, &env, &state)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "erishub/callback_tune_delegations")
+        .add_attributes(attributes))
+}
+
+pub fn fund_tune_tip(deps: DepsMut<CustomQueryType>, funds: Vec<Coin>) -> ContractResult {
+    let state = State::default();
+    let tune_tip =
+        state.tune_tip.may_load(deps.storage)?.ok_or(ContractError::TuneTipDisabled {})?;
+
+    if funds.len() != 1 {
+        return Err(ContractError::ExpectingSingleCoin {});
+    }
+    if funds[0].denom != tune_tip.denom {
+        return Err(ContractError::InvalidTuneTipDenom(tune_tip.denom));
+    }
+
+    let budget = state.get_tune_tip_budget(deps.storage).checked_add(funds[0].amount)?;
+    state.tune_tip_budget.save(deps.storage, &budget)?;
+
+    let event = Event::new("erishub/tune_tip_funded")
+        .add_attribute("amount", funds[0].to_string())
+        .add_attribute("budget", budget);
+
+    Ok(Response::new().add_event(event).add_attribute("action", "erishub/fund_tune_tip"))
+}
+
+/// Computes and stores the delegation goal from the current gauges.
+fn save_delegation_goal(
+    deps: DepsMut<CustomQueryType>,
+    env: &Env,
+    state: &State,
+) -> Result<Vec<Attribute>, ContractError> {
     let (wanted_delegations, save) =
-        get_wanted_delegations(&state, &env, deps.storage, &deps.querier, TuneInfoGaugeLoader {})?;
+        get_wanted_delegations(state, env, deps.storage, &deps.querier, TuneInfoGaugeLoader {})?;
     let attributes = if save {
         state.delegation_goal.save(deps.storage, &wanted_delegations)?;
         wanted_delegations
@@ -1116,9 +1453,67 @@ pub fn tune_delegations(deps: DepsMut<CustomQueryType>, env: Env, sender: Addr)
         // these would be boring, as all are the same
         vec![]
     };
-    Ok(Response::new()
-        .add_attribute("action", "erishub/tune_delegations")
-        .add_attributes(attributes))
+    Ok(attributes)
+}
+
+/// Creates the tune messages of the gauges that were not tuned in the current period yet.
+fn gauge_tune_msgs(
+    state: &State,
+    storage: &dyn Storage,
+    querier: &QuerierWrapper<CustomQueryType>,
+    period: u64,
+) -> StdResult<Vec<CosmosMsg<CustomMsgType>>> {
+    let mut msgs = vec![];
+
+    if let Some(DelegationStrategy::Gauges {
+        amp_gauges,
+        emp_gauges,
+        ..
+    }) = state.delegation_strategy.may_load(storage)?
+    {
+        if get_period(get_amp_tune_info(querier, amp_gauges.clone())?.tune_ts)? < period {
+            msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: amp_gauges.to_string(),
+                msg: to_json_binary(&eris::amp_gauges::ExecuteMsg::TuneVamp {})?,
+                funds: vec![],
+            }));
+        }
+
+        if let Some(emp_gauges) = emp_gauges {
+            if get_emp_tune_info(querier, emp_gauges.clone())?.tune_period < period {
+                msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: emp_gauges.to_string(),
+                    msg: to_json_binary(&eris::emp_gauges::ExecuteMsg::TuneEmps {})?,
+                    funds: vec![],
+                }));
+            }
+        }
+    }
+
+    Ok(msgs)
+}
+
+/// Pays the tip to the keeper, if the remaining budget covers it.
+fn tune_tip_msg(
+    state: &State,
+    storage: &mut dyn Storage,
+    recipient: &Addr,
+) -> Result<Option<CosmosMsg<CustomMsgType>>, ContractError> {
+    let tune_tip = match state.tune_tip.may_load(storage)? {
+        Some(tune_tip) if !tune_tip.amount.is_zero() => tune_tip,
+        _ => return Ok(None),
+    };
+
+    let budget = state.get_tune_tip_budget(storage);
+    if budget < tune_tip.amount {
+        return Ok(None);
+    }
+    state.tune_tip_budget.save(storage, &(budget - tune_tip.amount))?;
+
+    Ok(Some(CosmosMsg::Bank(BankMsg::Send {
+        to_address: recipient.to_string(),
+        amount: vec![tune_tip],
+    })))
 }
 
 //--------------------------------------------------------------------------------------------------
@@ -1226,10 +1621,12 @@ pub fn accept_ownership(deps: DepsMut<CustomQueryType>, sender: Addr) -> Contrac
 #[allow(clippy::too_many_arguments)]
 pub fn update_config(
     deps: DepsMut<CustomQueryType>,
+    env: Env,
     sender: Addr,
     protocol_fee_contract: Option<String>,
     protocol_reward_fee: Option<Decimal>,
     operator: Option<String>,
+    vote_operator: Option<String>,
     stages_preset: Option<Vec<Vec<SingleSwapConfig>>>,
     withdrawals_preset: Option<Vec<(WithdrawType, DenomType)>>,
     allow_donations: Option<bool>,
@@ -1241,25 +1638,24 @@ pub fn update_config(
     whale_denom: Option<String>,
     btc_denom: Option<String>,
     whale_btc_pool: Option<Addr>,
+    undelegation_tolerance_bps: Option<u16>,
+    guardian: Option<String>,
+    timelock_delay: Option<u64>,
+    priority_unbond_fee_bps: Option<u16>,
+    priority_unbond_min_fee: Option<Uint128>,
+    claim_contracts: Option<Vec<String>>,
+    tune_tip: Option<Coin>,
 ) -> ContractResult {
     let state = State::default();
 
     state.assert_owner(deps.storage, &sender)?;
 
-    if protocol_fee_contract.is_some() || protocol_reward_fee.is_some() {
-        let mut fee_config = state.fee_config.load(deps.storage)?;
-
-        if let Some(protocol_fee_contract) = protocol_fee_contract {
-            fee_config.protocol_fee_contract = deps.api.addr_validate(&protocol_fee_contract)?;
-        }
-
-        if let Some(protocol_reward_fee) = protocol_reward_fee {
-            if protocol_reward_fee.gt(&get_reward_fee_cap()) {
-                return Err(ContractError::ProtocolRewardFeeTooHigh {});
-            }
-            fee_config.protocol_reward_fee = protocol_reward_fee;
+    if let Some(protocol_reward_fee) = protocol_reward_fee {
+        if protocol_reward_fee.gt(&get_reward_fee_cap()) {
+            return Err(ContractError::ProtocolRewardFeeTooHigh {});
         }
-
+        let mut fee_config = state.fee_config.load(deps.storage)?;
+        fee_config.protocol_reward_fee = protocol_reward_fee;
         state.fee_config.save(deps.storage, &fee_config)?;
     }
 
@@ -1281,49 +1677,286 @@ pub fn update_config(
         state.operator.save(deps.storage, &deps.api.addr_validate(operator.as_str())?)?;
     }
 
-    if let Some(validator_proxy) = validator_proxy {
-        state
-            .validator_proxy
-            .save(deps.storage, &deps.api.addr_validate(validator_proxy.as_str())?)?;
+    if let Some(vote_operator) = vote_operator {
+        state.vote_operator.save(deps.storage, &deps.api.addr_validate(&vote_operator)?)?;
+    }
+
+    if let Some(allow_donations) = allow_donations {
+        state.allow_donations.save(deps.storage, &allow_donations)?;
+    }
+    if let Some(default_max_spread) = default_max_spread {
+        state.default_max_spread.save(deps.storage, &default_max_spread)?;
+    }
+
+    if let Some(undelegation_tolerance_bps) = undelegation_tolerance_bps {
+        BasicPoints::try_from(undelegation_tolerance_bps)?;
+        state.undelegation_tolerance_bps.save(deps.storage, &undelegation_tolerance_bps)?;
+    }
+    if let Some(priority_unbond_fee_bps) = priority_unbond_fee_bps {
+        if priority_unbond_fee_bps > MAX_PRIORITY_UNBOND_FEE_BPS {
+            return Err(ContractError::PriorityUnbondFeeTooHigh {});
+        }
+        state.priority_unbond_fee_bps.save(deps.storage, &priority_unbond_fee_bps)?;
+    }
+    if let Some(priority_unbond_min_fee) = priority_unbond_min_fee {
+        state.priority_unbond_min_fee.save(deps.storage, &priority_unbond_min_fee)?;
+    }
+    if let Some(tune_tip) = tune_tip {
+        update_tune_tip(deps.storage, &state, tune_tip)?;
+    }
+
+    let response = Response::new().add_attribute("action", "erishub/update_config");
+
+    // fields that can redirect funds are only proposed and applied after the timelock
+    let change = TimelockedConfig {
+        protocol_fee_contract,
+        stages_preset,
+        withdrawals_preset,
+        delegation_strategy,
+        validator_proxy,
+        whale_btc_pool: whale_btc_pool.map(|addr| addr.to_string()),
+        whale_denom,
+        btc_denom,
+        guardian,
+        timelock_delay,
+        claim_contracts,
+    };
+
+    if change.changed_fields().is_empty() {
+        return Ok(response);
+    }
+
+    validate_config_change(deps.as_ref(), &state, &change)?;
+
+    let id = state.config_change_id.may_load(deps.storage)?.unwrap_or_default() + 1;
+    let current_time = env.block.time.seconds();
+    let pending = PendingConfigChange {
+        id,
+        change,
+        proposed_at: current_time,
+        executable_at: current_time + state.get_timelock_delay(deps.storage),
+    };
+
+    state.config_change_id.save(deps.storage, &id)?;
+    state.pending_config_changes.save(deps.storage, id, &pending)?;
+
+    let event = Event::new("erishub/config_change_proposed")
+        .add_attribute("id", id.to_string())
+        .add_attribute("fields", pending.change.changed_fields().join(","))
+        .add_attribute("executable_at", pending.executable_at.to_string());
+
+    Ok(response.add_event(event))
+}
+
+pub fn update_denom_metadata(
+    deps: DepsMut<CustomQueryType>,
+    env: Env,
+    sender: Addr,
+    metadata: DenomMetadata,
+) -> ContractResult {
+    let state = State::default();
+    let chain = chain(&env);
+
+    state.assert_owner(deps.storage, &sender)?;
+
+    let stake_token = state.stake_token.load(deps.storage)?;
+    metadata.validate(&stake_token.denom)?;
+    let set_metadata_msg = chain
+        .create_set_metadata_msg(stake_token.denom, metadata)
+        .ok_or_else(|| ContractError::NotSupported("denom metadata".into()))?;
+
+    Ok(Response::new()
+        .add_message(set_metadata_msg)
+        .add_attribute("action", "erishub/update_denom_metadata"))
+}
+
+pub fn execute_config_change(
+    deps: DepsMut<CustomQueryType>,
+    env: Env,
+    sender: Addr,
+    id: u64,
+) -> ContractResult {
+    let state = State::default();
+
+    state.assert_owner(deps.storage, &sender)?;
+
+    let pending = state.pending_config_changes.load(deps.storage, id)?;
+    if env.block.time.seconds() < pending.executable_at {
+        return Err(ContractError::ConfigChangeTimelocked(id, pending.executable_at));
+    }
+
+    state.pending_config_changes.remove(deps.storage, id);
+    apply_config_change(deps, &state, &pending.change)?;
+
+    let event = Event::new("erishub/config_change_executed")
+        .add_attribute("id", id.to_string())
+        .add_attribute("fields", pending.change.changed_fields().join(","));
+
+    Ok(Response::new().add_event(event).add_attribute("action", "erishub/execute_config_change"))
+}
+
+pub fn veto_config_change(deps: DepsMut<CustomQueryType>, sender: Addr, id: u64) -> ContractResult {
+    let state = State::default();
+
+    state.assert_owner_or_guardian(deps.storage, &sender)?;
+
+    let pending = state.pending_config_changes.load(deps.storage, id)?;
+    state.pending_config_changes.remove(deps.storage, id);
+
+    let event = Event::new("erishub/config_change_vetoed")
+        .add_attribute("id", id.to_string())
+        .add_attribute("fields", pending.change.changed_fields().join(","))
+        .add_attribute("sender", sender);
+
+    Ok(Response::new().add_event(event).add_attribute("action", "erishub/veto_config_change"))
+}
+
+/// The tip is paid from its own budget, so it must not use a denom that is handled by harvesting.
+fn update_tune_tip(
+    storage: &mut dyn Storage,
+    state: &State,
+    tune_tip: Coin,
+) -> Result<(), ContractError> {
+    let stake = state.stake_token.load(storage)?;
+    let used_denoms = [
+        stake.utoken,
+        stake.denom,
+        state.whale_denom.load(storage)?,
+        state.btc_denom.load(storage)?,
+    ];
+    if used_denoms.contains(&tune_tip.denom) {
+        return Err(ContractError::TuneTipDenomNotAllowed(tune_tip.denom));
     }
 
-    if stages_preset.is_some() {
-        validate_no_utoken_or_ustake_swap(&stages_preset, &state.stake_token.load(deps.storage)?)?;
+    let current_denom = state.tune_tip.may_load(storage)?.map(|tip| tip.denom);
+    if current_denom.is_some_and(|denom| denom != tune_tip.denom)
+        && !state.get_tune_tip_budget(storage).is_zero()
+    {
+        return Err(ContractError::TuneTipBudgetNotEmpty {});
     }
 
-    if let Some(stages_preset) = stages_preset {
+    state.tune_tip.save(storage, &tune_tip)?;
+    Ok(())
+}
+
+/// Validates a config change against the current state, so that invalid changes are rejected
+/// before anyone waits for their timelock.
+fn validate_config_change(
+    deps: Deps<CustomQueryType>,
+    state: &State,
+    change: &TimelockedConfig,
+) -> Result<(), ContractError> {
+    if let Some(protocol_fee_contract) = &change.protocol_fee_contract {
+        deps.api.addr_validate(protocol_fee_contract)?;
+    }
+    if let Some(whale_btc_pool) = &change.whale_btc_pool {
+        deps.api.addr_validate(whale_btc_pool)?;
+    }
+    if let Some(guardian) = &change.guardian {
+        deps.api.addr_validate(guardian)?;
+    }
+
+    // the tip budget must not be mixed with denoms that are handled by harvesting
+    if let Some(tune_tip) = state.tune_tip.may_load(deps.storage)? {
+        let mut new_denoms = change.whale_denom.iter().chain(change.btc_denom.iter());
+        if let Some(denom) = new_denoms.find(|denom| **denom == tune_tip.denom) {
+            return Err(ContractError::TuneTipDenomNotAllowed(denom.clone()));
+        }
+    }
+
+    if let Some(stages_preset) = &change.stages_preset {
+        validate_no_utoken_or_ustake_swap(
+            &change.stages_preset,
+            &state.stake_token.load(deps.storage)?,
+        )?;
         // belief price is not allowed. We still store it with None, as otherwise a lot of additional logic is required to load it.
-        validate_no_belief_price(&stages_preset)?;
-        state.stages_preset.save(deps.storage, &stages_preset)?;
+        validate_no_belief_price(stages_preset)?;
+    }
+
+    if let Some(validator_proxy) = &change.validator_proxy {
+        deps.api.addr_validate(validator_proxy)?;
+    }
+
+    if let Some(claim_contracts) = &change.claim_contracts {
+        for contract in claim_contracts {
+            deps.api.addr_validate(contract)?;
+        }
     }
 
-    if let Some(withdrawals_preset) = withdrawals_preset {
-        state.withdrawals_preset.save(deps.storage, &withdrawals_preset)?;
+    if let Some(delegation_strategy) = &change.delegation_strategy {
+        // the delegation strategy needs to be valid for the validators of the new proxy
+        let validators = match &change.validator_proxy {
+            Some(validator_proxy) => state
+                .validators_proxy_item
+                .query(&deps.querier, Addr::unchecked(validator_proxy))?,
+            None => state.get_validators(deps.storage, &deps.querier)?,
+        };
+        delegation_strategy.clone().validate(deps.api, &validators)?;
     }
 
-    if let Some(delegation_strategy) = delegation_strategy {
+    Ok(())
+}
+
+fn apply_config_change(
+    deps: DepsMut<CustomQueryType>,
+    state: &State,
+    change: &TimelockedConfig,
+) -> Result<(), ContractError> {
+    // the state might have changed since the proposal (e.g. validators of the proxy)
+    validate_config_change(deps.as_ref(), state, change)?;
+
+    if let Some(protocol_fee_contract) = &change.protocol_fee_contract {
+        let mut fee_config = state.fee_config.load(deps.storage)?;
+        fee_config.protocol_fee_contract = deps.api.addr_validate(protocol_fee_contract)?;
+        state.fee_config.save(deps.storage, &fee_config)?;
+    }
+
+    if let Some(validator_proxy) = &change.validator_proxy {
+        state.validator_proxy.save(deps.storage, &deps.api.addr_validate(validator_proxy)?)?;
+    }
+
+    if let Some(stages_preset) = &change.stages_preset {
+        state.stages_preset.save(deps.storage, stages_preset)?;
+    }
+
+    if let Some(withdrawals_preset) = &change.withdrawals_preset {
+        state.withdrawals_preset.save(deps.storage, withdrawals_preset)?;
+    }
+
+    if let Some(delegation_strategy) = &change.delegation_strategy {
         let validators = state.get_validators(deps.storage, &deps.querier)?;
         state
             .delegation_strategy
-            .save(deps.storage, &delegation_strategy.validate(deps.api, &validators)?)?;
+            .save(deps.storage, &delegation_strategy.clone().validate(deps.api, &validators)?)?;
     }
 
-    if let Some(allow_donations) = allow_donations {
-        state.allow_donations.save(deps.storage, &allow_donations)?;
+    if let Some(whale_btc_pool) = &change.whale_btc_pool {
+        state.whale_btc_pool.save(deps.storage, &deps.api.addr_validate(whale_btc_pool)?)?;
     }
-    if let Some(default_max_spread) = default_max_spread {
-        state.default_max_spread.save(deps.storage, &default_max_spread)?;
+
+    if let Some(whale_denom) = &change.whale_denom {
+        state.whale_denom.save(deps.storage, whale_denom)?;
     }
 
-    if let Some(whale_denom) = whale_denom {
-        state.whale_denom.save(deps.storage, &whale_denom)?;
+    if let Some(btc_denom) = &change.btc_denom {
+        state.btc_denom.save(deps.storage, btc_denom)?;
     }
-    if let Some(btc_denom) = btc_denom {
-        state.btc_denom.save(deps.storage, &btc_denom)?;
+
+    if let Some(guardian) = &change.guardian {
+        state.guardian.save(deps.storage, &deps.api.addr_validate(guardian)?)?;
     }
-    if let Some(whale_btc_pool) = whale_btc_pool {
-        state.whale_btc_pool.save(deps.storage, &whale_btc_pool)?;
+
+    if let Some(timelock_delay) = change.timelock_delay {
+        state.timelock_delay.save(deps.storage, &timelock_delay)?;
     }
 
-    Ok(Response::new().add_attribute("action", "erishub/update_config"))
+    if let Some(claim_contracts) = &change.claim_contracts {
+        let claim_contracts = claim_contracts
+            .iter()
+            .map(|contract| deps.api.addr_validate(contract))
+            .collect::<StdResult<Vec<_>>>()?;
+        state.claim_contracts.save(deps.storage, &claim_contracts)?;
+    }
+
+    Ok(())
 }
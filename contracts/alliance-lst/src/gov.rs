@@ -0,0 +1,74 @@
+use cosmwasm_std::{
+    Binary, CosmosMsg, Decimal, DepsMut, Env, Event, GovMsg, MessageInfo, Response,
+};
+use eris_chain_adapter::types::{CustomMsgType, CustomQueryType};
+use itertools::Itertools;
+use terra_proto_rs::{
+    cosmos::gov::v1::{MsgVoteWeighted, VoteOption, WeightedVoteOption},
+    prost::Message,
+};
+
+use crate::{error::ContractResult, state::State};
+
+pub fn vote(
+    deps: DepsMut<CustomQueryType>,
+    _env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    vote: cosmwasm_std::VoteOption,
+) -> ContractResult {
+    let state = State::default();
+    state.assert_vote_operator(deps.storage, &info.sender)?;
+
+    let event = Event::new("erishub/voted").add_attribute("prop", proposal_id.to_string());
+
+    let vote = CosmosMsg::Gov(GovMsg::Vote {
+        proposal_id,
+        vote,
+    });
+
+    Ok(Response::new().add_message(vote).add_event(event).add_attribute("action", "erishub/vote"))
+}
+
+pub fn vote_weighted(
+    deps: DepsMut<CustomQueryType>,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    votes: Vec<(Decimal, cosmwasm_std::VoteOption)>,
+) -> ContractResult {
+    let state = State::default();
+    state.assert_vote_operator(deps.storage, &info.sender)?;
+
+    let event = Event::new("erishub/voted_weighted").add_attribute("prop", proposal_id.to_string());
+
+    let vote = MsgVoteWeighted {
+        proposal_id,
+        voter: env.contract.address.to_string(),
+        options: votes
+            .into_iter()
+            .map(|(weight, option)| WeightedVoteOption {
+                option: match option {
+                    cosmwasm_std::VoteOption::Yes => VoteOption::Yes,
+                    cosmwasm_std::VoteOption::No => VoteOption::No,
+                    cosmwasm_std::VoteOption::Abstain => VoteOption::Abstain,
+                    cosmwasm_std::VoteOption::NoWithVeto => VoteOption::NoWithVeto,
+                }
+                .into(),
+                // gov v1 parses the weight as a decimal string, e.g. "0.5"
+                weight: weight.to_string(),
+            })
+            .collect_vec(),
+        metadata: String::new(),
+    };
+
+    let vote = CosmosMsg::Stargate {
+        type_url: "/cosmos.gov.v1.MsgVoteWeighted".to_string(),
+        value: Binary::from(vote.encode_to_vec()),
+    };
+
+    Ok(Response::<CustomMsgType>::new()
+        .add_message(vote)
+        .add_event(event)
+        .add_attribute("action", "erishub/vote_weighted"))
+}
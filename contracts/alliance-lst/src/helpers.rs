@@ -16,6 +16,7 @@ use crate::{
 };
 
 /// Query the amounts of Token a staker is delegating to a specific validator
+#[cfg(not(feature = "X-terra-X"))]
 pub(crate) fn query_delegation(
     alliance_delegations: &AllianceDelegations,
     _querier: &QuerierWrapper<CustomQueryType>,
@@ -24,24 +25,23 @@ pub(crate) fn query_delegation(
     _delegator_addr: &Addr,
 ) -> StdResult<Delegation> {
     Ok(alliance_delegations.query_delegation(validator, denom))
+}
 
-    // let delegation = AllianceQuerier::new(querier).query_delegation(
-    //     denom.to_string(),
-    //     delegator_addr.to_string(),
-    //     validator.to_string(),
-    // );
-
-    // Ok(delegation
-    //     .map(|fd| Delegation {
-    //         validator: validator.to_string(),
-    //         amount: fd.amount.amount.u128(),
-    //         denom: fd.amount.denom,
-    //     })
-    //     .unwrap_or(Delegation {
-    //         validator: validator.to_string(),
-    //         amount: 0,
-    //         denom: "".into(),
-    //     }))
+/// Query the amounts of Token a staker is delegating to a specific validator
+/// Terra exposes the alliance module to contracts, so the delegation is queried from the chain.
+#[cfg(feature = "X-terra-X")]
+pub(crate) fn query_delegation(
+    _alliance_delegations: &AllianceDelegations,
+    querier: &QuerierWrapper<CustomQueryType>,
+    denom: &str,
+    validator: &str,
+    delegator_addr: &Addr,
+) -> StdResult<Delegation> {
+    Ok(crate::types::alliance_querier::AllianceQuerier::new(querier).query_delegation_or_empty(
+        denom,
+        delegator_addr,
+        validator,
+    ))
 }
 
 /// Query the amounts of Token a staker is delegating to each of the validators specified
@@ -60,6 +60,7 @@ pub(crate) fn query_delegations(
         .collect()
 }
 
+#[cfg(not(feature = "X-terra-X"))]
 pub(crate) fn query_all_delegations(
     alliance_delegations: &AllianceDelegations,
     _querier: &QuerierWrapper<CustomQueryType>,
@@ -67,35 +68,46 @@ pub(crate) fn query_all_delegations(
     utoken: &str,
 ) -> StdResult<Vec<Delegation>> {
     Ok(alliance_delegations.query_all_delegations(utoken))
+}
 
-    // let delegations = state.alliance_delegations.load(deps)
-
-    // let result: Vec<_> = querier
-    //     .query_all_delegationsx(delegator_addr)?
-    //     .into_iter()
-    //     .filter(|d| d.amount.denom == *utoken && !d.amount.amount.is_zero())
-    //     .map(|d| Delegation {
-    //         validator: d.validator,
-    //         amount: d.amount.amount.u128(),
-    //         denom: d.amount.denom,
-    //     })
-    //     .collect();
-
-    // Ok(result)
+/// Terra queries the delegations to the validators known to the hub from the chain.
+#[cfg(feature = "X-terra-X")]
+pub(crate) fn query_all_delegations(
+    alliance_delegations: &AllianceDelegations,
+    querier: &QuerierWrapper<CustomQueryType>,
+    delegator_addr: &Addr,
+    utoken: &str,
+) -> StdResult<Vec<Delegation>> {
+    Ok(crate::types::alliance_querier::AllianceQuerier::new(querier).query_all_delegations(
+        utoken,
+        delegator_addr,
+        alliance_delegations.delegations.keys(),
+    ))
 }
 
-pub(crate) fn query_all_delegations_amount(stake: &AllianceStakeToken) -> StdResult<u128> {
+#[cfg(not(feature = "X-terra-X"))]
+pub(crate) fn query_all_delegations_amount(
+    _alliance_delegations: &AllianceDelegations,
+    _querier: &QuerierWrapper<CustomQueryType>,
+    _delegator_addr: &Addr,
+    stake: &AllianceStakeToken,
+) -> StdResult<u128> {
     Ok(stake.total_utoken_bonded.u128())
+}
 
-    // // same as query_all_delegations, but will only collect the sum of the delegations
-    // let total_utoken: u128 = querier
-    //     .query_all_delegationsx(delegator_addr)?
-    //     .into_iter()
-    //     .filter(|d| d.amount.denom == *utoken && !d.amount.amount.is_zero())
-    //     .map(|d| d.amount.amount.u128())
-    //     .sum();
-
-    // Ok(total_utoken)
+/// Same as query_all_delegations, but will only collect the sum of the delegations
+#[cfg(feature = "X-terra-X")]
+pub(crate) fn query_all_delegations_amount(
+    alliance_delegations: &AllianceDelegations,
+    querier: &QuerierWrapper<CustomQueryType>,
+    delegator_addr: &Addr,
+    stake: &AllianceStakeToken,
+) -> StdResult<u128> {
+    Ok(crate::types::alliance_querier::AllianceQuerier::new(querier).query_all_delegations_amount(
+        &stake.utoken,
+        delegator_addr,
+        alliance_delegations.delegations.keys(),
+    ))
 }
 
 /// Calculates the wanted delegations based on the delegation strategy and the amp + emp gauges
@@ -2,8 +2,10 @@
 pub mod contract;
 
 pub mod execute;
+pub mod gov;
 pub mod helpers;
 pub mod math;
+pub mod migrations;
 pub mod queries;
 pub mod state;
 pub mod types;
@@ -4,7 +4,7 @@ use std::{
     collections::{HashMap, HashSet},
 };
 
-use cosmwasm_std::{attr, Attribute, QuerierWrapper, StdResult, Storage, Uint128};
+use cosmwasm_std::{attr, Addr, Attribute, QuerierWrapper, StdResult, Storage, Uint128};
 
 use eris::{
     alliance_lst::{AllianceStakeToken, Undelegation},
@@ -12,6 +12,7 @@ use eris::{
     DecimalCheckedOps,
 };
 use eris_chain_adapter::types::CustomQueryType;
+use itertools::Itertools;
 
 use crate::{
     helpers::query_all_delegations_amount,
@@ -61,6 +62,10 @@ pub(crate) fn compute_unbond_amount(
 /// compute the undelegations to make such that the delegated amount to each validator is as even
 /// as possible.
 ///
+/// Validators in `saturated_validators` have no free unbonding entry left and are skipped. Their
+/// part is undelegated from the validators with the most remaining delegation instead. If this is
+/// still not enough, the returned undelegations sum up to less than `utoken_to_unbond`.
+///
 /// This function is based on Lido's implementation:
 /// https://github.com/lidofinance/lido-terra-contracts/blob/v1.0.2/contracts/lido_terra_validators_registry/src/common.rs#L55-102
 pub(crate) fn compute_undelegations(
@@ -70,6 +75,7 @@ pub(crate) fn compute_undelegations(
     current_delegations: &[Delegation],
     validators: Vec<String>,
     utoken: &str,
+    saturated_validators: &HashSet<String>,
 ) -> StdResult<Vec<Undelegation>> {
     let utoken_staked: u128 = current_delegations.iter().map(|d| d.amount).sum();
     let utoken_to_distribute = utoken_staked - utoken_to_unbond.u128();
@@ -79,10 +85,15 @@ pub(crate) fn compute_undelegations(
 
     let mut new_undelegations: Vec<Undelegation> = vec![];
     let mut utoken_available = utoken_to_unbond.u128();
-    for d in merge_with_validators(current_delegations, validators, utoken).iter() {
+    let delegations = merge_with_validators(current_delegations, validators, utoken);
+    for d in delegations.iter() {
         let utoken_for_validator =
             get_utoken_for_validator(&utoken_per_validator, d, &mut add, &mut remove);
 
+        if saturated_validators.contains(&d.validator) {
+            continue;
+        }
+
         let mut utoken_to_undelegate = if d.amount < utoken_for_validator {
             0
         } else {
@@ -103,6 +114,41 @@ pub(crate) fn compute_undelegations(
         }
     }
 
+    if utoken_available > 0 {
+        // saturated validators could not take their part, so it is taken from the validators with
+        // the most remaining delegation. Sorting by validator keeps the result deterministic.
+        let candidates = delegations
+            .iter()
+            .filter(|d| !saturated_validators.contains(&d.validator))
+            .map(|d| {
+                let undelegated = new_undelegations
+                    .iter()
+                    .find(|u| u.validator == d.validator)
+                    .map(|u| u.amount.u128())
+                    .unwrap_or_default();
+                (d.validator.clone(), d.amount.saturating_sub(undelegated))
+            })
+            .filter(|(_, remaining)| *remaining > 0)
+            .sorted_by(|(val_a, a), (val_b, b)| b.cmp(a).then_with(|| val_a.cmp(val_b)))
+            .collect_vec();
+
+        for (validator, remaining) in candidates {
+            let utoken_to_undelegate = cmp::min(remaining, utoken_available);
+            utoken_available -= utoken_to_undelegate;
+
+            match new_undelegations.iter_mut().find(|u| u.validator == validator) {
+                Some(undelegation) => undelegation.amount += Uint128::new(utoken_to_undelegate),
+                None => {
+                    new_undelegations.push(UndelegationExt::new(&validator, utoken_to_undelegate))
+                },
+            }
+
+            if utoken_available == 0 {
+                break;
+            }
+        }
+    }
+
     Ok(new_undelegations)
 }
 
@@ -233,9 +279,15 @@ pub(crate) fn get_utoken_per_validator_prepared(
     storage: &dyn Storage,
     stake_token: &AllianceStakeToken,
     querier: &QuerierWrapper<CustomQueryType>,
+    delegator_addr: &Addr,
     goal: Option<WantedDelegationsShare>,
 ) -> StdResult<UtokenPerValidator> {
-    let utoken_staked: u128 = query_all_delegations_amount(stake_token)?;
+    let utoken_staked: u128 = query_all_delegations_amount(
+        &state.alliance_delegations.load(storage)?,
+        querier,
+        delegator_addr,
+        stake_token,
+    )?;
     let validators = state.get_validators(storage, querier)?;
     get_utoken_per_validator(state, storage, utoken_staked, &validators, goal)
 }
@@ -0,0 +1,139 @@
+use std::convert::TryFrom;
+
+use cosmwasm_std::{attr, Attribute, DepsMut, Env, Order, Response, StdResult};
+use cw2::{get_contract_version, set_contract_version};
+use eris::alliance_lst::{MigrateMsg, MigrateV1_8_0};
+use eris::helpers::bps::BasicPoints;
+use eris_chain_adapter::types::CustomQueryType;
+use semver::Version;
+
+use crate::constants::{CONTRACT_NAME, CONTRACT_VERSION, LEGACY_CONTRACT_NAMES};
+use crate::error::{ContractError, ContractResult};
+use crate::state::State;
+use crate::types::unbonding_entries::UnbondingEntries;
+
+type MigrationStep =
+    fn(&mut DepsMut<CustomQueryType>, &Env, &MigrateMsg) -> Result<Vec<Attribute>, ContractError>;
+
+/// All migration steps, ordered by the version that introduced them.
+/// A step is executed when the stored version is older than the step and the step is not newer
+/// than the version of the contract being migrated to.
+const MIGRATIONS: &[(&str, MigrationStep)] = &[("1.8.0", migrate_v1_8_0)];
+
+pub fn migrate(mut deps: DepsMut<CustomQueryType>, env: Env, msg: MigrateMsg) -> ContractResult {
+    let stored = get_contract_version(deps.storage)?;
+
+    if stored.contract != CONTRACT_NAME
+        && !LEGACY_CONTRACT_NAMES.contains(&stored.contract.as_str())
+    {
+        return Err(ContractError::MigrationContractName(stored.contract, CONTRACT_NAME.into()));
+    }
+
+    let previous = parse_version(&stored.version)?;
+    let new = parse_version(CONTRACT_VERSION)?;
+
+    if previous > new {
+        return Err(ContractError::MigrationDowngrade(stored.version, CONTRACT_VERSION.into()));
+    }
+
+    let mut attrs = vec![];
+    for (version, step) in MIGRATIONS {
+        let version_parsed = parse_version(version)?;
+        if previous < version_parsed && version_parsed <= new {
+            attrs.push(attr("migration_step", *version));
+            attrs.extend(step(&mut deps, &env, &msg)?);
+        }
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("previous_contract_name", &stored.contract)
+        .add_attribute("previous_contract_version", &stored.version)
+        .add_attribute("new_contract_name", CONTRACT_NAME)
+        .add_attribute("new_contract_version", CONTRACT_VERSION)
+        .add_attributes(attrs))
+}
+
+fn parse_version(version: &str) -> Result<Version, ContractError> {
+    Version::parse(version).map_err(|_| ContractError::InvalidVersion(version.to_string()))
+}
+
+/// v1.8.0 introduced operator undelegation validation, unbonding entry tracking and timelocked
+/// config changes.
+fn migrate_v1_8_0(
+    deps: &mut DepsMut<CustomQueryType>,
+    env: &Env,
+    msg: &MigrateMsg,
+) -> Result<Vec<Attribute>, ContractError> {
+    let state = State::default();
+    let params = msg.v1_8_0.clone().unwrap_or_default();
+    let mut attrs = vec![];
+
+    if state.unbonding_entries.may_load(deps.storage)?.is_none() {
+        let entries = seed_unbonding_entries(deps, &state, env.block.time.seconds())?;
+        attrs.push(attr("seeded_unbonding_entries", entries.to_string()));
+    }
+
+    let MigrateV1_8_0 {
+        undelegation_tolerance_bps,
+        guardian,
+        timelock_delay,
+    } = params;
+
+    if let Some(undelegation_tolerance_bps) = undelegation_tolerance_bps {
+        BasicPoints::try_from(undelegation_tolerance_bps)?;
+        state.undelegation_tolerance_bps.save(deps.storage, &undelegation_tolerance_bps)?;
+        attrs.push(attr("undelegation_tolerance_bps", undelegation_tolerance_bps.to_string()));
+    }
+
+    // without a guardian, nobody but the owner could veto a pending config change
+    match guardian {
+        Some(guardian) => {
+            state.guardian.save(deps.storage, &deps.api.addr_validate(&guardian)?)?;
+            attrs.push(attr("guardian", guardian));
+        },
+        None if state.guardian.may_load(deps.storage)?.is_none() => {
+            return Err(ContractError::GuardianRequired {});
+        },
+        None => (),
+    }
+
+    if let Some(timelock_delay) = timelock_delay {
+        state.timelock_delay.save(deps.storage, &timelock_delay)?;
+        attrs.push(attr("timelock_delay", timelock_delay.to_string()));
+    }
+
+    Ok(attrs)
+}
+
+/// Batches submitted before v1.8.0 did not record which validators they undelegated from. Every
+/// batch that is still unbonding might have used an entry of each delegated validator, so one entry
+/// per validator and batch is assumed until the batch finished unbonding.
+fn seed_unbonding_entries(
+    deps: &mut DepsMut<CustomQueryType>,
+    state: &State,
+    current_time: u64,
+) -> Result<usize, ContractError> {
+    let end_times = state
+        .previous_batches
+        .idx
+        .reconciled
+        .prefix(false.into())
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| Ok(item?.1.est_unbond_end_time))
+        .collect::<StdResult<Vec<_>>>()?
+        .into_iter()
+        .filter(|end_time| *end_time > current_time)
+        .collect::<Vec<_>>();
+
+    let delegations = state.alliance_delegations.load(deps.storage)?.delegations;
+    let entries = UnbondingEntries {
+        entries: delegations.into_keys().map(|validator| (validator, end_times.clone())).collect(),
+    }
+    .prune(current_time);
+
+    let count = entries.entries.values().map(|end_times| end_times.len()).sum();
+    entries.save(state, deps.storage)?;
+    Ok(count)
+}
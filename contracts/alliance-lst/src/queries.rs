@@ -1,4 +1,6 @@
-use crate::constants::DAY;
+use crate::constants::{DAY, MAX_UNBONDING_ENTRIES};
+use crate::error::ContractError;
+use crate::execute::assert_undelegations_complete;
 use crate::helpers::{get_wanted_delegations, query_all_delegations};
 use crate::math::{
     compute_unbond_amount, compute_undelegations, get_utoken_per_validator_prepared,
@@ -7,7 +9,10 @@ use crate::state::State;
 use crate::types::gauges::PeriodGaugeLoader;
 use cosmwasm_std::{Addr, Decimal, Deps, Env, Order, StdResult, Uint128};
 use cw_storage_plus::Bound;
-use eris::alliance_lst::{ConfigResponse, Undelegation};
+use eris::alliance_lst::{
+    ConfigResponse, PendingConfigChange, SimulateUndelegationsResponse, UnbondingEntriesResponse,
+    UndelegationOverride,
+};
 use eris::governance_helper::get_period;
 use eris::hub::{
     Batch, DelegationsResponse, ExchangeRatesResponse, PendingBatch, StateResponse,
@@ -29,6 +34,7 @@ pub fn config(deps: Deps<CustomQueryType>) -> StdResult<ConfigResponse> {
     Ok(ConfigResponse {
         owner: state.owner.load(deps.storage)?.into(),
         operator: state.operator.load(deps.storage)?.into(),
+        vote_operator: state.vote_operator.may_load(deps.storage)?.map(|addr| addr.into()),
         new_owner: state.new_owner.may_load(deps.storage)?.map(|addr| addr.into()),
         utoken: stake.utoken,
         stake_token: stake.denom,
@@ -67,7 +73,21 @@ pub fn config(deps: Deps<CustomQueryType>) -> StdResult<ConfigResponse> {
                 validator_count,
             },
         },
-        validator_proxy: state.validator_proxy.load(deps.storage)?.to_string()
+        validator_proxy: state.validator_proxy.load(deps.storage)?.to_string(),
+        undelegation_tolerance_bps: state.get_undelegation_tolerance_bps(deps.storage),
+        guardian: state.guardian.may_load(deps.storage)?.map(|addr| addr.into()),
+        timelock_delay: state.get_timelock_delay(deps.storage),
+        priority_unbond_fee_bps: state.get_priority_unbond_fee_bps(deps.storage),
+        priority_unbond_min_fee: state.get_priority_unbond_min_fee(deps.storage),
+        claim_contracts: state
+            .claim_contracts
+            .may_load(deps.storage)?
+            .unwrap_or_default()
+            .into_iter()
+            .map(|addr| addr.into())
+            .collect(),
+        tune_tip: state.tune_tip.may_load(deps.storage)?.filter(|tip| !tip.amount.is_zero()),
+        tune_tip_budget: state.get_tune_tip_budget(deps.storage),
     })
 }
 
@@ -114,13 +134,19 @@ pub fn state(deps: Deps<CustomQueryType>, env: Env) -> StdResult<StateResponse>
 
 pub fn wanted_delegations(
     deps: Deps<CustomQueryType>,
-    _env: Env,
+    env: Env,
 ) -> StdResult<WantedDelegationsResponse> {
     let state = State::default();
     let stake_token = state.stake_token.load(deps.storage)?;
 
-    let (delegations, _, _, share) =
-        get_utoken_per_validator_prepared(&state, deps.storage, &stake_token, &deps.querier, None)?;
+    let (delegations, _, _, share) = get_utoken_per_validator_prepared(
+        &state,
+        deps.storage,
+        &stake_token,
+        &deps.querier,
+        &env.contract.address,
+        None,
+    )?;
 
     Ok(WantedDelegationsResponse {
         delegations: sort_delegations(delegations),
@@ -153,6 +179,7 @@ pub fn simulate_wanted_delegations(
         deps.storage,
         &stake_token,
         &deps.querier,
+        &env.contract.address,
         Some(delegation_goal),
     )?;
 
@@ -368,12 +395,14 @@ pub fn delegations(deps: Deps<CustomQueryType>, _env: Env) -> StdResult<Delegati
 pub fn simulate_undelegations(
     deps: Deps<CustomQueryType>,
     env: Env,
-) -> StdResult<Vec<Undelegation>> {
+) -> StdResult<SimulateUndelegationsResponse> {
     let state = State::default();
     let stake = state.stake_token.load(deps.storage)?;
     let validators = state.get_validators(deps.storage, &deps.querier)?;
     let pending_batch = state.pending_batch.load(deps.storage)?;
     let alliance_delegations = state.alliance_delegations.load(deps.storage)?;
+    let current_time = env.block.time.seconds();
+    let unbonding_entries = state.get_unbonding_entries(deps.storage, current_time)?;
 
     let delegations = query_all_delegations(
         &alliance_delegations,
@@ -396,7 +425,83 @@ pub fn simulate_undelegations(
         &delegations,
         validators,
         &stake.utoken,
+        &unbonding_entries.saturated_validators(),
     )?;
 
-    Ok(new_undelegations)
+    let reason = if current_time < pending_batch.est_unbond_start_time {
+        Some(ContractError::SubmitBatchAfter(pending_batch.est_unbond_start_time).to_string())
+    } else {
+        assert_undelegations_complete(&new_undelegations, utoken_to_unbond)
+            .err()
+            .map(|err| err.to_string())
+    };
+
+    Ok(SimulateUndelegationsResponse {
+        undelegations: new_undelegations,
+        submittable: reason.is_none(),
+        reason,
+    })
+}
+
+pub fn unbonding_entries(
+    deps: Deps<CustomQueryType>,
+    env: Env,
+) -> StdResult<UnbondingEntriesResponse> {
+    let state = State::default();
+    let unbonding_entries = state.get_unbonding_entries(deps.storage, env.block.time.seconds())?;
+
+    Ok(UnbondingEntriesResponse {
+        max_entries: MAX_UNBONDING_ENTRIES as u32,
+        entries: unbonding_entries
+            .entries
+            .into_iter()
+            .sorted_by(|(a, _), (b, _)| a.cmp(b))
+            .map(|(validator, mut end_times)| {
+                end_times.sort_unstable();
+                (validator, end_times)
+            })
+            .collect(),
+    })
+}
+
+pub fn pending_config_changes(
+    deps: Deps<CustomQueryType>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<PendingConfigChange>> {
+    let state = State::default();
+
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    state
+        .pending_config_changes
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (_, v) = item?;
+            Ok(v)
+        })
+        .collect()
+}
+
+pub fn undelegation_overrides(
+    deps: Deps<CustomQueryType>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<UndelegationOverride>> {
+    let state = State::default();
+
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    state
+        .undelegation_overrides
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (_, v) = item?;
+            Ok(v)
+        })
+        .collect()
 }
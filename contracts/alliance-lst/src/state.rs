@@ -1,8 +1,8 @@
-use cosmwasm_std::{Addr, Coin, Decimal, QuerierWrapper, StdError, Storage};
+use cosmwasm_std::{Addr, Coin, Decimal, QuerierWrapper, StdError, StdResult, Storage, Uint128};
 use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 
 use eris::{
-    alliance_lst::AllianceStakeToken,
+    alliance_lst::{AllianceStakeToken, PendingConfigChange, UndelegationOverride},
     hub::{
         Batch, DelegationStrategy, FeeConfig, PendingBatch, SingleSwapConfig, UnbondRequest,
         WantedDelegationsShare,
@@ -12,8 +12,11 @@ use eris_chain_adapter::types::{CustomQueryType, DenomType, WithdrawType};
 use serde::{de::DeserializeOwned, Serialize};
 
 use crate::{
+    constants::{DEFAULT_TIMELOCK_DELAY, DEFAULT_UNDELEGATION_TOLERANCE_BPS},
     error::ContractError,
-    types::{alliance_delegations::AllianceDelegations, BooleanKey},
+    types::{
+        alliance_delegations::AllianceDelegations, unbonding_entries::UnbondingEntries, BooleanKey,
+    },
 };
 
 pub struct State<'a> {
@@ -21,6 +24,8 @@ pub struct State<'a> {
     pub owner: Item<'a, Addr>,
     /// Account who can call harvest
     pub operator: Item<'a, Addr>,
+    /// Operator who is allowed to vote on props
+    pub vote_operator: Item<'a, Addr>,
     /// Stages that must be used by permissionless users
     pub stages_preset: Item<'a, Vec<Vec<SingleSwapConfig>>>,
     /// Withdraws that must be used by permissionless users
@@ -49,6 +54,8 @@ pub struct State<'a> {
 
     /// stores all delegations
     pub alliance_delegations: Item<'a, AllianceDelegations>,
+    /// open unbonding entries per validator
+    pub unbonding_entries: Item<'a, UnbondingEntries>,
 
     /// Coins that can be reinvested
     pub unlocked_coins: Item<'a, Vec<Coin>>,
@@ -71,6 +78,36 @@ pub struct State<'a> {
     pub exchange_history: Map<'a, u64, Decimal>,
 
     pub default_max_spread: Item<'a, u64>,
+
+    /// Allowed deviation from the delegation goal for undelegations provided by the operator
+    pub undelegation_tolerance_bps: Item<'a, u16>,
+    /// Undelegations provided by the operator instead of the computed ones, by batch id
+    pub undelegation_overrides: Map<'a, u64, UndelegationOverride>,
+
+    /// Account who can veto pending config changes
+    pub guardian: Item<'a, Addr>,
+    /// Delay until proposed config changes can be executed, in seconds
+    pub timelock_delay: Item<'a, u64>,
+    /// ID of the last proposed config change
+    pub config_change_id: Item<'a, u64>,
+    /// Config changes waiting for their timelock, by id
+    pub pending_config_changes: Map<'a, u64, PendingConfigChange>,
+
+    /// Fee in stake tokens for priority unbonding, disabled if not set or 0
+    pub priority_unbond_fee_bps: Item<'a, u16>,
+    /// Minimum fee in stake tokens for priority unbonding
+    pub priority_unbond_min_fee: Item<'a, Uint128>,
+    /// ID of the last batch created by a priority unbond
+    pub priority_batch_id: Item<'a, u64>,
+    /// Contracts that can receive custom claim messages
+    pub claim_contracts: Item<'a, Vec<Addr>>,
+
+    /// Tip paid to keepers for tuning the delegations
+    pub tune_tip: Item<'a, Coin>,
+    /// Remaining budget of the tip denom the keeper tips are paid from
+    pub tune_tip_budget: Item<'a, Uint128>,
+    /// Period of the last tuning of the delegations
+    pub tune_period: Item<'a, u64>,
 }
 
 impl Default for State<'static> {
@@ -93,6 +130,7 @@ impl Default for State<'static> {
             owner: Item::new("owner"),
             new_owner: Item::new("new_owner"),
             operator: Item::new("operator"),
+            vote_operator: Item::new("vote_operator"),
             stages_preset: Item::new("stages_preset"),
             withdrawals_preset: Item::new("withdrawals_preset"),
             stake_token: Item::new("stake_token"),
@@ -104,6 +142,7 @@ impl Default for State<'static> {
             validator_proxy: Item::new("validator_proxy"),
             validators_proxy_item: Item::new("validators"),
             alliance_delegations: Item::new("alliance_delegations"),
+            unbonding_entries: Item::new("unbonding_entries"),
             unlocked_coins: Item::new("unlocked_coins"),
             pending_batch: Item::new("pending_batch"),
             previous_batches: IndexedMap::new("previous_batches", pb_indexes),
@@ -114,6 +153,19 @@ impl Default for State<'static> {
             allow_donations: Item::new("allow_donations"),
             exchange_history: Map::new("exchange_history"),
             default_max_spread: Item::new("default_max_spread"),
+            undelegation_tolerance_bps: Item::new("undelegation_tolerance_bps"),
+            undelegation_overrides: Map::new("undelegation_overrides"),
+            guardian: Item::new("guardian"),
+            timelock_delay: Item::new("timelock_delay"),
+            config_change_id: Item::new("config_change_id"),
+            pending_config_changes: Map::new("pending_config_changes"),
+            priority_unbond_fee_bps: Item::new("priority_unbond_fee_bps"),
+            priority_unbond_min_fee: Item::new("priority_unbond_min_fee"),
+            priority_batch_id: Item::new("priority_batch_id"),
+            claim_contracts: Item::new("claim_contracts"),
+            tune_tip: Item::new("tune_tip"),
+            tune_tip_budget: Item::new("tune_tip_budget"),
+            tune_period: Item::new("tune_period"),
         }
     }
 }
@@ -141,6 +193,21 @@ impl<'a> State<'a> {
         }
     }
 
+    pub fn assert_vote_operator(
+        &self,
+        storage: &dyn Storage,
+        sender: &Addr,
+    ) -> Result<(), ContractError> {
+        let vote_operator =
+            self.vote_operator.load(storage).map_err(|_| ContractError::NoVoteOperatorSet {})?;
+
+        if *sender == vote_operator {
+            Ok(())
+        } else {
+            Err(ContractError::UnauthorizedSenderNotVoteOperator {})
+        }
+    }
+
     pub fn assert_owner_or_operator(
         &self,
         storage: &dyn Storage,
@@ -154,6 +221,19 @@ impl<'a> State<'a> {
         }
     }
 
+    pub fn assert_owner_or_guardian(
+        &self,
+        storage: &dyn Storage,
+        sender: &Addr,
+    ) -> Result<(), ContractError> {
+        if self.guardian.may_load(storage)?.is_some_and(|guardian| *sender == guardian) {
+            Ok(())
+        } else {
+            self.assert_owner(storage, sender)
+                .map_err(|_| ContractError::UnauthorizedSenderNotGuardian {})
+        }
+    }
+
     pub fn get_or_preset<T>(
         &self,
         storage: &dyn Storage,
@@ -185,6 +265,42 @@ impl<'a> State<'a> {
         Decimal::percent(self.default_max_spread.load(storage).unwrap_or(10))
     }
 
+    pub fn get_undelegation_tolerance_bps(&self, storage: &dyn Storage) -> u16 {
+        self.undelegation_tolerance_bps.load(storage).unwrap_or(DEFAULT_UNDELEGATION_TOLERANCE_BPS)
+    }
+
+    pub fn get_timelock_delay(&self, storage: &dyn Storage) -> u64 {
+        self.timelock_delay.load(storage).unwrap_or(DEFAULT_TIMELOCK_DELAY)
+    }
+
+    pub fn get_priority_unbond_fee_bps(&self, storage: &dyn Storage) -> u16 {
+        self.priority_unbond_fee_bps.load(storage).unwrap_or_default()
+    }
+
+    pub fn get_priority_unbond_min_fee(&self, storage: &dyn Storage) -> Uint128 {
+        self.priority_unbond_min_fee.load(storage).unwrap_or_default()
+    }
+
+    pub fn get_tune_tip_budget(&self, storage: &dyn Storage) -> Uint128 {
+        self.tune_tip_budget.load(storage).unwrap_or_default()
+    }
+
+    /// Returns the id for the next batch. Priority batches take ids ahead of the pending batch, so
+    /// the id after the pending batch can already be in use.
+    pub fn next_batch_id(&self, storage: &dyn Storage, pending_batch_id: u64) -> StdResult<u64> {
+        let priority_batch_id = self.priority_batch_id.may_load(storage)?.unwrap_or_default();
+        Ok(pending_batch_id.max(priority_batch_id) + 1)
+    }
+
+    /// Loads all unbonding entries that are still open at `current_time`
+    pub fn get_unbonding_entries(
+        &self,
+        storage: &dyn Storage,
+        current_time: u64,
+    ) -> StdResult<UnbondingEntries> {
+        Ok(self.unbonding_entries.may_load(storage)?.unwrap_or_default().prune(current_time))
+    }
+
     pub fn get_validators(
         &self,
         storage: &dyn Storage,
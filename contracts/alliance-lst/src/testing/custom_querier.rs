@@ -1,8 +1,13 @@
+use std::collections::HashMap;
+
 use cosmwasm_std::testing::{BankQuerier, StakingQuerier, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
-    from_json, to_json_binary, Addr, Coin, ContractResult, Empty, FullDelegation, Querier,
+    from_json, to_json_binary, Addr, Binary, Coin, ContractResult, FullDelegation, Querier,
     QuerierResult, QueryRequest, SystemError, SystemResult, WasmQuery,
 };
+use serde::Serialize;
+
+use eris_chain_adapter::types::CustomQueryType;
 
 use crate::types::Delegation;
 
@@ -12,6 +17,8 @@ use super::helpers::{err_unsupported_query, MOCK_UTOKEN};
 pub(super) struct CustomQuerier {
     pub bank_querier: BankQuerier,
     pub staking_querier: StakingQuerier,
+    /// Responses of smart queries by contract address
+    pub wasm_smart: HashMap<String, Binary>,
 }
 
 impl Querier for CustomQuerier {
@@ -35,6 +42,10 @@ impl CustomQuerier {
         self.bank_querier = BankQuerier::new(&[(MOCK_CONTRACT_ADDR, balances)])
     }
 
+    pub fn set_smart_query_result(&mut self, contract_addr: &str, result: &impl Serialize) {
+        self.wasm_smart.insert(contract_addr.to_string(), to_json_binary(result).unwrap());
+    }
+
     pub fn set_staking_delegations(&mut self, delegations: &[Delegation]) {
         let fds = delegations
             .iter()
@@ -50,17 +61,54 @@ impl CustomQuerier {
         self.staking_querier = StakingQuerier::new(MOCK_UTOKEN, &[], &fds);
     }
 
-    pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+    pub fn handle_query(&self, request: &QueryRequest<CustomQueryType>) -> QuerierResult {
         match request {
+            #[cfg(feature = "X-terra-X")]
+            QueryRequest::Custom(
+                eris_chain_shared::alliance_query::AllianceQuery::Delegation {
+                    denom,
+                    delegator,
+                    validator,
+                },
+            ) => {
+                // alliance delegations are answered from the mocked staking delegations
+                let response: cosmwasm_std::DelegationResponse = from_json(
+                    self.staking_querier
+                        .query(&cosmwasm_std::StakingQuery::Delegation {
+                            delegator: delegator.clone(),
+                            validator: validator.clone(),
+                        })
+                        .unwrap()
+                        .unwrap(),
+                )
+                .unwrap();
+
+                match response.delegation {
+                    Some(delegation) => SystemResult::Ok(ContractResult::Ok(
+                        to_json_binary(&crate::types::alliance_querier::DelegationResponse {
+                            denom: denom.clone(),
+                            delegator: delegator.clone(),
+                            validator: validator.clone(),
+                            amount: delegation.amount,
+                        })
+                        .unwrap(),
+                    )),
+                    None => SystemResult::Ok(ContractResult::Err("delegation not found".into())),
+                }
+            },
+
             QueryRequest::Wasm(WasmQuery::Smart {
+                contract_addr,
                 msg,
-                ..
             }) => {
                 // if let Ok(query) = from_binary::<Cw20QueryMsg>(msg) {
                 //     return self.cw20_querier.handle_query(contract_addr, query);
                 // }
 
-                err_unsupported_query(msg)
+                match self.wasm_smart.get(contract_addr) {
+                    Some(result) => SystemResult::Ok(ContractResult::Ok(result.clone())),
+                    None => err_unsupported_query(msg),
+                }
             },
 
             QueryRequest::Bank(query) => self.bank_querier.query(query),
@@ -1,10 +1,10 @@
 use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
-    coin, from_json, to_json_binary, Addr, BlockInfo, ContractInfo, CosmosMsg, Decimal, Deps, Env,
-    OwnedDeps, QuerierResult, SubMsg, SystemError, SystemResult, Timestamp, Uint128, WasmMsg,
+    coin, from_json, to_json_binary, Addr, BlockInfo, Coin, ContractInfo, CosmosMsg, Decimal, Deps,
+    Env, OwnedDeps, QuerierResult, SubMsg, SystemError, SystemResult, Timestamp, Uint128, WasmMsg,
 };
 use eris::alliance_lst::{AllianceStakeToken, ExecuteMsg, InstantiateMsg, QueryMsg};
-use eris::hub::CallbackMsg;
+use eris::hub::{CallbackMsg, DelegationStrategy, SingleSwapConfig};
 use eris_chain_adapter::types::{
     chain, CustomMsgType, CustomQueryType, DenomType, HubChainConfig, StageType, WithdrawType,
 };
@@ -12,7 +12,7 @@ use serde::de::DeserializeOwned;
 
 use crate::contract::{instantiate, query};
 use crate::state::State;
-use eris_chain_shared::chain_trait::ChainInterface;
+use eris_chain_shared::chain_trait::{ChainInterface, DenomMetadata};
 
 use super::custom_querier::CustomQuerier;
 
@@ -23,6 +23,16 @@ pub const WHALE_BTC_POOL: &str =
 pub const BTC_DENOM: &str = "ibc/6E5BF71FE1BEBBD648C8A7CB7A790AEF0081120B2E5746E6563FC95764716D61";
 pub const WHALE_DENOM: &str = "uwhale";
 
+pub(super) fn denom_metadata() -> DenomMetadata {
+    DenomMetadata {
+        name: "Eris Amplified WhaleX".to_string(),
+        symbol: "ampWhaleX".to_string(),
+        display: "ampwhalex".to_string(),
+        exponent: 6,
+        description: "Liquid staked WhaleX".to_string(),
+    }
+}
+
 pub(super) fn err_unsupported_query<T: std::fmt::Debug>(request: T) -> QuerierResult {
     SystemResult::Err(SystemError::InvalidRequest {
         error: format!("[mock] unsupported query: {:?}", request),
@@ -103,6 +113,62 @@ pub fn check_received_coin(amount: u128, amount_stake: u128) -> SubMsg<CustomMsg
     }))
 }
 
+/// Fields of `ExecuteMsg::UpdateConfig`, so that tests only have to set the fields they change
+#[derive(Default)]
+pub(super) struct UpdateConfig {
+    pub protocol_fee_contract: Option<String>,
+    pub protocol_reward_fee: Option<Decimal>,
+    pub operator: Option<String>,
+    pub vote_operator: Option<String>,
+    pub stages_preset: Option<Vec<Vec<SingleSwapConfig>>>,
+    pub withdrawals_preset: Option<Vec<(WithdrawType, DenomType)>>,
+    pub allow_donations: Option<bool>,
+    pub delegation_strategy: Option<DelegationStrategy>,
+    pub default_max_spread: Option<u64>,
+    pub epoch_period: Option<u64>,
+    pub unbond_period: Option<u64>,
+    pub validator_proxy: Option<String>,
+    pub whale_denom: Option<String>,
+    pub btc_denom: Option<String>,
+    pub whale_btc_pool: Option<Addr>,
+    pub undelegation_tolerance_bps: Option<u16>,
+    pub guardian: Option<String>,
+    pub timelock_delay: Option<u64>,
+    pub priority_unbond_fee_bps: Option<u16>,
+    pub priority_unbond_min_fee: Option<Uint128>,
+    pub claim_contracts: Option<Vec<String>>,
+    pub tune_tip: Option<Coin>,
+}
+
+impl From<UpdateConfig> for ExecuteMsg {
+    fn from(config: UpdateConfig) -> Self {
+        ExecuteMsg::UpdateConfig {
+            protocol_fee_contract: config.protocol_fee_contract,
+            protocol_reward_fee: config.protocol_reward_fee,
+            operator: config.operator,
+            vote_operator: config.vote_operator,
+            stages_preset: config.stages_preset,
+            withdrawals_preset: config.withdrawals_preset,
+            allow_donations: config.allow_donations,
+            delegation_strategy: config.delegation_strategy,
+            default_max_spread: config.default_max_spread,
+            epoch_period: config.epoch_period,
+            unbond_period: config.unbond_period,
+            validator_proxy: config.validator_proxy,
+            whale_denom: config.whale_denom,
+            btc_denom: config.btc_denom,
+            whale_btc_pool: config.whale_btc_pool,
+            undelegation_tolerance_bps: config.undelegation_tolerance_bps,
+            guardian: config.guardian,
+            timelock_delay: config.timelock_delay,
+            priority_unbond_fee_bps: config.priority_unbond_fee_bps,
+            priority_unbond_min_fee: config.priority_unbond_min_fee,
+            claim_contracts: config.claim_contracts,
+            tune_tip: config.tune_tip,
+        }
+    }
+}
+
 //--------------------------------------------------------------------------------------------------
 // Test setup
 //--------------------------------------------------------------------------------------------------
@@ -128,16 +194,17 @@ pub(super) fn setup_test() -> OwnedDeps<MockStorage, MockApi, CustomQuerier, Cus
             whale_btc_pool: WHALE_BTC_POOL.to_string(),
             btc_denom: BTC_DENOM.to_string(),
             whale_denom: WHALE_DENOM.to_string(),
+            denom_metadata: Some(denom_metadata()),
+            guardian: "guardian".to_string(),
         },
     )
     .unwrap();
 
-    assert_eq!(res.messages.len(), 1);
-
-    assert_eq!(
-        res.messages[0].msg,
-        chain_test().create_denom_msg(get_stake_full_denom(), "stake".to_string())
-    );
+    // chains without denom metadata support only create the denom
+    let mut expected =
+        vec![chain_test().create_denom_msg(get_stake_full_denom(), "stake".to_string())];
+    expected.extend(chain_test().create_set_metadata_msg(get_stake_full_denom(), denom_metadata()));
+    assert_eq!(res.messages.into_iter().map(|m| m.msg).collect::<Vec<_>>(), expected);
 
     deps
 }
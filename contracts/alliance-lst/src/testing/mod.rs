@@ -2,6 +2,18 @@ mod custom_querier;
 mod helpers;
 pub mod test_defined_delegations;
 pub mod tests_claim;
+mod tests_config_timelock;
 mod tests_default;
+mod tests_denom_metadata;
 pub mod tests_exchange_rates;
 pub mod tests_gauges;
+#[cfg(feature = "X-whitewhale-X")]
+mod tests_harvest;
+mod tests_liquidity;
+mod tests_math_proptest;
+mod tests_migrate;
+mod tests_priority_unbond;
+mod tests_tune_delegations;
+mod tests_unbonding_entries;
+mod tests_undelegation_overrides;
+mod tests_vote;
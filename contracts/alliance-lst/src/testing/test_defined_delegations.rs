@@ -10,8 +10,8 @@ use crate::contract::{execute, instantiate};
 use crate::error::ContractError;
 use crate::state::State;
 use crate::testing::helpers::{
-    chain_test, check_received_coin, get_stake_full_denom, BTC_DENOM, MOCK_UTOKEN, WHALE_BTC_POOL,
-    WHALE_DENOM,
+    chain_test, check_received_coin, denom_metadata, get_stake_full_denom, BTC_DENOM, MOCK_UTOKEN,
+    WHALE_BTC_POOL, WHALE_DENOM,
 };
 use crate::types::{Delegation, Redelegation};
 use eris::alliance_lst::{AllianceStakeToken, ExecuteMsg, InstantiateMsg, QueryMsg};
@@ -51,15 +51,16 @@ fn setup_test() -> OwnedDeps<MockStorage, MockApi, CustomQuerier, CustomQueryTyp
             whale_btc_pool: WHALE_BTC_POOL.to_string(),
             btc_denom: BTC_DENOM.to_string(),
             whale_denom: WHALE_DENOM.to_string(),
+            denom_metadata: Some(denom_metadata()),
+            guardian: "guardian".to_string(),
         },
     )
     .unwrap();
 
-    assert_eq!(res.messages.len(), 1);
-    assert_eq!(
-        res.messages[0].msg,
-        chain_test().create_denom_msg(get_stake_full_denom(), "stake".to_string())
-    );
+    let mut expected =
+        vec![chain_test().create_denom_msg(get_stake_full_denom(), "stake".to_string())];
+    expected.extend(chain_test().create_set_metadata_msg(get_stake_full_denom(), denom_metadata()));
+    assert_eq!(res.messages.into_iter().map(|m| m.msg).collect::<Vec<_>>(), expected);
 
     let res = execute(
         deps.as_mut(),
@@ -161,6 +162,7 @@ fn validate_update() {
             protocol_fee_contract: None,
             protocol_reward_fee: None,
             operator: None,
+            vote_operator: None,
             stages_preset: None,
             withdrawals_preset: None,
             allow_donations: None,
@@ -174,6 +176,13 @@ fn validate_update() {
             whale_denom: None,
             btc_denom: None,
             whale_btc_pool: None,
+            undelegation_tolerance_bps: None,
+            guardian: None,
+            timelock_delay: None,
+            priority_unbond_fee_bps: None,
+            priority_unbond_min_fee: None,
+            claim_contracts: None,
+            tune_tip: None,
         },
     )
     .unwrap_err();
@@ -187,6 +196,7 @@ fn validate_update() {
             protocol_fee_contract: None,
             protocol_reward_fee: None,
             operator: None,
+            vote_operator: None,
             stages_preset: None,
             withdrawals_preset: None,
             allow_donations: None,
@@ -201,6 +211,13 @@ fn validate_update() {
             whale_denom: None,
             btc_denom: None,
             whale_btc_pool: None,
+            undelegation_tolerance_bps: None,
+            guardian: None,
+            timelock_delay: None,
+            priority_unbond_fee_bps: None,
+            priority_unbond_min_fee: None,
+            claim_contracts: None,
+            tune_tip: None,
         },
     )
     .unwrap_err();
@@ -214,6 +231,7 @@ fn validate_update() {
             protocol_fee_contract: None,
             protocol_reward_fee: None,
             operator: None,
+            vote_operator: None,
             stages_preset: None,
             withdrawals_preset: None,
             allow_donations: None,
@@ -227,6 +245,13 @@ fn validate_update() {
             whale_denom: None,
             btc_denom: None,
             whale_btc_pool: None,
+            undelegation_tolerance_bps: None,
+            guardian: None,
+            timelock_delay: None,
+            priority_unbond_fee_bps: None,
+            priority_unbond_min_fee: None,
+            claim_contracts: None,
+            tune_tip: None,
         },
     )
     .unwrap_err();
@@ -240,6 +265,7 @@ fn validate_update() {
             protocol_fee_contract: None,
             protocol_reward_fee: None,
             operator: None,
+            vote_operator: None,
             stages_preset: None,
             withdrawals_preset: None,
             allow_donations: None,
@@ -253,6 +279,13 @@ fn validate_update() {
             whale_denom: None,
             btc_denom: None,
             whale_btc_pool: None,
+            undelegation_tolerance_bps: None,
+            guardian: None,
+            timelock_delay: None,
+            priority_unbond_fee_bps: None,
+            priority_unbond_min_fee: None,
+            claim_contracts: None,
+            tune_tip: None,
         },
     )
     .unwrap();
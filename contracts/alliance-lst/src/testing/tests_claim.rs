@@ -1,13 +1,17 @@
-use cosmwasm_std::attr;
-use cosmwasm_std::testing::mock_info;
+use cosmwasm_std::testing::{mock_info, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    attr, coin, to_json_binary, Addr, BankMsg, CosmosMsg, SubMsg, Uint128, WasmMsg,
+};
+use cw20::{BalanceResponse, Cw20ExecuteMsg};
 
 use eris::alliance_lst::ExecuteMsg;
-use eris::hub::ClaimType;
+use eris::hub::{CallbackMsg, ClaimType};
 
-use crate::claim::ClaimExecuteMsg;
+use crate::claim::{ClaimExecuteMsg, MerkleAirdropExecuteMsg};
 use crate::contract::execute;
 use crate::error::ContractError;
-use crate::testing::helpers::setup_test;
+use crate::state::State;
+use crate::testing::helpers::{check_received_coin, setup_test, MOCK_UTOKEN, WHALE_DENOM};
 
 use super::helpers::mock_env_at_timestamp;
 
@@ -25,6 +29,8 @@ fn check_claim() {
         mock_info("anyone", &[]),
         ExecuteMsg::Claim {
             claims: vec![],
+            route_denoms: None,
+            route_cw20s: None,
         },
     )
     .unwrap_err();
@@ -36,6 +42,8 @@ fn check_claim() {
         mock_info("owner", &[]),
         ExecuteMsg::Claim {
             claims: vec![],
+            route_denoms: None,
+            route_cw20s: None,
         },
     )
     .unwrap_err();
@@ -50,6 +58,8 @@ fn check_claim() {
                 ClaimType::Default("claim1".to_string()),
                 ClaimType::Default("claim2".to_string()),
             ],
+            route_denoms: None,
+            route_cw20s: None,
         },
     )
     .unwrap();
@@ -66,3 +76,246 @@ fn check_claim() {
 
     assert_eq!(res.attributes, vec![attr("action", "erishub/exec_claim")]);
 }
+
+#[test]
+fn check_claim_airdrop_and_custom() {
+    let mut deps = setup_test();
+    let state = State::default();
+
+    let custom_msg = to_json_binary(&ClaimExecuteMsg::Claim {}).unwrap();
+    let claims = vec![
+        ClaimType::MerkleAirdrop {
+            contract: "airdrop".to_string(),
+            stage: 2,
+            amount: Uint128::new(1000),
+            proof: vec!["proof".to_string()],
+        },
+        ClaimType::Custom {
+            contract: "custom".to_string(),
+            msg: custom_msg.clone(),
+        },
+    ];
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(0),
+        mock_info("owner", &[]),
+        ExecuteMsg::Claim {
+            claims: claims.clone(),
+            route_denoms: None,
+            route_cw20s: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(res, ContractError::ClaimContractNotWhitelisted("custom".to_string()));
+
+    state.claim_contracts.save(deps.as_mut().storage, &vec![Addr::unchecked("custom")]).unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(0),
+        mock_info("owner", &[]),
+        ExecuteMsg::Claim {
+            claims,
+            route_denoms: None,
+            route_cw20s: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 2);
+    assert_eq!(
+        res.messages[0].msg,
+        MerkleAirdropExecuteMsg::Claim {
+            stage: 2,
+            amount: Uint128::new(1000),
+            proof: vec!["proof".to_string()],
+        }
+        .into_msg("airdrop".to_string())
+        .unwrap()
+    );
+    assert_eq!(
+        res.messages[1].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "custom".to_string(),
+            msg: custom_msg,
+            funds: vec![],
+        })
+    );
+}
+
+#[test]
+fn check_claim_routing() {
+    let mut deps = setup_test();
+    let state = State::default();
+
+    deps.querier.set_bank_balances(&[coin(100, MOCK_UTOKEN), coin(50, "uother")]);
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(0),
+        mock_info("owner", &[]),
+        ExecuteMsg::Claim {
+            claims: vec![ClaimType::Default("claim1".to_string())],
+            route_denoms: Some(vec![
+                MOCK_UTOKEN.to_string(),
+                WHALE_DENOM.to_string(),
+                "uother".to_string(),
+                "uother".to_string(),
+            ]),
+            route_cw20s: None,
+        },
+    )
+    .unwrap();
+
+    let snapshots = vec![coin(100, MOCK_UTOKEN), coin(0, WHALE_DENOM), coin(50, "uother")];
+    assert_eq!(res.messages.len(), 2);
+    assert_eq!(
+        res.messages[1].msg,
+        CallbackMsg::RouteClaimed {
+            snapshots: snapshots.clone(),
+            cw20_snapshots: vec![],
+        }
+        .into_cosmos_msg(&Addr::unchecked(MOCK_CONTRACT_ADDR))
+        .unwrap()
+    );
+
+    // only callable by the contract itself
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(0),
+        mock_info("owner", &[]),
+        ExecuteMsg::Callback(CallbackMsg::RouteClaimed {
+            snapshots: snapshots.clone(),
+            cw20_snapshots: vec![],
+        }),
+    )
+    .unwrap_err();
+    assert_eq!(res, ContractError::CallbackOnlyCalledByContract {});
+
+    deps.querier.set_bank_balances(&[
+        coin(130, MOCK_UTOKEN),
+        coin(20, WHALE_DENOM),
+        coin(80, "uother"),
+    ]);
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(0),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::Callback(CallbackMsg::RouteClaimed {
+            snapshots,
+            cw20_snapshots: vec![],
+        }),
+    )
+    .unwrap();
+
+    // tokens that can't be compounded go to the fee contract
+    assert_eq!(res.messages.len(), 5);
+    assert_eq!(
+        res.messages[0],
+        SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "fee".to_string(),
+            amount: vec![coin(30, "uother")],
+        }))
+    );
+    assert_eq!(
+        res.messages[1].msg,
+        CallbackMsg::HalfSwapReward {}
+            .into_cosmos_msg(&Addr::unchecked(MOCK_CONTRACT_ADDR))
+            .unwrap()
+    );
+    assert_eq!(
+        res.messages[2].msg,
+        CallbackMsg::ProvideLiquidity {}
+            .into_cosmos_msg(&Addr::unchecked(MOCK_CONTRACT_ADDR))
+            .unwrap()
+    );
+    assert_eq!(res.messages[3], check_received_coin(130, 0));
+    assert_eq!(
+        res.messages[4].msg,
+        CallbackMsg::Reinvest {
+            skip_fee: false,
+        }
+        .into_cosmos_msg(&Addr::unchecked(MOCK_CONTRACT_ADDR))
+        .unwrap()
+    );
+
+    assert_eq!(
+        state.unlocked_coins.load(deps.as_ref().storage).unwrap(),
+        vec![coin(30, MOCK_UTOKEN)]
+    );
+}
+
+#[test]
+fn check_claim_cw20_airdrop() {
+    let mut deps = setup_test();
+
+    deps.querier.set_smart_query_result(
+        "airdrop_token",
+        &BalanceResponse {
+            balance: Uint128::new(5),
+        },
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(0),
+        mock_info("owner", &[]),
+        ExecuteMsg::Claim {
+            claims: vec![ClaimType::MerkleAirdrop {
+                contract: "airdrop".to_string(),
+                stage: 1,
+                amount: Uint128::new(100),
+                proof: vec!["proof".to_string()],
+            }],
+            route_denoms: None,
+            route_cw20s: Some(vec!["airdrop_token".to_string(), "airdrop_token".to_string()]),
+        },
+    )
+    .unwrap();
+
+    let cw20_snapshots = vec![(Addr::unchecked("airdrop_token"), Uint128::new(5))];
+    assert_eq!(res.messages.len(), 2);
+    assert_eq!(
+        res.messages[1].msg,
+        CallbackMsg::RouteClaimed {
+            snapshots: vec![],
+            cw20_snapshots: cw20_snapshots.clone(),
+        }
+        .into_cosmos_msg(&Addr::unchecked(MOCK_CONTRACT_ADDR))
+        .unwrap()
+    );
+
+    deps.querier.set_smart_query_result(
+        "airdrop_token",
+        &BalanceResponse {
+            balance: Uint128::new(105),
+        },
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(0),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::Callback(CallbackMsg::RouteClaimed {
+            snapshots: vec![],
+            cw20_snapshots,
+        }),
+    )
+    .unwrap();
+
+    // cw20 tokens can't be compounded, only the claimed amount goes to the fee contract
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "airdrop_token".to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "fee".to_string(),
+                amount: Uint128::new(100),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+    assert_eq!(res.events[0].attributes[0].value, "100airdrop_token");
+}
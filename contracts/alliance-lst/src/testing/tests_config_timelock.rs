@@ -0,0 +1,217 @@
+use cosmwasm_std::testing::{mock_info, MockApi, MockStorage};
+use cosmwasm_std::{Addr, OwnedDeps, StdError};
+
+use eris::alliance_lst::{
+    ConfigResponse, ExecuteMsg, PendingConfigChange, QueryMsg, TimelockedConfig,
+};
+use eris_chain_adapter::types::CustomQueryType;
+
+use crate::constants::DEFAULT_TIMELOCK_DELAY;
+use crate::contract::execute;
+use crate::error::{ContractError, ContractResult};
+use crate::state::State;
+use crate::testing::helpers::{query_helper_env, setup_test, WHALE_DENOM};
+
+use super::custom_querier::CustomQuerier;
+use super::helpers::{mock_env_at_timestamp, UpdateConfig};
+
+const PROPOSE_TIME: u64 = 20000;
+const EXECUTE_TIME: u64 = PROPOSE_TIME + DEFAULT_TIMELOCK_DELAY;
+
+fn update_config(
+    deps: &mut OwnedDeps<MockStorage, MockApi, CustomQuerier, CustomQueryType>,
+    sender: &str,
+    config: UpdateConfig,
+) -> ContractResult {
+    execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(PROPOSE_TIME),
+        mock_info(sender, &[]),
+        config.into(),
+    )
+}
+
+fn execute_change(
+    deps: &mut OwnedDeps<MockStorage, MockApi, CustomQuerier, CustomQueryType>,
+    sender: &str,
+    id: u64,
+    time: u64,
+) -> ContractResult {
+    execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(time),
+        mock_info(sender, &[]),
+        ExecuteMsg::ExecuteConfigChange {
+            id,
+        },
+    )
+}
+
+fn veto_change(
+    deps: &mut OwnedDeps<MockStorage, MockApi, CustomQuerier, CustomQueryType>,
+    sender: &str,
+    id: u64,
+) -> ContractResult {
+    execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(PROPOSE_TIME),
+        mock_info(sender, &[]),
+        ExecuteMsg::VetoConfigChange {
+            id,
+        },
+    )
+}
+
+fn pending_changes(
+    deps: &OwnedDeps<MockStorage, MockApi, CustomQuerier, CustomQueryType>,
+) -> Vec<PendingConfigChange> {
+    query_helper_env(
+        deps.as_ref(),
+        QueryMsg::PendingConfigChanges {
+            start_after: None,
+            limit: None,
+        },
+        PROPOSE_TIME,
+    )
+}
+
+#[test]
+fn sensitive_fields_are_timelocked() {
+    let mut deps = setup_test();
+
+    let res = update_config(
+        &mut deps,
+        "owner",
+        UpdateConfig {
+            operator: Some("new_operator".to_string()),
+            protocol_fee_contract: Some("new_fee".to_string()),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    let event = res.events.iter().find(|e| e.ty == "erishub/config_change_proposed").unwrap();
+    assert!(event
+        .attributes
+        .iter()
+        .any(|a| a.key == "fields" && a.value == "protocol_fee_contract"));
+    assert!(event
+        .attributes
+        .iter()
+        .any(|a| a.key == "executable_at" && a.value == EXECUTE_TIME.to_string()));
+
+    // operator is changed instantly, the fee contract only after the delay
+    let config: ConfigResponse = query_helper_env(deps.as_ref(), QueryMsg::Config {}, PROPOSE_TIME);
+    assert_eq!(config.operator, "new_operator".to_string());
+    assert_eq!(config.fee_config.protocol_fee_contract, Addr::unchecked("fee"));
+    assert_eq!(config.timelock_delay, DEFAULT_TIMELOCK_DELAY);
+
+    assert_eq!(
+        pending_changes(&deps),
+        vec![PendingConfigChange {
+            id: 1,
+            change: TimelockedConfig {
+                protocol_fee_contract: Some("new_fee".to_string()),
+                ..TimelockedConfig::default()
+            },
+            proposed_at: PROPOSE_TIME,
+            executable_at: EXECUTE_TIME,
+        }]
+    );
+
+    let res = execute_change(&mut deps, "owner", 1, EXECUTE_TIME - 1).unwrap_err();
+    assert_eq!(res, ContractError::ConfigChangeTimelocked(1, EXECUTE_TIME));
+
+    let res = execute_change(&mut deps, "new_operator", 1, EXECUTE_TIME).unwrap_err();
+    assert_eq!(res, ContractError::Unauthorized {});
+
+    let res = execute_change(&mut deps, "owner", 1, EXECUTE_TIME).unwrap();
+    let event = res.events.iter().find(|e| e.ty == "erishub/config_change_executed").unwrap();
+    assert!(event.attributes.iter().any(|a| a.key == "id" && a.value == "1"));
+
+    let fee_config = State::default().fee_config.load(deps.as_ref().storage).unwrap();
+    assert_eq!(fee_config.protocol_fee_contract, Addr::unchecked("new_fee"));
+    assert_eq!(pending_changes(&deps), vec![]);
+
+    // an executed change can't be executed again
+    let res = execute_change(&mut deps, "owner", 1, EXECUTE_TIME).unwrap_err();
+    assert!(matches!(res, ContractError::Std(StdError::NotFound { .. })));
+}
+
+#[test]
+fn guardian_can_veto_changes() {
+    let mut deps = setup_test();
+
+    let config: ConfigResponse = query_helper_env(deps.as_ref(), QueryMsg::Config {}, PROPOSE_TIME);
+    assert_eq!(config.guardian, Some("guardian".to_string()));
+
+    update_config(
+        &mut deps,
+        "owner",
+        UpdateConfig {
+            guardian: Some("new_guardian".to_string()),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    execute_change(&mut deps, "owner", 1, EXECUTE_TIME).unwrap();
+
+    let config: ConfigResponse = query_helper_env(deps.as_ref(), QueryMsg::Config {}, PROPOSE_TIME);
+    assert_eq!(config.guardian, Some("new_guardian".to_string()));
+
+    update_config(
+        &mut deps,
+        "owner",
+        UpdateConfig {
+            protocol_fee_contract: Some("evil".to_string()),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    let res = veto_change(&mut deps, "guardian", 2).unwrap_err();
+    assert_eq!(res, ContractError::UnauthorizedSenderNotGuardian {});
+
+    let res = veto_change(&mut deps, "new_guardian", 2).unwrap();
+    let event = res.events.iter().find(|e| e.ty == "erishub/config_change_vetoed").unwrap();
+    assert!(event.attributes.iter().any(|a| a.key == "sender" && a.value == "new_guardian"));
+    assert_eq!(pending_changes(&deps), vec![]);
+
+    let res = execute_change(&mut deps, "owner", 2, EXECUTE_TIME).unwrap_err();
+    assert!(matches!(res, ContractError::Std(StdError::NotFound { .. })));
+
+    let fee_config = State::default().fee_config.load(deps.as_ref().storage).unwrap();
+    assert_eq!(fee_config.protocol_fee_contract, Addr::unchecked("fee"));
+}
+
+#[test]
+fn swap_denoms_are_timelocked() {
+    let mut deps = setup_test();
+    let state = State::default();
+
+    update_config(
+        &mut deps,
+        "owner",
+        UpdateConfig {
+            whale_denom: Some("uwhale2".to_string()),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(state.whale_denom.load(deps.as_ref().storage).unwrap(), WHALE_DENOM.to_string());
+    assert_eq!(
+        pending_changes(&deps),
+        vec![PendingConfigChange {
+            id: 1,
+            change: TimelockedConfig {
+                whale_denom: Some("uwhale2".to_string()),
+                ..TimelockedConfig::default()
+            },
+            proposed_at: PROPOSE_TIME,
+            executable_at: EXECUTE_TIME,
+        }]
+    );
+
+    execute_change(&mut deps, "owner", 1, EXECUTE_TIME).unwrap();
+    assert_eq!(state.whale_denom.load(deps.as_ref().storage).unwrap(), "uwhale2".to_string());
+}
@@ -0,0 +1,129 @@
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{Decimal, StdError};
+
+use eris::alliance_lst::{ExecuteMsg, InstantiateMsg};
+use eris_chain_shared::chain_trait::{ChainInterface, DenomMetadata};
+
+use crate::contract::{execute, instantiate};
+use crate::error::ContractError;
+use crate::testing::helpers::{
+    chain_test, denom_metadata, get_stake_full_denom, mock_dependencies, mock_env_at_timestamp,
+    setup_test, BTC_DENOM, MOCK_UTOKEN, WHALE_BTC_POOL, WHALE_DENOM,
+};
+
+#[test]
+fn update_denom_metadata() {
+    let mut deps = setup_test();
+
+    let metadata = DenomMetadata {
+        symbol: "ampWHALEX".to_string(),
+        ..denom_metadata()
+    };
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("operator", &[]),
+        ExecuteMsg::UpdateDenomMetadata {
+            metadata: metadata.clone(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateDenomMetadata {
+            metadata: DenomMetadata {
+                exponent: 0,
+                ..metadata.clone()
+            },
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("exponent of the display denom can't be zero").into());
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateDenomMetadata {
+            metadata: DenomMetadata {
+                display: "1amp whale".to_string(),
+                ..metadata.clone()
+            },
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("invalid denom: 1amp whale").into());
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateDenomMetadata {
+            metadata: DenomMetadata {
+                display: get_stake_full_denom(),
+                ..metadata.clone()
+            },
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("display denom must differ from the base denom").into());
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateDenomMetadata {
+            metadata: metadata.clone(),
+        },
+    );
+
+    match chain_test().create_set_metadata_msg(get_stake_full_denom(), metadata) {
+        Some(msg) => {
+            let res = res.unwrap();
+            assert_eq!(res.messages.len(), 1);
+            assert_eq!(res.messages[0].msg, msg);
+        },
+        None => {
+            assert_eq!(res.unwrap_err(), ContractError::NotSupported("denom metadata".into()));
+        },
+    }
+}
+
+#[test]
+fn instantiate_without_denom_metadata() {
+    let mut deps = mock_dependencies();
+
+    let res = instantiate(
+        deps.as_mut(),
+        mock_env_at_timestamp(10000),
+        mock_info("deployer", &[]),
+        InstantiateMsg {
+            owner: "owner".to_string(),
+            denom: "stake".to_string(),
+            utoken: MOCK_UTOKEN.to_string(),
+            epoch_period: 259200,
+            unbond_period: 1814400,
+            protocol_fee_contract: "fee".to_string(),
+            protocol_reward_fee: Decimal::from_ratio(1u128, 100u128),
+            operator: "operator".to_string(),
+            delegation_strategy: None,
+            validator_proxy: "proxy".to_string(),
+            whale_btc_pool: WHALE_BTC_POOL.to_string(),
+            btc_denom: BTC_DENOM.to_string(),
+            whale_denom: WHALE_DENOM.to_string(),
+            denom_metadata: None,
+            guardian: "guardian".to_string(),
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        res.messages.into_iter().map(|m| m.msg).collect::<Vec<_>>(),
+        vec![chain_test().create_denom_msg(get_stake_full_denom(), "stake".to_string())]
+    );
+}
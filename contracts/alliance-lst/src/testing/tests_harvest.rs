@@ -0,0 +1,130 @@
+use cosmwasm_std::testing::{mock_info, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{coin, coins, to_json_binary, Addr, CosmosMsg, Decimal, Uint128, WasmMsg};
+
+use eris::alliance_lst::ExecuteMsg;
+use eris::hub::CallbackMsg;
+use eris_chain_adapter::types::{native_denom, StageType};
+use eris_whitewhale::adapters::whitewhaledex::AssetInfo;
+use eris_whitewhale::adapters::whitewhalerouter::{
+    ExecuteMsg as RouterExecuteMsg, SimulateSwapOperationsResponse, SwapOperation,
+};
+
+use crate::constants::DEFAULT_TIMELOCK_DELAY;
+use crate::contract::execute;
+use crate::testing::helpers::{
+    check_received_coin, mock_env_at_timestamp, setup_test, UpdateConfig, BTC_DENOM, WHALE_DENOM,
+};
+
+const PROPOSE_TIME: u64 = 20000;
+const HARVEST_TIME: u64 = PROPOSE_TIME + DEFAULT_TIMELOCK_DELAY;
+
+fn route() -> Vec<SwapOperation> {
+    let native = |denom: &str| AssetInfo::NativeToken {
+        denom: denom.to_string(),
+    };
+    vec![
+        SwapOperation::TerraSwap {
+            offer_asset_info: native("uother"),
+            ask_asset_info: native(BTC_DENOM),
+        },
+        SwapOperation::TerraSwap {
+            offer_asset_info: native(BTC_DENOM),
+            ask_asset_info: native(WHALE_DENOM),
+        },
+    ]
+}
+
+#[test]
+fn harvesting_runs_router_stages_preset() {
+    let mut deps = setup_test();
+
+    let stage =
+        vec![(StageType::router("router", route()), native_denom("uother"), None, None, None)];
+
+    execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(PROPOSE_TIME),
+        mock_info("owner", &[]),
+        UpdateConfig {
+            stages_preset: Some(vec![stage.clone()]),
+            ..Default::default()
+        }
+        .into(),
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(HARVEST_TIME),
+        mock_info("owner", &[]),
+        ExecuteMsg::ExecuteConfigChange {
+            id: 1,
+        },
+    )
+    .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(HARVEST_TIME),
+        mock_info("keeper", &[]),
+        ExecuteMsg::Harvest {
+            validators: Some(vec!["alice".to_string()]),
+            withdrawals: None,
+            stages: None,
+        },
+    )
+    .unwrap();
+
+    // withdraw, preset stage, half swap, provide liquidity, check received coin, reinvest
+    let contract = Addr::unchecked(MOCK_CONTRACT_ADDR);
+    assert_eq!(res.messages.len(), 6);
+    assert_eq!(
+        res.messages[1].msg,
+        CallbackMsg::SingleStageSwap {
+            stage: stage.clone(),
+            index: 0,
+        }
+        .into_cosmos_msg(&contract)
+        .unwrap()
+    );
+    assert_eq!(
+        res.messages[2].msg,
+        CallbackMsg::HalfSwapReward {}.into_cosmos_msg(&contract).unwrap()
+    );
+    assert_eq!(res.messages[4], check_received_coin(0, 0));
+
+    // the minimum receive of the whole route is limited by the simulated swap
+    deps.querier.set_bank_balances(&[coin(1000, "uother")]);
+    deps.querier.set_smart_query_result(
+        "router",
+        &SimulateSwapOperationsResponse {
+            amount: Uint128::new(500),
+        },
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(HARVEST_TIME),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::Callback(CallbackMsg::SingleStageSwap {
+            stage,
+            index: 0,
+        }),
+    )
+    .unwrap();
+
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "router".to_string(),
+            msg: to_json_binary(&RouterExecuteMsg::ExecuteSwapOperations {
+                operations: route(),
+                minimum_receive: Some(Uint128::new(450)),
+                to: None,
+                max_spread: Some(Decimal::percent(10)),
+            })
+            .unwrap(),
+            funds: coins(1000, "uother"),
+        })
+    );
+}
@@ -0,0 +1,64 @@
+use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{coin, Addr, CosmosMsg, Uint128, WasmMsg};
+
+use eris::alliance_lst::ExecuteMsg;
+use eris::hub::CallbackMsg;
+use eris_chain_adapter::types::{native_denom, HubChainConfig, WithdrawType};
+use eris_chain_shared::chain_trait::ChainInterface;
+
+use crate::contract::execute;
+use crate::error::ContractError;
+use crate::testing::helpers::{chain_test, setup_test, BTC_DENOM, WHALE_BTC_POOL, WHALE_DENOM};
+
+#[test]
+fn providing_liquidity() {
+    let mut deps = setup_test();
+
+    deps.querier.set_bank_balances(&[coin(1000, WHALE_DENOM)]);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::Callback(CallbackMsg::ProvideLiquidity {}),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NoReward {});
+
+    deps.querier.set_bank_balances(&[coin(1000, WHALE_DENOM), coin(20, BTC_DENOM)]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::Callback(CallbackMsg::ProvideLiquidity {}),
+    )
+    .unwrap();
+
+    let expected = chain_test()
+        .create_provide_liquidity_msg(
+            &deps.as_ref().querier.into_empty(),
+            || Ok(HubChainConfig {}),
+            WithdrawType::Dex {
+                addr: Addr::unchecked(WHALE_BTC_POOL),
+            },
+            vec![
+                (native_denom(WHALE_DENOM), Uint128::new(1000)),
+                (native_denom(BTC_DENOM), Uint128::new(20)),
+            ],
+            None,
+        )
+        .unwrap();
+    assert_eq!(res.messages.iter().map(|m| m.msg.clone()).collect::<Vec<_>>(), expected);
+
+    match &res.messages[0].msg {
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr,
+            funds,
+            ..
+        }) => {
+            assert_eq!(contract_addr, WHALE_BTC_POOL);
+            // funds need to be sorted by denom
+            assert_eq!(funds, &vec![coin(20, BTC_DENOM), coin(1000, WHALE_DENOM)]);
+        },
+        msg => panic!("unexpected message {:?}", msg),
+    }
+}
@@ -0,0 +1,344 @@
+use std::collections::{HashMap, HashSet};
+
+use cosmwasm_std::testing::MockStorage;
+use cosmwasm_std::{Decimal, Uint128, Uint256};
+use eris::hub::{Batch, WantedDelegationsShare};
+use proptest::prelude::*;
+
+use crate::math::{
+    compute_mint_amount, compute_redelegations_for_rebalancing, compute_unbond_amount,
+    compute_undelegations, get_utoken_per_validator, reconcile_batches,
+};
+use crate::state::State;
+use crate::testing::helpers::MOCK_UTOKEN;
+use crate::types::Delegation;
+
+const UNLISTED: &str = "unlisted";
+
+/// Delegation strategy of the hub, stored as the delegation goal.
+#[derive(Debug, Clone)]
+enum DelegationStrategy {
+    Uniform,
+    /// Weights are converted to bps that sum up to 10000
+    Defined(Vec<u64>),
+    /// Weights are used as they are, like the votes of a gauge
+    Gauges(Vec<u64>),
+}
+
+impl DelegationStrategy {
+    fn goal(&self, validators: &[String]) -> Option<WantedDelegationsShare> {
+        let shares = match self {
+            DelegationStrategy::Uniform => return None,
+            DelegationStrategy::Defined(weights) => {
+                let total: u64 = weights.iter().sum();
+                let mut bps: Vec<u64> = weights.iter().map(|w| w * 10000 / total).collect();
+                let first = weights.iter().position(|w| *w > 0).unwrap();
+                bps[first] += 10000 - bps.iter().sum::<u64>();
+                validators
+                    .iter()
+                    .zip(bps)
+                    .filter(|(_, bps)| *bps > 0)
+                    .map(|(val, bps)| (val.clone(), Decimal::from_ratio(bps, 10000u64)))
+                    .collect()
+            },
+            DelegationStrategy::Gauges(weights) => {
+                let total: u64 = weights.iter().sum();
+                validators
+                    .iter()
+                    .zip(weights)
+                    .filter(|(_, weight)| **weight > 0)
+                    .map(|(val, weight)| (val.clone(), Decimal::from_ratio(*weight, total)))
+                    .collect()
+            },
+        };
+
+        Some(WantedDelegationsShare {
+            tune_time: 0,
+            tune_period: 0,
+            shares,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct DelegationSetup {
+    validators: Vec<String>,
+    delegations: Vec<Delegation>,
+    strategy: DelegationStrategy,
+    /// per validator, whether it has no free unbonding entry left
+    saturated: Vec<bool>,
+}
+
+impl DelegationSetup {
+    fn staked(&self) -> u128 {
+        self.delegations.iter().map(|d| d.amount).sum()
+    }
+
+    fn saturated_validators(&self) -> HashSet<String> {
+        self.validators
+            .iter()
+            .zip(self.saturated.iter())
+            .filter(|(_, saturated)| **saturated)
+            .map(|(val, _)| val.clone())
+            .collect()
+    }
+
+    fn state(&self) -> (State<'static>, MockStorage) {
+        let state = State::default();
+        let mut storage = MockStorage::default();
+        if let Some(goal) = self.strategy.goal(&self.validators) {
+            state.delegation_goal.save(&mut storage, &goal).unwrap();
+        }
+        (state, storage)
+    }
+}
+
+fn strategy(validator_count: usize) -> impl Strategy<Value = DelegationStrategy> {
+    let weights = prop::collection::vec(0u64..100, validator_count)
+        .prop_filter("at least one weight", |weights| weights.iter().any(|w| *w > 0));
+    prop_oneof![
+        Just(DelegationStrategy::Uniform),
+        weights.clone().prop_map(DelegationStrategy::Defined),
+        weights.prop_map(DelegationStrategy::Gauges),
+    ]
+}
+
+prop_compose! {
+    fn delegation_setup()(validator_count in 1usize..8)(
+        amounts in prop::collection::vec(0u128..1_000_000_000_000, validator_count),
+        unlisted in prop::option::of(1u128..1_000_000_000_000),
+        strategy in strategy(validator_count),
+        saturated in prop::collection::vec(prop::bool::weighted(0.2), validator_count),
+    ) -> DelegationSetup {
+        let validators: Vec<String> = (0..amounts.len()).map(|i| format!("val{}", i)).collect();
+        let mut delegations: Vec<Delegation> = validators
+            .iter()
+            .zip(amounts)
+            .filter(|(_, amount)| *amount > 0)
+            .map(|(val, amount)| Delegation::new(val, amount, MOCK_UTOKEN))
+            .collect();
+        // delegations to validators that have been removed from the whitelist
+        if let Some(unlisted) = unlisted {
+            delegations.push(Delegation::new(UNLISTED, unlisted, MOCK_UTOKEN));
+        }
+
+        DelegationSetup {
+            validators,
+            delegations,
+            strategy,
+            saturated,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum StakeOperation {
+    Bond(u128),
+    /// Unbonds the bps of the stake token supply
+    Unbond(u128),
+    Reinvest(u128),
+    /// Slashes the bps of the bonded amount
+    Slash(u128),
+}
+
+fn stake_operation() -> impl Strategy<Value = StakeOperation> {
+    prop_oneof![
+        (1u128..1_000_000_000_000).prop_map(StakeOperation::Bond),
+        (1u128..=10000).prop_map(StakeOperation::Unbond),
+        (1u128..1_000_000_000).prop_map(StakeOperation::Reinvest),
+        (1u128..5000).prop_map(StakeOperation::Slash),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn undelegations_sum_to_requested_amount(
+        setup in delegation_setup(),
+        unbond_ratio in 0u128..=1000,
+    ) {
+        let (state, storage) = setup.state();
+        let staked = setup.staked();
+        let utoken_to_unbond = staked * unbond_ratio / 1000;
+        let saturated = setup.saturated_validators();
+
+        let undelegations = compute_undelegations(
+            &state,
+            &storage,
+            Uint128::new(utoken_to_unbond),
+            &setup.delegations,
+            setup.validators.clone(),
+            MOCK_UTOKEN,
+            &saturated,
+        )
+        .unwrap();
+
+        let mut undelegated: HashMap<String, u128> = HashMap::new();
+        for undelegation in undelegations.iter() {
+            prop_assert!(!saturated.contains(&undelegation.validator));
+            *undelegated.entry(undelegation.validator.clone()).or_default() +=
+                undelegation.amount.u128();
+        }
+
+        // no validator goes negative
+        for (validator, amount) in undelegated.iter() {
+            let delegated = setup
+                .delegations
+                .iter()
+                .find(|d| d.validator == *validator)
+                .map(|d| d.amount)
+                .unwrap_or_default();
+            prop_assert!(*amount <= delegated, "{} undelegates {} of {}", validator, amount, delegated);
+        }
+
+        // the requested amount is undelegated, as far as the free validators allow it
+        let available: u128 = setup
+            .delegations
+            .iter()
+            .filter(|d| !saturated.contains(&d.validator))
+            .map(|d| d.amount)
+            .sum();
+        let total: u128 = undelegated.values().sum();
+        prop_assert_eq!(total, std::cmp::min(utoken_to_unbond, available));
+    }
+
+    #[test]
+    fn rebalancing_converges_to_goal(setup in delegation_setup()) {
+        let (state, storage) = setup.state();
+        let staked = setup.staked();
+
+        let redelegations = compute_redelegations_for_rebalancing(
+            &state,
+            &storage,
+            &setup.delegations,
+            setup.validators.clone(),
+            MOCK_UTOKEN,
+        )
+        .unwrap();
+
+        // apply the redelegations, keeping the order in which the validators are iterated
+        let mut delegations = setup.delegations.clone();
+        for validator in setup.validators.iter() {
+            if !delegations.iter().any(|d| d.validator == *validator) {
+                delegations.push(Delegation::new(validator, 0, MOCK_UTOKEN));
+            }
+        }
+        for redelegation in redelegations.iter() {
+            prop_assert!(redelegation.amount > 0);
+            prop_assert_ne!(&redelegation.src, &redelegation.dst);
+
+            let src = delegations.iter_mut().find(|d| d.validator == redelegation.src).unwrap();
+            prop_assert!(src.amount >= redelegation.amount, "{} goes negative", src.validator);
+            src.amount -= redelegation.amount;
+
+            let dst = delegations.iter_mut().find(|d| d.validator == redelegation.dst).unwrap();
+            dst.amount += redelegation.amount;
+        }
+        prop_assert_eq!(delegations.iter().map(|d| d.amount).sum::<u128>(), staked);
+
+        // the targets of the goal sum up to the staked amount, so all surplus is moved to the
+        // validators below their target and a second rebalancing has nothing left to do.
+        let (_, _, remove, _) =
+            get_utoken_per_validator(&state, &storage, staked, &setup.validators, None).unwrap();
+        prop_assert!(remove.is_none());
+
+        let redelegations = compute_redelegations_for_rebalancing(
+            &state,
+            &storage,
+            &delegations,
+            setup.validators.clone(),
+            MOCK_UTOKEN,
+        )
+        .unwrap();
+        prop_assert!(redelegations.is_empty(), "not converged: {:?}", redelegations);
+        prop_assert!(delegations.iter().all(|d| d.validator != UNLISTED || d.amount == 0));
+    }
+
+    #[test]
+    fn reconciliation_never_over_deducts(
+        unclaimed in prop::collection::vec(0u128..1_000_000_000, 1..6),
+        deduct_ratio in 0u128..=1000,
+    ) {
+        let mut batches: Vec<Batch> = unclaimed
+            .iter()
+            .enumerate()
+            .map(|(i, utoken_unclaimed)| Batch {
+                id: i as u64 + 1,
+                reconciled: false,
+                total_shares: Uint128::new(*utoken_unclaimed),
+                utoken_unclaimed: Uint128::new(*utoken_unclaimed),
+                est_unbond_end_time: 0,
+            })
+            .collect();
+        // the missing amount can't be more than what the batches expect to receive
+        let total_unclaimed: u128 = unclaimed.iter().sum();
+        let utoken_to_deduct = total_unclaimed * deduct_ratio / 1000;
+
+        reconcile_batches(&mut batches, Uint128::new(utoken_to_deduct));
+
+        let mut deducted = 0u128;
+        for (batch, before) in batches.iter().zip(unclaimed.iter()) {
+            prop_assert!(batch.reconciled);
+            prop_assert!(batch.utoken_unclaimed.u128() <= *before);
+            deducted += before - batch.utoken_unclaimed.u128();
+        }
+        prop_assert!(deducted <= utoken_to_deduct, "deducted {} of {}", deducted, utoken_to_deduct);
+
+        // if every batch can take its part, the amount is deducted exactly
+        if unclaimed.iter().all(|unclaimed| *unclaimed >= utoken_to_deduct) {
+            prop_assert_eq!(deducted, utoken_to_deduct);
+        }
+    }
+
+    #[test]
+    fn exchange_rate_only_decreases_on_slashing(
+        operations in prop::collection::vec(stake_operation(), 1..30),
+    ) {
+        let mut ustake_supply = Uint128::zero();
+        let mut utoken_bonded = Uint128::zero();
+
+        for operation in operations {
+            let (supply_before, bonded_before) = (ustake_supply, utoken_bonded);
+
+            match operation {
+                StakeOperation::Bond(amount) => {
+                    let amount = Uint128::new(amount);
+                    ustake_supply += compute_mint_amount(ustake_supply, amount, utoken_bonded);
+                    utoken_bonded += amount;
+                },
+                StakeOperation::Unbond(bps) => {
+                    let ustake_to_burn = ustake_supply.multiply_ratio(bps, 10000u128);
+                    if ustake_to_burn.is_zero() {
+                        continue;
+                    }
+                    let utoken_to_unbond =
+                        compute_unbond_amount(ustake_supply, ustake_to_burn, utoken_bonded);
+                    prop_assert!(utoken_to_unbond <= utoken_bonded);
+                    ustake_supply -= ustake_to_burn;
+                    utoken_bonded -= utoken_to_unbond;
+                },
+                StakeOperation::Reinvest(amount) => {
+                    utoken_bonded += Uint128::new(amount);
+                },
+                StakeOperation::Slash(bps) => {
+                    utoken_bonded -= utoken_bonded.multiply_ratio(bps, 10000u128);
+                    continue;
+                },
+            }
+
+            if supply_before.is_zero() || ustake_supply.is_zero() {
+                continue;
+            }
+
+            // bonded / supply >= bonded_before / supply_before
+            prop_assert!(
+                Uint256::from(utoken_bonded) * Uint256::from(supply_before)
+                    >= Uint256::from(bonded_before) * Uint256::from(ustake_supply),
+                "exchange rate decreased from {}/{} to {}/{}",
+                bonded_before,
+                supply_before,
+                utoken_bonded,
+                ustake_supply
+            );
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use std::collections::{BTreeMap, HashMap};
+
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::{Addr, Uint128};
+use cw2::{get_contract_version, set_contract_version, ContractVersion};
+
+use eris::alliance_lst::{MigrateMsg, MigrateV1_8_0};
+use eris::hub::Batch;
+
+use crate::constants::{CONTRACT_NAME, CONTRACT_VERSION};
+use crate::contract::migrate;
+use crate::error::ContractError;
+use crate::state::State;
+use crate::testing::helpers::setup_test;
+use crate::types::alliance_delegations::AllianceDelegations;
+
+fn migrate_msg() -> MigrateMsg {
+    MigrateMsg {
+        v1_8_0: Some(MigrateV1_8_0 {
+            undelegation_tolerance_bps: Some(800),
+            guardian: Some("new_guardian".to_string()),
+            timelock_delay: None,
+        }),
+    }
+}
+
+#[test]
+fn migrate_from_previous_version() {
+    let mut deps = setup_test();
+    let state = State::default();
+
+    // previous versions stored the hub name on migrate
+    set_contract_version(deps.as_mut().storage, "eris-staking-hub-tokenfactory", "1.7.4").unwrap();
+    state.unbonding_entries.remove(deps.as_mut().storage);
+    state.guardian.remove(deps.as_mut().storage);
+
+    let res = migrate(deps.as_mut(), mock_env(), migrate_msg()).unwrap();
+    assert!(res.attributes.iter().any(|a| a.key == "migration_step" && a.value == "1.8.0"));
+    assert!(res
+        .attributes
+        .iter()
+        .any(|a| a.key == "previous_contract_version" && a.value == "1.7.4"));
+
+    assert_eq!(
+        get_contract_version(deps.as_ref().storage).unwrap(),
+        ContractVersion {
+            contract: CONTRACT_NAME.to_string(),
+            version: CONTRACT_VERSION.to_string()
+        }
+    );
+    assert_eq!(state.get_undelegation_tolerance_bps(deps.as_ref().storage), 800);
+    assert_eq!(
+        state.guardian.load(deps.as_ref().storage).unwrap(),
+        Addr::unchecked("new_guardian")
+    );
+    assert!(state.unbonding_entries.may_load(deps.as_ref().storage).unwrap().is_some());
+}
+
+#[test]
+fn migrate_seeds_unbonding_entries() {
+    let mut deps = setup_test();
+    let state = State::default();
+    let env = mock_env();
+    let now = env.block.time.seconds();
+
+    set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "1.7.4").unwrap();
+    state.unbonding_entries.remove(deps.as_mut().storage);
+    state
+        .alliance_delegations
+        .save(
+            deps.as_mut().storage,
+            &AllianceDelegations {
+                delegations: HashMap::from([
+                    ("alice".to_string(), Uint128::new(100)),
+                    ("bob".to_string(), Uint128::new(100)),
+                ]),
+            },
+        )
+        .unwrap();
+
+    // reconciled and finished batches don't hold an entry anymore
+    for (id, reconciled, est_unbond_end_time) in
+        [(1, true, now + 100), (2, false, now), (3, false, now + 1000), (4, false, now + 2000)]
+    {
+        let batch = Batch {
+            id,
+            reconciled,
+            total_shares: Uint128::new(100),
+            utoken_unclaimed: Uint128::new(100),
+            est_unbond_end_time,
+        };
+        state.previous_batches.save(deps.as_mut().storage, id, &batch).unwrap();
+    }
+
+    let res = migrate(deps.as_mut(), env, migrate_msg()).unwrap();
+    assert!(res.attributes.iter().any(|a| a.key == "seeded_unbonding_entries" && a.value == "4"));
+
+    let entries = state.unbonding_entries.load(deps.as_ref().storage).unwrap();
+    assert_eq!(
+        entries.entries,
+        BTreeMap::from([
+            ("alice".to_string(), vec![now + 1000, now + 2000]),
+            ("bob".to_string(), vec![now + 1000, now + 2000]),
+        ])
+    );
+}
+
+#[test]
+fn migrate_skips_applied_steps() {
+    let mut deps = setup_test();
+    let state = State::default();
+
+    set_contract_version(deps.as_mut().storage, CONTRACT_NAME, CONTRACT_VERSION).unwrap();
+
+    let res = migrate(deps.as_mut(), mock_env(), migrate_msg()).unwrap();
+    assert!(!res.attributes.iter().any(|a| a.key == "migration_step"));
+
+    // parameters of steps that are not executed are not applied
+    assert!(state.undelegation_tolerance_bps.may_load(deps.as_ref().storage).unwrap().is_none());
+    assert_eq!(state.guardian.load(deps.as_ref().storage).unwrap(), Addr::unchecked("guardian"));
+}
+
+#[test]
+fn migrate_requires_guardian() {
+    let mut deps = setup_test();
+    let state = State::default();
+
+    set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "1.7.4").unwrap();
+    state.guardian.remove(deps.as_mut().storage);
+
+    let err = migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).unwrap_err();
+    assert_eq!(err, ContractError::GuardianRequired {});
+
+    migrate(deps.as_mut(), mock_env(), migrate_msg()).unwrap();
+}
+
+#[test]
+fn migrate_rejects_foreign_contracts_and_downgrades() {
+    let mut deps = setup_test();
+
+    set_contract_version(deps.as_mut().storage, "crates.io:cw20-base", "1.7.4").unwrap();
+    let err = migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::MigrationContractName("crates.io:cw20-base".into(), CONTRACT_NAME.into())
+    );
+
+    set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "99.0.0").unwrap();
+    let err = migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).unwrap_err();
+    assert_eq!(err, ContractError::MigrationDowngrade("99.0.0".into(), CONTRACT_VERSION.into()));
+
+    set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "latest").unwrap();
+    let err = migrate(deps.as_mut(), mock_env(), MigrateMsg::default()).unwrap_err();
+    assert_eq!(err, ContractError::InvalidVersion("latest".into()));
+}
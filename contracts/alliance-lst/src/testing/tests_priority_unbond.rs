@@ -0,0 +1,301 @@
+use cosmwasm_std::testing::{mock_info, MockApi, MockStorage};
+use cosmwasm_std::{coin, Addr, BankMsg, CosmosMsg, OwnedDeps, SubMsg, Uint128};
+
+use eris::alliance_lst::{ConfigResponse, ExecuteMsg, QueryMsg, UnbondingEntriesResponse};
+use eris::hub::{Batch, PendingBatch, UnbondRequest};
+use eris_chain_adapter::types::CustomQueryType;
+
+use crate::constants::{MAX_UNBONDING_ENTRIES, PRIORITY_UNBOND_RESERVED_ENTRIES};
+use crate::contract::execute;
+use crate::error::{ContractError, ContractResult};
+use crate::state::State;
+use crate::testing::helpers::{get_stake_full_denom, query_helper_env, MOCK_UTOKEN};
+use crate::testing::tests_unbonding_entries::{setup_unbonding, SUBMIT_TIME};
+
+use super::custom_querier::CustomQuerier;
+use super::helpers::{mock_env_at_timestamp, UpdateConfig};
+
+const PRIORITY_TIME: u64 = 20000;
+const UNBOND_PERIOD: u64 = 1814400;
+
+fn set_priority_unbond_fee(
+    deps: &mut OwnedDeps<MockStorage, MockApi, CustomQuerier, CustomQueryType>,
+    priority_unbond_fee_bps: u16,
+    priority_unbond_min_fee: Option<u128>,
+) -> ContractResult {
+    execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(PRIORITY_TIME),
+        mock_info("owner", &[]),
+        UpdateConfig {
+            priority_unbond_fee_bps: Some(priority_unbond_fee_bps),
+            priority_unbond_min_fee: priority_unbond_min_fee.map(Uint128::new),
+            ..Default::default()
+        }
+        .into(),
+    )
+}
+
+fn priority_unbond(
+    deps: &mut OwnedDeps<MockStorage, MockApi, CustomQuerier, CustomQueryType>,
+    user: &str,
+    amount: u128,
+) -> ContractResult {
+    execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(PRIORITY_TIME),
+        mock_info(user, &[coin(amount, get_stake_full_denom())]),
+        ExecuteMsg::PriorityUnbond {
+            receiver: None,
+        },
+    )
+}
+
+#[test]
+fn priority_unbond_is_disabled_by_default() {
+    let mut deps = setup_unbonding(vec![]);
+
+    let res = priority_unbond(&mut deps, "user", 10_000).unwrap_err();
+    assert_eq!(res, ContractError::PriorityUnbondDisabled {});
+
+    let res = set_priority_unbond_fee(&mut deps, 1001, None).unwrap_err();
+    assert_eq!(res, ContractError::PriorityUnbondFeeTooHigh {});
+
+    set_priority_unbond_fee(&mut deps, 100, None).unwrap();
+    let config: ConfigResponse =
+        query_helper_env(deps.as_ref(), QueryMsg::Config {}, PRIORITY_TIME);
+    assert_eq!(config.priority_unbond_fee_bps, 100);
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(PRIORITY_TIME),
+        mock_info("user", &[coin(10_000, "utoken")]),
+        ExecuteMsg::PriorityUnbond {
+            receiver: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(res, ContractError::ExpectingAllianceStakeToken("utoken".to_string()));
+}
+
+#[test]
+fn priority_unbond_submits_own_batch() {
+    let mut deps = setup_unbonding(vec![("alice", MAX_UNBONDING_ENTRIES)]);
+    let state = State::default();
+    set_priority_unbond_fee(&mut deps, 100, None).unwrap();
+
+    let res = priority_unbond(&mut deps, "user", 10_000).unwrap();
+
+    // 1% of the stake tokens is paid as fee, the rest is burned
+    assert!(res.messages.contains(&SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+        to_address: "fee".to_string(),
+        amount: vec![coin(100, get_stake_full_denom())],
+    }))));
+
+    // the batch starts unbonding right away, next to the pending batch
+    let batch = state.previous_batches.load(deps.as_ref().storage, 2).unwrap();
+    assert_eq!(
+        batch,
+        Batch {
+            id: 2,
+            reconciled: false,
+            total_shares: Uint128::new(9_900),
+            utoken_unclaimed: Uint128::new(9_900),
+            est_unbond_end_time: PRIORITY_TIME + UNBOND_PERIOD,
+        }
+    );
+    assert_eq!(
+        state.unbond_requests.load(deps.as_ref().storage, (2, &Addr::unchecked("user"))).unwrap(),
+        UnbondRequest {
+            id: 2,
+            user: Addr::unchecked("user"),
+            shares: Uint128::new(9_900),
+        }
+    );
+    assert_eq!(
+        state.pending_batch.load(deps.as_ref().storage).unwrap(),
+        PendingBatch {
+            id: 1,
+            ustake_to_burn: Uint128::new(30_000),
+            est_unbond_start_time: SUBMIT_TIME,
+        }
+    );
+
+    let stake = state.stake_token.load(deps.as_ref().storage).unwrap();
+    assert_eq!(stake.total_supply, Uint128::new(290_100));
+    assert_eq!(stake.total_utoken_bonded, Uint128::new(290_100));
+
+    // undelegations only use validators with free unbonding entries
+    let delegations = state.alliance_delegations.load(deps.as_ref().storage).unwrap();
+    assert_eq!(delegations.delegations["alice"], Uint128::new(100_010));
+    assert_eq!(
+        delegations.delegations.values().copied().sum::<Uint128>(),
+        Uint128::new(300_030 - 9_900)
+    );
+    let res: UnbondingEntriesResponse =
+        query_helper_env(deps.as_ref(), QueryMsg::UnbondingEntries {}, PRIORITY_TIME);
+    assert_eq!(
+        res.entries[0],
+        ("alice".to_string(), vec![SUBMIT_TIME + 100; MAX_UNBONDING_ENTRIES])
+    );
+
+    // another priority batch takes the next id, the pending batch continues after it
+    priority_unbond(&mut deps, "user2", 10_000).unwrap();
+    assert_eq!(state.previous_batches.load(deps.as_ref().storage, 3).unwrap().id, 3);
+
+    execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(SUBMIT_TIME),
+        mock_info("anyone", &[]),
+        ExecuteMsg::SubmitBatch {
+            undelegations: None,
+        },
+    )
+    .unwrap();
+
+    let batch = state.previous_batches.load(deps.as_ref().storage, 1).unwrap();
+    assert_eq!(batch.est_unbond_end_time, SUBMIT_TIME + UNBOND_PERIOD);
+    assert_eq!(state.pending_batch.load(deps.as_ref().storage).unwrap().id, 4);
+}
+
+#[test]
+fn priority_unbond_fee_does_not_round_to_zero() {
+    let mut deps = setup_unbonding(vec![]);
+    set_priority_unbond_fee(&mut deps, 100, None).unwrap();
+
+    // 1% of 50 rounds down to zero, the entry would be free
+    let res = priority_unbond(&mut deps, "user", 50).unwrap_err();
+    assert_eq!(res, ContractError::PriorityUnbondFeeZero {});
+
+    set_priority_unbond_fee(&mut deps, 100, Some(10)).unwrap();
+    let config: ConfigResponse =
+        query_helper_env(deps.as_ref(), QueryMsg::Config {}, PRIORITY_TIME);
+    assert_eq!(config.priority_unbond_min_fee, Uint128::new(10));
+
+    let res = priority_unbond(&mut deps, "user", 50).unwrap();
+    assert!(res.messages.contains(&SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+        to_address: "fee".to_string(),
+        amount: vec![coin(10, get_stake_full_denom())],
+    }))));
+    let state = State::default();
+    assert_eq!(
+        state.previous_batches.load(deps.as_ref().storage, 2).unwrap().total_shares,
+        Uint128::new(40)
+    );
+
+    // the relative fee is used once it is above the minimum
+    let res = priority_unbond(&mut deps, "user", 10_000).unwrap();
+    assert!(res.messages.contains(&SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+        to_address: "fee".to_string(),
+        amount: vec![coin(100, get_stake_full_denom())],
+    }))));
+
+    // the whole amount is paid as fee
+    let res = priority_unbond(&mut deps, "user", 10).unwrap_err();
+    assert_eq!(res, ContractError::CantBeZero("ustake_to_burn".into()));
+}
+
+#[test]
+fn priority_unbond_keeps_entry_for_regular_batch() {
+    let reserved_count = MAX_UNBONDING_ENTRIES - PRIORITY_UNBOND_RESERVED_ENTRIES;
+    let mut deps = setup_unbonding(vec![("alice", reserved_count), ("bob", reserved_count)]);
+    let state = State::default();
+    set_priority_unbond_fee(&mut deps, 100, None).unwrap();
+
+    // alice and bob still have a free entry, but it is kept for the pending batch
+    priority_unbond(&mut deps, "user", 10_000).unwrap();
+    let delegations = state.alliance_delegations.load(deps.as_ref().storage).unwrap();
+    assert_eq!(delegations.delegations["alice"], Uint128::new(100_010));
+    assert_eq!(delegations.delegations["bob"], Uint128::new(100_010));
+    assert_eq!(delegations.delegations["charlie"], Uint128::new(100_010 - 9_900));
+
+    let entries = state.unbonding_entries.load(deps.as_ref().storage).unwrap();
+    assert_eq!(entries.active_count("charlie"), 1);
+
+    // once charlie also reached the reserve, priority unbonds are rejected
+    let mut deps = setup_unbonding(vec![
+        ("alice", reserved_count),
+        ("bob", reserved_count),
+        ("charlie", reserved_count),
+    ]);
+    set_priority_unbond_fee(&mut deps, 100, None).unwrap();
+
+    let res = priority_unbond(&mut deps, "user", 10_000).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::SubmitBatchFailure(
+            "not enough free unbonding entries, missing 9900".to_string()
+        )
+    );
+
+    // the regular batch can still use the reserved entries
+    execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(SUBMIT_TIME),
+        mock_info("anyone", &[]),
+        ExecuteMsg::SubmitBatch {
+            undelegations: None,
+        },
+    )
+    .unwrap();
+
+    let entries = state.unbonding_entries.load(deps.as_ref().storage).unwrap();
+    assert_eq!(entries.saturated_validators().len(), 3);
+}
+
+#[test]
+fn withdrawing_from_priority_batch() {
+    let mut deps = setup_unbonding(vec![]);
+    let state = State::default();
+    set_priority_unbond_fee(&mut deps, 100, None).unwrap();
+
+    priority_unbond(&mut deps, "user", 10_000).unwrap();
+    let unbond_end_time = PRIORITY_TIME + UNBOND_PERIOD;
+
+    // nothing can be withdrawn before the batch finished unbonding
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(unbond_end_time),
+        mock_info("user", &[]),
+        ExecuteMsg::WithdrawUnbonded {
+            receiver: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(res, ContractError::CantBeZero("withdrawable amount".into()));
+
+    deps.querier.set_bank_balances(&[coin(9_900, MOCK_UTOKEN)]);
+    execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(unbond_end_time + 1),
+        mock_info("anyone", &[]),
+        ExecuteMsg::Reconcile {},
+    )
+    .unwrap();
+    assert!(state.previous_batches.load(deps.as_ref().storage, 2).unwrap().reconciled);
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(unbond_end_time + 1),
+        mock_info("user", &[]),
+        ExecuteMsg::WithdrawUnbonded {
+            receiver: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "user".to_string(),
+            amount: vec![coin(9_900, MOCK_UTOKEN)],
+        }))]
+    );
+
+    // the batch and the request are removed once everything is withdrawn
+    assert!(state.previous_batches.may_load(deps.as_ref().storage, 2).unwrap().is_none());
+    assert!(state
+        .unbond_requests
+        .may_load(deps.as_ref().storage, (2, &Addr::unchecked("user")))
+        .unwrap()
+        .is_none());
+}
@@ -0,0 +1,238 @@
+use cosmwasm_std::testing::{mock_info, MockApi, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    coin, to_json_binary, BankMsg, Coin, CosmosMsg, OwnedDeps, SubMsg, Uint128, WasmMsg,
+};
+
+use eris::alliance_lst::{ConfigResponse, ExecuteMsg, QueryMsg};
+use eris::amp_gauges::GaugeInfoResponse as AmpGauge;
+use eris::emp_gauges::GaugeInfoResponse as EmpGauge;
+use eris::governance_helper::{EPOCH_START, WEEK};
+use eris::hub::{CallbackMsg, DelegationStrategy};
+use eris_chain_adapter::types::CustomQueryType;
+
+use crate::contract::execute;
+use crate::error::{ContractError, ContractResult};
+use crate::state::State;
+use crate::testing::helpers::{query_helper_env, setup_test, UpdateConfig, MOCK_UTOKEN};
+
+use super::custom_querier::CustomQuerier;
+use super::helpers::mock_env_at_timestamp;
+
+fn setup_tune(
+    delegation_strategy: DelegationStrategy,
+) -> OwnedDeps<MockStorage, MockApi, CustomQuerier, CustomQueryType> {
+    let mut deps = setup_test();
+
+    let state = State::default();
+    let validators = state.get_validators(deps.as_ref().storage, &deps.as_ref().querier).unwrap();
+    let delegation_strategy = delegation_strategy.validate(deps.as_ref().api, &validators).unwrap();
+    state.delegation_strategy.save(deps.as_mut().storage, &delegation_strategy).unwrap();
+
+    deps
+}
+
+fn set_gauges(
+    deps: &mut OwnedDeps<MockStorage, MockApi, CustomQuerier, CustomQueryType>,
+    tune_period: u64,
+) {
+    deps.querier.set_smart_query_result(
+        "amp_gauges",
+        &AmpGauge {
+            tune_ts: EPOCH_START + tune_period * WEEK,
+            vamp_points: vec![("alice".to_string(), Uint128::new(300))],
+        },
+    );
+    deps.querier.set_smart_query_result(
+        "emp_gauges",
+        &EmpGauge {
+            tune_ts: EPOCH_START + tune_period * WEEK,
+            tune_period,
+            emp_points: vec![("bob".to_string(), Uint128::new(100))],
+        },
+    );
+}
+
+fn set_tune_tip(
+    deps: &mut OwnedDeps<MockStorage, MockApi, CustomQuerier, CustomQueryType>,
+    tune_tip: Coin,
+) -> ContractResult {
+    execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(EPOCH_START),
+        mock_info("owner", &[]),
+        UpdateConfig {
+            tune_tip: Some(tune_tip),
+            ..Default::default()
+        }
+        .into(),
+    )
+}
+
+fn tune(
+    deps: &mut OwnedDeps<MockStorage, MockApi, CustomQuerier, CustomQueryType>,
+    sender: &str,
+    period: u64,
+) -> ContractResult {
+    execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(EPOCH_START + period * WEEK + 100),
+        mock_info(sender, &[]),
+        ExecuteMsg::TuneDelegations {},
+    )
+}
+
+fn defined_strategy() -> DelegationStrategy {
+    DelegationStrategy::Defined {
+        shares_bps: vec![("alice".into(), 6000), ("bob".into(), 4000)],
+    }
+}
+
+#[test]
+fn tuning_is_permissionless_once_per_period() {
+    let mut deps = setup_tune(defined_strategy());
+
+    let res = tune(&mut deps, "keeper", 1).unwrap();
+    assert_eq!(res.messages.len(), 0);
+    let goal = State::default().delegation_goal.load(deps.as_ref().storage).unwrap();
+    assert_eq!(goal.tune_period, 1);
+
+    let res = tune(&mut deps, "keeper", 1).unwrap_err();
+    assert_eq!(res, ContractError::TuneCooldown(1));
+
+    // the owner can tune at any time
+    tune(&mut deps, "owner", 1).unwrap();
+    let res = tune(&mut deps, "keeper", 1).unwrap_err();
+    assert_eq!(res, ContractError::TuneCooldown(1));
+
+    tune(&mut deps, "keeper", 2).unwrap();
+}
+
+#[test]
+fn tuning_chains_gauges() {
+    let mut deps = setup_tune(DelegationStrategy::Gauges {
+        amp_gauges: "amp_gauges".to_string(),
+        emp_gauges: Some("emp_gauges".to_string()),
+        amp_factor_bps: 5000,
+        min_delegation_bps: 100,
+        max_delegation_bps: 10000,
+        validator_count: 3,
+    });
+    set_gauges(&mut deps, 0);
+
+    // gauges that were not tuned in the current period are tuned first
+    let res = tune(&mut deps, "keeper", 1).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "amp_gauges".to_string(),
+                msg: to_json_binary(&eris::amp_gauges::ExecuteMsg::TuneVamp {}).unwrap(),
+                funds: vec![],
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "emp_gauges".to_string(),
+                msg: to_json_binary(&eris::emp_gauges::ExecuteMsg::TuneEmps {}).unwrap(),
+                funds: vec![],
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+                msg: to_json_binary(&ExecuteMsg::Callback(CallbackMsg::TuneDelegations {}))
+                    .unwrap(),
+                funds: vec![],
+            })),
+        ]
+    );
+    assert!(State::default().delegation_goal.may_load(deps.as_ref().storage).unwrap().is_none());
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(EPOCH_START + WEEK + 100),
+        mock_info("keeper", &[]),
+        ExecuteMsg::Callback(CallbackMsg::TuneDelegations {}),
+    )
+    .unwrap_err();
+    assert_eq!(res, ContractError::CallbackOnlyCalledByContract {});
+
+    set_gauges(&mut deps, 1);
+    execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(EPOCH_START + WEEK + 100),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::Callback(CallbackMsg::TuneDelegations {}),
+    )
+    .unwrap();
+    let goal = State::default().delegation_goal.load(deps.as_ref().storage).unwrap();
+    assert_eq!(goal.tune_period, 1);
+    assert_eq!(
+        goal.shares.iter().map(|(val, _)| val.as_str()).collect::<Vec<_>>(),
+        vec!["alice", "bob"]
+    );
+
+    // gauges that are already tuned are not tuned again
+    set_gauges(&mut deps, 2);
+    let res = tune(&mut deps, "keeper", 2).unwrap();
+    assert_eq!(res.messages.len(), 0);
+    let goal = State::default().delegation_goal.load(deps.as_ref().storage).unwrap();
+    assert_eq!(goal.tune_period, 2);
+}
+
+#[test]
+fn tuning_pays_keeper_tip() {
+    let mut deps = setup_tune(defined_strategy());
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(EPOCH_START),
+        mock_info("user", &[coin(150, "uluna")]),
+        ExecuteMsg::FundTuneTip {},
+    )
+    .unwrap_err();
+    assert_eq!(res, ContractError::TuneTipDisabled {});
+
+    let res = set_tune_tip(&mut deps, coin(100, MOCK_UTOKEN)).unwrap_err();
+    assert_eq!(res, ContractError::TuneTipDenomNotAllowed(MOCK_UTOKEN.to_string()));
+    set_tune_tip(&mut deps, coin(100, "uluna")).unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(EPOCH_START),
+        mock_info("user", &[coin(150, "uatom")]),
+        ExecuteMsg::FundTuneTip {},
+    )
+    .unwrap_err();
+    assert_eq!(res, ContractError::InvalidTuneTipDenom("uluna".to_string()));
+
+    execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(EPOCH_START),
+        mock_info("user", &[coin(150, "uluna")]),
+        ExecuteMsg::FundTuneTip {},
+    )
+    .unwrap();
+
+    let config: ConfigResponse = query_helper_env(deps.as_ref(), QueryMsg::Config {}, EPOCH_START);
+    assert_eq!(config.tune_tip, Some(coin(100, "uluna")));
+    assert_eq!(config.tune_tip_budget, Uint128::new(150));
+
+    let res = tune(&mut deps, "keeper", 1).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "keeper".to_string(),
+            amount: vec![coin(100, "uluna")],
+        }))]
+    );
+
+    // the tip denom can't be changed while there is a budget left
+    let res = set_tune_tip(&mut deps, coin(100, "uatom")).unwrap_err();
+    assert_eq!(res, ContractError::TuneTipBudgetNotEmpty {});
+
+    // the owner does not receive a tip and the remaining budget does not cover the next one
+    let res = tune(&mut deps, "owner", 2).unwrap();
+    assert_eq!(res.messages.len(), 0);
+    let res = tune(&mut deps, "keeper", 3).unwrap();
+    assert_eq!(res.messages.len(), 0);
+
+    let config: ConfigResponse = query_helper_env(deps.as_ref(), QueryMsg::Config {}, EPOCH_START);
+    assert_eq!(config.tune_tip_budget, Uint128::new(50));
+}
@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+
+use cosmwasm_std::testing::{mock_info, MockApi, MockStorage};
+use cosmwasm_std::{OwnedDeps, Uint128};
+
+use eris::alliance_lst::{
+    ExecuteMsg, QueryMsg, SimulateUndelegationsResponse, UnbondingEntriesResponse, Undelegation,
+};
+use eris::hub::PendingBatch;
+use eris_chain_adapter::types::CustomQueryType;
+use itertools::Itertools;
+
+use crate::constants::MAX_UNBONDING_ENTRIES;
+use crate::contract::execute;
+use crate::error::ContractError;
+use crate::state::State;
+use crate::testing::helpers::{query_helper_env, set_total_stake_supply, setup_test};
+use crate::types::alliance_delegations::AllianceDelegations;
+use crate::types::unbonding_entries::UnbondingEntries;
+use crate::types::UndelegationExt;
+
+use super::custom_querier::CustomQuerier;
+use super::helpers::mock_env_at_timestamp;
+
+pub(super) const SUBMIT_TIME: u64 = 10000 + 259200;
+const UNBOND_PERIOD: u64 = 1814400;
+
+pub(super) fn setup_unbonding(
+    entries: Vec<(&str, usize)>,
+) -> OwnedDeps<MockStorage, MockApi, CustomQuerier, CustomQueryType> {
+    let mut deps = setup_test();
+    let state = State::default();
+
+    // 10 is removed by the delegation reduction, so each validator has 100_000 delegated
+    state
+        .alliance_delegations
+        .save(
+            deps.as_mut().storage,
+            &AllianceDelegations {
+                delegations: HashMap::from([
+                    ("alice".to_string(), Uint128::new(100_010)),
+                    ("bob".to_string(), Uint128::new(100_010)),
+                    ("charlie".to_string(), Uint128::new(100_010)),
+                ]),
+            },
+        )
+        .unwrap();
+    set_total_stake_supply(&state, &mut deps, 300_000, 300_000);
+
+    state
+        .pending_batch
+        .save(
+            deps.as_mut().storage,
+            &PendingBatch {
+                id: 1,
+                ustake_to_burn: Uint128::new(30_000),
+                est_unbond_start_time: SUBMIT_TIME,
+            },
+        )
+        .unwrap();
+
+    state
+        .unbonding_entries
+        .save(
+            deps.as_mut().storage,
+            &UnbondingEntries {
+                entries: entries
+                    .into_iter()
+                    .map(|(validator, count)| {
+                        (validator.to_string(), vec![SUBMIT_TIME + 100; count])
+                    })
+                    .collect(),
+            },
+        )
+        .unwrap();
+
+    deps
+}
+
+pub(super) fn sorted(undelegations: Vec<Undelegation>) -> Vec<Undelegation> {
+    undelegations.into_iter().sorted_by(|a, b| a.validator.cmp(&b.validator)).collect()
+}
+
+#[test]
+fn undelegations_skip_saturated_validators() {
+    let deps = setup_unbonding(vec![("alice", MAX_UNBONDING_ENTRIES), ("bob", 3)]);
+
+    let res: SimulateUndelegationsResponse =
+        query_helper_env(deps.as_ref(), QueryMsg::SimulateUndelegations {}, SUBMIT_TIME);
+
+    // alice's part is taken from the validators with free entries
+    assert_eq!(
+        sorted(res.undelegations),
+        vec![Undelegation::new("bob", 20_000), Undelegation::new("charlie", 10_000)]
+    );
+    assert!(res.submittable);
+    assert_eq!(res.reason, None);
+
+    // before the epoch, the batch can't be submitted
+    let res: SimulateUndelegationsResponse =
+        query_helper_env(deps.as_ref(), QueryMsg::SimulateUndelegations {}, SUBMIT_TIME - 1);
+    assert!(!res.submittable);
+    assert_eq!(res.reason, Some(ContractError::SubmitBatchAfter(SUBMIT_TIME).to_string()));
+
+    // after the entries matured, alice can be used again
+    let res: SimulateUndelegationsResponse =
+        query_helper_env(deps.as_ref(), QueryMsg::SimulateUndelegations {}, SUBMIT_TIME + 100);
+    assert_eq!(
+        sorted(res.undelegations),
+        vec![
+            Undelegation::new("alice", 10_000),
+            Undelegation::new("bob", 10_000),
+            Undelegation::new("charlie", 10_000)
+        ]
+    );
+}
+
+#[test]
+fn submit_batch_records_unbonding_entries() {
+    let mut deps = setup_unbonding(vec![("alice", MAX_UNBONDING_ENTRIES)]);
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(SUBMIT_TIME),
+        mock_info("operator", &[]),
+        ExecuteMsg::SubmitBatch {
+            undelegations: Some(vec![Undelegation::new("alice", 30_000)]),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::SubmitBatchFailure("validator alice has no free unbonding entry".into())
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(SUBMIT_TIME),
+        mock_info("anyone", &[]),
+        ExecuteMsg::SubmitBatch {
+            undelegations: None,
+        },
+    )
+    .unwrap();
+
+    let res: UnbondingEntriesResponse =
+        query_helper_env(deps.as_ref(), QueryMsg::UnbondingEntries {}, SUBMIT_TIME);
+    assert_eq!(
+        res,
+        UnbondingEntriesResponse {
+            max_entries: MAX_UNBONDING_ENTRIES as u32,
+            entries: vec![
+                ("alice".to_string(), vec![SUBMIT_TIME + 100; MAX_UNBONDING_ENTRIES]),
+                ("bob".to_string(), vec![SUBMIT_TIME + UNBOND_PERIOD]),
+                ("charlie".to_string(), vec![SUBMIT_TIME + UNBOND_PERIOD]),
+            ]
+        }
+    );
+}
+
+#[test]
+fn submit_batch_fails_without_free_entries() {
+    let mut deps = setup_unbonding(vec![
+        ("alice", MAX_UNBONDING_ENTRIES),
+        ("bob", MAX_UNBONDING_ENTRIES),
+        ("charlie", MAX_UNBONDING_ENTRIES),
+    ]);
+
+    let res: SimulateUndelegationsResponse =
+        query_helper_env(deps.as_ref(), QueryMsg::SimulateUndelegations {}, SUBMIT_TIME);
+    assert_eq!(res.undelegations, vec![]);
+    assert!(!res.submittable);
+    assert_eq!(
+        res.reason,
+        Some("Submit Batch Failed: not enough free unbonding entries, missing 30000".to_string())
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(SUBMIT_TIME),
+        mock_info("anyone", &[]),
+        ExecuteMsg::SubmitBatch {
+            undelegations: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::SubmitBatchFailure(
+            "not enough free unbonding entries, missing 30000".to_string()
+        )
+    );
+}
@@ -0,0 +1,136 @@
+use cosmwasm_std::testing::{mock_info, MockApi, MockStorage};
+use cosmwasm_std::{Addr, OwnedDeps};
+
+use eris::alliance_lst::{ExecuteMsg, QueryMsg, Undelegation, UndelegationOverride};
+use eris_chain_adapter::types::CustomQueryType;
+
+use crate::contract::execute;
+use crate::error::{ContractError, ContractResult};
+use crate::testing::helpers::query_helper_env;
+use crate::types::UndelegationExt;
+
+use super::custom_querier::CustomQuerier;
+use super::helpers::{mock_env_at_timestamp, UpdateConfig};
+use super::tests_unbonding_entries::{setup_unbonding, SUBMIT_TIME};
+
+fn submit_batch(
+    deps: &mut OwnedDeps<MockStorage, MockApi, CustomQuerier, CustomQueryType>,
+    sender: &str,
+    undelegations: Vec<Undelegation>,
+) -> ContractResult {
+    execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(SUBMIT_TIME),
+        mock_info(sender, &[]),
+        ExecuteMsg::SubmitBatch {
+            undelegations: Some(undelegations),
+        },
+    )
+}
+
+#[test]
+fn only_operator_can_provide_undelegations() {
+    let mut deps = setup_unbonding(vec![]);
+
+    let res =
+        submit_batch(&mut deps, "anyone", vec![Undelegation::new("alice", 30_000)]).unwrap_err();
+    assert_eq!(res, ContractError::UnauthorizedSenderNotOperator {});
+}
+
+#[test]
+fn reject_undelegations_outside_of_tolerance() {
+    let mut deps = setup_unbonding(vec![]);
+
+    // alice would end at 70_000 while the goal is 90_000, more than 5% of her goal away
+    let res =
+        submit_batch(&mut deps, "operator", vec![Undelegation::new("alice", 30_000)]).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::SubmitBatchFailure(
+            "undelegations move validator alice outside of the delegation goal tolerance".into()
+        )
+    );
+
+    // dave is not in the validator set, while alice, bob and charlie have stake
+    let res =
+        submit_batch(&mut deps, "operator", vec![Undelegation::new("dave", 30_000)]).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::SubmitBatchFailure("validator dave is not in the validator set".into())
+    );
+
+    // the tolerance is measured against each validator's goal, not the total amount delegated
+    execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(SUBMIT_TIME),
+        mock_info("owner", &[]),
+        UpdateConfig {
+            undelegation_tolerance_bps: Some(1000),
+            ..Default::default()
+        }
+        .into(),
+    )
+    .unwrap();
+
+    // alice would end 20_000 away from her goal, which is within 10% of 270_000 but not of 90_000
+    let res =
+        submit_batch(&mut deps, "operator", vec![Undelegation::new("alice", 30_000)]).unwrap_err();
+    assert_eq!(
+        res,
+        ContractError::SubmitBatchFailure(
+            "undelegations move validator alice outside of the delegation goal tolerance".into()
+        )
+    );
+
+    submit_batch(
+        &mut deps,
+        "operator",
+        vec![
+            Undelegation::new("alice", 15_000),
+            Undelegation::new("bob", 10_000),
+            Undelegation::new("charlie", 5_000),
+        ],
+    )
+    .unwrap();
+}
+
+#[test]
+fn record_operator_overrides() {
+    let mut deps = setup_unbonding(vec![]);
+
+    let res = submit_batch(
+        &mut deps,
+        "operator",
+        vec![
+            Undelegation::new("alice", 14_000),
+            Undelegation::new("bob", 10_000),
+            Undelegation::new("charlie", 6_000),
+        ],
+    )
+    .unwrap();
+
+    let event = res.events.iter().find(|e| e.ty == "erishub/unbond_submitted").unwrap();
+    assert!(event.attributes.iter().any(|a| a.key == "operator_override" && a.value == "true"));
+
+    let res: Vec<UndelegationOverride> = query_helper_env(
+        deps.as_ref(),
+        QueryMsg::UndelegationOverrides {
+            start_after: None,
+            limit: None,
+        },
+        SUBMIT_TIME,
+    );
+    assert_eq!(
+        res,
+        vec![UndelegationOverride {
+            batch_id: 1,
+            operator: Addr::unchecked("operator"),
+            time: SUBMIT_TIME,
+            undelegations: vec![
+                Undelegation::new("alice", 14_000),
+                Undelegation::new("bob", 10_000),
+                Undelegation::new("charlie", 6_000)
+            ],
+        }]
+    );
+}
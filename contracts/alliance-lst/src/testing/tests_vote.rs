@@ -0,0 +1,125 @@
+use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{Binary, CosmosMsg, Decimal, GovMsg, SubMsg, VoteOption};
+use terra_proto_rs::{
+    cosmos::gov::v1::{MsgVoteWeighted, WeightedVoteOption},
+    prost::Message,
+};
+
+use eris::alliance_lst::{ConfigResponse, ExecuteMsg, QueryMsg};
+
+use crate::contract::execute;
+use crate::error::ContractError;
+
+use super::helpers::{query_helper, setup_test, UpdateConfig};
+
+#[test]
+fn vote() {
+    let mut deps = setup_test();
+
+    let vote = |proposal_id: u64| ExecuteMsg::Vote {
+        proposal_id,
+        vote: VoteOption::Yes,
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), mock_info("jake", &[]), vote(3)).unwrap_err();
+    assert_eq!(res, ContractError::NoVoteOperatorSet {});
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("jake", &[]),
+        UpdateConfig {
+            vote_operator: Some("vote_operator".to_string()),
+            ..Default::default()
+        }
+        .into(),
+    )
+    .unwrap_err();
+    assert_eq!(res, ContractError::Unauthorized {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        UpdateConfig {
+            vote_operator: Some("vote_operator".to_string()),
+            ..Default::default()
+        }
+        .into(),
+    )
+    .unwrap();
+
+    let config: ConfigResponse = query_helper(deps.as_ref(), QueryMsg::Config {});
+    assert_eq!(config.vote_operator, Some("vote_operator".to_string()));
+
+    let res = execute(deps.as_mut(), mock_env(), mock_info("jake", &[]), vote(3)).unwrap_err();
+    assert_eq!(res, ContractError::UnauthorizedSenderNotVoteOperator {});
+
+    let res = execute(deps.as_mut(), mock_env(), mock_info("vote_operator", &[]), vote(3)).unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(
+        res.messages[0],
+        SubMsg::new(CosmosMsg::Gov(GovMsg::Vote {
+            proposal_id: 3,
+            vote: VoteOption::Yes,
+        }))
+    );
+}
+
+#[test]
+fn vote_weighted() {
+    let mut deps = setup_test();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        UpdateConfig {
+            vote_operator: Some("vote_operator".to_string()),
+            ..Default::default()
+        }
+        .into(),
+    )
+    .unwrap();
+
+    let vote_weighted = ExecuteMsg::VoteWeighted {
+        proposal_id: 3,
+        votes: vec![
+            (Decimal::percent(25), VoteOption::Yes),
+            (Decimal::percent(75), VoteOption::NoWithVeto),
+        ],
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), mock_info("jake", &[]), vote_weighted.clone())
+        .unwrap_err();
+    assert_eq!(res, ContractError::UnauthorizedSenderNotVoteOperator {});
+
+    let res =
+        execute(deps.as_mut(), mock_env(), mock_info("vote_operator", &[]), vote_weighted).unwrap();
+    assert_eq!(res.messages.len(), 1);
+
+    assert_eq!(
+        res.messages[0],
+        SubMsg::new(CosmosMsg::Stargate {
+            type_url: "/cosmos.gov.v1.MsgVoteWeighted".to_string(),
+            value: Binary::from(
+                MsgVoteWeighted {
+                    proposal_id: 3,
+                    voter: MOCK_CONTRACT_ADDR.to_string(),
+                    options: vec![
+                        WeightedVoteOption {
+                            option: 1,
+                            weight: "0.25".to_string(),
+                        },
+                        WeightedVoteOption {
+                            option: 4,
+                            weight: "0.75".to_string(),
+                        },
+                    ],
+                    metadata: String::new(),
+                }
+                .encode_to_vec()
+            ),
+        })
+    );
+}
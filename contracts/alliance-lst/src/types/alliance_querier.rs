@@ -1,7 +1,9 @@
-use cosmwasm_std::{Coin, QuerierWrapper, StdResult};
+use cosmwasm_std::{Addr, Coin, QuerierWrapper, StdResult};
 use eris_chain_shared::alliance_query::{AllianceQuery, AllianceQueryWrapper};
 use serde::{Deserialize, Serialize};
 
+use crate::types::Delegation;
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub struct DelegationResponse {
@@ -37,4 +39,49 @@ impl<'a> AllianceQuerier<'a> {
 
         self.querier.query(&request)
     }
+
+    /// Queries the delegation to a validator, a missing delegation is returned with amount 0.
+    pub fn query_delegation_or_empty(
+        &self,
+        denom: &str,
+        delegator: &Addr,
+        validator: &str,
+    ) -> Delegation {
+        self.query_delegation(denom.to_string(), delegator.to_string(), validator.to_string())
+            .map(|d| Delegation {
+                validator: validator.to_string(),
+                amount: d.amount.amount.u128(),
+                denom: d.amount.denom,
+            })
+            .unwrap_or(Delegation {
+                validator: validator.to_string(),
+                amount: 0,
+                denom: denom.to_string(),
+            })
+    }
+
+    /// The alliance module can't list all delegations of a delegator, so each of the validators is
+    /// queried. Validators without a delegation are skipped.
+    pub fn query_all_delegations<'v>(
+        &self,
+        denom: &str,
+        delegator: &Addr,
+        validators: impl IntoIterator<Item = &'v String>,
+    ) -> Vec<Delegation> {
+        validators
+            .into_iter()
+            .map(|validator| self.query_delegation_or_empty(denom, delegator, validator))
+            .filter(|d| d.amount > 0)
+            .collect()
+    }
+
+    /// Same as query_all_delegations, but only returns the sum of the delegations
+    pub fn query_all_delegations_amount<'v>(
+        &self,
+        denom: &str,
+        delegator: &Addr,
+        validators: impl IntoIterator<Item = &'v String>,
+    ) -> u128 {
+        self.query_all_delegations(denom, delegator, validators).iter().map(|d| d.amount).sum()
+    }
 }
@@ -4,6 +4,7 @@ mod coins;
 pub mod gauges;
 mod keys;
 mod staking;
+pub mod unbonding_entries;
 
 pub use coins::Coins;
 pub use keys::BooleanKey;
@@ -0,0 +1,64 @@
+use std::collections::{BTreeMap, HashSet};
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{StdResult, Storage};
+use eris::alliance_lst::Undelegation;
+
+use crate::{constants::MAX_UNBONDING_ENTRIES, state::State};
+
+/// Tracks the unbonding entries that are still open per validator.
+/// The staking module (and the alliance module) only allows a limited amount of concurrent entries
+/// per delegator-validator pair, undelegating from a validator without a free slot fails the whole tx.
+#[cw_serde]
+#[derive(Default)]
+pub struct UnbondingEntries {
+    /// end times (in seconds) of all open unbonding entries of a validator
+    pub entries: BTreeMap<String, Vec<u64>>,
+}
+
+impl UnbondingEntries {
+    /// removes all entries that finished unbonding before or at `current_time`
+    pub fn prune(mut self, current_time: u64) -> UnbondingEntries {
+        for end_times in self.entries.values_mut() {
+            end_times.retain(|end_time| *end_time > current_time);
+        }
+        self.entries.retain(|_, end_times| !end_times.is_empty());
+        self
+    }
+
+    pub fn active_count(&self, validator: &str) -> usize {
+        self.entries.get(validator).map(|end_times| end_times.len()).unwrap_or_default()
+    }
+
+    pub fn has_free_slot(&self, validator: &str) -> bool {
+        self.active_count(validator) < MAX_UNBONDING_ENTRIES
+    }
+
+    /// validators where no additional unbonding entry can be created
+    pub fn saturated_validators(&self) -> HashSet<String> {
+        self.saturated_validators_reserving(0)
+    }
+
+    /// validators where no additional unbonding entry can be created without using one of the
+    /// last `reserved` entries
+    pub fn saturated_validators_reserving(&self, reserved: usize) -> HashSet<String> {
+        let max_entries = MAX_UNBONDING_ENTRIES.saturating_sub(reserved);
+        self.entries
+            .iter()
+            .filter(|(_, end_times)| end_times.len() >= max_entries)
+            .map(|(validator, _)| validator.clone())
+            .collect()
+    }
+
+    pub fn add(mut self, undelegations: &[Undelegation], end_time: u64) -> UnbondingEntries {
+        for undelegation in undelegations {
+            self.entries.entry(undelegation.validator.clone()).or_default().push(end_time);
+        }
+        self
+    }
+
+    pub fn save(self, state: &State, storage: &mut dyn Storage) -> StdResult<UnbondingEntries> {
+        state.unbonding_entries.save(storage, &self)?;
+        Ok(self)
+    }
+}
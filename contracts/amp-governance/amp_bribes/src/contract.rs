@@ -0,0 +1,461 @@
+use astroport::common::{claim_ownership, drop_ownership_proposal, propose_new_owner};
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_json_binary, Addr, BankMsg, Binary, Coin, Deps, DepsMut, Env, MessageInfo, Order, Response,
+    StdError, StdResult,
+};
+use cw2::{get_contract_version, set_contract_version};
+use cw_storage_plus::Bound;
+
+use eris::amp_bribes::{
+    BribeInfoResponse, ClaimableResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
+};
+use eris::governance_helper::get_period;
+use eris::hub::get_hub_validators;
+use eris::voting_escrow::{DEFAULT_LIMIT, MAX_LIMIT};
+
+use crate::error::ContractError;
+use crate::state::{Config, BRIBES, CLAIMS, CONFIG, DEPOSITS, OWNERSHIP_PROPOSAL};
+use crate::utils::{
+    add_coins, assert_claim_periods, assert_period_tuned, calc_claimable, coins_to_string,
+    query_user_vamp, query_validator_vamp, split_unclaimed, unclaimed_coins,
+};
+
+/// Contract name that is used for migration.
+const CONTRACT_NAME: &str = "amp-bribes";
+/// Contract version that is used for migration.
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+type ExecuteResult = Result<Response, ContractError>;
+
+/// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> ExecuteResult {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    assert_claim_periods(msg.claim_periods)?;
+
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            owner: deps.api.addr_validate(&msg.owner)?,
+            amp_gauges_addr: deps.api.addr_validate(&msg.amp_gauges_addr)?,
+            claim_periods: msg.claim_periods,
+            allowed_denoms: msg.allowed_denoms,
+        },
+    )?;
+
+    Ok(Response::default())
+}
+
+/// Exposes all the execute functions available in the contract.
+///
+/// ## Execute messages
+/// * **ExecuteMsg::DepositBribe { validator_addr, period }** Deposits a bribe for the voters of a validator
+///
+/// * **ExecuteMsg::Claim { validator_addr, period }** Claims the share of a bribe
+///
+/// * **ExecuteMsg::Refund { validator_addr, period }** Refunds a deposit if the validator received no votes
+///
+/// * **ExecuteMsg::Rollover { validator_addr, period }** Moves the unclaimed bribes to the next period
+///
+/// * **ExecuteMsg::UpdateConfig { claim_periods, allowed_denoms }** Changes the number of periods in which bribes can be claimed
+///     and the denoms that can be deposited
+///
+/// * **ExecuteMsg::ProposeNewOwner { owner, expires_in }** Creates a new request to change
+///     contract ownership.
+///
+/// * **ExecuteMsg::DropOwnershipProposal {}** Removes a request to change contract ownership.
+///
+/// * **ExecuteMsg::ClaimOwnership {}** Claims contract ownership.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> ExecuteResult {
+    match msg {
+        ExecuteMsg::DepositBribe {
+            validator_addr,
+            period,
+        } => deposit_bribe(deps, env, info, validator_addr, period),
+        ExecuteMsg::Claim {
+            validator_addr,
+            period,
+        } => claim(deps, env, info, validator_addr, period),
+        ExecuteMsg::Refund {
+            validator_addr,
+            period,
+        } => refund(deps, info, validator_addr, period),
+        ExecuteMsg::Rollover {
+            validator_addr,
+            period,
+        } => rollover(deps, env, validator_addr, period),
+        ExecuteMsg::UpdateConfig {
+            claim_periods,
+            allowed_denoms,
+        } => update_config(deps, info, claim_periods, allowed_denoms),
+        ExecuteMsg::ProposeNewOwner {
+            new_owner,
+            expires_in,
+        } => {
+            let config: Config = CONFIG.load(deps.storage)?;
+
+            propose_new_owner(
+                deps,
+                info,
+                env,
+                new_owner,
+                expires_in,
+                config.owner,
+                OWNERSHIP_PROPOSAL,
+            )
+            .map_err(Into::into)
+        },
+        ExecuteMsg::DropOwnershipProposal {} => {
+            let config: Config = CONFIG.load(deps.storage)?;
+
+            drop_ownership_proposal(deps, info, config.owner, OWNERSHIP_PROPOSAL)
+                .map_err(Into::into)
+        },
+        ExecuteMsg::ClaimOwnership {} => {
+            claim_ownership(deps, info, env, OWNERSHIP_PROPOSAL, |deps, new_owner| {
+                CONFIG
+                    .update::<_, StdError>(deps.storage, |mut v| {
+                        v.owner = new_owner;
+                        Ok(v)
+                    })
+                    .map(|_| ())
+            })
+            .map_err(Into::into)
+        },
+    }
+}
+
+/// Deposits the sent funds as a bribe for the voters of a hub validator in a future period.
+fn deposit_bribe(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    validator_addr: String,
+    period: u64,
+) -> ExecuteResult {
+    let config = CONFIG.load(deps.storage)?;
+
+    let mut funds = vec![];
+    add_coins(&mut funds, &info.funds);
+    if funds.is_empty() {
+        return Err(ContractError::NoFunds {});
+    }
+    if let Some(coin) = funds.iter().find(|coin| !config.allowed_denoms.contains(&coin.denom)) {
+        return Err(ContractError::DenomNotAllowed(coin.denom.clone()));
+    }
+
+    let block_period = get_period(env.block.time.seconds())?;
+    if period <= block_period {
+        return Err(ContractError::PeriodNotInFuture {});
+    }
+
+    let gauge_config: eris::amp_gauges::ConfigResponse = deps
+        .querier
+        .query_wasm_smart(&config.amp_gauges_addr, &eris::amp_gauges::QueryMsg::Config {})?;
+    if !get_hub_validators(&deps.querier, gauge_config.hub_addr)?.contains(&validator_addr) {
+        return Err(ContractError::InvalidValidatorAddress(validator_addr));
+    }
+
+    let mut bribe =
+        BRIBES.may_load(deps.storage, (period, validator_addr.as_str()))?.unwrap_or_default();
+    add_coins(&mut bribe.funds, &funds);
+    BRIBES.save(deps.storage, (period, validator_addr.as_str()), &bribe)?;
+
+    let key = (period, validator_addr.as_str(), &info.sender);
+    let mut deposit = DEPOSITS.may_load(deps.storage, key)?.unwrap_or_default();
+    add_coins(&mut deposit, &funds);
+    DEPOSITS.save(deps.storage, key, &deposit)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "bribes/deposit")
+        .add_attribute("validator", validator_addr)
+        .add_attribute("period", period.to_string())
+        .add_attribute("funds", coins_to_string(&funds)))
+}
+
+/// Claims the user's share of the bribes of a validator in a tuned period. The share is pro-rata
+/// to the vAMP the user voted for the validator in that period.
+fn claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    validator_addr: String,
+    period: u64,
+) -> ExecuteResult {
+    let config = CONFIG.load(deps.storage)?;
+    let mut bribe = load_open_bribe(deps.as_ref(), &validator_addr, period)?;
+    assert_period_tuned(&deps.querier, &config, period)?;
+
+    let block_period = get_period(env.block.time.seconds())?;
+    if block_period >= period + config.claim_periods {
+        return Err(ContractError::ClaimPeriodsEnded(period));
+    }
+
+    let key = (period, validator_addr.as_str(), &info.sender);
+    if CLAIMS.has(deps.storage, key) {
+        return Err(ContractError::AlreadyClaimed {});
+    }
+
+    let user_vamp = query_user_vamp(&deps.querier, &config, &info.sender, &validator_addr, period)?;
+    let total_vamp = query_validator_vamp(&deps.querier, &config, &validator_addr, period)?;
+    let funds = calc_claimable(&bribe, user_vamp, total_vamp)?;
+    if funds.is_empty() {
+        return Err(ContractError::NothingToClaim {});
+    }
+
+    add_coins(&mut bribe.claimed, &funds);
+    BRIBES.save(deps.storage, (period, validator_addr.as_str()), &bribe)?;
+    CLAIMS.save(deps.storage, key, &funds)?;
+
+    Ok(Response::new()
+        .add_message(send_msg(&info.sender, funds.clone()))
+        .add_attribute("action", "bribes/claim")
+        .add_attribute("validator", validator_addr)
+        .add_attribute("period", period.to_string())
+        .add_attribute("vAMP", user_vamp)
+        .add_attribute("funds", coins_to_string(&funds)))
+}
+
+/// Refunds the deposit of a bribe, if nobody voted for the validator in the tuned period.
+fn refund(deps: DepsMut, info: MessageInfo, validator_addr: String, period: u64) -> ExecuteResult {
+    let config = CONFIG.load(deps.storage)?;
+    let mut bribe = load_open_bribe(deps.as_ref(), &validator_addr, period)?;
+    assert_period_tuned(&deps.querier, &config, period)?;
+
+    if !query_validator_vamp(&deps.querier, &config, &validator_addr, period)?.is_zero() {
+        return Err(ContractError::ValidatorHasVotes {});
+    }
+
+    let key = (period, validator_addr.as_str(), &info.sender);
+    let deposit = DEPOSITS.may_load(deps.storage, key)?.ok_or(ContractError::NoDeposit {})?;
+    DEPOSITS.remove(deps.storage, key);
+
+    add_coins(&mut bribe.claimed, &deposit);
+    BRIBES.save(deps.storage, (period, validator_addr.as_str()), &bribe)?;
+
+    Ok(Response::new()
+        .add_message(send_msg(&info.sender, deposit.clone()))
+        .add_attribute("action", "bribes/refund")
+        .add_attribute("validator", validator_addr)
+        .add_attribute("period", period.to_string())
+        .add_attribute("funds", coins_to_string(&deposit)))
+}
+
+/// Moves the unclaimed bribes of a validator to the next period after the claim periods ended.
+/// Anyone can roll over the bribes. The depositors keep their share of the rolled over funds as a
+/// deposit of the next period. Bribes of validators without votes stay refundable by their
+/// depositors.
+fn rollover(deps: DepsMut, env: Env, validator_addr: String, period: u64) -> ExecuteResult {
+    let config = CONFIG.load(deps.storage)?;
+    let mut bribe = load_open_bribe(deps.as_ref(), &validator_addr, period)?;
+
+    let block_period = get_period(env.block.time.seconds())?;
+    if block_period < period + config.claim_periods {
+        return Err(ContractError::ClaimPeriodsNotEnded(period));
+    }
+
+    assert_period_tuned(&deps.querier, &config, period)?;
+    if query_validator_vamp(&deps.querier, &config, &validator_addr, period)?.is_zero() {
+        return Err(ContractError::ValidatorHasNoVotes {});
+    }
+
+    let unclaimed = unclaimed_coins(&bribe)?;
+    bribe.rolled_over = true;
+    BRIBES.save(deps.storage, (period, validator_addr.as_str()), &bribe)?;
+
+    let next_period = block_period + 1;
+    if !unclaimed.is_empty() {
+        let mut next_bribe = BRIBES
+            .may_load(deps.storage, (next_period, validator_addr.as_str()))?
+            .unwrap_or_default();
+        add_coins(&mut next_bribe.funds, &unclaimed);
+        BRIBES.save(deps.storage, (next_period, validator_addr.as_str()), &next_bribe)?;
+
+        // the rolled over funds can be refunded, if nobody votes for the validator in the next period
+        let deposits = DEPOSITS
+            .prefix((period, validator_addr.as_str()))
+            .range(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for (depositor, share) in split_unclaimed(&bribe, &unclaimed, &deposits)? {
+            let key = (next_period, validator_addr.as_str(), &depositor);
+            let mut deposit = DEPOSITS.may_load(deps.storage, key)?.unwrap_or_default();
+            add_coins(&mut deposit, &share);
+            DEPOSITS.save(deps.storage, key, &deposit)?;
+        }
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "bribes/rollover")
+        .add_attribute("validator", validator_addr)
+        .add_attribute("period", period.to_string())
+        .add_attribute("next_period", next_period.to_string())
+        .add_attribute("funds", coins_to_string(&unclaimed)))
+}
+
+/// Only contract owner can call this function.
+/// The function sets the number of periods in which bribes can be claimed and the denoms that can
+/// be deposited.
+fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    claim_periods: Option<u64>,
+    allowed_denoms: Option<Vec<String>>,
+) -> ExecuteResult {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    config.assert_owner(&info.sender)?;
+
+    if let Some(claim_periods) = claim_periods {
+        assert_claim_periods(claim_periods)?;
+        config.claim_periods = claim_periods;
+    }
+
+    if let Some(allowed_denoms) = allowed_denoms {
+        config.allowed_denoms = allowed_denoms;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default().add_attribute("action", "bribes/update_config"))
+}
+
+/// Loads a bribe that was not rolled over yet.
+fn load_open_bribe(
+    deps: Deps,
+    validator_addr: &str,
+    period: u64,
+) -> Result<BribeInfoResponse, ContractError> {
+    let bribe = BRIBES
+        .may_load(deps.storage, (period, validator_addr))?
+        .ok_or_else(|| ContractError::BribeNotFound(validator_addr.to_string(), period))?;
+
+    if bribe.rolled_over {
+        return Err(ContractError::BribeRolledOver {});
+    }
+    Ok(bribe)
+}
+
+fn send_msg(recipient: &Addr, amount: Vec<Coin>) -> BankMsg {
+    BankMsg::Send {
+        to_address: recipient.to_string(),
+        amount,
+    }
+}
+
+/// Expose available contract queries.
+///
+/// ## Queries
+/// * **QueryMsg::Config** Fetch contract config
+///
+/// * **QueryMsg::BribeInfo { validator_addr, period }** Fetch the bribes of a validator in a period
+///
+/// * **QueryMsg::BribeInfos { period, start_after, limit }** Fetch the bribes of all validators in a period
+///
+/// * **QueryMsg::Deposit { user, validator_addr, period }** Fetch the deposit of a user
+///
+/// * **QueryMsg::Claimable { user, validator_addr, period }** Fetch the bribes a user can claim
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_json_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::BribeInfo {
+            validator_addr,
+            period,
+        } => to_json_binary(
+            &BRIBES.may_load(deps.storage, (period, validator_addr.as_str()))?.unwrap_or_default(),
+        ),
+        QueryMsg::BribeInfos {
+            period,
+            start_after,
+            limit,
+        } => to_json_binary(&bribe_infos(deps, period, start_after, limit)?),
+        QueryMsg::Deposit {
+            user,
+            validator_addr,
+            period,
+        } => {
+            let user = deps.api.addr_validate(&user)?;
+            to_json_binary(
+                &DEPOSITS
+                    .may_load(deps.storage, (period, validator_addr.as_str(), &user))?
+                    .unwrap_or_default(),
+            )
+        },
+        QueryMsg::Claimable {
+            user,
+            validator_addr,
+            period,
+        } => to_json_binary(&claimable(deps, user, validator_addr, period)?),
+    }
+}
+
+/// Returns the bribes of all validators in a period.
+fn bribe_infos(
+    deps: Deps,
+    period: u64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(String, BribeInfoResponse)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    BRIBES.prefix(period).range(deps.storage, start, None, Order::Ascending).take(limit).collect()
+}
+
+/// Returns the bribes a user can claim for a validator in a period.
+fn claimable(
+    deps: Deps,
+    user: String,
+    validator_addr: String,
+    period: u64,
+) -> StdResult<ClaimableResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let user = deps.api.addr_validate(&user)?;
+
+    let user_vamp = query_user_vamp(&deps.querier, &config, &user, &validator_addr, period)?;
+    let total_vamp = query_validator_vamp(&deps.querier, &config, &validator_addr, period)?;
+
+    let funds = if CLAIMS.has(deps.storage, (period, validator_addr.as_str(), &user)) {
+        vec![]
+    } else {
+        let bribe =
+            BRIBES.may_load(deps.storage, (period, validator_addr.as_str()))?.unwrap_or_default();
+        calc_claimable(&bribe, user_vamp, total_vamp)?
+    };
+
+    Ok(ClaimableResponse {
+        user_vamp,
+        total_vamp,
+        funds,
+    })
+}
+
+/// Manages contract migration
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let contract_version = get_contract_version(deps.storage)?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    if contract_version.contract != CONTRACT_NAME {
+        return Err(StdError::generic_err(format!(
+            "contract_name does not match: prev: {0}, new: {1}",
+            contract_version.contract, CONTRACT_VERSION
+        ))
+        .into());
+    }
+
+    Ok(Response::new()
+        .add_attribute("previous_contract_name", &contract_version.contract)
+        .add_attribute("previous_contract_version", &contract_version.version)
+        .add_attribute("new_contract_name", CONTRACT_NAME)
+        .add_attribute("new_contract_version", CONTRACT_VERSION))
+}
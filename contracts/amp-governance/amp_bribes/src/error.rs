@@ -0,0 +1,54 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+/// This enum describes contract errors
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Invalid validator address: {0}")]
+    InvalidValidatorAddress(String),
+
+    #[error("No funds sent")]
+    NoFunds {},
+
+    #[error("Denom {0} can not be deposited as a bribe")]
+    DenomNotAllowed(String),
+
+    #[error("The claim periods must be at least 1")]
+    InvalidClaimPeriods {},
+
+    #[error("Bribes can only be deposited for future periods")]
+    PeriodNotInFuture {},
+
+    #[error("There are no bribes for validator {0} in period {1}")]
+    BribeNotFound(String, u64),
+
+    #[error("Period {0} was not tuned yet")]
+    PeriodNotTuned(u64),
+
+    #[error("The bribes were rolled over to a later period")]
+    BribeRolledOver {},
+
+    #[error("The claim periods of period {0} ended")]
+    ClaimPeriodsEnded(u64),
+
+    #[error("The claim periods of period {0} did not end yet")]
+    ClaimPeriodsNotEnded(u64),
+
+    #[error("Bribes already claimed")]
+    AlreadyClaimed {},
+
+    #[error("Nothing to claim")]
+    NothingToClaim {},
+
+    #[error("No deposit found")]
+    NoDeposit {},
+
+    #[error("Deposits can only be refunded if nobody voted for the validator")]
+    ValidatorHasVotes {},
+
+    #[error("Bribes of validators without votes can only be refunded")]
+    ValidatorHasNoVotes {},
+}
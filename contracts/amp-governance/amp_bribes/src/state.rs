@@ -0,0 +1,25 @@
+use astroport::common::OwnershipProposal;
+use cosmwasm_std::{Addr, Coin};
+use cw_storage_plus::{Item, Map};
+use eris::amp_bribes::{BribeInfoResponse, ConfigResponse};
+
+/// This structure describes the main control config of the bribe contract.
+pub type Config = ConfigResponse;
+
+/// This structure describes the bribes of a validator in a period.
+pub type Bribe = BribeInfoResponse;
+
+/// Stores config at the given key.
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// Bribes by key ( period -> validator_addr ).
+pub const BRIBES: Map<(u64, &str), Bribe> = Map::new("bribes");
+
+/// Deposited bribes by key ( period -> validator_addr -> depositor ).
+pub const DEPOSITS: Map<(u64, &str, &Addr), Vec<Coin>> = Map::new("deposits");
+
+/// Claimed bribes by key ( period -> validator_addr -> user ).
+pub const CLAIMS: Map<(u64, &str, &Addr), Vec<Coin>> = Map::new("claims");
+
+/// Contains a proposal to change contract ownership
+pub const OWNERSHIP_PROPOSAL: Item<OwnershipProposal> = Item::new("ownership_proposal");
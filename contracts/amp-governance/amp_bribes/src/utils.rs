@@ -0,0 +1,190 @@
+use cosmwasm_std::{Addr, Coin, QuerierWrapper, StdResult, Uint128};
+use itertools::Itertools;
+
+use eris::amp_gauges::{GaugeInfoResponse, QueryMsg, UserInfoResponse, VotedValidatorInfoResponse};
+use eris::governance_helper::get_period;
+use eris::helpers::bps::BasicPoints;
+
+use crate::error::ContractError;
+use crate::state::{Bribe, Config};
+
+/// Adds the coins to the list, merging coins of the same denom.
+pub(crate) fn add_coins(coins: &mut Vec<Coin>, add: &[Coin]) {
+    for coin in add.iter().filter(|coin| !coin.amount.is_zero()) {
+        match coins.iter_mut().find(|c| c.denom == coin.denom) {
+            Some(existing) => existing.amount += coin.amount,
+            None => coins.push(coin.clone()),
+        }
+    }
+}
+
+/// Returns the funds of a bribe that were not claimed or refunded yet.
+pub(crate) fn unclaimed_coins(bribe: &Bribe) -> StdResult<Vec<Coin>> {
+    let mut unclaimed = vec![];
+    for coin in bribe.funds.iter() {
+        let claimed = bribe
+            .claimed
+            .iter()
+            .find(|c| c.denom == coin.denom)
+            .map(|c| c.amount)
+            .unwrap_or_default();
+
+        let amount = coin.amount.checked_sub(claimed)?;
+        if !amount.is_zero() {
+            unclaimed.push(Coin {
+                denom: coin.denom.clone(),
+                amount,
+            });
+        }
+    }
+    Ok(unclaimed)
+}
+
+/// Splits the unclaimed funds of a bribe pro-rata to the deposits, so that rolled over funds stay
+/// refundable by their depositors. The last depositor of a denom receives its rounding remainder.
+pub(crate) fn split_unclaimed(
+    bribe: &Bribe,
+    unclaimed: &[Coin],
+    deposits: &[(Addr, Vec<Coin>)],
+) -> StdResult<Vec<(Addr, Vec<Coin>)>> {
+    let mut shares: Vec<(Addr, Vec<Coin>)> =
+        deposits.iter().map(|(depositor, _)| (depositor.clone(), vec![])).collect();
+
+    for coin in unclaimed {
+        let total = bribe
+            .funds
+            .iter()
+            .find(|c| c.denom == coin.denom)
+            .map(|c| c.amount)
+            .unwrap_or_default();
+        let depositors = deposits
+            .iter()
+            .enumerate()
+            .filter_map(|(index, (_, deposit))| {
+                let amount = deposit.iter().find(|c| c.denom == coin.denom)?.amount;
+                Some((index, amount))
+            })
+            .collect_vec();
+
+        let mut remaining = coin.amount;
+        for (i, (index, deposited)) in depositors.iter().enumerate() {
+            let amount = if i == depositors.len() - 1 {
+                remaining
+            } else {
+                deposited.multiply_ratio(coin.amount, total)
+            };
+            remaining = remaining.checked_sub(amount)?;
+            add_coins(
+                &mut shares[*index].1,
+                &[Coin {
+                    denom: coin.denom.clone(),
+                    amount,
+                }],
+            );
+        }
+    }
+
+    Ok(shares.into_iter().filter(|(_, share)| !share.is_empty()).collect())
+}
+
+/// Calculates the share of the unclaimed funds of a bribe pro-rata to the user's vAMP.
+pub(crate) fn calc_claimable(
+    bribe: &Bribe,
+    user_vamp: Uint128,
+    total_vamp: Uint128,
+) -> StdResult<Vec<Coin>> {
+    if user_vamp.is_zero() || total_vamp.is_zero() || bribe.rolled_over {
+        return Ok(vec![]);
+    }
+
+    let unclaimed = unclaimed_coins(bribe)?;
+    Ok(bribe
+        .funds
+        .iter()
+        .filter_map(|coin| {
+            let available = unclaimed.iter().find(|c| c.denom == coin.denom)?.amount;
+            let amount = coin.amount.multiply_ratio(user_vamp, total_vamp).min(available);
+            (!amount.is_zero()).then(|| Coin {
+                denom: coin.denom.clone(),
+                amount,
+            })
+        })
+        .collect())
+}
+
+/// Formats the coins for attributes.
+pub(crate) fn coins_to_string(coins: &[Coin]) -> String {
+    coins.iter().map(|coin| coin.to_string()).join(",")
+}
+
+/// Checks that bribes can be claimed in at least one period, otherwise they could be rolled over
+/// before anybody claimed them.
+pub(crate) fn assert_claim_periods(claim_periods: u64) -> Result<(), ContractError> {
+    if claim_periods == 0 {
+        return Err(ContractError::InvalidClaimPeriods {});
+    }
+    Ok(())
+}
+
+/// Checks that the amp gauges were tuned in or after the period, so the votes of the period are final.
+pub(crate) fn assert_period_tuned(
+    querier: &QuerierWrapper,
+    config: &Config,
+    period: u64,
+) -> Result<(), ContractError> {
+    let tune_info: GaugeInfoResponse =
+        querier.query_wasm_smart(&config.amp_gauges_addr, &QueryMsg::TuneInfo {})?;
+
+    if get_period(tune_info.tune_ts)? < period {
+        return Err(ContractError::PeriodNotTuned(period));
+    }
+    Ok(())
+}
+
+/// Queries the vAMP that voted for the validator in the period.
+pub(crate) fn query_validator_vamp(
+    querier: &QuerierWrapper,
+    config: &Config,
+    validator_addr: &str,
+    period: u64,
+) -> StdResult<Uint128> {
+    let info: VotedValidatorInfoResponse = querier.query_wasm_smart(
+        &config.amp_gauges_addr,
+        &QueryMsg::ValidatorInfoAtPeriod {
+            validator_addr: validator_addr.to_string(),
+            period,
+        },
+    )?;
+    Ok(info.voting_power.checked_add(info.fixed_amount)?)
+}
+
+/// Queries the vAMP the user voted for the validator in the period.
+pub(crate) fn query_user_vamp(
+    querier: &QuerierWrapper,
+    config: &Config,
+    user: &Addr,
+    validator_addr: &str,
+    period: u64,
+) -> StdResult<Uint128> {
+    let user_info: UserInfoResponse = querier.query_wasm_smart(
+        &config.amp_gauges_addr,
+        &QueryMsg::UserInfoAtPeriod {
+            user: user.to_string(),
+            period,
+        },
+    )?;
+
+    let vote = user_info
+        .votes
+        .into_iter()
+        .find(|(addr, _)| addr == validator_addr)
+        .map(|(_, bps)| (bps, user_info.current_power));
+
+    match vote {
+        Some((bps, current_power)) => {
+            let bps: BasicPoints = bps.try_into()?;
+            Ok(bps * current_power)
+        },
+        None => Ok(Uint128::zero()),
+    }
+}
@@ -5,8 +5,8 @@ use astroport::common::{claim_ownership, drop_ownership_proposal, propose_new_ow
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    attr, to_json_binary, Addr, Attribute, Binary, Deps, DepsMut, Env, MessageInfo, Order,
-    Response, StdError, StdResult, Storage, Uint128,
+    attr, to_json_binary, Addr, Attribute, Binary, Coin, Deps, DepsMut, Env, MessageInfo, Order,
+    Response, StdError, StdResult,
 };
 use cw2::{get_contract_version, set_contract_version};
 use cw_storage_plus::Bound;
@@ -17,18 +17,17 @@ use eris::amp_gauges::{
     ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, UserInfoResponse, UserInfosResponse,
     VotedValidatorInfoResponse,
 };
-use eris::governance_helper::{calc_voting_power, get_period};
+use eris::governance_helper::{get_keeper_tip_msg, get_period};
 use eris::helpers::bps::BasicPoints;
+use eris::helpers::gauge_votes::VoteUpdate;
 use eris::voting_escrow::{get_lock_info, LockInfoResponse, DEFAULT_LIMIT, MAX_LIMIT};
 
 use crate::error::ContractError;
 use crate::state::{
-    Config, TuneInfo, UserInfo, CONFIG, OWNERSHIP_PROPOSAL, TUNE_INFO, USER_INFO, VALIDATORS,
-};
-use crate::utils::{
-    add_fixed_vamp, cancel_user_changes, fetch_last_validator_fixed_vamp_value, filter_validators,
-    get_validator_info, remove_fixed_vamp, update_validator_info, vote_for_validator,
+    user_info_response, Config, TuneInfo, CONFIG, OWNERSHIP_PROPOSAL, TUNE_INFO, USER_INFO,
+    VALIDATORS, VALIDATOR_GAUGE,
 };
+use crate::utils::{filter_validators, get_validator_info};
 
 /// Contract name that is used for migration.
 const CONTRACT_NAME: &str = "amp-gauges";
@@ -58,6 +57,7 @@ pub fn instantiate(
             escrow_addr: deps.api.addr_validate(&msg.escrow_addr)?,
             hub_addr: deps.api.addr_validate(&msg.hub_addr)?,
             validators_limit: msg.validators_limit,
+            keeper_tip: None,
         },
     )?;
 
@@ -108,7 +108,8 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> E
         ExecuteMsg::TuneVamp {} => tune_vamp(deps, env, info),
         ExecuteMsg::UpdateConfig {
             validators_limit,
-        } => update_config(deps, info, validators_limit),
+            keeper_tip,
+        } => update_config(deps, info, validators_limit, keeper_tip),
         ExecuteMsg::ProposeNewOwner {
             new_owner,
             expires_in,
@@ -153,7 +154,8 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> E
 /// * sum of all BPS values <= 10000.
 ///
 /// The function cancels changes applied by previous votes and apply new votes for the next period.
-/// New vote parameters are saved in [`USER_INFO`].
+/// New vote parameters are saved in [`USER_INFO`]. The votes are also applied to the voting power
+/// delegated to the user, unless the delegator voted themselves.
 ///
 /// The function returns [`Response`] in case of success or [`ContractError`] in case of errors.
 ///
@@ -171,13 +173,16 @@ fn handle_vote(
     let config = CONFIG.load(deps.storage)?;
 
     let ve_lock_info = get_lock_info(&deps.querier, &config.escrow_addr, &user)?;
-    let vamp = ve_lock_info.voting_power + ve_lock_info.fixed_amount;
+    let vamp = ve_lock_info.delegated.iter().fold(
+        ve_lock_info.voting_power + ve_lock_info.fixed_amount,
+        |vamp, delegated| {
+            vamp + delegated.lock_info.voting_power + delegated.lock_info.fixed_amount
+        },
+    );
     if vamp.is_zero() {
         return Err(ContractError::ZeroVotingPower {});
     }
 
-    let user_info = USER_INFO.may_load(deps.storage, &user)?.unwrap_or_default();
-
     // Check duplicated votes
     let addrs_set = votes.iter().cloned().map(|(addr, _)| addr).collect::<HashSet<_>>();
     if votes.len() != addrs_set.len() {
@@ -201,106 +206,19 @@ fn handle_vote(
     // Check the bps sum is within the limit
     votes.iter().try_fold(BasicPoints::default(), |acc, (_, bps)| acc.checked_add(*bps))?;
 
-    remove_votes_of_user(&user_info, block_period, deps.storage)?;
-
-    apply_votest_of_user(
-        votes,
-        deps,
+    VALIDATOR_GAUGE.vote(
+        deps.api,
+        deps.storage,
+        env.block.time.seconds(),
         block_period,
-        ve_lock_info.voting_power,
+        &user,
+        votes,
         ve_lock_info,
-        env,
-        user,
     )?;
 
     Ok(Response::new().add_attribute("action", "vamp/vote").add_attribute("vAMP", vamp))
 }
 
-fn apply_votest_of_user(
-    votes: Vec<(String, BasicPoints)>,
-    deps: DepsMut,
-    block_period: u64,
-    user_vp: Uint128,
-    ve_lock_info: LockInfoResponse,
-    env: Env,
-    user: Addr,
-) -> Result<(), ContractError> {
-    votes.iter().try_for_each(|(validator_addr, bps)| {
-        add_fixed_vamp(
-            deps.storage,
-            block_period + 1,
-            validator_addr,
-            *bps * ve_lock_info.fixed_amount,
-        )?;
-        vote_for_validator(
-            deps.storage,
-            block_period + 1,
-            validator_addr,
-            *bps,
-            user_vp,
-            ve_lock_info.slope,
-            ve_lock_info.end,
-        )
-    })?;
-    let user_info = UserInfo {
-        vote_ts: env.block.time.seconds(),
-        voting_power: user_vp,
-        slope: ve_lock_info.slope,
-        lock_end: ve_lock_info.end,
-        fixed_amount: ve_lock_info.fixed_amount,
-        votes,
-    };
-    USER_INFO.save(deps.storage, &user, &user_info)?;
-    Ok(())
-}
-
-fn remove_votes_of_user(
-    user_info: &UserInfo,
-    block_period: u64,
-    storage: &mut dyn Storage,
-) -> Result<(), ContractError> {
-    if user_info.lock_end > block_period {
-        let user_last_vote_period = get_period(user_info.vote_ts)?;
-        // Calculate voting power before changes
-        let old_vp_at_period = calc_voting_power(
-            user_info.slope,
-            user_info.voting_power,
-            user_last_vote_period,
-            block_period,
-        );
-
-        // Cancel changes applied by previous votes
-        user_info.votes.iter().try_for_each(|(validator_addr, bps)| {
-            remove_fixed_vamp(
-                storage,
-                block_period + 1,
-                validator_addr,
-                *bps * user_info.fixed_amount,
-            )?;
-            cancel_user_changes(
-                storage,
-                block_period + 1,
-                validator_addr,
-                *bps,
-                old_vp_at_period,
-                user_info.slope,
-                user_info.lock_end,
-            )
-        })?;
-    } else {
-        // still need to remove fixed vamp on remove
-        user_info.votes.iter().try_for_each(|(validator_addr, bps)| {
-            remove_fixed_vamp(
-                storage,
-                block_period + 1,
-                validator_addr,
-                *bps * user_info.fixed_amount,
-            )
-        })?;
-    };
-    Ok(())
-}
-
 fn update_vote(
     deps: DepsMut,
     env: Env,
@@ -316,42 +234,20 @@ fn update_vote(
     }
 
     let user = deps.api.addr_validate(&user)?;
-    let user_info = USER_INFO.may_load(deps.storage, &user)?;
-
-    if let Some(user_info) = user_info {
-        remove_votes_of_user(&user_info, block_period, deps.storage)?;
-
-        if lock.voting_power.is_zero() && lock.fixed_amount.is_zero() {
-            let user_info = UserInfo {
-                vote_ts: env.block.time.seconds(),
-                voting_power: Uint128::zero(),
-                slope: lock.slope,
-                lock_end: lock.end,
-                fixed_amount: lock.fixed_amount,
-                votes: user_info.votes,
-            };
-            USER_INFO.save(deps.storage, &user, &user_info)?;
-
-            return Ok(Response::new().add_attribute("action", "vamp/update_vote_removed"));
-        }
-
-        let vamp = lock.voting_power + lock.fixed_amount;
-        apply_votest_of_user(
-            user_info.votes,
-            deps,
-            block_period,
-            lock.voting_power,
-            lock,
-            env,
-            user,
-        )?;
-
-        return Ok(Response::new()
-            .add_attribute("action", "vamp/update_vote_changed")
-            .add_attribute("vAMP", vamp));
-    }
+    let update = VALIDATOR_GAUGE.update_vote(
+        deps.api,
+        deps.storage,
+        env.block.time.seconds(),
+        block_period,
+        &user,
+        lock,
+    )?;
 
-    Ok(Response::new().add_attribute("action", "vamp/update_vote_noop"))
+    let response = Response::new().add_attribute("action", format!("vamp/{}", update.action()));
+    Ok(match update {
+        VoteUpdate::Changed(vamp) => response.add_attribute("vAMP", vamp),
+        _ => response,
+    })
 }
 
 fn remove_user(deps: DepsMut, env: Env, info: MessageInfo, user: String) -> ExecuteResult {
@@ -359,13 +255,9 @@ fn remove_user(deps: DepsMut, env: Env, info: MessageInfo, user: String) -> Exec
     config.assert_owner(&info.sender)?;
 
     let user = deps.api.addr_validate(&user)?;
-    let user_info = USER_INFO.may_load(deps.storage, &user)?;
-
-    if let Some(user_info) = user_info {
-        let block_period = get_period(env.block.time.seconds())?;
-        USER_INFO.remove(deps.storage, &user);
+    let block_period = get_period(env.block.time.seconds())?;
 
-        let result = remove_votes_of_user(&user_info, block_period, deps.storage);
+    if let Some(result) = VALIDATOR_GAUGE.remove_user(deps.storage, block_period, &user)? {
         let msg = if let Err(err) = result {
             err.to_string()
         } else {
@@ -386,10 +278,14 @@ fn remove_user(deps: DepsMut, env: Env, info: MessageInfo, user: String) -> Exec
 /// for these pools and applies allocation points in generator contract.
 fn tune_vamp(deps: DepsMut, env: Env, info: MessageInfo) -> ExecuteResult {
     let config = CONFIG.load(deps.storage)?;
-    config.assert_owner(&info.sender)?;
-
     let block_period = get_period(env.block.time.seconds())?;
 
+    // anyone can tune once per period, the owner at any time
+    let is_keeper = config.assert_owner(&info.sender).is_err();
+    if is_keeper && get_period(TUNE_INFO.load(deps.storage)?.tune_ts)? >= block_period {
+        return Err(ContractError::TuneCooldown(block_period));
+    }
+
     let validator_votes: Vec<_> = VALIDATORS
         .keys(deps.as_ref().storage, None, None, Order::Ascending)
         .collect::<Vec<_>>()
@@ -397,17 +293,25 @@ fn tune_vamp(deps: DepsMut, env: Env, info: MessageInfo) -> ExecuteResult {
         .map(|validator_addr| {
             let validator_addr = validator_addr?;
 
-            let validator_info =
-                update_validator_info(deps.storage, block_period, &validator_addr, None)?;
+            let validator_info = VALIDATOR_GAUGE.update_target_info(
+                deps.storage,
+                block_period,
+                &validator_addr,
+                None,
+            )?;
 
             let vamp = validator_info.voting_power.checked_add(
-                fetch_last_validator_fixed_vamp_value(deps.storage, block_period, &validator_addr)?,
+                VALIDATOR_GAUGE.fetch_last_fixed_vamp_value(
+                    deps.storage,
+                    block_period,
+                    &validator_addr,
+                )?,
             )?;
 
             // Remove pools with zero voting power so we won't iterate over them in future
             if vamp.is_zero()
             // and the next period is also unset
-                && fetch_last_validator_fixed_vamp_value(
+                && VALIDATOR_GAUGE.fetch_last_fixed_vamp_value(
                     deps.storage,
                     block_period + 1,
                     &validator_addr,
@@ -442,7 +346,18 @@ fn tune_vamp(deps: DepsMut, env: Env, info: MessageInfo) -> ExecuteResult {
     let attributes: Vec<Attribute> =
         tune_info.vamp_points.iter().map(|a| attr("vamp", format!("{0}={1}", a.0, a.1))).collect();
 
-    Ok(Response::new().add_attribute("action", "vamp/tune_vamp").add_attributes(attributes))
+    // the hub pays its own keeper when it chains the tuning
+    let tip_msg = if is_keeper && info.sender != config.hub_addr {
+        get_keeper_tip_msg(&deps.querier, &env.contract.address, &info.sender, &config.keeper_tip)?
+    } else {
+        None
+    };
+
+    Ok(Response::new()
+        .add_attribute("action", "vamp/tune_vamp")
+        .add_attributes(attributes)
+        .add_attribute("keeper_tip", tip_msg.is_some().to_string())
+        .add_messages(tip_msg))
 }
 
 /// Only contract owner can call this function.  
@@ -455,7 +370,12 @@ fn tune_vamp(deps: DepsMut, env: Env, info: MessageInfo) -> ExecuteResult {
 /// * **main_pool_min_alloc** is a minimum percentage of ASTRO emissions that this pool should get every block
 ///
 /// * **remove_main_pool** should the main pool be removed or not
-fn update_config(deps: DepsMut, info: MessageInfo, validators_limit: Option<u64>) -> ExecuteResult {
+fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    validators_limit: Option<u64>,
+    keeper_tip: Option<Coin>,
+) -> ExecuteResult {
     let mut config = CONFIG.load(deps.storage)?;
 
     config.assert_owner(&info.sender)?;
@@ -464,6 +384,10 @@ fn update_config(deps: DepsMut, info: MessageInfo, validators_limit: Option<u64>
         config.validators_limit = validators_limit;
     }
 
+    if let Some(keeper_tip) = keeper_tip {
+        config.keeper_tip = Some(keeper_tip).filter(|tip| !tip.amount.is_zero());
+    }
+
     CONFIG.save(deps.storage, &config)?;
 
     Ok(Response::default().add_attribute("action", "vamp/update_config"))
@@ -474,6 +398,8 @@ fn update_config(deps: DepsMut, info: MessageInfo, validators_limit: Option<u64>
 /// ## Queries
 /// * **QueryMsg::UserInfo { user }** Fetch user information
 ///
+/// * **QueryMsg::UserInfoAtPeriod { user, period }** Fetch the user's votes applied in a specified period
+///
 /// * **QueryMsg::TuneInfo** Fetch last tuning information
 ///
 /// * **QueryMsg::Config** Fetch contract config
@@ -487,6 +413,10 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::UserInfo {
             user,
         } => to_json_binary(&user_info(deps, env, user)?),
+        QueryMsg::UserInfoAtPeriod {
+            user,
+            period,
+        } => to_json_binary(&user_info_at_period(deps, user, period)?),
         QueryMsg::UserInfos {
             start_after,
             limit,
@@ -515,7 +445,17 @@ fn user_info(deps: Deps, env: Env, user: String) -> StdResult<UserInfoResponse>
         .ok_or_else(|| StdError::generic_err("User not found"))?;
 
     let block_period = get_period(env.block.time.seconds())?;
-    UserInfo::into_response(user, block_period)
+    user_info_response(user, block_period)
+}
+
+/// Returns the user's votes that were applied in a specified period.
+fn user_info_at_period(deps: Deps, user: String, period: u64) -> StdResult<UserInfoResponse> {
+    let user_addr = deps.api.addr_validate(&user)?;
+    let user_info = VALIDATOR_GAUGE
+        .user_info_at_period(deps.storage, &user_addr, period)?
+        .ok_or_else(|| StdError::generic_err("User not found"))?;
+
+    user_info_response(user_info, period)
 }
 
 // returns all user votes
@@ -543,7 +483,7 @@ fn user_infos(
         .take(limit)
         .map(|item| {
             let (user, v) = item?;
-            Ok((user, UserInfo::into_response(v, block_period)?))
+            Ok((user, user_info_response(v, block_period)?))
         })
         .collect::<StdResult<Vec<(Addr, UserInfoResponse)>>>()?;
 
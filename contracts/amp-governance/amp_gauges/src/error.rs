@@ -22,6 +22,9 @@ pub enum ContractError {
     #[error("There are no validators to tune")]
     TuneNoValidators {},
 
+    #[error("Tuning already happened in period {0}")]
+    TuneCooldown(u64),
+
     #[error("Contract can't be migrated!")]
     MigrationError {},
 }
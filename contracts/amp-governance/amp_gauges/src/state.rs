@@ -1,62 +1,30 @@
 use astroport::common::OwnershipProposal;
 
-use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, StdResult, Uint128};
 use cw_storage_plus::{Item, Map};
 use eris::amp_gauges::{ConfigResponse, GaugeInfoResponse, UserInfoResponse};
-use eris::governance_helper::{calc_voting_power, get_period};
-use eris::helpers::bps::BasicPoints;
+use eris::helpers::gauge_votes::{GaugeVotes, UserInfo, VotedInfo};
 
 /// This structure describes the main control config of generator controller contract.
 pub type Config = ConfigResponse;
 /// This structure describes voting parameters for a specific validator.
-#[cw_serde]
-#[derive(Default)]
-pub struct VotedValidatorInfo {
-    /// voting_power for this validator
-    pub voting_power: Uint128,
-    /// The slope at which the amount of vAMP that voted for this validator will decay
-    pub slope: Uint128,
-}
+pub type VotedValidatorInfo = VotedInfo;
 
 /// This structure describes last tuning parameters.
 pub type TuneInfo = GaugeInfoResponse;
 
-/// The struct describes last user's votes parameters.
-#[cw_serde]
-#[derive(Default)]
-pub struct UserInfo {
-    pub vote_ts: u64,
-    pub voting_power: Uint128,
-    pub slope: Uint128,
-    pub lock_end: u64,
-    pub votes: Vec<(String, BasicPoints)>,
-    pub fixed_amount: Uint128,
-}
-
-impl UserInfo {
-    /// The function converts [`UserInfo`] object into [`UserInfoResponse`].
-    pub(crate) fn into_response(self, period: u64) -> StdResult<UserInfoResponse> {
-        let votes = self
-            .votes
-            .into_iter()
-            .map(|(validator_addr, bps)| (validator_addr, u16::from(bps)))
-            .collect();
-
-        let user_last_vote_period = get_period(self.vote_ts).unwrap_or(period);
-        let vp_at_period =
-            calc_voting_power(self.slope, self.voting_power, user_last_vote_period, period);
-
-        Ok(UserInfoResponse {
-            vote_ts: self.vote_ts,
-            voting_power: self.voting_power,
-            slope: self.slope,
-            lock_end: self.lock_end,
-            votes,
-            fixed_amount: self.fixed_amount,
-            current_power: self.fixed_amount.checked_add(vp_at_period)?,
-        })
-    }
+/// Converts the user's voting information into a [`UserInfoResponse`] at the specified period.
+pub(crate) fn user_info_response(user_info: UserInfo, period: u64) -> StdResult<UserInfoResponse> {
+    Ok(UserInfoResponse {
+        vote_ts: user_info.vote_ts,
+        voting_power: user_info.voting_power,
+        slope: user_info.slope,
+        lock_end: user_info.lock_end,
+        votes: user_info.votes_bps(),
+        fixed_amount: user_info.fixed_amount,
+        current_power: user_info.current_power(period)?,
+        delegated_by: user_info.delegated_by,
+    })
 }
 
 /// Stores config at the given key.
@@ -77,9 +45,23 @@ pub const VALIDATOR_SLOPE_CHANGES: Map<(&str, u64), Uint128> = Map::new("validat
 
 pub const VALIDATOR_FIXED_VAMP: Map<(&str, u64), Uint128> = Map::new("validator_fixed_vamp");
 
+/// The slope based vote accounting of the validators, stored in the maps above.
+pub const VALIDATOR_GAUGE: GaugeVotes = GaugeVotes {
+    targets: VALIDATORS,
+    votes: VALIDATOR_VOTES,
+    periods: VALIDATOR_PERIODS,
+    slope_changes: VALIDATOR_SLOPE_CHANGES,
+    fixed_vamp: VALIDATOR_FIXED_VAMP,
+    user_info: USER_INFO,
+    user_info_history: USER_INFO_HISTORY,
+};
+
 /// User's voting information.
 pub const USER_INFO: Map<&Addr, UserInfo> = Map::new("user_info");
 
+/// User's voting information by key ( user -> period ), stored for the period in which the votes are applied.
+pub const USER_INFO_HISTORY: Map<(&Addr, u64), UserInfo> = Map::new("user_info_history");
+
 /// Last tuning information.
 pub const TUNE_INFO: Item<TuneInfo> = Item::new("tune_info");
 
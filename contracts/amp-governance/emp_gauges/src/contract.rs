@@ -4,26 +4,32 @@ use astroport::common::{claim_ownership, drop_ownership_proposal, propose_new_ow
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    attr, to_json_binary, Attribute, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response,
-    StdError, StdResult,
+    attr, to_json_binary, Attribute, Binary, Coin, Deps, DepsMut, Env, MessageInfo, Order,
+    Response, StdError, StdResult, Uint128,
 };
 use cw2::{get_contract_version, set_contract_version};
+use cw_storage_plus::Bound;
 use eris::helpers::slope::adjust_vp_and_slope;
 use eris::hub::get_hub_validators;
 use itertools::Itertools;
 
 use crate::error::ContractError;
-use crate::state::{Config, TuneInfo, CONFIG, OWNERSHIP_PROPOSAL, TUNE_INFO, VALIDATORS};
+use crate::state::{
+    Config, EmpGrant, TuneInfo, CONFIG, EMP_GRANTS, EMP_ID, OWNERSHIP_PROPOSAL, TUNE_INFO,
+    VALIDATORS, VALIDATOR_GRANTS,
+};
 use crate::utils::{
-    add_fixed_emp, fetch_last_validator_fixed_emps_value, filter_validators, get_validator_info,
-    update_validator_info, vote_for_validator,
+    add_fixed_emp, cancel_validator_changes, fetch_last_validator_fixed_emps_value,
+    filter_validators, get_validator_info, remove_fixed_emp, update_validator_info,
+    vote_for_validator,
 };
 use eris::emp_gauges::{
-    get_tune_msg, AddEmpInfo, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
+    get_tune_msg, AddEmpInfo, EmpGrantResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
     VotedValidatorInfoResponse,
 };
 
-use eris::governance_helper::get_period;
+use eris::governance_helper::{calc_voting_power, get_keeper_tip_msg, get_period};
+use eris::voting_escrow::{DEFAULT_LIMIT, MAX_LIMIT};
 
 /// Contract name that is used for migration.
 const CONTRACT_NAME: &str = "emp-gauges";
@@ -48,6 +54,7 @@ pub fn instantiate(
             owner: deps.api.addr_validate(&msg.owner)?,
             hub_addr: deps.api.addr_validate(&msg.hub_addr)?,
             validators_limit: msg.validators_limit,
+            keeper_tip: None,
         },
     )?;
 
@@ -90,9 +97,13 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> E
             emps,
         } => add_emps(deps, env, info, emps),
         ExecuteMsg::TuneEmps {} => tune_emps(deps, env, info),
+        ExecuteMsg::RemoveEmps {
+            ids,
+        } => remove_emps(deps, env, info, ids),
         ExecuteMsg::UpdateConfig {
             validators_limit,
-        } => update_config(deps, info, validators_limit),
+            keeper_tip,
+        } => update_config(deps, info, validators_limit, keeper_tip),
         ExecuteMsg::ProposeNewOwner {
             new_owner,
             expires_in,
@@ -171,12 +182,22 @@ fn add_emps(
             return Err(ContractError::InvalidValidatorAddress(validator_addr));
         }
 
-        added_points.iter().try_for_each(|emp| -> StdResult<()> {
-            if let Some(decaying_periods) = emp.decaying_period {
+        added_points.into_iter().try_for_each(|emp| -> StdResult<()> {
+            let mut grant = EmpGrant {
+                validator_addr: validator_addr.clone(),
+                period: block_period,
+                voting_power: Uint128::zero(),
+                slope: Uint128::zero(),
+                end: block_period,
+                revoked_period: None,
+                emp,
+            };
+
+            if let Some(decaying_periods) = grant.emp.decaying_period {
                 let dt = decaying_periods;
 
                 let end = block_period + dt;
-                let mut add_voting_power = emp.umerit_points;
+                let mut add_voting_power = grant.emp.umerit_points;
                 let slope = adjust_vp_and_slope(&mut add_voting_power, dt)?; // Uint128::zero()
 
                 vote_for_validator(
@@ -187,10 +208,28 @@ fn add_emps(
                     slope,
                     end,
                 )?;
+
+                grant.voting_power = add_voting_power;
+                grant.slope = slope;
+                grant.end = end;
             } else {
-                add_fixed_emp(deps.storage, block_period, &validator_addr, emp.umerit_points)?;
+                add_fixed_emp(
+                    deps.storage,
+                    block_period,
+                    &validator_addr,
+                    grant.emp.umerit_points,
+                )?;
             }
 
+            let id = EMP_ID.may_load(deps.storage)?.unwrap_or_default() + 1;
+            EMP_ID.save(deps.storage, &id)?;
+            EMP_GRANTS.save(deps.storage, id, &grant)?;
+            VALIDATOR_GRANTS.save(
+                deps.storage,
+                (validator_addr.as_str(), block_period, id),
+                &(),
+            )?;
+
             Ok(())
         })?;
     }
@@ -200,6 +239,55 @@ fn add_emps(
         .add_attribute("action", "emp/vote"))
 }
 
+/// Revokes the remaining points of the grants from the current period onwards. The grants are kept
+/// with the revoked period, so the EMP allocation stays auditable.
+fn remove_emps(deps: DepsMut, env: Env, info: MessageInfo, ids: Vec<u64>) -> ExecuteResult {
+    let config = CONFIG.load(deps.storage)?;
+    config.assert_owner(&info.sender)?;
+
+    let block_period = get_period(env.block.time.seconds())?;
+
+    for id in ids.iter() {
+        let mut grant =
+            EMP_GRANTS.may_load(deps.storage, *id)?.ok_or(ContractError::GrantNotFound(*id))?;
+
+        if grant.revoked_period.is_some() {
+            return Err(ContractError::GrantAlreadyRevoked(*id));
+        }
+
+        if grant.emp.decaying_period.is_some() {
+            // decayed grants do not have any points left
+            if block_period <= grant.end {
+                let remaining_vp =
+                    calc_voting_power(grant.slope, grant.voting_power, grant.period, block_period);
+                cancel_validator_changes(
+                    deps.storage,
+                    block_period,
+                    &grant.validator_addr,
+                    remaining_vp,
+                    grant.slope,
+                    grant.end,
+                )?;
+            }
+        } else {
+            remove_fixed_emp(
+                deps.storage,
+                block_period,
+                &grant.validator_addr,
+                grant.emp.umerit_points,
+            )?;
+        }
+
+        grant.revoked_period = Some(block_period);
+        EMP_GRANTS.save(deps.storage, *id, &grant)?;
+    }
+
+    Ok(Response::new()
+        .add_message(get_tune_msg(env.contract.address.to_string())?)
+        .add_attribute("action", "emp/remove_emps")
+        .add_attribute("ids", ids.iter().join(",")))
+}
+
 /// The function checks that the last pools tuning happened >= 14 days ago.
 /// Then it calculates voting power for each pool at the current period, filters all pools which
 /// are not eligible to receive allocation points,
@@ -207,16 +295,17 @@ fn add_emps(
 /// for these pools and applies allocation points in generator contract.
 fn tune_emps(deps: DepsMut, env: Env, info: MessageInfo) -> ExecuteResult {
     let config = CONFIG.load(deps.storage)?;
-    config.assert_owner_or_self(&info.sender, &env.contract.address)?;
 
     let mut tune_info = TUNE_INFO.load(deps.storage)?;
 
     // for emps we always tune immediately after the vote and apply the next period
     let block_period = get_period(env.block.time.seconds())?;
 
-    // if block_period <= tune_info.tune_period {
-    //     return Err(ContractError::CooldownError {});
-    // }
+    // anyone can tune once per period, the owner and the contract itself at any time
+    let is_keeper = config.assert_owner_or_self(&info.sender, &env.contract.address).is_err();
+    if is_keeper && block_period <= tune_info.tune_period {
+        return Err(ContractError::TuneCooldown(block_period));
+    }
 
     let validator_votes: Vec<_> = VALIDATORS
         .keys(deps.as_ref().storage, None, None, Order::Ascending)
@@ -262,10 +351,19 @@ fn tune_emps(deps: DepsMut, env: Env, info: MessageInfo) -> ExecuteResult {
     let attributes: Vec<Attribute> =
         tune_info.emp_points.iter().map(|a| attr("emps", format!("{0}={1}", a.0, a.1))).collect();
 
+    // the hub pays its own keeper when it chains the tuning
+    let tip_msg = if is_keeper && info.sender != config.hub_addr {
+        get_keeper_tip_msg(&deps.querier, &env.contract.address, &info.sender, &config.keeper_tip)?
+    } else {
+        None
+    };
+
     Ok(Response::new()
         .add_attribute("action", "emp/tune_emps")
         .add_attribute("next_period", block_period.to_string())
-        .add_attributes(attributes))
+        .add_attributes(attributes)
+        .add_attribute("keeper_tip", tip_msg.is_some().to_string())
+        .add_messages(tip_msg))
 }
 
 /// Only contract owner can call this function.  
@@ -278,7 +376,12 @@ fn tune_emps(deps: DepsMut, env: Env, info: MessageInfo) -> ExecuteResult {
 /// * **main_pool_min_alloc** is a minimum percentage of ASTRO emissions that this pool should get every block
 ///
 /// * **remove_main_pool** should the main pool be removed or not
-fn update_config(deps: DepsMut, info: MessageInfo, validators_limit: Option<u64>) -> ExecuteResult {
+fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    validators_limit: Option<u64>,
+    keeper_tip: Option<Coin>,
+) -> ExecuteResult {
     let mut config = CONFIG.load(deps.storage)?;
     config.assert_owner(&info.sender)?;
 
@@ -286,6 +389,10 @@ fn update_config(deps: DepsMut, info: MessageInfo, validators_limit: Option<u64>
         config.validators_limit = validators_limit;
     }
 
+    if let Some(keeper_tip) = keeper_tip {
+        config.keeper_tip = Some(keeper_tip).filter(|tip| !tip.amount.is_zero());
+    }
+
     CONFIG.save(deps.storage, &config)?;
 
     Ok(Response::default().add_attribute("action", "emp/update_config"))
@@ -319,9 +426,53 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             validator_addr,
             period,
         } => to_json_binary(&validator_info(deps, env, validator_addr, Some(period))?),
+        QueryMsg::EmpGrant {
+            id,
+        } => to_json_binary(&EMP_GRANTS.load(deps.storage, id)?.into_response(id)),
+        QueryMsg::EmpGrants {
+            validator_addr,
+            period,
+            start_after,
+            limit,
+        } => to_json_binary(&emp_grants(deps, validator_addr, period, start_after, limit)?),
     }
 }
 
+/// Returns the grants of a validator, optionally only of a single period.
+fn emp_grants(
+    deps: Deps,
+    validator_addr: String,
+    period: Option<u64>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<EmpGrantResponse>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    let ids = if let Some(period) = period {
+        VALIDATOR_GRANTS
+            .prefix((validator_addr.as_str(), period))
+            .keys(deps.storage, start_after.map(Bound::exclusive), None, Order::Ascending)
+            .take(limit)
+            .collect::<StdResult<Vec<_>>>()?
+    } else {
+        let start = start_after
+            .map(|id| -> StdResult<_> {
+                let grant = EMP_GRANTS.load(deps.storage, id)?;
+                Ok(Bound::exclusive((grant.period, id)))
+            })
+            .transpose()?;
+
+        VALIDATOR_GRANTS
+            .sub_prefix(validator_addr.as_str())
+            .keys(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|key| key.map(|(_, id)| id))
+            .collect::<StdResult<Vec<_>>>()?
+    };
+
+    ids.into_iter().map(|id| Ok(EMP_GRANTS.load(deps.storage, id)?.into_response(id))).collect()
+}
+
 /// Returns all active validators info at a specified period.
 fn validator_infos(
     deps: Deps,
@@ -13,9 +13,18 @@ pub enum ContractError {
     #[error("Votes contain duplicated validators addresses")]
     DuplicatedValidators {},
 
+    #[error("Grant {0} not found")]
+    GrantNotFound(u64),
+
+    #[error("Grant {0} was already revoked")]
+    GrantAlreadyRevoked(u64),
+
     #[error("There are no validators to tune")]
     TuneNoValidators {},
 
+    #[error("Tuning already happened in period {0}")]
+    TuneCooldown(u64),
+
     #[error("Contract can't be migrated!")]
     MigrationError {},
 }
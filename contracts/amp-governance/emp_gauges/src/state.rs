@@ -3,7 +3,7 @@ use astroport::common::OwnershipProposal;
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::Uint128;
 use cw_storage_plus::{Item, Map};
-use eris::emp_gauges::{ConfigResponse, GaugeInfoResponse};
+use eris::emp_gauges::{ConfigResponse, EmpGrantResponse, EmpInfo, GaugeInfoResponse};
 
 /// This structure describes the main control config of generator controller contract.
 pub type Config = ConfigResponse;
@@ -21,10 +21,32 @@ pub struct VotedValidatorInfo {
 /// This structure describes last tuning parameters.
 pub type TuneInfo = GaugeInfoResponse;
 
-/// The struct describes last user's votes parameters.
+/// The struct describes a grant of EMPs and the parameters needed to revoke it.
 #[cw_serde]
-#[derive(Default)]
-pub struct EmpInfo {}
+pub struct EmpGrant {
+    pub validator_addr: String,
+    pub period: u64,
+    pub emp: EmpInfo,
+    /// Decaying power after adjusting it to the slope
+    pub voting_power: Uint128,
+    pub slope: Uint128,
+    /// Last period of the decaying power
+    pub end: u64,
+    pub revoked_period: Option<u64>,
+}
+
+impl EmpGrant {
+    /// The function converts [`EmpGrant`] object into [`EmpGrantResponse`].
+    pub(crate) fn into_response(self, id: u64) -> EmpGrantResponse {
+        EmpGrantResponse {
+            id,
+            validator_addr: self.validator_addr,
+            period: self.period,
+            emp: self.emp,
+            revoked_period: self.revoked_period,
+        }
+    }
+}
 
 /// Stores config at the given key.
 pub const CONFIG: Item<Config> = Item::new("config");
@@ -43,8 +65,12 @@ pub const VALIDATOR_SLOPE_CHANGES: Map<(&str, u64), Uint128> = Map::new("validat
 
 pub const VALIDATOR_FIXED_EMPS: Map<(&str, u64), Uint128> = Map::new("validator_fixed_emps");
 
-// pub const EMP_ID: Item<u64> = Item::new("emp_id");
-// pub const EMP_INFOS: Map<u64, EmpInfo> = Map::new("emp_info");
+/// Id of the last grant.
+pub const EMP_ID: Item<u64> = Item::new("emp_id");
+/// All grants by their id.
+pub const EMP_GRANTS: Map<u64, EmpGrant> = Map::new("emp_grants");
+/// HashSet based on [`Map`]. It stores null object by key ( validator_addr -> period -> id ).
+pub const VALIDATOR_GRANTS: Map<(&str, u64, u64), ()> = Map::new("validator_grants");
 
 /// Last tuning information.
 pub const TUNE_INFO: Item<TuneInfo> = Item::new("tune_info");
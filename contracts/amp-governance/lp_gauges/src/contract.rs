@@ -0,0 +1,552 @@
+use std::collections::HashSet;
+use std::convert::TryInto;
+
+use astroport::common::{claim_ownership, drop_ownership_proposal, propose_new_owner};
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    attr, to_json_binary, Addr, Api, Attribute, Binary, Deps, DepsMut, Env, MessageInfo, Order,
+    Response, StdError, StdResult,
+};
+use cw2::{get_contract_version, set_contract_version};
+use cw_storage_plus::Bound;
+use itertools::Itertools;
+
+use eris::governance_helper::get_period;
+use eris::helpers::bps::BasicPoints;
+use eris::helpers::gauge_votes::VoteUpdate;
+use eris::lp_gauges::{
+    ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, UserInfoResponse, UserInfosResponse,
+    VotedTargetInfoResponse,
+};
+use eris::voting_escrow::{get_lock_info, LockInfoResponse, DEFAULT_LIMIT, MAX_LIMIT};
+
+use crate::error::ContractError;
+use crate::state::{
+    user_info_response, Config, TuneInfo, CONFIG, OWNERSHIP_PROPOSAL, TARGETS, TARGET_GAUGE,
+    TUNE_INFO, USER_INFO,
+};
+use crate::utils::{calc_weights, filter_targets, get_target_info};
+
+/// Contract name that is used for migration.
+const CONTRACT_NAME: &str = "lp-gauges";
+/// Contract version that is used for migration.
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// const DAY: u64 = 86400;
+// It is possible to tune pools once every 14 days
+// const TUNE_COOLDOWN: u64 = WEEK * 3;
+
+type ExecuteResult = Result<Response, ContractError>;
+
+/// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> ExecuteResult {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            owner: deps.api.addr_validate(&msg.owner)?,
+            escrow_addr: deps.api.addr_validate(&msg.escrow_addr)?,
+            targets: validate_targets(deps.api, msg.targets)?,
+            targets_limit: msg.targets_limit,
+        },
+    )?;
+
+    // Set tune_ts just for safety so the first tuning could happen in 2 weeks
+    TUNE_INFO.save(
+        deps.storage,
+        &TuneInfo {
+            tune_ts: env.block.time.seconds(),
+            target_points: vec![],
+            weights: vec![],
+        },
+    )?;
+
+    Ok(Response::default())
+}
+
+/// Exposes all the execute functions available in the contract.
+///
+/// ## Execute messages
+/// * **ExecuteMsg::Vote { votes }** Casts votes for LP targets
+///
+/// * **ExecuteMsg::UpdateVote { user, lock_info }** Updates the votes of a user after a lock change
+///
+/// * **ExecuteMsg::TuneTargets {}** Launches the tuning of the LP target weights
+///
+/// * **ExecuteMsg::UpdateConfig { targets_limit }** Changes the number of targets which are eligible
+///     to receive a weight
+///
+/// * **ExecuteMsg::UpdateTargets { add, remove }** Adds or removes whitelisted LP targets
+///
+/// * **ExecuteMsg::RemoveUser { user }** Removes the votes of a user
+///
+/// * **ExecuteMsg::ProposeNewOwner { owner, expires_in }** Creates a new request to change
+///     contract ownership.
+///
+/// * **ExecuteMsg::DropOwnershipProposal {}** Removes a request to change contract ownership.
+///
+/// * **ExecuteMsg::ClaimOwnership {}** Claims contract ownership.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> ExecuteResult {
+    match msg {
+        ExecuteMsg::Vote {
+            votes,
+        } => handle_vote(deps, env, info, votes),
+        ExecuteMsg::UpdateVote {
+            user,
+            lock_info,
+        } => update_vote(deps, env, info, user, lock_info),
+        ExecuteMsg::RemoveUser {
+            user,
+        } => remove_user(deps, env, info, user),
+        ExecuteMsg::TuneTargets {} => tune_targets(deps, env, info),
+        ExecuteMsg::UpdateConfig {
+            targets_limit,
+        } => update_config(deps, info, targets_limit),
+        ExecuteMsg::UpdateTargets {
+            add,
+            remove,
+        } => update_targets(deps, info, add, remove),
+        ExecuteMsg::ProposeNewOwner {
+            new_owner,
+            expires_in,
+        } => {
+            let config: Config = CONFIG.load(deps.storage)?;
+
+            propose_new_owner(
+                deps,
+                info,
+                env,
+                new_owner,
+                expires_in,
+                config.owner,
+                OWNERSHIP_PROPOSAL,
+            )
+            .map_err(Into::into)
+        },
+        ExecuteMsg::DropOwnershipProposal {} => {
+            let config: Config = CONFIG.load(deps.storage)?;
+
+            drop_ownership_proposal(deps, info, config.owner, OWNERSHIP_PROPOSAL)
+                .map_err(Into::into)
+        },
+        ExecuteMsg::ClaimOwnership {} => {
+            claim_ownership(deps, info, env, OWNERSHIP_PROPOSAL, |deps, new_owner| {
+                CONFIG
+                    .update::<_, StdError>(deps.storage, |mut v| {
+                        v.owner = new_owner;
+                        Ok(v)
+                    })
+                    .map(|_| ())
+            })
+            .map_err(Into::into)
+        },
+    }
+}
+
+/// The function checks that:
+/// * the user voting power is > 0,
+/// * all target addresses are whitelisted,
+/// * 'votes' vector doesn't contain duplicated target addresses,
+/// * sum of all BPS values <= 10000.
+///
+/// The function cancels changes applied by previous votes and apply new votes for the next period.
+/// New vote parameters are saved in [`USER_INFO`]. The votes are also applied to the voting power
+/// delegated to the user, unless the delegator voted themselves.
+///
+/// The function returns [`Response`] in case of success or [`ContractError`] in case of errors.
+///
+/// * **votes** is a vector of pairs ([`String`], [`u16`]).
+///     Tuple consists of target address and percentage of user's voting power for a given target.
+///     Percentage should be in BPS form.
+fn handle_vote(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    votes: Vec<(String, u16)>,
+) -> ExecuteResult {
+    let user = info.sender;
+    let block_period = get_period(env.block.time.seconds())?;
+    let config = CONFIG.load(deps.storage)?;
+
+    let ve_lock_info = get_lock_info(&deps.querier, &config.escrow_addr, &user)?;
+    let vamp = ve_lock_info.delegated.iter().fold(
+        ve_lock_info.voting_power + ve_lock_info.fixed_amount,
+        |vamp, delegated| {
+            vamp + delegated.lock_info.voting_power + delegated.lock_info.fixed_amount
+        },
+    );
+    if vamp.is_zero() {
+        return Err(ContractError::ZeroVotingPower {});
+    }
+
+    // Check duplicated votes
+    let addrs_set = votes.iter().cloned().map(|(addr, _)| addr).collect::<HashSet<_>>();
+    if votes.len() != addrs_set.len() {
+        return Err(ContractError::DuplicatedTargets {});
+    }
+
+    // Validating addrs and bps
+    let votes = votes
+        .into_iter()
+        .map(|(addr, bps)| {
+            if !config.targets.iter().any(|target| target.as_str() == addr) {
+                return Err(ContractError::InvalidTargetAddress(addr));
+            }
+            let bps: BasicPoints = bps.try_into()?;
+            Ok((addr, bps))
+        })
+        .collect::<Result<Vec<_>, ContractError>>()?;
+
+    // Check the bps sum is within the limit
+    votes.iter().try_fold(BasicPoints::default(), |acc, (_, bps)| acc.checked_add(*bps))?;
+
+    TARGET_GAUGE.vote(
+        deps.api,
+        deps.storage,
+        env.block.time.seconds(),
+        block_period,
+        &user,
+        votes,
+        ve_lock_info,
+    )?;
+
+    Ok(Response::new().add_attribute("action", "lp/vote").add_attribute("vAMP", vamp))
+}
+
+fn update_vote(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    user: String,
+    lock: LockInfoResponse,
+) -> ExecuteResult {
+    let block_period = get_period(env.block.time.seconds())?;
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.escrow_addr {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let user = deps.api.addr_validate(&user)?;
+    let update = TARGET_GAUGE.update_vote(
+        deps.api,
+        deps.storage,
+        env.block.time.seconds(),
+        block_period,
+        &user,
+        lock,
+    )?;
+
+    let response = Response::new().add_attribute("action", format!("lp/{}", update.action()));
+    Ok(match update {
+        VoteUpdate::Changed(vamp) => response.add_attribute("vAMP", vamp),
+        _ => response,
+    })
+}
+
+fn remove_user(deps: DepsMut, env: Env, info: MessageInfo, user: String) -> ExecuteResult {
+    let config = CONFIG.load(deps.storage)?;
+    config.assert_owner(&info.sender)?;
+
+    let user = deps.api.addr_validate(&user)?;
+    let block_period = get_period(env.block.time.seconds())?;
+
+    if let Some(result) = TARGET_GAUGE.remove_user(deps.storage, block_period, &user)? {
+        let msg = if let Err(err) = result {
+            err.to_string()
+        } else {
+            "ok".to_string()
+        };
+        return Ok(Response::new()
+            .add_attribute("action", "lp/remove_user")
+            .add_attribute("remove_votes", msg));
+    }
+
+    Ok(Response::new().add_attribute("action", "lp/remove_user_noop"))
+}
+
+/// The function calculates voting power for each target at the current period, filters all
+/// targets which are no longer whitelisted, takes top X targets by voting power, where X is
+/// 'config.targets_limit', and stores the weights of these targets for the router.
+fn tune_targets(deps: DepsMut, env: Env, info: MessageInfo) -> ExecuteResult {
+    let config = CONFIG.load(deps.storage)?;
+    config.assert_owner(&info.sender)?;
+
+    let block_period = get_period(env.block.time.seconds())?;
+
+    let target_votes: Vec<_> = TARGETS
+        .keys(deps.as_ref().storage, None, None, Order::Ascending)
+        .collect::<Vec<_>>()
+        .into_iter()
+        .map(|target_addr| {
+            let target_addr = target_addr?;
+
+            let target_info =
+                TARGET_GAUGE.update_target_info(deps.storage, block_period, &target_addr, None)?;
+
+            let vamp =
+                target_info.voting_power.checked_add(TARGET_GAUGE.fetch_last_fixed_vamp_value(
+                    deps.storage,
+                    block_period,
+                    &target_addr,
+                )?)?;
+
+            // Remove pools with zero voting power so we won't iterate over them in future
+            if vamp.is_zero()
+            // and the next period is also unset
+                && TARGET_GAUGE.fetch_last_fixed_vamp_value(
+                    deps.storage,
+                    block_period + 1,
+                    &target_addr,
+                )?
+                .is_zero()
+            {
+                TARGETS.remove(deps.storage, &target_addr)
+            }
+            Ok((target_addr, vamp))
+        })
+        .collect::<StdResult<Vec<_>>>()?
+        .into_iter()
+        .filter(|(_, vamp_amount)| !vamp_amount.is_zero())
+        .sorted_by(|(_, a), (_, b)| b.cmp(a)) // Sort in descending order
+        .collect();
+
+    let mut tune_info = TUNE_INFO.load(deps.storage)?;
+    tune_info.target_points = filter_targets(&config, target_votes);
+
+    if tune_info.target_points.is_empty() {
+        return Err(ContractError::TuneNoTargets {});
+    }
+
+    tune_info.weights = calc_weights(&tune_info.target_points);
+    tune_info.tune_ts = env.block.time.seconds();
+    TUNE_INFO.save(deps.storage, &tune_info)?;
+
+    let attributes: Vec<Attribute> =
+        tune_info.weights.iter().map(|a| attr("weight", format!("{0}={1}", a.0, a.1))).collect();
+
+    Ok(Response::new().add_attribute("action", "lp/tune_targets").add_attributes(attributes))
+}
+
+/// Only contract owner can call this function.  
+/// The function sets a new limit of targets that can receive a weight at the same time.
+///
+/// * **targets_limit** is a new limit of targets which can receive a weight
+fn update_config(deps: DepsMut, info: MessageInfo, targets_limit: Option<u64>) -> ExecuteResult {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    config.assert_owner(&info.sender)?;
+
+    if let Some(targets_limit) = targets_limit {
+        config.targets_limit = targets_limit;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default().add_attribute("action", "lp/update_config"))
+}
+
+/// Only contract owner can call this function.  
+/// The function adds and removes whitelisted LP targets. Votes for removed targets are kept, but
+/// they are no longer considered during tuning.
+fn update_targets(
+    deps: DepsMut,
+    info: MessageInfo,
+    add: Option<Vec<String>>,
+    remove: Option<Vec<String>>,
+) -> ExecuteResult {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    config.assert_owner(&info.sender)?;
+
+    if let Some(remove) = remove {
+        config.targets.retain(|target| !remove.contains(&target.to_string()));
+    }
+
+    if let Some(add) = add {
+        for target in validate_targets(deps.api, add)? {
+            if !config.targets.contains(&target) {
+                config.targets.push(target);
+            }
+        }
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default().add_attribute("action", "lp/update_targets"))
+}
+
+fn validate_targets(api: &dyn Api, targets: Vec<String>) -> StdResult<Vec<Addr>> {
+    targets.iter().map(|target| api.addr_validate(target)).collect()
+}
+
+/// Expose available contract queries.
+///
+/// ## Queries
+/// * **QueryMsg::UserInfo { user }** Fetch user information
+///
+/// * **QueryMsg::UserInfoAtPeriod { user, period }** Fetch the user's votes applied in a specified period
+///
+/// * **QueryMsg::TuneInfo** Fetch last tuning information
+///
+/// * **QueryMsg::Config** Fetch contract config
+///
+/// * **QueryMsg::TargetInfo { target_addr }** Fetch target's voting information at the current period.
+///
+/// * **QueryMsg::TargetInfoAtPeriod { target_addr, period }** Fetch target's voting information at a specified period.
+///
+/// * **QueryMsg::TargetInfos { target_addrs, period }** Fetch the voting information of multiple targets.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::UserInfo {
+            user,
+        } => to_json_binary(&user_info(deps, env, user)?),
+        QueryMsg::UserInfoAtPeriod {
+            user,
+            period,
+        } => to_json_binary(&user_info_at_period(deps, user, period)?),
+        QueryMsg::UserInfos {
+            start_after,
+            limit,
+        } => to_json_binary(&user_infos(deps, env, start_after, limit)?),
+        QueryMsg::TuneInfo {} => to_json_binary(&TUNE_INFO.load(deps.storage)?),
+        QueryMsg::Config {} => to_json_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::TargetInfo {
+            target_addr,
+        } => to_json_binary(&target_info(deps, env, target_addr, None)?),
+        QueryMsg::TargetInfos {
+            period,
+            target_addrs,
+        } => to_json_binary(&target_infos(deps, env, target_addrs, period)?),
+        QueryMsg::TargetInfoAtPeriod {
+            target_addr,
+            period,
+        } => to_json_binary(&target_info(deps, env, target_addr, Some(period))?),
+    }
+}
+
+/// Returns user information.
+fn user_info(deps: Deps, env: Env, user: String) -> StdResult<UserInfoResponse> {
+    let user_addr = deps.api.addr_validate(&user)?;
+    let user = USER_INFO
+        .may_load(deps.storage, &user_addr)?
+        .ok_or_else(|| StdError::generic_err("User not found"))?;
+
+    let block_period = get_period(env.block.time.seconds())?;
+    user_info_response(user, block_period)
+}
+
+/// Returns the user's votes that were applied in a specified period.
+fn user_info_at_period(deps: Deps, user: String, period: u64) -> StdResult<UserInfoResponse> {
+    let user_addr = deps.api.addr_validate(&user)?;
+    let user_info = TARGET_GAUGE
+        .user_info_at_period(deps.storage, &user_addr, period)?
+        .ok_or_else(|| StdError::generic_err("User not found"))?;
+
+    user_info_response(user_info, period)
+}
+
+// returns all user votes
+fn user_infos(
+    deps: Deps,
+    env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<UserInfosResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    let mut start: Option<Bound<&Addr>> = None;
+    let addr: Addr;
+    if let Some(start_after) = start_after {
+        if let Ok(start_after_addr) = deps.api.addr_validate(&start_after) {
+            addr = start_after_addr;
+            start = Some(Bound::exclusive(&addr));
+        }
+    }
+
+    let block_period = get_period(env.block.time.seconds())?;
+
+    let users = USER_INFO
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (user, v) = item?;
+            Ok((user, user_info_response(v, block_period)?))
+        })
+        .collect::<StdResult<Vec<(Addr, UserInfoResponse)>>>()?;
+
+    Ok(UserInfosResponse {
+        users,
+    })
+}
+
+/// Returns all active targets info at a specified period.
+fn target_infos(
+    deps: Deps,
+    env: Env,
+    target_addrs: Option<Vec<String>>,
+    period: Option<u64>,
+) -> StdResult<Vec<(String, VotedTargetInfoResponse)>> {
+    let period = period.unwrap_or(get_period(env.block.time.seconds())?);
+
+    // use active targets as fallback
+    let target_addrs = target_addrs.unwrap_or_else(|| {
+        let active_targets =
+            TARGETS.keys(deps.storage, None, None, Order::Ascending).collect::<StdResult<Vec<_>>>();
+
+        active_targets.unwrap_or_default()
+    });
+
+    let target_infos: Vec<_> = target_addrs
+        .into_iter()
+        .map(|target_addr| {
+            let target_info = get_target_info(deps.storage, period, &target_addr)?;
+            Ok((target_addr, target_info))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(target_infos)
+}
+
+/// Returns target's voting information at a specified period.
+fn target_info(
+    deps: Deps,
+    env: Env,
+    target_addr: String,
+    period: Option<u64>,
+) -> StdResult<VotedTargetInfoResponse> {
+    let block_period = get_period(env.block.time.seconds())?;
+    let period = period.unwrap_or(block_period);
+    get_target_info(deps.storage, period, &target_addr)
+}
+
+/// Manages contract migration
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let contract_version = get_contract_version(deps.storage)?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    if contract_version.contract != CONTRACT_NAME {
+        return Err(StdError::generic_err(format!(
+            "contract_name does not match: prev: {0}, new: {1}",
+            contract_version.contract, CONTRACT_VERSION
+        ))
+        .into());
+    }
+
+    Ok(Response::new()
+        .add_attribute("previous_contract_name", &contract_version.contract)
+        .add_attribute("previous_contract_version", &contract_version.version)
+        .add_attribute("new_contract_name", CONTRACT_NAME)
+        .add_attribute("new_contract_version", CONTRACT_VERSION))
+}
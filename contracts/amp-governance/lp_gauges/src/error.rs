@@ -0,0 +1,27 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+/// This enum describes contract errors
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("You can't vote with zero voting power")]
+    ZeroVotingPower {},
+
+    #[error("Invalid target address: {0}")]
+    InvalidTargetAddress(String),
+
+    #[error("Votes contain duplicated target addresses")]
+    DuplicatedTargets {},
+
+    #[error("There are no targets to tune")]
+    TuneNoTargets {},
+
+    #[error("Contract can't be migrated!")]
+    MigrationError {},
+}
@@ -0,0 +1,69 @@
+use astroport::common::OwnershipProposal;
+
+use cosmwasm_std::{Addr, StdResult, Uint128};
+use cw_storage_plus::{Item, Map};
+use eris::helpers::gauge_votes::{GaugeVotes, UserInfo, VotedInfo};
+use eris::lp_gauges::{ConfigResponse, GaugeInfoResponse, UserInfoResponse};
+
+/// This structure describes the main control config of the LP gauges contract.
+pub type Config = ConfigResponse;
+/// This structure describes voting parameters for a specific LP target.
+pub type VotedTargetInfo = VotedInfo;
+
+/// This structure describes last tuning parameters.
+pub type TuneInfo = GaugeInfoResponse;
+
+/// Converts the user's voting information into a [`UserInfoResponse`] at the specified period.
+pub(crate) fn user_info_response(user_info: UserInfo, period: u64) -> StdResult<UserInfoResponse> {
+    Ok(UserInfoResponse {
+        vote_ts: user_info.vote_ts,
+        voting_power: user_info.voting_power,
+        slope: user_info.slope,
+        lock_end: user_info.lock_end,
+        votes: user_info.votes_bps(),
+        fixed_amount: user_info.fixed_amount,
+        current_power: user_info.current_power(period)?,
+        delegated_by: user_info.delegated_by,
+    })
+}
+
+/// Stores config at the given key.
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// Stores voting parameters per pool at a specific period by key ( period -> target_addr ).
+pub const TARGET_VOTES: Map<(u64, &str), VotedTargetInfo> = Map::new("target_votes");
+
+/// HashSet based on [`Map`]. It contains all LP target addresses whose voting power > 0.
+pub const TARGETS: Map<&str, ()> = Map::new("targets");
+
+/// Hashset based on [`Map`]. It stores null object by key ( target_addr -> period ).
+/// This hashset contains all periods which have saved result in [`TARGET_VOTES`] for a specific target address.
+pub const TARGET_PERIODS: Map<(&str, u64), ()> = Map::new("target_periods");
+
+/// Slope changes for a specific target address by key ( target_addr -> period ).
+pub const TARGET_SLOPE_CHANGES: Map<(&str, u64), Uint128> = Map::new("target_slope_changes");
+
+pub const TARGET_FIXED_VAMP: Map<(&str, u64), Uint128> = Map::new("target_fixed_vamp");
+
+/// The slope based vote accounting of the targets, stored in the maps above.
+pub const TARGET_GAUGE: GaugeVotes = GaugeVotes {
+    targets: TARGETS,
+    votes: TARGET_VOTES,
+    periods: TARGET_PERIODS,
+    slope_changes: TARGET_SLOPE_CHANGES,
+    fixed_vamp: TARGET_FIXED_VAMP,
+    user_info: USER_INFO,
+    user_info_history: USER_INFO_HISTORY,
+};
+
+/// User's voting information.
+pub const USER_INFO: Map<&Addr, UserInfo> = Map::new("user_info");
+
+/// User's voting information by key ( user -> period ), stored for the period in which the votes are applied.
+pub const USER_INFO_HISTORY: Map<(&Addr, u64), UserInfo> = Map::new("user_info_history");
+
+/// Last tuning information.
+pub const TUNE_INFO: Item<TuneInfo> = Item::new("tune_info");
+
+/// Contains a proposal to change contract ownership
+pub const OWNERSHIP_PROPOSAL: Item<OwnershipProposal> = Item::new("ownership_proposal");
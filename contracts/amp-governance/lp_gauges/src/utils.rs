@@ -0,0 +1,42 @@
+use cosmwasm_std::{Decimal, StdResult, Storage, Uint128};
+
+use eris::lp_gauges::VotedTargetInfoResponse;
+
+use crate::state::{Config, TARGET_GAUGE};
+
+/// Filters the LP targets that are still whitelisted and takes the first `targets_limit` of them.
+pub(crate) fn filter_targets(
+    config: &Config,
+    targets: Vec<(String, Uint128)>,
+) -> Vec<(String, Uint128)> {
+    targets
+        .into_iter()
+        .filter(|(target_addr, _)| config.targets.iter().any(|t| t.as_str() == target_addr))
+        .take(config.targets_limit as usize)
+        .collect()
+}
+
+/// Calculates the share of every LP target from its vAMP.
+pub(crate) fn calc_weights(target_points: &[(String, Uint128)]) -> Vec<(String, Decimal)> {
+    let total: Uint128 = target_points.iter().map(|(_, vamp)| *vamp).sum();
+
+    target_points
+        .iter()
+        .map(|(target_addr, vamp)| (target_addr.clone(), Decimal::from_ratio(*vamp, total)))
+        .collect()
+}
+
+/// Returns LP target info at specified period or calculates it.
+pub(crate) fn get_target_info(
+    storage: &dyn Storage,
+    period: u64,
+    target_addr: &str,
+) -> StdResult<VotedTargetInfoResponse> {
+    let target_info = TARGET_GAUGE.get_target_info(storage, period, target_addr)?;
+
+    Ok(VotedTargetInfoResponse {
+        voting_power: target_info.voting_power,
+        fixed_amount: TARGET_GAUGE.fetch_last_fixed_vamp_value(storage, period, target_addr)?,
+        slope: target_info.slope,
+    })
+}
@@ -2,24 +2,29 @@ use astroport::common::{claim_ownership, drop_ownership_proposal, propose_new_ow
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
-    Uint128, VoteOption,
+    to_json_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response, StdError,
+    StdResult, Uint128, VoteOption,
 };
 use cw2::{get_contract_version, set_contract_version};
 use eris::CustomResponse;
 use eris_chain_adapter::types::CustomMsgType;
 
+use eris::adapters::alliance_lst::AllianceLst;
+use eris::adapters::hub::Hub;
 use eris::governance_helper::get_period;
 use eris::helpers::bps::BasicPoints;
 use eris::prop_gauges::{ExecuteMsg, InstantiateMsg, MigrateMsg, PropInfo, PropUserInfo, QueryMsg};
-use eris::voting_escrow::{get_lock_info, get_total_voting_power_at_by_period, LockInfoResponse};
+use eris::voting_escrow::{
+    get_lock_info, get_total_voting_power_at_by_period, DelegatedVotingPower, LockInfoResponse,
+};
 
 use crate::error::ContractError;
+use crate::gov::{query_gov_proposal, PROPOSAL_STATUS_VOTING_PERIOD};
 use crate::queries::{
     get_active_props, get_finished_props, get_prop_detail, get_prop_voters, get_user_votes,
 };
 use crate::state::{Config, State};
-use crate::vote::{get_vote_msg, remove_vote_of_user, update_vote_state};
+use crate::vote::{remove_vote_state, update_vote_state};
 
 /// Contract name that is used for migration.
 const CONTRACT_NAME: &str = "prop-gauges";
@@ -54,6 +59,11 @@ pub fn instantiate(
             hub_addr: deps.api.addr_validate(&msg.hub_addr)?,
             quorum_bps: msg.quorum_bps,
             use_weighted_vote: msg.use_weighted_vote,
+            sync_buffer_s: msg.sync_buffer_s,
+            alliance_lst_addr: msg
+                .alliance_lst_addr
+                .map(|addr| deps.api.addr_validate(&addr))
+                .transpose()?,
         },
     )?;
 
@@ -86,10 +96,16 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> E
             proposal_id,
             end_time_s,
         } => init_prop(deps, env, info, proposal_id, end_time_s),
+        ExecuteMsg::SyncProp {
+            proposal_id,
+        } => sync_prop(deps, env, proposal_id),
         ExecuteMsg::Vote {
             proposal_id,
             vote,
         } => handle_vote(deps, env, info, proposal_id, vote),
+        ExecuteMsg::ExecuteVote {
+            proposal_id,
+        } => execute_vote(deps, env, proposal_id),
         ExecuteMsg::UpdateVote {
             user,
             lock_info,
@@ -103,7 +119,16 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> E
         ExecuteMsg::UpdateConfig {
             quorum_bps,
             use_weighted_vote,
-        } => update_config(deps, info, quorum_bps, use_weighted_vote),
+            sync_buffer_s,
+            alliance_lst_addr,
+        } => update_config(
+            deps,
+            info,
+            quorum_bps,
+            use_weighted_vote,
+            sync_buffer_s,
+            alliance_lst_addr,
+        ),
         ExecuteMsg::ProposeNewOwner {
             new_owner,
             expires_in,
@@ -162,6 +187,48 @@ fn init_prop(
     let config = state.config.load(deps.storage)?;
     config.assert_owner(&info.sender)?;
 
+    save_prop(deps, &env, &state, &config, proposal_id, end_time_s)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "prop/init_prop")
+        .add_attribute("prop", proposal_id.to_string())
+        .add_attribute("end", get_period(end_time_s)?.to_string()))
+}
+
+/// Initializes a prop from the chain proposal, which must be in voting period. The prop ends
+/// `sync_buffer_s` seconds before the voting end time, so the vote can still be executed.
+fn sync_prop(deps: DepsMut, env: Env, proposal_id: u64) -> ExecuteResult {
+    let state = State::default();
+    let config = state.config.load(deps.storage)?;
+
+    let proposal = query_gov_proposal(&deps.querier, proposal_id)?;
+    if proposal.status != PROPOSAL_STATUS_VOTING_PERIOD {
+        return Err(ContractError::PropNotInVotingPeriod(proposal_id));
+    }
+
+    let voting_end_time = proposal
+        .voting_end_time
+        .ok_or_else(|| StdError::generic_err("Proposal has no voting end time."))?;
+    let end_time_s = u64::try_from(voting_end_time.seconds)
+        .map_err(|_| StdError::generic_err("Invalid voting end time."))?
+        .saturating_sub(config.sync_buffer_s);
+
+    save_prop(deps, &env, &state, &config, proposal_id, end_time_s)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "prop/sync_prop")
+        .add_attribute("prop", proposal_id.to_string())
+        .add_attribute("end_time_s", end_time_s.to_string()))
+}
+
+fn save_prop(
+    deps: DepsMut,
+    env: &Env,
+    state: &State,
+    config: &Config,
+    proposal_id: u64,
+    end_time_s: u64,
+) -> Result<(), ContractError> {
     let prop = state.props.may_load(deps.storage, proposal_id)?;
 
     if prop.is_some() {
@@ -184,7 +251,7 @@ fn init_prop(
             period,
             total_vp: get_total_voting_power_at_by_period(
                 &deps.querier,
-                config.escrow_addr,
+                config.escrow_addr.clone(),
                 period,
             )?,
             current_vote: None,
@@ -195,10 +262,7 @@ fn init_prop(
         },
     )?;
 
-    Ok(Response::new()
-        .add_attribute("action", "prop/init_prop")
-        .add_attribute("prop", proposal_id.to_string())
-        .add_attribute("end", period.to_string()))
+    Ok(())
 }
 
 /// The function checks that:
@@ -216,7 +280,7 @@ fn init_prop(
 ///     Tuple consists of pool address and percentage of user's voting power for a given pool.
 ///     Percentage should be in BPS form.
 fn handle_vote(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     proposal_id: u64,
@@ -228,7 +292,12 @@ fn handle_vote(
     let config = state.config.load(deps.storage)?;
 
     let ve_lock_info = get_lock_info(&deps.querier, &config.escrow_addr, &sender)?;
-    let vamp = ve_lock_info.voting_power + ve_lock_info.fixed_amount;
+    let vamp = ve_lock_info.delegated.iter().fold(
+        ve_lock_info.voting_power + ve_lock_info.fixed_amount,
+        |vamp, delegated| {
+            vamp + delegated.lock_info.voting_power + delegated.lock_info.fixed_amount
+        },
+    );
     if vamp.is_zero() {
         return Err(ContractError::ZeroVotingPower {});
     }
@@ -238,6 +307,7 @@ fn handle_vote(
             user: sender.clone(),
             current_vote: VoteOption::Abstain,
             vp: Uint128::zero(),
+            delegated_by: None,
         });
 
     let (user, vote_msg) = update_vote_state(
@@ -249,19 +319,90 @@ fn handle_vote(
         &sender,
         proposal_id,
         None,
-        vote,
+        vote.clone(),
         user_info,
         &ve_lock_info,
+        None,
+    )?;
+    let delegated_vote_msg = apply_delegated_votes(
+        deps.branch(),
+        &env,
+        &state,
+        &config,
+        &sender,
+        proposal_id,
+        &vote,
+        &ve_lock_info.delegated,
     )?;
 
     Ok(Response::new()
-        .add_optional_message(vote_msg)
+        .add_optional_message(delegated_vote_msg.or(vote_msg))
         .add_attribute("action", "prop/vote")
         .add_attribute("vp", user.vp))
 }
 
+/// Sends the vote of a proposal to the hub and, when configured, to the alliance LST. The vote is
+/// only sent after the end time of the proposal passed and when the quorum was reached. Weighted
+/// votes are sent when configured. Executing the vote again re-casts it. As the chain only accepts
+/// votes while the proposal is in voting period, its status is checked before every cast.
+fn execute_vote(deps: DepsMut, env: Env, proposal_id: u64) -> ExecuteResult {
+    let state = State::default();
+    let config = state.config.load(deps.storage)?;
+
+    let mut prop = state.props.load(deps.storage, proposal_id).map_err(|_| {
+        StdError::generic_err(format!("proposal with id {0} not initialized", proposal_id))
+    })?;
+
+    if env.block.time.seconds() <= prop.end_time_s {
+        return Err(ContractError::VoteNotEnded(proposal_id));
+    }
+
+    let proposal = query_gov_proposal(&deps.querier, proposal_id)?;
+    if proposal.status != PROPOSAL_STATUS_VOTING_PERIOD {
+        return Err(ContractError::PropNotInVotingPeriod(proposal_id));
+    }
+
+    let total_vp = get_total_voting_power_at_by_period(
+        &deps.querier,
+        config.escrow_addr.clone(),
+        prop.period,
+    )?;
+
+    let wanted = if total_vp.is_zero() {
+        None
+    } else {
+        prop.get_wanted_vote(total_vp, config.quorum_bps)?
+    };
+    let wanted = wanted.ok_or(ContractError::QuorumNotReached(proposal_id))?;
+
+    let hub = Hub(config.hub_addr);
+    let alliance_lst = config.alliance_lst_addr.map(AllianceLst);
+    let vote_msgs = if config.use_weighted_vote {
+        let votes = prop.get_weighted_votes();
+        vec![
+            Some(hub.vote_weighted_msg(proposal_id, votes.clone())?),
+            alliance_lst.map(|lst| lst.vote_weighted_msg(proposal_id, votes)).transpose()?,
+        ]
+    } else {
+        vec![
+            Some(hub.vote_msg(proposal_id, wanted.clone())?),
+            alliance_lst.map(|lst| lst.vote_msg(proposal_id, wanted.clone())).transpose()?,
+        ]
+    };
+
+    prop.current_vote = Some(wanted.clone());
+    prop.total_vp = total_vp;
+    state.props.save(deps.storage, proposal_id, &prop)?;
+
+    Ok(Response::new()
+        .add_messages(vote_msgs.into_iter().flatten())
+        .add_attribute("action", "prop/execute_vote")
+        .add_attribute("prop", proposal_id.to_string())
+        .add_attribute("vote", format!("{:?}", wanted)))
+}
+
 fn update_vote(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     user: String,
@@ -281,27 +422,163 @@ fn update_vote(
         let user = state.get_user_info(deps.storage, proposal_id, &sender)?;
 
         if let Some(user_info) = user {
+            let vote_msg = if let Some(delegatee) = user_info.delegated_by.clone() {
+                update_delegated_vote(
+                    deps.branch(),
+                    &env,
+                    &state,
+                    &config,
+                    &sender,
+                    delegatee,
+                    proposal_id,
+                    prop,
+                    user_info,
+                    &ve_lock_info,
+                )?
+            } else {
+                let vote = user_info.current_vote.clone();
+                let (_, vote_msg) = update_vote_state(
+                    &env,
+                    &deps.querier,
+                    deps.storage,
+                    &state,
+                    &config,
+                    &sender,
+                    proposal_id,
+                    Some(prop),
+                    vote.clone(),
+                    user_info,
+                    &ve_lock_info,
+                    None,
+                )?;
+                // the vote of the user also applies to the voting power delegated to them
+                let delegated_vote_msg = apply_delegated_votes(
+                    deps.branch(),
+                    &env,
+                    &state,
+                    &config,
+                    &sender,
+                    proposal_id,
+                    &vote,
+                    &ve_lock_info.delegated,
+                )?;
+                delegated_vote_msg.or(vote_msg)
+            };
+
+            response = response
+                .add_optional_message(vote_msg)
+                .add_attribute("prop", proposal_id.to_string());
+        }
+    }
+
+    Ok(response.add_attribute("action", "prop/update_vote"))
+}
+
+/// Applies the vote of a delegatee to the voting power delegated to them. Delegators that voted
+/// themselves keep their own vote. Delegated voting power is not delegated any further.
+/// Returns the latest vote message, as it contains the resulting vote of the proposal.
+#[allow(clippy::too_many_arguments)]
+fn apply_delegated_votes(
+    deps: DepsMut,
+    env: &Env,
+    state: &State,
+    config: &Config,
+    delegatee: &Addr,
+    proposal_id: u64,
+    vote: &VoteOption,
+    delegated: &[DelegatedVotingPower],
+) -> Result<Option<CosmosMsg<CustomMsgType>>, ContractError> {
+    let mut vote_msg = None;
+
+    for delegated in delegated {
+        let delegator = deps.api.addr_validate(&delegated.delegator)?;
+        let delegator_info = state.get_user_info(deps.storage, proposal_id, &delegator)?;
+
+        if matches!(&delegator_info, Some(info) if info.delegated_by.is_none()) {
+            continue;
+        }
+
+        let delegator_info = delegator_info.unwrap_or(PropUserInfo {
+            user: delegator.clone(),
+            current_vote: VoteOption::Abstain,
+            vp: Uint128::zero(),
+            delegated_by: None,
+        });
+
+        let (_, msg) = update_vote_state(
+            env,
+            &deps.querier,
+            deps.storage,
+            state,
+            config,
+            &delegator,
+            proposal_id,
+            None,
+            vote.clone(),
+            delegator_info,
+            &delegated.lock_info,
+            Some(delegatee.clone()),
+        )?;
+        vote_msg = msg.or(vote_msg);
+    }
+
+    Ok(vote_msg)
+}
+
+/// Re-applies the vote of the delegatee with the delegated share of the user's voting power. The
+/// vote is removed when the delegation to the delegatee ended.
+#[allow(clippy::too_many_arguments)]
+fn update_delegated_vote(
+    deps: DepsMut,
+    env: &Env,
+    state: &State,
+    config: &Config,
+    user: &Addr,
+    delegatee: Addr,
+    proposal_id: u64,
+    prop: PropInfo,
+    user_info: PropUserInfo,
+    ve_lock_info: &LockInfoResponse,
+) -> Result<Option<CosmosMsg<CustomMsgType>>, ContractError> {
+    if let Some(delegation) = ve_lock_info
+        .delegation
+        .as_ref()
+        .filter(|delegation| delegation.delegatee == delegatee.as_str())
+    {
+        let delegatee_info = state
+            .get_user_info(deps.storage, proposal_id, &delegatee)?
+            .filter(|info| info.delegated_by.is_none());
+
+        if let Some(delegatee_info) = delegatee_info {
+            let share = ve_lock_info.share(delegation.bps.try_into()?);
             let (_, vote_msg) = update_vote_state(
-                &env,
+                env,
                 &deps.querier,
                 deps.storage,
-                &state,
-                &config,
-                &sender,
+                state,
+                config,
+                user,
                 proposal_id,
                 Some(prop),
-                user_info.current_vote.clone(),
+                delegatee_info.current_vote,
                 user_info,
-                &ve_lock_info,
+                &share,
+                Some(delegatee),
             )?;
-
-            response = response
-                .add_optional_message(vote_msg)
-                .add_attribute("prop", proposal_id.to_string());
+            return Ok(vote_msg);
         }
     }
 
-    Ok(response.add_attribute("action", "prop/update_vote"))
+    remove_vote_state(
+        &deps.querier,
+        deps.storage,
+        state,
+        config,
+        user,
+        proposal_id,
+        prop,
+        &user_info,
+    )
 }
 
 fn remove_user(deps: DepsMut, env: Env, info: MessageInfo, user: String) -> ExecuteResult {
@@ -316,16 +593,16 @@ fn remove_user(deps: DepsMut, env: Env, info: MessageInfo, user: String) -> Exec
         let user = state.get_user_info(deps.storage, proposal_id, &user_addr)?;
 
         if let Some(user) = user {
-            let mut prop = remove_vote_of_user(prop, &user)?;
-
-            let (vote_msg, total_vp) =
-                get_vote_msg(&deps.querier, &config, &mut prop, proposal_id)?;
-
-            prop.total_vp = total_vp;
-
-            state.props.save(deps.storage, proposal_id, &prop)?;
-            state.users.remove(deps.storage, (proposal_id, user_addr.clone()))?;
-            state.voters.remove(deps.storage, (proposal_id, user.vp.u128(), user_addr.clone()));
+            let vote_msg = remove_vote_state(
+                &deps.querier,
+                deps.storage,
+                &state,
+                &config,
+                &user_addr,
+                proposal_id,
+                prop,
+                &user,
+            )?;
 
             response = response
                 .add_optional_message(vote_msg)
@@ -361,6 +638,8 @@ fn update_config(
     info: MessageInfo,
     quorum_bps: Option<u16>,
     use_weighted_vote: Option<bool>,
+    sync_buffer_s: Option<u64>,
+    alliance_lst_addr: Option<String>,
 ) -> ExecuteResult {
     let state = State::default();
     let mut config = state.config.load(deps.storage)?;
@@ -375,6 +654,14 @@ fn update_config(
         config.use_weighted_vote = use_weighted_vote;
     }
 
+    if let Some(sync_buffer_s) = sync_buffer_s {
+        config.sync_buffer_s = sync_buffer_s;
+    }
+
+    if let Some(alliance_lst_addr) = alliance_lst_addr {
+        config.alliance_lst_addr = Some(deps.api.addr_validate(&alliance_lst_addr)?);
+    }
+
     state.config.save(deps.storage, &config)?;
 
     Ok(Response::default().add_attribute("action", "prop/update_config"))
@@ -13,6 +13,15 @@ pub enum ContractError {
     #[error("You can't vote with zero voting power")]
     ZeroVotingPower {},
 
+    #[error("Voting on proposal {0} did not end yet")]
+    VoteNotEnded(u64),
+
+    #[error("Proposal {0} did not reach the quorum")]
+    QuorumNotReached(u64),
+
+    #[error("Proposal {0} is not in voting period")]
+    PropNotInVotingPeriod(u64),
+
     #[error("Invalid validator address: {0}")]
     InvalidValidatorAddress(String),
 
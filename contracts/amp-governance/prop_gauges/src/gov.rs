@@ -0,0 +1,70 @@
+use cosmwasm_std::{
+    to_json_vec, Binary, ContractResult, Empty, QuerierWrapper, QueryRequest, StdError, StdResult,
+    SystemResult,
+};
+use prost::Message;
+
+/// `ProposalStatus::PROPOSAL_STATUS_VOTING_PERIOD` of the gov module.
+pub const PROPOSAL_STATUS_VOTING_PERIOD: i32 = 2;
+
+/// Request of `/cosmos.gov.v1.Query/Proposal`.
+#[derive(Clone, PartialEq, Message)]
+pub struct QueryProposalRequest {
+    #[prost(uint64, tag = "1")]
+    pub proposal_id: u64,
+}
+
+/// Response of `/cosmos.gov.v1.Query/Proposal`.
+#[derive(Clone, PartialEq, Message)]
+pub struct QueryProposalResponse {
+    #[prost(message, optional, tag = "1")]
+    pub proposal: Option<Proposal>,
+}
+
+/// The fields of `cosmos.gov.v1.Proposal` used by the contract. Other fields are skipped when decoding.
+#[derive(Clone, PartialEq, Message)]
+pub struct Proposal {
+    #[prost(uint64, tag = "1")]
+    pub id: u64,
+    #[prost(int32, tag = "3")]
+    pub status: i32,
+    #[prost(message, optional, tag = "9")]
+    pub voting_end_time: Option<Timestamp>,
+}
+
+/// `google.protobuf.Timestamp`
+#[derive(Clone, PartialEq, Message)]
+pub struct Timestamp {
+    #[prost(int64, tag = "1")]
+    pub seconds: i64,
+    #[prost(int32, tag = "2")]
+    pub nanos: i32,
+}
+
+/// Queries a proposal from the gov module.
+pub fn query_gov_proposal(querier: &QuerierWrapper, proposal_id: u64) -> StdResult<Proposal> {
+    let request = QueryRequest::<Empty>::Stargate {
+        path: "/cosmos.gov.v1.Query/Proposal".to_string(),
+        data: Binary::from(
+            QueryProposalRequest {
+                proposal_id,
+            }
+            .encode_to_vec(),
+        ),
+    };
+
+    let response = match querier.raw_query(&to_json_vec(&request)?) {
+        SystemResult::Err(system_err) => {
+            Err(StdError::generic_err(format!("Querier system error: {}", system_err)))
+        },
+        SystemResult::Ok(ContractResult::Err(contract_err)) => {
+            Err(StdError::generic_err(format!("Querier contract error: {}", contract_err)))
+        },
+        SystemResult::Ok(ContractResult::Ok(value)) => Ok(value),
+    }?;
+
+    QueryProposalResponse::decode(response.as_slice())
+        .map_err(|err| StdError::parse_err("QueryProposalResponse", err))?
+        .proposal
+        .ok_or_else(|| StdError::not_found("Proposal"))
+}
@@ -2,6 +2,7 @@ pub mod contract;
 pub mod state;
 
 mod error;
+mod gov;
 pub mod queries;
 pub mod vote;
 
@@ -4,11 +4,14 @@ use std::ops::Mul;
 use cosmwasm_schema::serde::Serialize;
 use cosmwasm_std::testing::{BankQuerier, StakingQuerier, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
-    from_json, to_json_binary, Coin, Decimal, Empty, Querier, QuerierResult, QueryRequest,
-    SystemError, Uint128, WasmQuery,
+    from_json, to_json_binary, Binary, Coin, ContractResult, Decimal, Empty, Querier,
+    QuerierResult, QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
 };
 use cw20::Cw20QueryMsg;
 use eris::voting_escrow::{LockInfoResponse, VotingPowerResponse};
+use prost::Message;
+
+use crate::gov::{Proposal, QueryProposalRequest, QueryProposalResponse, Timestamp};
 
 use super::cw20_querier::Cw20Querier;
 use super::helpers::err_unsupported_query;
@@ -20,6 +23,7 @@ pub(super) struct CustomQuerier {
     pub staking_querier: StakingQuerier,
 
     pub vp: HashMap<String, LockInfoResponse>,
+    pub gov_proposals: HashMap<u64, Proposal>,
 }
 
 impl Querier for CustomQuerier {
@@ -74,6 +78,24 @@ impl CustomQuerier {
                 slope: Uint128::new(1),
                 fixed_amount: Uint128::new(fixed),
                 voting_power: Uint128::new(dynamic),
+                permanent: false,
+                delegation: None,
+                delegated: vec![],
+                ends: vec![],
+            },
+        );
+    }
+
+    pub fn set_gov_proposal(&mut self, id: u64, status: i32, voting_end_time: u64) {
+        self.gov_proposals.insert(
+            id,
+            Proposal {
+                id,
+                status,
+                voting_end_time: Some(Timestamp {
+                    seconds: voting_end_time as i64,
+                    nanos: 0,
+                }),
             },
         );
     }
@@ -114,6 +136,25 @@ impl CustomQuerier {
 
             QueryRequest::Staking(query) => self.staking_querier.query(query),
 
+            QueryRequest::Stargate {
+                path,
+                data,
+            } if path == "/cosmos.gov.v1.Query/Proposal" => {
+                let request = QueryProposalRequest::decode(data.as_slice()).unwrap();
+                match self.gov_proposals.get(&request.proposal_id) {
+                    Some(proposal) => {
+                        let response = QueryProposalResponse {
+                            proposal: Some(proposal.clone()),
+                        };
+                        SystemResult::Ok(ContractResult::Ok(Binary::from(response.encode_to_vec())))
+                    },
+                    None => SystemResult::Ok(ContractResult::Err(format!(
+                        "proposal {0} doesn't exist",
+                        request.proposal_id
+                    ))),
+                }
+            },
+
             _ => err_unsupported_query(request),
         }
     }
@@ -180,6 +221,7 @@ impl CustomQuerier {
                 ..
             } => todo!(),
             eris::voting_escrow::QueryMsg::Config {} => todo!(),
+            query => err_unsupported_query(query),
         }
     }
 }
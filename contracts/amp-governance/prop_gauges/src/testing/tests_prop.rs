@@ -37,6 +37,8 @@ fn setup_test() -> OwnedDeps<MockStorage, MockApi, CustomQuerier> {
             hub_addr: "hub".to_string(),
             quorum_bps: 500,
             use_weighted_vote: false,
+            sync_buffer_s: 3600,
+            alliance_lst_addr: None,
         },
     )
     .unwrap();
@@ -62,7 +64,9 @@ fn proper_instantiation() {
             escrow_addr: Addr::unchecked("escrow"),
             hub_addr: Addr::unchecked("hub"),
             quorum_bps: 500,
-            use_weighted_vote: false
+            use_weighted_vote: false,
+            sync_buffer_s: 3600,
+            alliance_lst_addr: None
         }
     );
 
@@ -258,6 +262,56 @@ fn check_init_prop() {
     );
 }
 
+#[test]
+fn sync_prop() {
+    let mut deps = setup_test();
+
+    deps.querier.set_gov_proposal(1, 1, EPOCH_START + WEEK);
+    deps.querier.set_gov_proposal(2, 2, EPOCH_START + WEEK * 2);
+    deps.querier.set_gov_proposal(3, 2, EPOCH_START + 1000);
+
+    let sync = |proposal_id: u64| ExecuteMsg::SyncProp {
+        proposal_id,
+    };
+
+    let res =
+        execute(deps.as_mut(), mock_env_at_timestamp(EPOCH_START), mock_info("user", &[]), sync(1))
+            .unwrap_err();
+    assert_eq!(res.to_string(), "Proposal 1 is not in voting period");
+
+    let res =
+        execute(deps.as_mut(), mock_env_at_timestamp(EPOCH_START), mock_info("user", &[]), sync(4))
+            .unwrap_err();
+    assert_eq!(res.to_string(), "Generic error: Querier contract error: proposal 4 doesn't exist");
+
+    // the end time minus the sync buffer is already in the past
+    let res =
+        execute(deps.as_mut(), mock_env_at_timestamp(EPOCH_START), mock_info("user", &[]), sync(3))
+            .unwrap_err();
+    assert_eq!(res.to_string(), "Generic error: End time can't be in the past.");
+
+    let res =
+        execute(deps.as_mut(), mock_env_at_timestamp(EPOCH_START), mock_info("user", &[]), sync(2))
+            .unwrap();
+    assert_eq!(res.messages.len(), 0);
+
+    let res: PropDetailResponse = query_helper_env(
+        deps.as_ref(),
+        QueryMsg::PropDetail {
+            user: None,
+            proposal_id: 2,
+        },
+        EPOCH_START,
+    );
+    assert_eq!(res.prop.end_time_s, EPOCH_START + WEEK * 2 - 3600);
+    assert_eq!(res.prop.period, get_period(EPOCH_START + WEEK * 2 - 3600).unwrap());
+
+    let res =
+        execute(deps.as_mut(), mock_env_at_timestamp(EPOCH_START), mock_info("user", &[]), sync(2))
+            .unwrap_err();
+    assert_eq!(res.to_string(), "Generic error: prop 2 already initialized.");
+}
+
 #[test]
 fn vote_prop() {
     let deps = setup_test();
@@ -387,6 +441,158 @@ fn vote_prop() {
     );
 }
 
+#[test]
+fn execute_vote() {
+    let deps = setup_test();
+    let mut deps = setup_props(deps);
+
+    deps.querier.set_lock("user", 5, 5);
+    deps.querier.set_lock("user2", 100, 100);
+    deps.querier.set_gov_proposal(3, 2, EPOCH_START + WEEK * 4);
+
+    execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(EPOCH_START),
+        mock_info("user", &[]),
+        ExecuteMsg::Vote {
+            proposal_id: 3,
+            vote: cosmwasm_std::VoteOption::Yes,
+        },
+    )
+    .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(EPOCH_START + WEEK * 3),
+        mock_info("anyone", &[]),
+        ExecuteMsg::ExecuteVote {
+            proposal_id: 3,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(res.to_string(), "Voting on proposal 3 did not end yet");
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(EPOCH_START + WEEK * 3 + 1),
+        mock_info("anyone", &[]),
+        ExecuteMsg::ExecuteVote {
+            proposal_id: 3,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(res.to_string(), "Proposal 3 did not reach the quorum");
+
+    execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(EPOCH_START + WEEK),
+        mock_info("user2", &[]),
+        ExecuteMsg::Vote {
+            proposal_id: 3,
+            vote: cosmwasm_std::VoteOption::No,
+        },
+    )
+    .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(EPOCH_START + WEEK * 3 + 1),
+        mock_info("anyone", &[]),
+        ExecuteMsg::ExecuteVote {
+            proposal_id: 3,
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(
+        res.messages[0],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "hub".to_string(),
+            msg: to_json_binary(&eris::hub::ExecuteMsg::Vote {
+                proposal_id: 3,
+                vote: cosmwasm_std::VoteOption::No
+            })
+            .unwrap(),
+            funds: vec![]
+        }))
+    );
+
+    // re-casting the vote uses the weighted vote after the config changed and is also sent to the
+    // alliance LST
+    execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(EPOCH_START + WEEK * 3 + 1),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            quorum_bps: None,
+            use_weighted_vote: Some(true),
+            sync_buffer_s: None,
+            alliance_lst_addr: Some("alliance_lst".to_string()),
+        },
+    )
+    .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(EPOCH_START + WEEK * 3 + 1),
+        mock_info("anyone", &[]),
+        ExecuteMsg::ExecuteVote {
+            proposal_id: 3,
+        },
+    )
+    .unwrap();
+    let votes =
+        vec![(Decimal::permille(35), VoteOption::Yes), (Decimal::permille(965), VoteOption::No)];
+    assert_eq!(res.messages.len(), 2);
+    assert_eq!(
+        res.messages[0],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "hub".to_string(),
+            msg: to_json_binary(&eris::hub::ExecuteMsg::VoteWeighted {
+                proposal_id: 3,
+                votes: votes.clone()
+            })
+            .unwrap(),
+            funds: vec![]
+        }))
+    );
+    assert_eq!(
+        res.messages[1],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "alliance_lst".to_string(),
+            msg: to_json_binary(&eris::alliance_lst::ExecuteMsg::VoteWeighted {
+                proposal_id: 3,
+                votes
+            })
+            .unwrap(),
+            funds: vec![]
+        }))
+    );
+
+    // re-casting is rejected after the proposal left the voting period
+    deps.querier.set_gov_proposal(3, 3, EPOCH_START + WEEK * 4);
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_timestamp(EPOCH_START + WEEK * 4 + 1),
+        mock_info("anyone", &[]),
+        ExecuteMsg::ExecuteVote {
+            proposal_id: 3,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(res.to_string(), "Proposal 3 is not in voting period");
+
+    let res: PropDetailResponse = query_helper_env(
+        deps.as_ref(),
+        QueryMsg::PropDetail {
+            user: None,
+            proposal_id: 3,
+        },
+        EPOCH_START + WEEK * 3 + 1,
+    );
+    assert_eq!(res.prop.current_vote, Some(VoteOption::No));
+}
+
 #[test]
 fn remove_user() {
     let deps = setup_test();
@@ -443,7 +649,8 @@ fn remove_user() {
             user: Some(PropUserInfo {
                 current_vote: cosmwasm_std::VoteOption::No,
                 vp: Uint128::new(198),
-                user: Addr::unchecked("user2")
+                user: Addr::unchecked("user2"),
+                delegated_by: None,
             }),
             prop: PropInfo {
                 abstain_vp: Uint128::zero(),
@@ -914,6 +1121,10 @@ fn update_vote() {
                 slope: Uint128::new(1),
                 fixed_amount: Uint128::new(10000),
                 voting_power: Uint128::new(10),
+                permanent: false,
+                delegation: None,
+                delegated: vec![],
+                ends: vec![],
             },
         },
     )
@@ -935,6 +1146,10 @@ fn update_vote() {
                 slope: Uint128::new(1),
                 fixed_amount: Uint128::new(10000),
                 voting_power: Uint128::new(10),
+                permanent: false,
+                delegation: None,
+                delegated: vec![],
+                ends: vec![],
             },
         },
     )
@@ -1020,6 +1235,8 @@ fn update_config() {
         ExecuteMsg::UpdateConfig {
             quorum_bps: Some(1000),
             use_weighted_vote: None,
+            sync_buffer_s: None,
+            alliance_lst_addr: None,
         },
     )
     .unwrap_err();
@@ -1032,6 +1249,8 @@ fn update_config() {
         ExecuteMsg::UpdateConfig {
             quorum_bps: Some(12000),
             use_weighted_vote: None,
+            sync_buffer_s: None,
+            alliance_lst_addr: None,
         },
     )
     .unwrap_err();
@@ -1044,6 +1263,8 @@ fn update_config() {
         ExecuteMsg::UpdateConfig {
             quorum_bps: Some(5000),
             use_weighted_vote: None,
+            sync_buffer_s: Some(7200),
+            alliance_lst_addr: Some("alliance_lst".to_string()),
         },
     )
     .unwrap();
@@ -1057,7 +1278,9 @@ fn update_config() {
             escrow_addr: Addr::unchecked("escrow"),
             hub_addr: Addr::unchecked("hub"),
             quorum_bps: 5000,
-            use_weighted_vote: false
+            use_weighted_vote: false,
+            sync_buffer_s: 7200,
+            alliance_lst_addr: Some(Addr::unchecked("alliance_lst"))
         }
     );
 }
@@ -34,6 +34,7 @@ pub fn apply_vote_of_user(
     ve_lock_info: &LockInfoResponse,
     vote: VoteOption,
     user: Addr,
+    delegated_by: Option<Addr>,
 ) -> StdResult<(PropInfo, PropUserInfo)> {
     let current_period = get_period(env.block.time.seconds())?;
     let vp = calc_voting_power_for_prop(current_period, ve_lock_info, &prop);
@@ -45,6 +46,7 @@ pub fn apply_vote_of_user(
                 current_vote: VoteOption::Abstain,
                 vp,
                 user,
+                delegated_by,
             },
         ));
     }
@@ -62,6 +64,7 @@ pub fn apply_vote_of_user(
             current_vote: vote,
             vp,
             user,
+            delegated_by,
         },
     ))
 }
@@ -79,6 +82,7 @@ pub(crate) fn update_vote_state(
     vote: VoteOption,
     user_info: PropUserInfo,
     ve_lock_info: &LockInfoResponse,
+    delegated_by: Option<Addr>,
 ) -> Result<(PropUserInfo, Option<CosmosMsg<CustomMsgType>>), ContractError> {
     let prop = if let Some(prop) = prop {
         prop
@@ -89,7 +93,8 @@ pub(crate) fn update_vote_state(
     };
 
     let prop = remove_vote_of_user(prop, &user_info)?;
-    let (mut prop, user) = apply_vote_of_user(env, prop, ve_lock_info, vote, sender.clone())?;
+    let (mut prop, user) =
+        apply_vote_of_user(env, prop, ve_lock_info, vote, sender.clone(), delegated_by)?;
 
     let (vote_msg, total_vp) = get_vote_msg(querier, config, &mut prop, proposal_id)?;
     prop.total_vp = total_vp;
@@ -101,6 +106,29 @@ pub(crate) fn update_vote_state(
     Ok((user, vote_msg))
 }
 
+/// Removes the vote of a user from a proposal.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn remove_vote_state(
+    querier: &QuerierWrapper,
+    store: &mut dyn Storage,
+    state: &State,
+    config: &ConfigResponse,
+    user: &Addr,
+    proposal_id: u64,
+    prop: PropInfo,
+    user_info: &PropUserInfo,
+) -> Result<Option<CosmosMsg<CustomMsgType>>, ContractError> {
+    let mut prop = remove_vote_of_user(prop, user_info)?;
+
+    let (vote_msg, total_vp) = get_vote_msg(querier, config, &mut prop, proposal_id)?;
+    prop.total_vp = total_vp;
+
+    state.props.save(store, proposal_id, &prop)?;
+    state.users.remove(store, (proposal_id, user.clone()))?;
+    state.voters.remove(store, (proposal_id, user_info.vp.u128(), user.clone()));
+    Ok(vote_msg)
+}
+
 pub fn get_vote_msg(
     querier: &QuerierWrapper,
     config: &ConfigResponse,
@@ -156,12 +184,14 @@ fn calc_voting_power_for_prop(
 
     if start == period {
         ve_lock_info.voting_power + ve_lock_info.fixed_amount
-    } else if ve_lock_info.end <= period {
-        // the current period is after the voting end -> get default end power.
-        ve_lock_info.fixed_amount
     } else {
-        // The point before the intended period was found, thus we can calculate the user's voting power for the period we want
-        calc_voting_power(ve_lock_info.slope, ve_lock_info.voting_power, start, period)
-            + ve_lock_info.fixed_amount
+        // every end of the user's lock tokens decays on its own, ends before the period only
+        // keep the fixed power.
+        ve_lock_info.decaying_ends().iter().filter(|lock_end| lock_end.end > period).fold(
+            ve_lock_info.fixed_amount,
+            |vp, lock_end| {
+                vp + calc_voting_power(lock_end.slope, lock_end.voting_power, start, period)
+            },
+        )
     }
 }
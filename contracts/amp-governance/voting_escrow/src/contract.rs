@@ -3,21 +3,28 @@ use astroport::common::{claim_ownership, drop_ownership_proposal, propose_new_ow
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    attr, to_json_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response,
-    StdError, StdResult, Storage, Uint128, WasmMsg,
+    attr, to_json_binary, Addr, Binary, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo, Order,
+    Response, StdError, StdResult, Storage, Uint128, WasmMsg,
 };
 use cw2::{get_contract_version, set_contract_version};
-use cw20::{BalanceResponse, Logo, LogoInfo, MarketingInfoResponse, TokenInfoResponse};
+use cw20::{BalanceResponse, Expiration, Logo, LogoInfo, MarketingInfoResponse, TokenInfoResponse};
 use cw20_base::contract::{
     execute_update_marketing, execute_upload_logo, query_download_logo, query_marketing_info,
 };
 use cw20_base::state::{MinterData, TokenInfo, LOGO, MARKETING_INFO, TOKEN_INFO};
-use eris::governance_helper::{get_period, get_periods_count, EPOCH_START, MIN_LOCK_PERIODS, WEEK};
+use cw_storage_plus::Bound;
+use eris::governance_helper::{
+    get_period, get_periods_count, EPOCH_START, MAX_LOCK_TIME, MIN_LOCK_PERIODS, WEEK,
+};
 use eris::helper::{addr_opt_validate, validate_addresses, validate_received_funds};
+use eris::helpers::bps::BasicPoints;
 use eris::helpers::slope::{adjust_vp_and_slope, calc_coefficient};
 use eris::voting_escrow::{
-    BlacklistedVotersResponse, ConfigResponse, ExecuteMsg, InstantiateMsg, LockInfoResponse,
-    MigrateMsg, PushExecuteMsg, QueryMsg, VotingPowerResponse, DEFAULT_LIMIT, MAX_LIMIT,
+    Approval, ApprovalsResponse, BlacklistedVotersResponse, ConfigResponse, Cw721ReceiveMsg,
+    DelegatedVotingPower, DelegationResponse, EarlyWithdrawResponse, ExecuteMsg, InstantiateMsg,
+    LockEndResponse, LockInfoResponse, LockTokenResponse, MigrateMsg, NftContractInfoResponse,
+    NftInfoResponse, NumTokensResponse, OperatorsResponse, OwnerOfResponse, PushExecuteMsg,
+    QueryMsg, TokensResponse, VotingPowerResponse, DEFAULT_LIMIT, MAX_LIMIT,
 };
 use eris::DecimalCheckedOps;
 use std::collections::HashSet;
@@ -25,18 +32,28 @@ use std::collections::HashSet;
 use crate::error::ContractError;
 use crate::marketing_validation::{validate_marketing_info, validate_whitelist_links};
 use crate::state::{
-    Config, Lock, Point, BLACKLIST, CONFIG, HISTORY, LAST_SLOPE_CHANGE, LOCKED, OWNERSHIP_PROPOSAL,
+    Config, Delegation, Lock, LockToken, Point, TokenApproval, BLACKLIST, CONFIG, DELEGATIONS,
+    HISTORY, LAST_SLOPE_CHANGE, LAST_TOKEN_ID, LOCKED, LOCK_MIGRATION, LOCK_TOKENS, OPERATORS,
+    OWNERSHIP_PROPOSAL, OWNER_TOKENS, TOKEN_COUNT, TOKEN_HISTORY, USER_SLOPE_CHANGES,
 };
 use crate::utils::{
-    assert_blacklist, assert_not_decommissioned, assert_periods_remaining, assert_time_limits,
-    calc_voting_power, cancel_scheduled_slope, fetch_last_checkpoint, fetch_slope_changes,
-    schedule_slope_change,
+    add_delegator, add_to_lock, assert_blacklist, assert_can_approve, assert_can_send,
+    assert_early_withdraw_penalty, assert_locks_migrated, assert_not_decommissioned,
+    assert_periods_remaining, assert_time_limits, burn_lock_token, calc_permanent_boost,
+    calc_token_point, calc_voting_power, calc_withdraw_penalty, cancel_scheduled_slope,
+    cancel_user_slope_change, fetch_active_delegation, fetch_active_delegators,
+    fetch_future_user_slope_changes, fetch_last_checkpoint, fetch_last_delegation,
+    fetch_owner_token_ids, fetch_slope_changes, fetch_token_point, fetch_user_point,
+    load_lock_token, load_owned_lock_token, mint_lock_token, reduce_lock, remove_delegator,
+    resolve_lock_token, schedule_slope_change, schedule_user_slope_change,
 };
 
 /// Contract name that is used for migration.
 const CONTRACT_NAME: &str = "eris-voting-escrow";
 /// Contract version that is used for migration.
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+/// Max amount of locks that are converted into lock tokens at once.
+const MAX_MIGRATION_LIMIT: u32 = 50;
 
 /// Creates a new contract with the specified parameters in [`InstantiateMsg`].
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -59,6 +76,8 @@ pub fn instantiate(
         // makes no sense to set during init, as other contracts might not be deployed yet.
         push_update_contracts: vec![],
         decommissioned: None,
+        early_withdraw_penalty: None,
+        penalty_recipient: None,
     };
     CONFIG.save(deps.storage, &config)?;
 
@@ -72,6 +91,8 @@ pub fn instantiate(
     };
     HISTORY.save(deps.storage, (env.contract.address.clone(), cur_period), &point)?;
     BLACKLIST.save(deps.storage, &vec![])?;
+    LAST_TOKEN_ID.save(deps.storage, &0)?;
+    TOKEN_COUNT.save(deps.storage, &0)?;
 
     if let Some(marketing) = msg.marketing {
         if msg.logo_urls_whitelist.is_empty() {
@@ -127,17 +148,47 @@ pub fn instantiate(
 /// Exposes all the execute functions available in the contract.
 ///
 /// ## Execute messages
-/// * **ExecuteMsg::ExtendLockTime { time }** Increase a staker's lock time.
+/// * **ExecuteMsg::ExtendLockTime { time, token_id }** Increase the lock time of a lock token.
 ///
 /// * **ExecuteMsg::Receive(msg)** Parse incoming messages coming from the ampLP token contract.
 ///
-/// * **ExecuteMsg::Withdraw {}** Withdraw all ampLP from a lock position if the lock has expired.
+/// * **ExecuteMsg::Withdraw { token_id }** Withdraw all ampLP from a lock token if it has expired.
+///
+/// * **ExecuteMsg::EarlyWithdraw { token_id }** Withdraw all ampLP from an active lock token minus the early withdrawal penalty.
 ///
 /// * **ExecuteMsg::ProposeNewOwner { owner, expires_in }** Creates a new request to change contract ownership.
 ///
 /// * **ExecuteMsg::DropOwnershipProposal {}** Removes a request to change contract ownership.
 ///
 /// * **ExecuteMsg::ClaimOwnership {}** Claims contract ownership.
+///
+/// * **ExecuteMsg::Delegate { delegatee, bps, expires }** Delegates a share of the voting power.
+///
+/// * **ExecuteMsg::Undelegate {}** Revokes the delegation of the sender.
+///
+/// * **ExecuteMsg::ExpireDelegation { delegator }** Removes an expired delegation.
+///
+/// * **ExecuteMsg::TransferNft { recipient, token_id }** Transfers a lock token.
+///
+/// * **ExecuteMsg::SendNft { contract, token_id, msg }** Transfers a lock token to a contract and calls it.
+///
+/// * **ExecuteMsg::Approve { spender, token_id, expires }** Allows the spender to transfer a lock token.
+///
+/// * **ExecuteMsg::Revoke { spender, token_id }** Removes the approval of a lock token.
+///
+/// * **ExecuteMsg::ApproveAll { operator, expires }** Allows the operator to transfer all lock tokens of the sender.
+///
+/// * **ExecuteMsg::RevokeAll { operator }** Removes an operator of the sender.
+///
+/// * **ExecuteMsg::Merge { token_id, into_token_id }** Merges two lock tokens.
+///
+/// * **ExecuteMsg::Split { token_id, amount }** Splits a lock token.
+///
+/// * **ExecuteMsg::LockPermanent { token_id }** Keeps a lock token at the maximum lock time.
+///
+/// * **ExecuteMsg::UnlockPermanent { token_id }** Starts the decay of a permanent lock token.
+///
+/// * **ExecuteMsg::MigrateLocks { limit }** Converts the locks into lock tokens after a migration.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
@@ -145,11 +196,21 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
+    if !matches!(msg, ExecuteMsg::MigrateLocks { .. }) {
+        assert_locks_migrated(deps.storage)?;
+    }
+
     match msg {
         ExecuteMsg::ExtendLockTime {
             time,
-        } => extend_lock_time(deps, env, info, time),
-        ExecuteMsg::Withdraw {} => withdraw(deps, env, info),
+            token_id,
+        } => extend_lock_time(deps, env, info, time, token_id),
+        ExecuteMsg::Withdraw {
+            token_id,
+        } => withdraw(deps, env, info, token_id, false),
+        ExecuteMsg::EarlyWithdraw {
+            token_id,
+        } => withdraw(deps, env, info, token_id, true),
         ExecuteMsg::ProposeNewOwner {
             new_owner,
             expires_in,
@@ -219,7 +280,17 @@ pub fn execute(
             new_guardian,
             push_update_contracts,
             decommissioned,
-        } => execute_update_config(deps, info, new_guardian, push_update_contracts, decommissioned),
+            early_withdraw_penalty,
+            penalty_recipient,
+        } => execute_update_config(
+            deps,
+            info,
+            new_guardian,
+            push_update_contracts,
+            decommissioned,
+            early_withdraw_penalty,
+            penalty_recipient,
+        ),
 
         ExecuteMsg::CreateLock {
             time,
@@ -233,15 +304,17 @@ pub fn execute(
 
         ExecuteMsg::ExtendLockAmount {
             extend_to_min_periods,
+            token_id,
         } => {
             let config = CONFIG.load(deps.storage)?;
             let amount = validate_received_funds(&info.funds, config.deposit_denom.as_ref())?;
             let sender = info.sender;
             assert_blacklist(deps.storage, &sender)?;
-            deposit_for(deps, env, amount, sender, extend_to_min_periods)
+            deposit_for(deps, env, amount, sender, token_id, extend_to_min_periods)
         },
         ExecuteMsg::DepositFor {
             user,
+            token_id,
         } => {
             let config = CONFIG.load(deps.storage)?;
             let amount = validate_received_funds(&info.funds, config.deposit_denom.as_ref())?;
@@ -249,8 +322,59 @@ pub fn execute(
             assert_blacklist(deps.storage, &sender)?;
             let addr = deps.api.addr_validate(&user)?;
             assert_blacklist(deps.storage, &addr)?;
-            deposit_for(deps, env, amount, addr, None)
+            deposit_for(deps, env, amount, addr, token_id, None)
         },
+        ExecuteMsg::Delegate {
+            delegatee,
+            bps,
+            expires,
+        } => delegate(deps, env, info, delegatee, bps, expires),
+        ExecuteMsg::Undelegate {} => undelegate(deps, env, info),
+        ExecuteMsg::ExpireDelegation {
+            delegator,
+        } => expire_delegation(deps, env, delegator),
+        ExecuteMsg::TransferNft {
+            recipient,
+            token_id,
+        } => transfer_nft(deps, env, info, recipient, token_id),
+        ExecuteMsg::SendNft {
+            contract,
+            token_id,
+            msg,
+        } => send_nft(deps, env, info, contract, token_id, msg),
+        ExecuteMsg::Approve {
+            spender,
+            token_id,
+            expires,
+        } => approve(deps, env, info, spender, token_id, expires),
+        ExecuteMsg::Revoke {
+            spender,
+            token_id,
+        } => revoke(deps, env, info, spender, token_id),
+        ExecuteMsg::ApproveAll {
+            operator,
+            expires,
+        } => approve_all(deps, env, info, operator, expires),
+        ExecuteMsg::RevokeAll {
+            operator,
+        } => revoke_all(deps, info, operator),
+        ExecuteMsg::Merge {
+            token_id,
+            into_token_id,
+        } => merge(deps, env, info, token_id, into_token_id),
+        ExecuteMsg::Split {
+            token_id,
+            amount,
+        } => split(deps, env, info, token_id, amount),
+        ExecuteMsg::LockPermanent {
+            token_id,
+        } => lock_permanent(deps, env, info, token_id),
+        ExecuteMsg::UnlockPermanent {
+            token_id,
+        } => unlock_permanent(deps, env, info, token_id),
+        ExecuteMsg::MigrateLocks {
+            limit,
+        } => migrate_locks(deps, env, info, limit),
     }
 }
 
@@ -328,74 +452,66 @@ fn checkpoint_total(
     Ok(())
 }
 
-/// Checkpoint a user's voting power (vAMP balance).
-/// This function fetches the user's last available checkpoint, calculates the user's current voting power, applies slope changes based on
-/// `add_amount` and `new_end` parameters, schedules slope changes for total voting power and saves the new checkpoint for the current
-/// period in [`HISTORY`] (using the user's address).
-/// If a user already checkpointed themselves for the current period, then this function uses the current checkpoint as the latest
-/// available one.
+/// Checkpoint the voting power of a lock token.
+/// This function fetches the lock token's last available checkpoint, calculates its current voting power, applies slope changes based on
+/// `add_amount` and `new_end` parameters and saves the new checkpoint for the current period in [`TOKEN_HISTORY`].
+/// The change of the lock token's voting power is applied to its owner and the total voting power with [`checkpoint_owner`].
+/// Permanent lock tokens have no decaying voting power, the amount and the maximum boost are added to the fixed voting power.
 ///
-/// * **addr** staker for which we checkpoint the voting power.
+/// * **id** lock token for which we checkpoint the voting power.
 ///
-/// * **add_amount** amount of vAMP to add to the staker's balance.
+/// * **add_amount** amount of ampLP added to the lock token.
 ///
-/// * **new_end** new lock time for the staker's vAMP position.
+/// * **new_end** new lock time of the lock token.
 fn checkpoint(
     store: &mut dyn Storage,
     env: Env,
-    addr: Addr,
+    id: u64,
     add_amount: Option<Uint128>,
     new_end: Option<u64>,
 ) -> Result<(), ContractError> {
+    let mut token = LOCK_TOKENS.load(store, id)?;
     let cur_period = get_period(env.block.time.seconds())?;
-    let cur_period_key = cur_period;
     let add_amount = add_amount.unwrap_or_default();
-    let mut old_slope = Default::default();
-    let mut add_voting_power = Uint128::zero();
 
-    // Get the last user checkpoint
-    let last_checkpoint = fetch_last_checkpoint(store, &addr, cur_period_key)?;
-    let new_point = if let Some((_, point)) = last_checkpoint {
+    // Get the last lock token checkpoint
+    let old_point = fetch_token_point(store, id, cur_period)?;
+    let new_point = if token.permanent {
+        let fixed = old_point.as_ref().map(|point| point.fixed).unwrap_or_default();
+        Point {
+            power: Uint128::zero(),
+            start: cur_period,
+            end: cur_period,
+            slope: Uint128::zero(),
+            fixed: fixed + add_amount + calc_permanent_boost(add_amount)?,
+        }
+    } else if let Some(point) = &old_point {
         let end = new_end.unwrap_or(point.end);
         let dt = end.saturating_sub(cur_period);
-        let current_power = calc_voting_power(&point, cur_period);
 
-        let new_slope = if dt != 0 {
+        let (new_voting_power, new_slope) = if dt != 0 {
             // always recalculate slope when the end has changed
             if end > point.end {
-                // This is extend_lock_time. Recalculating user's voting power
-                let mut lock = LOCKED.load(store, addr.clone())?;
-                let mut new_voting_power = calc_coefficient(dt).checked_mul_uint(lock.amount)?;
-                let slope = adjust_vp_and_slope(&mut new_voting_power, dt)?; // end_vp
-                                                                             // new_voting_power should always be >= current_power. saturating_sub is used for extra safety
-                add_voting_power = new_voting_power.saturating_sub(current_power);
-                lock.last_extend_lock_period = cur_period;
-                LOCKED.save(store, addr.clone(), &lock, env.block.height)?;
-                slope
+                // This is extend_lock_time. Recalculating the lock token's voting power
+                let mut new_voting_power = calc_coefficient(dt).checked_mul_uint(token.amount)?;
+                let slope = adjust_vp_and_slope(&mut new_voting_power, dt)?;
+                token.last_extend_lock_period = cur_period;
+                LOCK_TOKENS.save(store, id, &token)?;
+                (new_voting_power, slope)
             } else {
-                // This is an increase in the user's lock amount
+                // This is an increase in the lock token's amount
                 let raw_add_voting_power = calc_coefficient(dt).checked_mul_uint(add_amount)?;
-                let mut new_voting_power = current_power.checked_add(raw_add_voting_power)?;
+                let mut new_voting_power = point.power.checked_add(raw_add_voting_power)?;
                 let slope = adjust_vp_and_slope(&mut new_voting_power, dt)?;
-                // new_voting_power should always be >= current_power. saturating_sub is used for extra safety
-                add_voting_power = new_voting_power.saturating_sub(current_power);
-                slope
+                (new_voting_power, slope)
             }
         } else {
-            Uint128::zero()
-        };
-
-        // Cancel the previously scheduled slope change (same logic as in cancel_scheduled_slope)
-        let last_slope_change = cancel_scheduled_slope(store, point.slope, point.end)?;
-
-        if point.end > last_slope_change {
-            // We need to subtract the slope point from the total voting power slope
-            // Only if the point is still active and has not been processed/applied yet.
-            old_slope = point.slope
+            (Uint128::zero(), Uint128::zero())
         };
 
         Point {
-            power: current_power + add_voting_power,
+            // new_voting_power should always be >= the current power. max is used for extra safety
+            power: new_voting_power.max(point.power),
             slope: new_slope,
             start: cur_period,
             end,
@@ -405,10 +521,10 @@ fn checkpoint(
         // This error can't happen since this if-branch is intended for checkpoint creation
         let end = new_end.ok_or(ContractError::CheckpointInitializationFailed {})?;
         let dt = end - cur_period;
-        add_voting_power = calc_coefficient(dt).checked_mul_uint(add_amount)?;
-        let slope = adjust_vp_and_slope(&mut add_voting_power, dt)?; //add_amount
+        let mut voting_power = calc_coefficient(dt).checked_mul_uint(add_amount)?;
+        let slope = adjust_vp_and_slope(&mut voting_power, dt)?;
         Point {
-            power: add_voting_power,
+            power: voting_power,
             slope,
             start: cur_period,
             end,
@@ -416,19 +532,71 @@ fn checkpoint(
         }
     };
 
-    // Schedule a slope change
-    schedule_slope_change(store, new_point.slope, new_point.end)?;
+    TOKEN_HISTORY.save(store, (id, cur_period), &new_point)?;
+
+    checkpoint_owner(store, env, &token.owner, old_point.as_ref(), Some(&new_point))
+}
+
+/// Checkpoint a user's voting power (vAMP balance) after a change of one of their lock tokens.
+/// The voting power of the lock token's old point is replaced by its new point in the user's checkpoint in [`HISTORY`],
+/// the slope changes of the user and the total voting power are moved from the old end to the new end and the change is
+/// applied to the total voting power with [`checkpoint_total`]. Both points are expected at the current period.
+/// Blacklisted users have no voting power, so nothing is changed for them.
+///
+/// * **owner** owner of the lock token.
+///
+/// * **old_point** the lock token's point before the change.
+///
+/// * **new_point** the lock token's point after the change.
+fn checkpoint_owner(
+    store: &mut dyn Storage,
+    env: Env,
+    owner: &Addr,
+    old_point: Option<&Point>,
+    new_point: Option<&Point>,
+) -> Result<(), ContractError> {
+    if BLACKLIST.load(store)?.contains(owner) {
+        return Ok(());
+    }
+
+    let cur_period = get_period(env.block.time.seconds())?;
+    let empty_point = Point {
+        power: Uint128::zero(),
+        start: cur_period,
+        end: cur_period,
+        slope: Uint128::zero(),
+        fixed: Uint128::zero(),
+    };
+    let old_point = old_point.unwrap_or(&empty_point);
+    let new_point = new_point.unwrap_or(&empty_point);
+
+    let point = fetch_user_point(store, owner, cur_period)?.unwrap_or_else(|| empty_point.clone());
+    HISTORY.save(
+        store,
+        (owner.clone(), cur_period),
+        &Point {
+            power: point.power.saturating_sub(old_point.power) + new_point.power,
+            start: cur_period,
+            end: point.end.max(new_point.end),
+            slope: point.slope.saturating_sub(old_point.slope) + new_point.slope,
+            fixed: point.fixed.saturating_sub(old_point.fixed) + new_point.fixed,
+        },
+    )?;
 
-    HISTORY.save(store, (addr, cur_period_key), &new_point)?;
+    // Move the scheduled slope changes of the user and the total voting power
+    cancel_user_slope_change(store, owner, old_point.slope, old_point.end)?;
+    schedule_user_slope_change(store, owner, new_point.slope, new_point.end)?;
+    cancel_scheduled_slope(store, old_point.slope, old_point.end)?;
+    schedule_slope_change(store, new_point.slope, new_point.end)?;
 
     checkpoint_total(
         store,
         env,
-        Some(add_voting_power),
-        Some(add_amount),
-        None,
-        None,
-        old_slope,
+        Some(new_point.power),
+        Some(new_point.fixed),
+        Some(old_point.power),
+        Some(old_point.fixed),
+        old_point.slope,
         new_point.slope,
     )
 }
@@ -436,7 +604,7 @@ fn checkpoint(
 /// Creates a lock for the user that lasts for the specified time duration (in seconds).
 /// Checks that the user is locking ampLP tokens.
 /// Checks that the lock time is within [`WEEK`]..[`MAX_LOCK_TIME`].
-/// Creates a lock if it doesn't exist and triggers a [`checkpoint`] for the staker.
+/// Creates a lock if it doesn't exist, mints its lock token and triggers a [`checkpoint`] for it.
 /// If a lock already exists, then a [`ContractError`] is returned.
 ///
 /// * **user** staker for which we create a lock position.
@@ -462,19 +630,25 @@ fn create_lock(
     let config = CONFIG.load(deps.storage)?;
     assert_not_decommissioned(&config)?;
 
-    LOCKED.update(deps.storage, user.clone(), env.block.height, |lock_opt| {
-        if lock_opt.is_some() && !lock_opt.unwrap().amount.is_zero() {
-            return Err(ContractError::LockAlreadyExists {});
-        }
-        Ok(Lock {
+    if LOCKED.may_load(deps.storage, user.clone())?.filter(|lock| !lock.amount.is_zero()).is_some()
+    {
+        return Err(ContractError::LockAlreadyExists {});
+    }
+
+    let token_id = mint_lock_token(
+        deps.storage,
+        &LockToken {
+            owner: user.clone(),
             amount,
             start: block_period,
             end,
             last_extend_lock_period: block_period,
-        })
-    })?;
-
-    checkpoint(deps.storage, env.clone(), user.clone(), Some(amount), Some(end))?;
+            permanent: false,
+            approvals: vec![],
+        },
+    )?;
+    add_to_lock(deps.storage, env.block.height, token_id, amount)?;
+    checkpoint(deps.storage, env.clone(), token_id, Some(amount), Some(end))?;
 
     let lock_info = get_user_lock_info(deps.as_ref(), &env, user.to_string())?;
 
@@ -483,54 +657,58 @@ fn create_lock(
         .add_attribute("voting_power", lock_info.voting_power.to_string())
         .add_attribute("fixed_power", lock_info.fixed_amount.to_string())
         .add_attribute("lock_end", lock_info.end.to_string())
-        .add_messages(get_push_update_msgs(config, user, Ok(lock_info))?))
+        .add_attribute("token_id", token_id.to_string())
+        .add_messages(get_push_update_msgs(config.clone(), user.clone(), Ok(lock_info))?)
+        .add_messages(get_delegatee_push_update_msgs(deps.as_ref(), &env, config, &user)?))
 }
 
-/// Deposits an 'amount' of ampLP tokens into 'user''s lock.
+/// Deposits an 'amount' of ampLP tokens into a lock token of 'user'.
 /// Checks that the user is transferring and locking ampLP.
-/// Triggers a [`checkpoint`] for the user.
-/// If the user does not have a lock, then a [`ContractError`] is returned.
+/// Triggers a [`checkpoint`] for the lock token.
+/// If the user does not have a lock token, then a [`ContractError`] is returned.
 ///
 /// * **amount** amount of ampLP to deposit.
 ///
 /// * **user** user who's lock amount will increase.
+///
+/// * **token_id** lock token receiving the deposit. Only required if the user owns multiple lock tokens.
 fn deposit_for(
     deps: DepsMut,
     env: Env,
     amount: Uint128,
     user: Addr,
+    token_id: Option<String>,
     extend_to_min_periods: Option<bool>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     assert_not_decommissioned(&config)?;
 
-    let mut new_end = None;
-    LOCKED.update(deps.storage, user.clone(), env.block.height, |lock_opt| match lock_opt {
-        Some(mut lock) if !lock.amount.is_zero() => {
-            let block_period = get_period(env.block.time.seconds())?;
-
-            match extend_to_min_periods {
-                Some(true) => {
-                    if lock.end < block_period + MIN_LOCK_PERIODS {
-                        lock.end = block_period + MIN_LOCK_PERIODS;
-                        new_end = Some(lock.end);
-                    }
-                },
-                Some(false) | None => {
-                    if lock.end <= block_period {
-                        Err(ContractError::LockExpired {})?
-                    }
-                    assert_periods_remaining(lock.end - block_period)?
-                },
-            }
+    let (id, mut token) = resolve_lock_token(deps.storage, &user, token_id)?;
+    let block_period = get_period(env.block.time.seconds())?;
 
-            lock.amount += amount;
-            Ok(lock)
-        },
-        _ => Err(ContractError::LockDoesNotExist {}),
-    })?;
+    let mut new_end = None;
+    // permanent lock tokens always have the maximum lock time
+    if !token.permanent {
+        match extend_to_min_periods {
+            Some(true) => {
+                if token.end < block_period + MIN_LOCK_PERIODS {
+                    token.end = block_period + MIN_LOCK_PERIODS;
+                    new_end = Some(token.end);
+                }
+            },
+            Some(false) | None => {
+                if token.end <= block_period {
+                    return Err(ContractError::LockExpired {});
+                }
+                assert_periods_remaining(token.end - block_period)?
+            },
+        }
+    }
 
-    checkpoint(deps.storage, env.clone(), user.clone(), Some(amount), new_end)?;
+    token.amount += amount;
+    LOCK_TOKENS.save(deps.storage, id, &token)?;
+    checkpoint(deps.storage, env.clone(), id, Some(amount), new_end)?;
+    add_to_lock(deps.storage, env.block.height, id, amount)?;
 
     let lock_info = get_user_lock_info(deps.as_ref(), &env, user.to_string())?;
 
@@ -539,106 +717,85 @@ fn deposit_for(
         .add_attribute("voting_power", lock_info.voting_power.to_string())
         .add_attribute("fixed_power", lock_info.fixed_amount.to_string())
         .add_attribute("lock_end", lock_info.end.to_string())
-        .add_messages(get_push_update_msgs(config, user, Ok(lock_info))?))
+        .add_attribute("token_id", id.to_string())
+        .add_messages(get_push_update_msgs(config.clone(), user.clone(), Ok(lock_info))?)
+        .add_messages(get_delegatee_push_update_msgs(deps.as_ref(), &env, config, &user)?))
 }
 
-/// Withdraws the whole amount of locked ampLP from a specific user lock.
-/// If the user lock doesn't exist or if it has not yet expired, then a [`ContractError`] is returned.
-fn withdraw(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+/// Withdraws the whole amount of locked ampLP from a lock token of the sender and burns it.
+/// If the lock token doesn't exist or if it has not yet expired, then a [`ContractError`] is returned.
+///
+/// * **token_id** lock token to withdraw. Only required if the sender owns multiple lock tokens.
+///
+/// * **early** withdraws an active lock token with the early withdrawal penalty.
+fn withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: Option<String>,
+    early: bool,
+) -> Result<Response, ContractError> {
     let sender = info.sender;
-    // 'LockDoesNotExist' is thrown either when a lock does not exist in LOCKED or when a lock exists but lock.amount == 0
-    let mut lock = LOCKED
-        .may_load(deps.storage, sender.clone())?
-        .filter(|lock| !lock.amount.is_zero())
-        .ok_or(ContractError::LockDoesNotExist {})?;
+    let (id, token) = resolve_lock_token(deps.storage, &sender, token_id)?;
 
     let cur_period = get_period(env.block.time.seconds())?;
     let config = CONFIG.load(deps.storage)?;
     let is_decommissioned = config.decommissioned.unwrap_or_default();
 
-    if lock.end > cur_period && !is_decommissioned {
-        Err(ContractError::LockHasNotExpired {})
-    } else {
-        let transfer_msg =
-            native_asset(config.deposit_denom.clone(), lock.amount).into_msg(sender.clone())?;
-
-        let amount = lock.amount;
-        lock.amount = Uint128::zero();
-        LOCKED.save(deps.storage, sender.clone(), &lock, env.block.height)?;
-
-        if lock.end > cur_period {
-            // early withdraw through decommissioned. Update voting power same as blacklist.
-            let cur_period_key = cur_period;
-            let last_checkpoint = fetch_last_checkpoint(deps.storage, &sender, cur_period_key)?;
-            if let Some((_, point)) = last_checkpoint {
-                // We need to checkpoint with zero power and zero slope
-                HISTORY.save(
-                    deps.storage,
-                    (sender.clone(), cur_period_key),
-                    &Point {
-                        power: Uint128::zero(),
-                        slope: Default::default(),
-                        start: cur_period,
-                        end: cur_period,
-                        fixed: Uint128::zero(),
-                    },
-                )?;
-
-                let cur_power = calc_voting_power(&point, cur_period);
-
-                // User's contribution in the total voting power calculation
-                let reduce_total_vp = cur_power;
-                let old_slopes = point.slope;
-                let old_amount = point.fixed;
-                cancel_scheduled_slope(deps.storage, point.slope, point.end)?;
-
-                checkpoint_total(
-                    deps.storage,
-                    env.clone(),
-                    None,
-                    None,
-                    Some(reduce_total_vp),
-                    Some(old_amount),
-                    old_slopes,
-                    Default::default(),
-                )?;
-            }
-        } else {
-            // We need to checkpoint and eliminate the slope influence on a future lock
-            HISTORY.save(
-                deps.storage,
-                (sender.clone(), cur_period),
-                &Point {
-                    power: Uint128::zero(),
-                    start: cur_period,
-                    end: cur_period,
-                    slope: Default::default(),
-                    fixed: Uint128::zero(),
-                },
-            )?;
+    // permanent lock tokens never expire
+    let is_active = token.end > cur_period || token.permanent;
+    if is_active && !is_decommissioned && !early {
+        return Err(ContractError::LockHasNotExpired {});
+    }
 
-            // normal withdraw
-            // removing funds needs to remove from total checkpoint aswell.
-            checkpoint_total(
-                deps.storage,
-                env.clone(),
-                None,
-                None,
-                None,
-                Some(amount),
-                Default::default(),
-                Default::default(),
-            )?;
-        }
+    let penalty = calc_withdraw_penalty(&config, &token, cur_period)?;
+    let mut transfer_msgs = vec![];
+    if token.amount > penalty {
+        transfer_msgs.push(
+            native_asset(config.deposit_denom.clone(), token.amount - penalty)
+                .into_msg(sender.clone())?,
+        );
+    }
+    if let Some(recipient) = config.penalty_recipient.clone().filter(|_| !penalty.is_zero()) {
+        transfer_msgs
+            .push(native_asset(config.deposit_denom.clone(), penalty).into_msg(recipient)?);
+    }
+
+    // We need to checkpoint with zero power and zero slope and remove the lock token's voting
+    // power from the sender and the total voting power
+    let point = fetch_token_point(deps.storage, id, cur_period)?;
+    TOKEN_HISTORY.save(
+        deps.storage,
+        (id, cur_period),
+        &Point {
+            power: Uint128::zero(),
+            start: cur_period,
+            end: cur_period,
+            slope: Uint128::zero(),
+            fixed: Uint128::zero(),
+        },
+    )?;
+    checkpoint_owner(deps.storage, env.clone(), &sender, point.as_ref(), None)?;
 
-        let lock_info = get_user_lock_info(deps.as_ref(), &env, sender.to_string());
-        let msgs = get_push_update_msgs(config, sender, lock_info)?;
+    reduce_lock(deps.storage, env.block.height, &sender, token.amount)?;
+    burn_lock_token(deps.storage, &sender, id)?;
 
-        Ok(Response::default()
-            .add_message(transfer_msg)
-            .add_messages(msgs)
-            .add_attribute("action", "veamp/withdraw"))
-    }
+    let lock_info = get_user_lock_info(deps.as_ref(), &env, sender.to_string());
+    let mut msgs = get_push_update_msgs(config.clone(), sender.clone(), lock_info)?;
+    msgs.extend(get_delegatee_push_update_msgs(deps.as_ref(), &env, config, &sender)?);
+
+    let action = if early {
+        "veamp/early_withdraw"
+    } else {
+        "veamp/withdraw"
+    };
+
+    Ok(Response::default()
+        .add_messages(transfer_msgs)
+        .add_messages(msgs)
+        .add_attribute("action", action)
+        .add_attribute("penalty", penalty)
+        .add_attribute("token_id", id.to_string()))
 }
 
 fn get_push_update_msgs_multi(
@@ -687,50 +844,71 @@ fn get_push_update_msgs(
     }
 }
 
-/// Increase the current lock time for a staker by a specified time period.
+/// The voting power delegated by a user changes with their lock, so the delegatee receives an
+/// update as well.
+fn get_delegatee_push_update_msgs(
+    deps: Deps,
+    env: &Env,
+    config: Config,
+    user: &Addr,
+) -> StdResult<Vec<CosmosMsg>> {
+    let cur_period = get_period(env.block.time.seconds())?;
+    match fetch_active_delegation(deps.storage, user, cur_period)? {
+        Some(delegation) => {
+            get_push_update_msgs_multi(deps, env.clone(), config, vec![delegation.delegatee])
+        },
+        None => Ok(vec![]),
+    }
+}
+
+/// Increase the current lock time of a lock token by a specified time period.
 /// Evaluates that the `time` is within [`WEEK`]..[`MAX_LOCK_TIME`]
 /// and then it triggers a [`checkpoint`].
-/// If the user lock doesn't exist or if it expired, then a [`ContractError`] is returned.
+/// If the lock token doesn't exist or if it is permanent, then a [`ContractError`] is returned.
 ///
 /// ## Note
-/// The time is added to the lock's `end`.
+/// The time is added to the lock token's `end`.
 /// For example, at period 0, the user has their ampLP locked for 3 weeks.
 /// In 1 week, they increase their lock time by 10 weeks, thus the unlock period becomes 13 weeks.
 ///
-/// * **time** increase in lock time applied to the staker's position.
+/// * **time** increase in lock time applied to the lock token.
+///
+/// * **token_id** lock token to extend. Only required if the sender owns multiple lock tokens.
 fn extend_lock_time(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
     time: u64,
+    token_id: Option<String>,
 ) -> Result<Response, ContractError> {
     let user = info.sender;
     assert_blacklist(deps.storage, &user)?;
-    let mut lock = LOCKED
-        .may_load(deps.storage, user.clone())?
-        .filter(|lock| !lock.amount.is_zero())
-        .ok_or(ContractError::LockDoesNotExist {})?;
+    let (id, mut token) = resolve_lock_token(deps.storage, &user, token_id)?;
+
+    if token.permanent {
+        return Err(ContractError::LockIsPermanent {});
+    }
 
     // Disable the ability to extend the lock time by less than a week
     assert_time_limits(time)?;
 
     let block_period = get_period(env.block.time.seconds())?;
-    if lock.end < block_period {
-        // if the lock.end is in the past, extend_lock_time always starts from the current period.
-        lock.end = block_period;
+    if token.end < block_period {
+        // if the token.end is in the past, extend_lock_time always starts from the current period.
+        token.end = block_period;
     };
 
-    lock.end += get_periods_count(time);
+    token.end += get_periods_count(time);
 
-    let periods = lock.end - block_period;
+    let periods = token.end - block_period;
     assert_periods_remaining(periods)?;
 
     // Should not exceed MAX_LOCK_TIME
-    assert_time_limits(EPOCH_START + lock.end * WEEK - env.block.time.seconds())?;
-
-    LOCKED.save(deps.storage, user.clone(), &lock, env.block.height)?;
+    assert_time_limits(EPOCH_START + token.end * WEEK - env.block.time.seconds())?;
 
-    checkpoint(deps.storage, env.clone(), user.clone(), None, Some(lock.end))?;
+    LOCK_TOKENS.save(deps.storage, id, &token)?;
+    checkpoint(deps.storage, env.clone(), id, None, Some(token.end))?;
+    add_to_lock(deps.storage, env.block.height, id, Uint128::zero())?;
 
     let config = CONFIG.load(deps.storage)?;
     assert_not_decommissioned(&config)?;
@@ -742,12 +920,15 @@ fn extend_lock_time(
         .add_attribute("voting_power", lock_info.voting_power.to_string())
         .add_attribute("fixed_power", lock_info.fixed_amount.to_string())
         .add_attribute("lock_end", lock_info.end.to_string())
-        .add_messages(get_push_update_msgs(config, user, Ok(lock_info))?))
+        .add_attribute("token_id", id.to_string())
+        .add_messages(get_push_update_msgs(config.clone(), user.clone(), Ok(lock_info))?)
+        .add_messages(get_delegatee_push_update_msgs(deps.as_ref(), &env, config, &user)?))
 }
 
 /// Update the staker blacklist. Whitelists addresses specified in 'remove_addrs'
 /// and blacklists new addresses specified in 'append_addrs'. Nullifies staker voting power and
 /// cancels their contribution in the total voting power (total vAMP supply).
+/// The voting power of the lock tokens of whitelisted addresses is counted again.
 ///
 /// * **append_addrs** array of addresses to blacklist.
 ///
@@ -793,8 +974,7 @@ fn update_blacklist(
             return Err(ContractError::AddressBlacklistDuplicated(addr.to_string()));
         }
 
-        let last_checkpoint = fetch_last_checkpoint(deps.storage, addr, cur_period_key)?;
-        if let Some((_, point)) = last_checkpoint {
+        if let Some(point) = fetch_user_point(deps.storage, addr, cur_period_key)? {
             // We need to checkpoint with zero power and zero slope
             HISTORY.save(
                 deps.storage,
@@ -808,21 +988,20 @@ fn update_blacklist(
                 },
             )?;
 
-            let cur_power = calc_voting_power(&point, cur_period);
-            // User's contribution is already zero. Skipping them
-            if cur_power.is_zero() {
-                continue;
+            // The scheduled slope changes of the user's lock tokens are cancelled
+            for (end, slope) in fetch_future_user_slope_changes(deps.storage, addr, cur_period)? {
+                USER_SLOPE_CHANGES.remove(deps.storage, (addr.clone(), end));
+                cancel_scheduled_slope(deps.storage, slope, end)?;
             }
 
             // User's contribution in the total voting power calculation
-            reduce_total_vp += cur_power;
+            reduce_total_vp += point.power;
             old_slopes += point.slope;
             old_amount += point.fixed;
-            cancel_scheduled_slope(deps.storage, point.slope, point.end)?;
         }
     }
 
-    if !reduce_total_vp.is_zero() || !old_slopes.is_zero() {
+    if !reduce_total_vp.is_zero() || !old_slopes.is_zero() || !old_amount.is_zero() {
         // Trigger a total voting power recalculation
         checkpoint_total(
             deps.storage,
@@ -840,16 +1019,6 @@ fn update_blacklist(
         if !used_addr.insert(addr.clone()) {
             return Err(ContractError::AddressBlacklistDuplicated(addr.to_string()));
         }
-
-        let lock_opt = LOCKED.may_load(deps.storage, addr.clone())?;
-        if let Some(Lock {
-            amount,
-            end,
-            ..
-        }) = lock_opt
-        {
-            checkpoint(deps.storage, env.clone(), addr.clone(), Some(amount), Some(end))?;
-        }
     }
 
     BLACKLIST.update(deps.storage, |blacklist| -> StdResult<Vec<Addr>> {
@@ -859,6 +1028,13 @@ fn update_blacklist(
         Ok(updated_blacklist)
     })?;
 
+    for addr in remove.iter() {
+        for id in fetch_owner_token_ids(deps.storage, addr)? {
+            let point = fetch_token_point(deps.storage, id, cur_period)?;
+            checkpoint_owner(deps.storage, env.clone(), addr, None, point.as_ref())?;
+        }
+    }
+
     let mut attrs = vec![attr("action", "veamp/update_blacklist")];
     if !append_addrs.is_empty() {
         attrs.push(attr("added_addresses", append_addrs.join(",")))
@@ -867,6 +1043,16 @@ fn update_blacklist(
         attrs.push(attr("removed_addresses", remove_addrs.join(",")))
     }
 
+    // the voting power delegated by the updated addresses changes as well
+    let mut delegatees = vec![];
+    for addr in append.iter().chain(remove.iter()) {
+        if let Some(delegation) = fetch_active_delegation(deps.storage, addr, cur_period)? {
+            if !delegatees.contains(&delegation.delegatee) {
+                delegatees.push(delegation.delegatee);
+            }
+        }
+    }
+
     Ok(Response::default()
         .add_attributes(attrs)
         .add_messages(get_push_update_msgs_multi(
@@ -875,100 +1061,821 @@ fn update_blacklist(
             config.clone(),
             append,
         )?)
-        .add_messages(get_push_update_msgs_multi(deps.as_ref(), env, config, remove)?))
+        .add_messages(get_push_update_msgs_multi(
+            deps.as_ref(),
+            env.clone(),
+            config.clone(),
+            remove,
+        )?)
+        .add_messages(get_push_update_msgs_multi(deps.as_ref(), env, config, delegatees)?))
 }
 
-/// Updates contracts' guardian address.
-fn execute_update_config(
+/// Delegates a share of the sender's voting power to `delegatee` until the period of `expires`.
+/// An existing delegation is replaced. The delegation starts in the current period.
+///
+/// * **delegatee** address receiving the voting power.
+///
+/// * **bps** share of the voting power to delegate.
+///
+/// * **expires** timestamp when the delegation expires.
+fn delegate(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
-    new_guardian: Option<String>,
-    push_update_contracts: Option<Vec<String>>,
-    decommissioned: Option<bool>,
+    delegatee: String,
+    bps: u16,
+    expires: u64,
 ) -> Result<Response, ContractError> {
-    let mut cfg = CONFIG.load(deps.storage)?;
+    let config = CONFIG.load(deps.storage)?;
+    let delegator = info.sender;
+    assert_blacklist(deps.storage, &delegator)?;
+    let delegatee = deps.api.addr_validate(&delegatee)?;
+    assert_blacklist(deps.storage, &delegatee)?;
 
-    if cfg.owner != info.sender {
-        return Err(ContractError::Unauthorized {});
+    if delegator == delegatee {
+        return Err(ContractError::DelegateToSelf {});
     }
 
-    if let Some(new_guardian) = new_guardian {
-        cfg.guardian_addr = Some(deps.api.addr_validate(&new_guardian)?);
+    let bps: BasicPoints = bps.try_into().map_err(|_| ContractError::InvalidDelegationShare {})?;
+    if bps.is_zero() {
+        return Err(ContractError::InvalidDelegationShare {});
     }
 
-    if let Some(decommissioned) = decommissioned {
-        if decommissioned {
-            cfg.decommissioned = Some(true);
+    LOCKED
+        .may_load(deps.storage, delegator.clone())?
+        .filter(|lock| !lock.amount.is_zero())
+        .ok_or(ContractError::LockDoesNotExist {})?;
+
+    let cur_period = get_period(env.block.time.seconds())?;
+    let expires = get_period(expires)?;
+    if expires <= cur_period {
+        return Err(ContractError::DelegationExpiryInPast {});
+    }
+
+    let previous = fetch_active_delegation(deps.storage, &delegator, cur_period)?;
+
+    DELEGATIONS.save(
+        deps.storage,
+        (delegator.clone(), cur_period),
+        &Some(Delegation {
+            delegatee: delegatee.clone(),
+            bps,
+            expires,
+        }),
+    )?;
+    add_delegator(deps.storage, &delegatee, &delegator, cur_period)?;
+
+    let mut users = vec![delegator.clone(), delegatee.clone()];
+    if let Some(previous) = previous {
+        if previous.delegatee != delegatee {
+            remove_delegator(deps.storage, &previous.delegatee, &delegator, cur_period)?;
+            users.push(previous.delegatee);
         }
     }
 
-    if let Some(push_update_contracts) = push_update_contracts {
-        cfg.push_update_contracts = push_update_contracts
-            .iter()
-            .map(|c| deps.api.addr_validate(c))
-            .collect::<StdResult<Vec<_>>>()?;
+    Ok(Response::default()
+        .add_attribute("action", "veamp/delegate")
+        .add_attribute("delegatee", delegatee)
+        .add_attribute("bps", bps.u16().to_string())
+        .add_attribute("expires", expires.to_string())
+        .add_messages(get_push_update_msgs_multi(deps.as_ref(), env, config, users)?))
+}
+
+/// Revokes the active delegation of the sender, starting with the current period.
+fn undelegate(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let delegator = info.sender;
+    let cur_period = get_period(env.block.time.seconds())?;
+
+    let delegation = fetch_active_delegation(deps.storage, &delegator, cur_period)?
+        .ok_or(ContractError::DelegationDoesNotExist {})?;
+
+    DELEGATIONS.save(deps.storage, (delegator.clone(), cur_period), &None)?;
+    remove_delegator(deps.storage, &delegation.delegatee, &delegator, cur_period)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "veamp/undelegate")
+        .add_attribute("delegatee", delegation.delegatee.to_string())
+        .add_messages(get_push_update_msgs_multi(
+            deps.as_ref(),
+            env,
+            config,
+            vec![delegator, delegation.delegatee],
+        )?))
+}
+
+/// Removes an expired delegation. Expired delegations are not counted by the queries anyway, but
+/// the push update contracts only see the change through this message.
+///
+/// * **delegator** address whose delegation expired.
+fn expire_delegation(
+    deps: DepsMut,
+    env: Env,
+    delegator: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let delegator = deps.api.addr_validate(&delegator)?;
+    let cur_period = get_period(env.block.time.seconds())?;
+
+    let delegation = fetch_last_delegation(deps.storage, &delegator, cur_period)?
+        .ok_or(ContractError::DelegationDoesNotExist {})?;
+    if delegation.expires > cur_period {
+        return Err(ContractError::DelegationNotExpired {});
     }
 
-    CONFIG.save(deps.storage, &cfg)?;
+    DELEGATIONS.save(deps.storage, (delegator.clone(), cur_period), &None)?;
+    remove_delegator(deps.storage, &delegation.delegatee, &delegator, cur_period)?;
 
-    Ok(Response::default().add_attribute("action", "veamp/execute_update_config"))
+    Ok(Response::default()
+        .add_attribute("action", "veamp/expire_delegation")
+        .add_attribute("delegator", delegator.to_string())
+        .add_messages(get_push_update_msgs_multi(
+            deps.as_ref(),
+            env,
+            config,
+            vec![delegator, delegation.delegatee],
+        )?))
 }
 
-/// Expose available contract queries.
+/// Transfers a lock token to the recipient. The voting power of the lock token is moved from its
+/// owner to the recipient, so the total voting power and the scheduled slope changes stay the same.
 ///
-/// ## Queries
-/// * **QueryMsg::TotalVotingPower {}** Fetch the total voting power (vAMP supply) at the current block.
+/// * **recipient** address receiving the lock token.
 ///
-/// * **QueryMsg::UserVotingPower { user }** Fetch the user's voting power (vAMP balance) at the current block.
+/// * **token_id** the lock token to transfer.
+fn transfer_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let recipient = deps.api.addr_validate(&recipient)?;
+    let msgs = transfer_lock_token(deps, &env, &info.sender, &recipient, &token_id)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "veamp/transfer_nft")
+        .add_attribute("token_id", token_id)
+        .add_attribute("recipient", recipient)
+        .add_messages(msgs))
+}
+
+/// Transfers a lock token to a contract and calls `ReceiveNft` on it.
 ///
-/// * **QueryMsg::TotalVotingPowerAt { time }** Fetch the total voting power (vAMP supply) at a specified timestamp.
+/// * **contract** contract receiving the lock token.
 ///
-/// * **QueryMsg::UserVotingPowerAt { time }** Fetch the user's voting power (vAMP balance) at a specified timestamp.
+/// * **token_id** the lock token to send.
 ///
-/// * **QueryMsg::LockInfo { user }** Fetch a user's lock information.
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
-    match msg {
-        QueryMsg::CheckVotersAreBlacklisted {
-            voters,
-        } => Ok(to_json_binary(&check_voters_are_blacklisted(deps, voters)?)?),
-        QueryMsg::BlacklistedVoters {
-            start_after,
-            limit,
-        } => Ok(to_json_binary(&get_blacklisted_voters(deps, start_after, limit)?)?),
-        QueryMsg::TotalVamp {} => Ok(to_json_binary(&get_total_vamp(deps, env, None)?)?),
-        QueryMsg::UserVamp {
-            user,
-        } => Ok(to_json_binary(&get_user_vamp(deps, env, user, None)?)?),
-        QueryMsg::TotalVampAt {
-            time,
-        } => Ok(to_json_binary(&get_total_vamp(deps, env, Some(time))?)?),
-        QueryMsg::TotalVampAtPeriod {
-            period,
-        } => Ok(to_json_binary(&get_total_vamp_at_period(deps, env, period)?)?),
-        QueryMsg::UserVampAt {
-            user,
-            time,
-        } => Ok(to_json_binary(&get_user_vamp(deps, env, user, Some(time))?)?),
-        QueryMsg::UserVampAtPeriod {
-            user,
-            period,
-        } => Ok(to_json_binary(&get_user_vamp_at_period(deps, user, period)?)?),
-        QueryMsg::LockInfo {
-            user,
-        } => Ok(to_json_binary(&get_user_lock_info(deps, &env, user)?)?),
-        QueryMsg::UserDepositAtHeight {
-            user,
-            height,
-        } => Ok(to_json_binary(&get_user_deposit_at_height(deps, user, height)?)?),
-        QueryMsg::Config {} => {
-            let config = CONFIG.load(deps.storage)?;
-            Ok(to_json_binary(&ConfigResponse {
-                owner: config.owner.to_string(),
-                guardian_addr: config.guardian_addr,
+/// * **msg** message forwarded to the receiving contract.
+fn send_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    contract: String,
+    token_id: String,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    let contract = deps.api.addr_validate(&contract)?;
+    let msgs = transfer_lock_token(deps, &env, &info.sender, &contract, &token_id)?;
+
+    let receive_msg = Cw721ReceiveMsg {
+        sender: info.sender.to_string(),
+        token_id: token_id.clone(),
+        msg,
+    }
+    .into_cosmos_msg(&contract)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "veamp/send_nft")
+        .add_attribute("token_id", token_id)
+        .add_attribute("recipient", contract)
+        .add_messages(msgs)
+        .add_message(receive_msg))
+}
+
+/// Moves a lock token and its voting power to the recipient and returns the push update messages
+/// of the previous owner and the recipient. The sender needs to own the lock token or be approved.
+fn transfer_lock_token(
+    deps: DepsMut,
+    env: &Env,
+    sender: &Addr,
+    recipient: &Addr,
+    token_id: &str,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    assert_not_decommissioned(&config)?;
+
+    let (id, mut token) = load_lock_token(deps.storage, token_id)?;
+    assert_can_send(deps.storage, &env.block, sender, &token)?;
+
+    let owner = token.owner.clone();
+    assert_blacklist(deps.storage, &owner)?;
+    assert_blacklist(deps.storage, recipient)?;
+
+    if owner == *recipient {
+        return Err(ContractError::TransferToSelf {});
+    }
+
+    let cur_period = get_period(env.block.time.seconds())?;
+    let point = fetch_token_point(deps.storage, id, cur_period)?;
+    checkpoint_owner(deps.storage, env.clone(), &owner, point.as_ref(), None)?;
+    checkpoint_owner(deps.storage, env.clone(), recipient, None, point.as_ref())?;
+
+    reduce_lock(deps.storage, env.block.height, &owner, token.amount)?;
+    OWNER_TOKENS.remove(deps.storage, (owner.clone(), id));
+    OWNER_TOKENS.save(deps.storage, (recipient.clone(), id), &())?;
+    token.owner = recipient.clone();
+    // approvals are granted by the previous owner
+    token.approvals = vec![];
+    LOCK_TOKENS.save(deps.storage, id, &token)?;
+    add_to_lock(deps.storage, env.block.height, id, token.amount)?;
+
+    let mut msgs = get_push_update_msgs_multi(
+        deps.as_ref(),
+        env.clone(),
+        config.clone(),
+        vec![owner.clone(), recipient.clone()],
+    )?;
+    msgs.extend(get_delegatee_push_update_msgs(deps.as_ref(), env, config.clone(), &owner)?);
+    msgs.extend(get_delegatee_push_update_msgs(deps.as_ref(), env, config, recipient)?);
+    Ok(msgs)
+}
+
+/// Allows the spender to transfer a lock token until `expires`. Only the owner of the lock token
+/// or one of their operators can approve.
+fn approve(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    spender: String,
+    token_id: String,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    let spender = deps.api.addr_validate(&spender)?;
+    let (id, mut token) = load_lock_token(deps.storage, &token_id)?;
+    assert_can_approve(deps.storage, &env.block, &info.sender, &token.owner)?;
+
+    let expires = expires.unwrap_or_default();
+    if expires.is_expired(&env.block) {
+        return Err(ContractError::ApprovalExpired {});
+    }
+
+    token.approvals.retain(|approval| approval.spender != spender);
+    token.approvals.push(TokenApproval {
+        spender: spender.clone(),
+        expires,
+    });
+    LOCK_TOKENS.save(deps.storage, id, &token)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "veamp/approve")
+        .add_attribute("token_id", token_id)
+        .add_attribute("spender", spender))
+}
+
+/// Removes the approval of the spender for a lock token.
+fn revoke(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    spender: String,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let spender = deps.api.addr_validate(&spender)?;
+    let (id, mut token) = load_lock_token(deps.storage, &token_id)?;
+    assert_can_approve(deps.storage, &env.block, &info.sender, &token.owner)?;
+
+    token.approvals.retain(|approval| approval.spender != spender);
+    LOCK_TOKENS.save(deps.storage, id, &token)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "veamp/revoke")
+        .add_attribute("token_id", token_id)
+        .add_attribute("spender", spender))
+}
+
+/// Allows the operator to transfer and approve all lock tokens of the sender until `expires`.
+fn approve_all(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    operator: String,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    let operator = deps.api.addr_validate(&operator)?;
+
+    let expires = expires.unwrap_or_default();
+    if expires.is_expired(&env.block) {
+        return Err(ContractError::ApprovalExpired {});
+    }
+
+    OPERATORS.save(deps.storage, (info.sender, operator.clone()), &expires)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "veamp/approve_all")
+        .add_attribute("operator", operator))
+}
+
+/// Removes an operator of the sender.
+fn revoke_all(
+    deps: DepsMut,
+    info: MessageInfo,
+    operator: String,
+) -> Result<Response, ContractError> {
+    let operator = deps.api.addr_validate(&operator)?;
+    OPERATORS.remove(deps.storage, (info.sender, operator.clone()));
+
+    Ok(Response::default()
+        .add_attribute("action", "veamp/revoke_all")
+        .add_attribute("operator", operator))
+}
+
+/// Merges the lock token `token_id` into the lock token `into_token_id`. Both lock tokens need to
+/// be owned by the sender. The merged lock token ends with the later end of both and its voting
+/// power is recalculated with a [`checkpoint`].
+fn merge(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+    into_token_id: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    assert_not_decommissioned(&config)?;
+
+    let sender = info.sender;
+    assert_blacklist(deps.storage, &sender)?;
+    let (id, token) = load_owned_lock_token(deps.storage, &token_id, &sender)?;
+    let (into_id, mut into_token) = load_owned_lock_token(deps.storage, &into_token_id, &sender)?;
+
+    if id == into_id {
+        return Err(ContractError::MergeSameToken {});
+    }
+
+    if token.permanent {
+        return Err(ContractError::LockIsPermanent {});
+    }
+
+    let cur_period = get_period(env.block.time.seconds())?;
+    if !into_token.permanent && into_token.end <= cur_period {
+        return Err(ContractError::LockExpired {});
+    }
+
+    // The merged lock token is removed together with its voting power
+    let point = fetch_token_point(deps.storage, id, cur_period)?;
+    TOKEN_HISTORY.save(
+        deps.storage,
+        (id, cur_period),
+        &Point {
+            power: Uint128::zero(),
+            start: cur_period,
+            end: cur_period,
+            slope: Uint128::zero(),
+            fixed: Uint128::zero(),
+        },
+    )?;
+    checkpoint_owner(deps.storage, env.clone(), &sender, point.as_ref(), None)?;
+    burn_lock_token(deps.storage, &sender, id)?;
+
+    let new_end = if !into_token.permanent && token.end > into_token.end {
+        into_token.end = token.end;
+        Some(token.end)
+    } else {
+        None
+    };
+    into_token.amount += token.amount;
+    LOCK_TOKENS.save(deps.storage, into_id, &into_token)?;
+    checkpoint(deps.storage, env.clone(), into_id, Some(token.amount), new_end)?;
+    // the amount of the sender's lock is unchanged, only the end needs an update
+    add_to_lock(deps.storage, env.block.height, into_id, Uint128::zero())?;
+
+    let lock_info = get_user_lock_info(deps.as_ref(), &env, sender.to_string());
+    let mut msgs = get_push_update_msgs(config.clone(), sender.clone(), lock_info)?;
+    msgs.extend(get_delegatee_push_update_msgs(deps.as_ref(), &env, config, &sender)?);
+
+    Ok(Response::default()
+        .add_attribute("action", "veamp/merge")
+        .add_attribute("token_id", token_id)
+        .add_attribute("into_token_id", into_token_id)
+        .add_attribute("amount", into_token.amount)
+        .add_messages(msgs))
+}
+
+/// Splits `amount` of a lock token into a new lock token of the sender with the same end. The
+/// voting power of the lock token is split proportionally, so the sender's voting power is not
+/// changed.
+fn split(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let (id, mut token) = load_owned_lock_token(deps.storage, &token_id, &info.sender)?;
+
+    if amount.is_zero() || amount >= token.amount {
+        return Err(ContractError::InvalidSplitAmount {});
+    }
+
+    let cur_period = get_period(env.block.time.seconds())?;
+    let point = fetch_token_point(deps.storage, id, cur_period)?
+        .ok_or(ContractError::CheckpointInitializationFailed {})?;
+
+    // share of the voting power that belongs to the new lock token
+    let slope = point.slope.multiply_ratio(amount, token.amount);
+    let power =
+        slope.checked_mul(Uint128::from(point.end.saturating_sub(cur_period)))?.min(point.power);
+    let fixed = point.fixed.multiply_ratio(amount, token.amount);
+
+    TOKEN_HISTORY.save(
+        deps.storage,
+        (id, cur_period),
+        &Point {
+            power: point.power - power,
+            slope: point.slope - slope,
+            fixed: point.fixed - fixed,
+            ..point.clone()
+        },
+    )?;
+
+    let new_token = LockToken {
+        amount,
+        approvals: vec![],
+        ..token.clone()
+    };
+    token.amount -= amount;
+    LOCK_TOKENS.save(deps.storage, id, &token)?;
+
+    let new_id = mint_lock_token(deps.storage, &new_token)?;
+    TOKEN_HISTORY.save(
+        deps.storage,
+        (new_id, cur_period),
+        &Point {
+            power,
+            slope,
+            fixed,
+            ..point
+        },
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("action", "veamp/split")
+        .add_attribute("token_id", token_id)
+        .add_attribute("new_token_id", new_id.to_string())
+        .add_attribute("amount", amount))
+}
+
+/// Makes a lock token of the sender permanent. The decaying voting power is replaced by the
+/// maximum boost of the lock token's amount, which is stored as fixed voting power, and the
+/// scheduled slope change of the lock token is cancelled.
+///
+/// * **token_id** lock token to make permanent. Only required if the sender owns multiple lock tokens.
+fn lock_permanent(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    assert_not_decommissioned(&config)?;
+
+    let user = info.sender;
+    assert_blacklist(deps.storage, &user)?;
+    let (id, mut token) = resolve_lock_token(deps.storage, &user, token_id)?;
+
+    if token.permanent {
+        return Err(ContractError::LockIsPermanent {});
+    }
+
+    let cur_period = get_period(env.block.time.seconds())?;
+    if token.end <= cur_period {
+        return Err(ContractError::LockExpired {});
+    }
+
+    let point = fetch_token_point(deps.storage, id, cur_period)?
+        .ok_or(ContractError::CheckpointInitializationFailed {})?;
+    let new_point = Point {
+        power: Uint128::zero(),
+        start: cur_period,
+        end: cur_period,
+        slope: Uint128::zero(),
+        fixed: point.fixed + calc_permanent_boost(token.amount)?,
+    };
+    TOKEN_HISTORY.save(deps.storage, (id, cur_period), &new_point)?;
+    checkpoint_owner(deps.storage, env.clone(), &user, Some(&point), Some(&new_point))?;
+
+    token.permanent = true;
+    token.end = cur_period + get_periods_count(MAX_LOCK_TIME);
+    token.last_extend_lock_period = cur_period;
+    LOCK_TOKENS.save(deps.storage, id, &token)?;
+    add_to_lock(deps.storage, env.block.height, id, Uint128::zero())?;
+
+    let lock_info = get_user_lock_info(deps.as_ref(), &env, user.to_string())?;
+
+    Ok(Response::default()
+        .add_attribute("action", "veamp/lock_permanent")
+        .add_attribute("fixed_power", lock_info.fixed_amount.to_string())
+        .add_attribute("token_id", id.to_string())
+        .add_messages(get_push_update_msgs(config.clone(), user.clone(), Ok(lock_info))?)
+        .add_messages(get_delegatee_push_update_msgs(deps.as_ref(), &env, config, &user)?))
+}
+
+/// Ends the permanent lock of a lock token of the sender. The boost is removed from the fixed
+/// voting power and the voting power starts to decay over the maximum lock time.
+///
+/// * **token_id** lock token to unlock. Only required if the sender owns multiple lock tokens.
+fn unlock_permanent(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let user = info.sender;
+    assert_blacklist(deps.storage, &user)?;
+    let (id, mut token) = resolve_lock_token(deps.storage, &user, token_id)?;
+
+    if !token.permanent {
+        return Err(ContractError::LockIsNotPermanent {});
+    }
+
+    let cur_period = get_period(env.block.time.seconds())?;
+    let point = fetch_token_point(deps.storage, id, cur_period)?;
+
+    let periods = get_periods_count(MAX_LOCK_TIME);
+    let end = cur_period + periods;
+    let mut voting_power = calc_coefficient(periods).checked_mul_uint(token.amount)?;
+    let slope = adjust_vp_and_slope(&mut voting_power, periods)?;
+    let new_point = Point {
+        power: voting_power,
+        start: cur_period,
+        end,
+        slope,
+        fixed: token.amount,
+    };
+    TOKEN_HISTORY.save(deps.storage, (id, cur_period), &new_point)?;
+    checkpoint_owner(deps.storage, env.clone(), &user, point.as_ref(), Some(&new_point))?;
+
+    token.permanent = false;
+    token.end = end;
+    token.last_extend_lock_period = cur_period;
+    LOCK_TOKENS.save(deps.storage, id, &token)?;
+    add_to_lock(deps.storage, env.block.height, id, Uint128::zero())?;
+
+    let lock_info = get_user_lock_info(deps.as_ref(), &env, user.to_string())?;
+
+    Ok(Response::default()
+        .add_attribute("action", "veamp/unlock_permanent")
+        .add_attribute("voting_power", lock_info.voting_power.to_string())
+        .add_attribute("fixed_power", lock_info.fixed_amount.to_string())
+        .add_attribute("lock_end", lock_info.end.to_string())
+        .add_attribute("token_id", id.to_string())
+        .add_messages(get_push_update_msgs(config.clone(), user.clone(), Ok(lock_info))?)
+        .add_messages(get_delegatee_push_update_msgs(deps.as_ref(), &env, config, &user)?))
+}
+
+/// Converts the next `limit` locks into lock tokens after a migration. Every lock token receives
+/// the current voting power of its lock, the checkpoint of the owner is normalized and the end of
+/// the lock becomes a scheduled slope change of the owner.
+fn migrate_locks(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let start_after = LOCK_MIGRATION
+        .may_load(deps.storage)?
+        .ok_or_else(|| ContractError::MigrationError("locks are already converted".to_string()))?;
+    let limit = limit.unwrap_or(MAX_MIGRATION_LIMIT).min(MAX_MIGRATION_LIMIT) as usize;
+
+    let locks = LOCKED
+        .range(deps.storage, start_after.map(Bound::exclusive), None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let cur_period = get_period(env.block.time.seconds())?;
+    let blacklist = BLACKLIST.load(deps.storage)?;
+    for (owner, lock) in locks.iter().filter(|(_, lock)| !lock.amount.is_zero()) {
+        convert_lock(deps.storage, &env, cur_period, owner, lock, blacklist.contains(owner))?;
+    }
+
+    let finished = locks.len() < limit;
+    if finished {
+        LOCK_MIGRATION.remove(deps.storage);
+    } else {
+        LOCK_MIGRATION.save(deps.storage, &locks.last().map(|(owner, _)| owner.clone()))?;
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "veamp/migrate_locks")
+        .add_attribute("converted", locks.len().to_string())
+        .add_attribute("finished", finished.to_string()))
+}
+
+/// Mints the lock token of a lock. Blacklisted owners have no voting power, so their lock token
+/// receives the voting power of the lock without changing the owner's checkpoints.
+fn convert_lock(
+    storage: &mut dyn Storage,
+    env: &Env,
+    cur_period: u64,
+    owner: &Addr,
+    lock: &Lock,
+    is_blacklisted: bool,
+) -> Result<(), ContractError> {
+    let id = mint_lock_token(
+        storage,
+        &LockToken {
+            owner: owner.clone(),
+            amount: lock.amount,
+            start: lock.start,
+            end: lock.end,
+            last_extend_lock_period: lock.last_extend_lock_period,
+            permanent: lock.permanent,
+            approvals: vec![],
+        },
+    )?;
+
+    let point = if !is_blacklisted {
+        let (_, point) = fetch_last_checkpoint(storage, owner, cur_period)?
+            .ok_or(ContractError::CheckpointInitializationFailed {})?;
+        // an expired lock keeps no slope, so that it is not applied again to the owner
+        let point = calc_token_point(&point, cur_period);
+        HISTORY.save(storage, (owner.clone(), cur_period), &point)?;
+        if point.end > cur_period {
+            schedule_user_slope_change(storage, owner, point.slope, point.end)?;
+        }
+        point
+    } else if lock.permanent {
+        Point {
+            power: Uint128::zero(),
+            start: cur_period,
+            end: cur_period,
+            slope: Uint128::zero(),
+            fixed: lock.amount + calc_permanent_boost(lock.amount)?,
+        }
+    } else if lock.end > cur_period {
+        let dt = lock.end - cur_period;
+        let mut voting_power = calc_coefficient(dt).checked_mul_uint(lock.amount)?;
+        let slope = adjust_vp_and_slope(&mut voting_power, dt)?;
+        Point {
+            power: voting_power,
+            start: cur_period,
+            end: lock.end,
+            slope,
+            fixed: lock.amount,
+        }
+    } else {
+        Point {
+            power: Uint128::zero(),
+            start: cur_period,
+            end: lock.end,
+            slope: Uint128::zero(),
+            fixed: lock.amount,
+        }
+    };
+    TOKEN_HISTORY.save(storage, (id, cur_period), &point)?;
+
+    LOCKED.save(
+        storage,
+        owner.clone(),
+        &Lock {
+            permanent: false,
+            ..lock.clone()
+        },
+        env.block.height,
+    )?;
+    Ok(())
+}
+
+/// Updates contracts' guardian address.
+fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    new_guardian: Option<String>,
+    push_update_contracts: Option<Vec<String>>,
+    decommissioned: Option<bool>,
+    early_withdraw_penalty: Option<Decimal>,
+    penalty_recipient: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut cfg = CONFIG.load(deps.storage)?;
+
+    if cfg.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(new_guardian) = new_guardian {
+        cfg.guardian_addr = Some(deps.api.addr_validate(&new_guardian)?);
+    }
+
+    if let Some(decommissioned) = decommissioned {
+        if decommissioned {
+            cfg.decommissioned = Some(true);
+        }
+    }
+
+    if let Some(push_update_contracts) = push_update_contracts {
+        cfg.push_update_contracts = push_update_contracts
+            .iter()
+            .map(|c| deps.api.addr_validate(c))
+            .collect::<StdResult<Vec<_>>>()?;
+    }
+
+    if let Some(early_withdraw_penalty) = early_withdraw_penalty {
+        assert_early_withdraw_penalty(early_withdraw_penalty)?;
+        // a zero penalty disables early withdrawals
+        cfg.early_withdraw_penalty = Some(early_withdraw_penalty).filter(|p| !p.is_zero());
+    }
+
+    if let Some(penalty_recipient) = penalty_recipient {
+        cfg.penalty_recipient = Some(deps.api.addr_validate(&penalty_recipient)?);
+    }
+
+    CONFIG.save(deps.storage, &cfg)?;
+
+    Ok(Response::default().add_attribute("action", "veamp/execute_update_config"))
+}
+
+/// Expose available contract queries.
+///
+/// ## Queries
+/// * **QueryMsg::TotalVotingPower {}** Fetch the total voting power (vAMP supply) at the current block.
+///
+/// * **QueryMsg::UserVotingPower { user }** Fetch the user's voting power (vAMP balance) at the current block.
+///
+/// * **QueryMsg::TotalVotingPowerAt { time }** Fetch the total voting power (vAMP supply) at a specified timestamp.
+///
+/// * **QueryMsg::UserVotingPowerAt { time }** Fetch the user's voting power (vAMP balance) at a specified timestamp.
+///
+/// * **QueryMsg::LockInfo { user }** Fetch a user's lock information.
+///
+/// * **QueryMsg::SimulateEarlyWithdraw { user, token_id }** Fetch the penalty of an early withdrawal of a user's lock token.
+///
+/// * **QueryMsg::Delegation { user }** Fetch a user's active delegation.
+///
+/// * **QueryMsg::OwnerOf { token_id, include_expired }** Fetch the owner and the approvals of a lock token.
+///
+/// * **QueryMsg::Approvals { token_id, include_expired }** Fetch the approvals of a lock token.
+///
+/// * **QueryMsg::AllOperators { owner, include_expired, start_after, limit }** Fetch the operators of an owner.
+///
+/// * **QueryMsg::NftInfo { token_id }** Fetch the lock and the voting power of a lock token.
+///
+/// * **QueryMsg::Tokens { owner, start_after, limit }** Fetch the lock tokens of an owner.
+///
+/// * **QueryMsg::AllTokens { start_after, limit }** Fetch all lock tokens.
+///
+/// * **QueryMsg::NumTokens {}** Fetch the amount of lock tokens.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+    match msg {
+        QueryMsg::CheckVotersAreBlacklisted {
+            voters,
+        } => Ok(to_json_binary(&check_voters_are_blacklisted(deps, voters)?)?),
+        QueryMsg::BlacklistedVoters {
+            start_after,
+            limit,
+        } => Ok(to_json_binary(&get_blacklisted_voters(deps, start_after, limit)?)?),
+        QueryMsg::TotalVamp {} => Ok(to_json_binary(&get_total_vamp(deps, env, None)?)?),
+        QueryMsg::UserVamp {
+            user,
+        } => Ok(to_json_binary(&get_user_vamp(deps, env, user, None)?)?),
+        QueryMsg::TotalVampAt {
+            time,
+        } => Ok(to_json_binary(&get_total_vamp(deps, env, Some(time))?)?),
+        QueryMsg::TotalVampAtPeriod {
+            period,
+        } => Ok(to_json_binary(&get_total_vamp_at_period(deps, env, period)?)?),
+        QueryMsg::UserVampAt {
+            user,
+            time,
+        } => Ok(to_json_binary(&get_user_vamp(deps, env, user, Some(time))?)?),
+        QueryMsg::UserVampAtPeriod {
+            user,
+            period,
+        } => Ok(to_json_binary(&get_user_vamp_at_period(deps, user, period)?)?),
+        QueryMsg::LockInfo {
+            user,
+        } => Ok(to_json_binary(&get_user_lock_info(deps, &env, user)?)?),
+        QueryMsg::UserDepositAtHeight {
+            user,
+            height,
+        } => Ok(to_json_binary(&get_user_deposit_at_height(deps, user, height)?)?),
+        QueryMsg::Config {} => {
+            let config = CONFIG.load(deps.storage)?;
+            Ok(to_json_binary(&ConfigResponse {
+                owner: config.owner.to_string(),
+                guardian_addr: config.guardian_addr,
                 deposit_token_addr: config.deposit_denom.to_string(),
                 logo_urls_whitelist: config.logo_urls_whitelist,
                 decommissioned: config.decommissioned.unwrap_or_default(),
+                early_withdraw_penalty: config.early_withdraw_penalty,
+                penalty_recipient: config.penalty_recipient,
                 push_update_contracts: config
                     .push_update_contracts
                     .into_iter()
@@ -982,6 +1889,67 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractErro
         QueryMsg::TokenInfo {} => Ok(to_json_binary(&query_token_info(deps, env)?)?),
         QueryMsg::MarketingInfo {} => Ok(to_json_binary(&query_marketing_info(deps)?)?),
         QueryMsg::DownloadLogo {} => Ok(to_json_binary(&query_download_logo(deps)?)?),
+        QueryMsg::SimulateEarlyWithdraw {
+            user,
+            token_id,
+        } => Ok(to_json_binary(&simulate_early_withdraw(deps, &env, user, token_id)?)?),
+        QueryMsg::Delegation {
+            user,
+        } => Ok(to_json_binary(&get_user_delegation(deps, &env, user)?)?),
+        QueryMsg::OwnerOf {
+            token_id,
+            include_expired,
+        } => {
+            let (_, token) = load_lock_token(deps.storage, &token_id)?;
+            Ok(to_json_binary(&OwnerOfResponse {
+                owner: token.owner.to_string(),
+                approvals: humanize_approvals(&env, &token, include_expired.unwrap_or_default()),
+            })?)
+        },
+        QueryMsg::Approvals {
+            token_id,
+            include_expired,
+        } => {
+            let (_, token) = load_lock_token(deps.storage, &token_id)?;
+            Ok(to_json_binary(&ApprovalsResponse {
+                approvals: humanize_approvals(&env, &token, include_expired.unwrap_or_default()),
+            })?)
+        },
+        QueryMsg::AllOperators {
+            owner,
+            include_expired,
+            start_after,
+            limit,
+        } => Ok(to_json_binary(&get_operators(
+            deps,
+            &env,
+            owner,
+            include_expired.unwrap_or_default(),
+            start_after,
+            limit,
+        )?)?),
+        QueryMsg::NftInfo {
+            token_id,
+        } => Ok(to_json_binary(&get_lock_token_info(deps, &env, token_id)?)?),
+        QueryMsg::Tokens {
+            owner,
+            start_after,
+            limit,
+        } => Ok(to_json_binary(&get_owner_tokens(deps, owner, start_after, limit)?)?),
+        QueryMsg::AllTokens {
+            start_after,
+            limit,
+        } => Ok(to_json_binary(&get_all_tokens(deps, start_after, limit)?)?),
+        QueryMsg::NumTokens {} => Ok(to_json_binary(&NumTokensResponse {
+            count: TOKEN_COUNT.may_load(deps.storage)?.unwrap_or_default(),
+        })?),
+        QueryMsg::ContractInfo {} => {
+            let info = TOKEN_INFO.load(deps.storage)?;
+            Ok(to_json_binary(&NftContractInfoResponse {
+                name: info.name,
+                symbol: info.symbol,
+            })?)
+        },
     }
 }
 
@@ -1042,7 +2010,7 @@ pub fn get_blacklisted_voters(
     Ok(black_list[start_index..end_index].to_vec())
 }
 
-/// Return a user's lock information.
+/// Return a user's lock information, including the delegations from and to the user.
 ///
 /// * **user** user for which we return lock information.
 fn get_user_lock_info(
@@ -1051,40 +2019,284 @@ fn get_user_lock_info(
     user: String,
 ) -> Result<LockInfoResponse, ContractError> {
     let addr = deps.api.addr_validate(&user)?;
+    let cur_period = get_period(env.block.time.seconds())?;
+
+    let mut delegated = vec![];
+    // blacklisted addresses can't use the voting power delegated to them
+    if !BLACKLIST.load(deps.storage)?.contains(&addr) {
+        for (delegator, delegation) in fetch_active_delegators(deps.storage, &addr, cur_period)? {
+            if let Some(lock_info) = get_own_lock_info(deps, &delegator, cur_period)? {
+                delegated.push(DelegatedVotingPower {
+                    delegator: delegator.to_string(),
+                    lock_info: lock_info.share(delegation.bps),
+                });
+            }
+        }
+    }
+
+    let lock_info = match get_own_lock_info(deps, &addr, cur_period)? {
+        Some(lock_info) => lock_info,
+        // users without a lock can still vote with the voting power delegated to them
+        None if !delegated.is_empty() => LockInfoResponse {
+            amount: Uint128::zero(),
+            coefficient: Decimal::zero(),
+            start: cur_period,
+            end: cur_period,
+            slope: Uint128::zero(),
+            fixed_amount: Uint128::zero(),
+            voting_power: Uint128::zero(),
+            permanent: false,
+            delegation: None,
+            delegated: vec![],
+            ends: vec![],
+        },
+        None => return Err(ContractError::UserNotFound(addr.to_string())),
+    };
+
+    Ok(LockInfoResponse {
+        delegation: fetch_active_delegation(deps.storage, &addr, cur_period)?
+            .map(|delegation| delegation.into_response()),
+        delegated,
+        ..lock_info
+    })
+}
+
+/// Return a user's own lock information, without delegations.
+///
+/// * **addr** user for which we return lock information.
+///
+/// * **cur_period** the current period.
+fn get_own_lock_info(
+    deps: Deps,
+    addr: &Addr,
+    cur_period: u64,
+) -> Result<Option<LockInfoResponse>, ContractError> {
     if let Some(lock) = LOCKED.may_load(deps.storage, addr.clone())? {
-        let cur_period = get_period(env.block.time.seconds())?;
-
-        let last_checkpoint = fetch_last_checkpoint(deps.storage, &addr, cur_period)?;
-        // The voting power point at the specified `time` was found
-        let (voting_power, slope, fixed_amount) =
-            if let Some(point) = last_checkpoint.map(|(_, point)| point) {
-                if point.start == cur_period {
-                    (point.power, point.slope, point.fixed)
-                } else {
-                    // The point before the intended period was found, thus we can calculate the user's voting power for the period we want
-                    (calc_voting_power(&point, cur_period), point.slope, point.fixed)
-                }
-            } else {
-                (Uint128::zero(), Uint128::zero(), Uint128::zero())
-            };
+        let (voting_power, slope, fixed_amount) = fetch_user_point(deps.storage, addr, cur_period)?
+            .map(|point| (point.power, point.slope, point.fixed))
+            .unwrap_or_default();
 
-        let coefficient = calc_coefficient(lock.end - lock.last_extend_lock_period);
+        // the decaying voting power of every end of the user's lock tokens
+        let ends: Vec<_> = fetch_future_user_slope_changes(deps.storage, addr, cur_period)?
+            .into_iter()
+            .map(|(end, slope)| LockEndResponse {
+                end,
+                voting_power: slope * Uint128::from(end - cur_period),
+                slope,
+            })
+            .collect();
+
+        // a lock without decaying voting power, but with a boost only consists of permanent
+        // lock tokens, which are always at the maximum lock time
+        let permanent = ends.is_empty() && fixed_amount > lock.amount;
+        let (coefficient, end) = if permanent {
+            let max_periods = get_periods_count(MAX_LOCK_TIME);
+            (calc_coefficient(max_periods), cur_period + max_periods)
+        } else {
+            let end = ends.last().map(|end| end.end).unwrap_or(lock.end);
+            (calc_coefficient(end.saturating_sub(lock.last_extend_lock_period)), end)
+        };
 
         let resp = LockInfoResponse {
             amount: lock.amount,
             coefficient,
             start: lock.start,
-            end: lock.end,
+            end,
             voting_power,
             fixed_amount,
             slope,
+            permanent,
+            delegation: None,
+            delegated: vec![],
+            ends,
         };
-        Ok(resp)
+        Ok(Some(resp))
     } else {
-        Err(ContractError::UserNotFound(addr.to_string()))
+        Ok(None)
     }
 }
 
+/// Return the returned amount and the penalty of an early withdrawal of a user's lock token.
+///
+/// * **user** user for which we simulate the early withdrawal.
+///
+/// * **token_id** lock token to withdraw. Only required if the user owns multiple lock tokens.
+fn simulate_early_withdraw(
+    deps: Deps,
+    env: &Env,
+    user: String,
+    token_id: Option<String>,
+) -> Result<EarlyWithdrawResponse, ContractError> {
+    let addr = deps.api.addr_validate(&user)?;
+    let (_, token) = resolve_lock_token(deps.storage, &addr, token_id)?;
+    let config = CONFIG.load(deps.storage)?;
+    let cur_period = get_period(env.block.time.seconds())?;
+
+    let penalty = calc_withdraw_penalty(&config, &token, cur_period)?;
+    Ok(EarlyWithdrawResponse {
+        amount: token.amount - penalty,
+        penalty,
+    })
+}
+
+/// Return a user's active delegation.
+///
+/// * **user** user for which we return the delegation.
+fn get_user_delegation(
+    deps: Deps,
+    env: &Env,
+    user: String,
+) -> StdResult<Option<DelegationResponse>> {
+    let addr = deps.api.addr_validate(&user)?;
+    let cur_period = get_period(env.block.time.seconds())?;
+    Ok(fetch_active_delegation(deps.storage, &addr, cur_period)?
+        .map(|delegation| delegation.into_response()))
+}
+
+/// Returns the lock of a lock token and its current voting power.
+///
+/// * **token_id** the lock token to return.
+fn get_lock_token_info(
+    deps: Deps,
+    env: &Env,
+    token_id: String,
+) -> Result<NftInfoResponse, ContractError> {
+    let (id, token) = load_lock_token(deps.storage, &token_id)?;
+    let cur_period = get_period(env.block.time.seconds())?;
+    let point = fetch_token_point(deps.storage, id, cur_period)?;
+
+    Ok(NftInfoResponse {
+        token_uri: None,
+        extension: LockTokenResponse {
+            amount: token.amount,
+            start: token.start,
+            // permanent lock tokens are always at the maximum lock time
+            end: if token.permanent {
+                cur_period + get_periods_count(MAX_LOCK_TIME)
+            } else {
+                token.end
+            },
+            permanent: token.permanent,
+            voting_power: point.as_ref().map(|point| point.power).unwrap_or_default(),
+            fixed_amount: point.map(|point| point.fixed).unwrap_or_default(),
+        },
+    })
+}
+
+/// Returns the approvals of a lock token.
+///
+/// * **include_expired** whether expired approvals are returned.
+fn humanize_approvals(env: &Env, token: &LockToken, include_expired: bool) -> Vec<Approval> {
+    token
+        .approvals
+        .iter()
+        .filter(|approval| include_expired || !approval.expires.is_expired(&env.block))
+        .map(|approval| Approval {
+            spender: approval.spender.to_string(),
+            expires: approval.expires,
+        })
+        .collect()
+}
+
+/// Returns the operators of an owner.
+///
+/// * **include_expired** whether expired operators are returned.
+///
+/// * **start_after** operator after which the operators are returned.
+///
+/// * **limit** max amount of operators to return.
+fn get_operators(
+    deps: Deps,
+    env: &Env,
+    owner: String,
+    include_expired: bool,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<OperatorsResponse, ContractError> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = addr_opt_validate(deps.api, &start_after)?.map(Bound::exclusive);
+
+    let operators = OPERATORS
+        .prefix(owner)
+        .range(deps.storage, start, None, Order::Ascending)
+        .filter(|item| {
+            include_expired
+                || item.as_ref().map_or(true, |(_, expires)| !expires.is_expired(&env.block))
+        })
+        .take(limit)
+        .map(|item| {
+            item.map(|(spender, expires)| Approval {
+                spender: spender.to_string(),
+                expires,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(OperatorsResponse {
+        operators,
+    })
+}
+
+/// Returns the lock tokens of an owner.
+///
+/// * **start_after** token id after which the tokens are returned.
+///
+/// * **limit** max amount of tokens to return.
+fn get_owner_tokens(
+    deps: Deps,
+    owner: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<TokensResponse, ContractError> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = parse_start_after(start_after)?.map(Bound::exclusive);
+
+    let tokens = OWNER_TOKENS
+        .prefix(owner)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|id| id.map(|id| id.to_string()))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(TokensResponse {
+        tokens,
+    })
+}
+
+/// Returns all lock tokens.
+///
+/// * **start_after** token id after which the tokens are returned.
+///
+/// * **limit** max amount of tokens to return.
+fn get_all_tokens(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<TokensResponse, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = parse_start_after(start_after)?.map(Bound::exclusive);
+
+    let tokens = LOCK_TOKENS
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|id| id.map(|id| id.to_string()))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(TokensResponse {
+        tokens,
+    })
+}
+
+fn parse_start_after(start_after: Option<String>) -> Result<Option<u64>, ContractError> {
+    start_after
+        .map(|token_id| {
+            token_id.parse::<u64>().map_err(|_| ContractError::LockTokenDoesNotExist(token_id))
+        })
+        .transpose()
+}
+
 /// Return a user's staked ampLP amount at a given block height.
 ///
 /// * **user** user for which we return lock information.
@@ -1116,7 +2328,8 @@ fn get_user_vamp(
     get_user_vamp_at_period(deps, user, period)
 }
 
-/// Calculates a user's voting power at a given period number.
+/// Calculates a user's voting power at a given period number. The delegated share of the user's
+/// own voting power is counted for the delegatee.
 ///
 /// * **user** user/staker for which we fetch the current voting power (vAMP balance).
 ///
@@ -1127,28 +2340,35 @@ fn get_user_vamp_at_period(
     period: u64,
 ) -> StdResult<VotingPowerResponse> {
     let user = deps.api.addr_validate(&user)?;
-    let last_checkpoint = fetch_last_checkpoint(deps.storage, &user, period)?;
-
-    if let Some(point) = last_checkpoint.map(|(_, point)| point) {
-        // The voting power point at the specified `time` was found
-        let voting_power = if point.start == period {
-            point.power + point.fixed
-        } else if point.end <= period {
-            // the current period is after the voting end -> get default end power.
-            point.fixed
-        } else {
-            // The point before the intended period was found, thus we can calculate the user's voting power for the period we want
-            calc_voting_power(&point, period) + point.fixed
-        };
-        Ok(VotingPowerResponse {
-            vamp: voting_power,
-        })
-    } else {
-        // User not found
-        Ok(VotingPowerResponse {
-            vamp: Uint128::zero(),
-        })
+    let own_vamp = get_own_vamp_at_period(deps, &user, period)?;
+
+    let mut vamp = match fetch_active_delegation(deps.storage, &user, period)? {
+        Some(delegation) => own_vamp - delegation.bps * own_vamp,
+        None => own_vamp,
+    };
+
+    // blacklisted addresses can't use the voting power delegated to them
+    if !BLACKLIST.load(deps.storage)?.contains(&user) {
+        for (delegator, delegation) in fetch_active_delegators(deps.storage, &user, period)? {
+            vamp += delegation.bps * get_own_vamp_at_period(deps, &delegator, period)?;
+        }
     }
+
+    Ok(VotingPowerResponse {
+        vamp,
+    })
+}
+
+/// Calculates a user's own voting power at a given period number, without delegations.
+///
+/// * **user** user/staker for which we fetch the voting power.
+///
+/// * **period** period number at which to fetch the voting power.
+fn get_own_vamp_at_period(deps: Deps, user: &Addr, period: u64) -> StdResult<Uint128> {
+    // The scheduled slope changes of the user's lock tokens are applied up to the period
+    Ok(fetch_user_point(deps.storage, user, period)?
+        .map(|point| point.power + point.fixed)
+        .unwrap_or_default())
 }
 
 /// Calculates a user's voting power at the current block.
@@ -1235,6 +2455,13 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, C
         )));
     }
 
+    // every existing lock becomes a lock token, which is done in pages with ExecuteMsg::MigrateLocks
+    if LAST_TOKEN_ID.may_load(deps.storage)?.is_none() {
+        LAST_TOKEN_ID.save(deps.storage, &0)?;
+        TOKEN_COUNT.save(deps.storage, &0)?;
+        LOCK_MIGRATION.save(deps.storage, &None)?;
+    }
+
     Ok(Response::new()
         .add_attribute("previous_contract_name", &contract_version.contract)
         .add_attribute("previous_contract_version", &contract_version.version)
@@ -74,6 +74,60 @@ pub enum ContractError {
     #[error("Checkpoint initialization error")]
     CheckpointInitializationFailed {},
 
+    #[error("Cannot delegate voting power to yourself")]
+    DelegateToSelf {},
+
+    #[error("Delegated share must be between 1 and 10000 bps")]
+    InvalidDelegationShare {},
+
+    #[error("The delegation must expire in a future period")]
+    DelegationExpiryInPast {},
+
+    #[error("Delegation does not exist")]
+    DelegationDoesNotExist {},
+
+    #[error("The delegation has not yet expired")]
+    DelegationNotExpired {},
+
+    #[error("The delegatee already has the maximum amount of {0} delegators")]
+    TooManyDelegators(usize),
+
+    #[error("Lock token {0} does not exist")]
+    LockTokenDoesNotExist(String),
+
+    #[error("Lock token {0} is not owned by the sender")]
+    LockTokenNotOwned(String),
+
+    #[error("Cannot merge a lock token into itself")]
+    MergeSameToken {},
+
+    #[error("Split amount must be between zero and the lock token amount")]
+    InvalidSplitAmount {},
+
+    #[error("Cannot transfer a lock token to yourself")]
+    TransferToSelf {},
+
+    #[error("The approval is already expired")]
+    ApprovalExpired {},
+
+    #[error("Multiple lock tokens exist, the token id is required")]
+    LockTokenRequired {},
+
+    #[error("The lock is permanent")]
+    LockIsPermanent {},
+
+    #[error("The lock is not permanent")]
+    LockIsNotPermanent {},
+
+    #[error("Early withdrawals are disabled")]
+    EarlyWithdrawDisabled {},
+
+    #[error("Early withdraw penalty must be at most 100%")]
+    InvalidEarlyWithdrawPenalty {},
+
     #[error("Contract can't be migrated: {0}")]
     MigrationError(String),
+
+    #[error("The locks are being converted into lock tokens")]
+    MigrationInProgress {},
 }
@@ -4,8 +4,11 @@ use cosmwasm_schema::{
     schemars::JsonSchema,
     serde::{Deserialize, Serialize},
 };
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw20::Expiration;
 use cw_storage_plus::{Item, Map, SnapshotMap, Strategy};
+use eris::helpers::bps::BasicPoints;
+use eris::voting_escrow::DelegationResponse;
 
 /// This structure stores the main parameters for the voting escrow contract.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -23,6 +26,12 @@ pub struct Config {
     /// Address that can only blacklist vAMP stakers and remove their governance power
     #[serde(default)]
     pub decommissioned: Option<bool>,
+    /// Penalty of an early withdrawal at the maximum lock time. Early withdrawals are disabled if not set.
+    #[serde(default)]
+    pub early_withdraw_penalty: Option<Decimal>,
+    /// Receiver of the early withdrawal penalties
+    #[serde(default)]
+    pub penalty_recipient: Option<Addr>,
 }
 
 /// This structure stores points along the checkpoint history for every vAMP staker.
@@ -41,17 +50,71 @@ pub struct Point {
     pub fixed: Uint128,
 }
 
-/// This structure stores data about the lockup position for a specific vAMP staker.
+/// This structure stores data about the lockup position for a specific vAMP staker. It sums up
+/// all lock tokens of the staker.
 #[cw_serde]
 pub struct Lock {
     /// The total amount of ampLP tokens that were deposited in the vAMP position
     pub amount: Uint128,
     /// The start period when the lock was created
     pub start: u64,
-    /// The period when the lock position expires
+    /// The latest period when a lock token of the staker expires
     pub end: u64,
     /// the last period when the lock's time was increased
     pub last_extend_lock_period: u64,
+    /// Only used by locks that were not yet converted into lock tokens
+    #[serde(default)]
+    pub permanent: bool,
+}
+
+/// This structure stores a lock token. Every lock token has its own end and its own voting power
+/// checkpoints in [`TOKEN_HISTORY`].
+#[cw_serde]
+pub struct LockToken {
+    /// The owner of the lock token
+    pub owner: Addr,
+    /// The amount of ampLP tokens that are part of the lock token
+    pub amount: Uint128,
+    /// The start period when the lock token was created
+    pub start: u64,
+    /// The period when the lock token expires
+    pub end: u64,
+    /// the last period when the lock token's time was increased
+    pub last_extend_lock_period: u64,
+    /// Permanent lock tokens keep the maximum lock time and do not decay
+    pub permanent: bool,
+    /// Addresses that can transfer the lock token
+    pub approvals: Vec<TokenApproval>,
+}
+
+/// This structure stores an approval to transfer a lock token.
+#[cw_serde]
+pub struct TokenApproval {
+    /// Address that can transfer the lock token
+    pub spender: Addr,
+    /// When the approval expires
+    pub expires: Expiration,
+}
+
+/// This structure stores a delegation of voting power to another address.
+#[cw_serde]
+pub struct Delegation {
+    /// The address receiving the voting power
+    pub delegatee: Addr,
+    /// Share of the voting power that is delegated
+    pub bps: BasicPoints,
+    /// The first period in which the delegation is no longer active
+    pub expires: u64,
+}
+
+impl Delegation {
+    pub fn into_response(self) -> DelegationResponse {
+        DelegationResponse {
+            delegatee: self.delegatee.to_string(),
+            bps: self.bps.u16(),
+            expires: self.expires,
+        }
+    }
 }
 
 /// Stores the contract config at the given key
@@ -65,6 +128,12 @@ pub const LOCKED: SnapshotMap<Addr, Lock> =
 /// Total voting power checkpoints are stored using a (contract_addr => period) key
 pub const HISTORY: Map<(Addr, u64), Point> = Map::new("history");
 
+/// Stores the checkpoint history for every lock token (token id => period)
+pub const TOKEN_HISTORY: Map<(u64, u64), Point> = Map::new("token_history");
+
+/// Scheduled slope changes of every staker's voting power (addr => period)
+pub const USER_SLOPE_CHANGES: Map<(Addr, u64), Uint128> = Map::new("user_slope_changes");
+
 /// Scheduled slope changes per period (week)
 pub const SLOPE_CHANGES: Map<u64, Uint128> = Map::new("slope_changes");
 
@@ -76,3 +145,28 @@ pub const OWNERSHIP_PROPOSAL: Item<OwnershipProposal> = Item::new("ownership_pro
 
 /// Contains blacklisted staker addresses
 pub const BLACKLIST: Item<Vec<Addr>> = Item::new("blacklist");
+
+/// Stores the delegation history of every delegator (addr => period). `None` marks a revoked delegation.
+pub const DELEGATIONS: Map<(Addr, u64), Option<Delegation>> = Map::new("delegations");
+
+/// Stores the delegators of every delegatee starting with a period (delegatee => period).
+/// Revoked and expired delegators are dropped from the next entry, older entries stay for past periods.
+pub const DELEGATORS: Map<(Addr, u64), Vec<Addr>> = Map::new("delegator_history");
+
+/// Stores all lock tokens (token id => lock token)
+pub const LOCK_TOKENS: Map<u64, LockToken> = Map::new("lock_tokens");
+
+/// Contains the lock tokens of every owner (owner => token id)
+pub const OWNER_TOKENS: Map<(Addr, u64), ()> = Map::new("owner_tokens");
+
+/// The last minted lock token id
+pub const LAST_TOKEN_ID: Item<u64> = Item::new("last_token_id");
+
+/// The amount of existing lock tokens
+pub const TOKEN_COUNT: Item<u64> = Item::new("token_count");
+
+/// Contains the operators of every owner (owner => operator)
+pub const OPERATORS: Map<(Addr, Addr), Expiration> = Map::new("operators");
+
+/// Exists while the locks are converted into lock tokens and contains the last converted owner
+pub const LOCK_MIGRATION: Item<Option<Addr>> = Item::new("lock_migration");
@@ -1,10 +1,17 @@
 use crate::{error::ContractError, state::Config};
-use eris::governance_helper::{MAX_LOCK_TIME, MIN_LOCK_PERIODS, WEEK};
+use eris::governance_helper::{get_periods_count, MAX_LOCK_TIME, MIN_LOCK_PERIODS, WEEK};
+use eris::helpers::slope::calc_coefficient;
+use eris::voting_escrow::MAX_DELEGATORS;
+use eris::DecimalCheckedOps;
 
-use cosmwasm_std::{Addr, Order, StdResult, Storage, Uint128};
+use cosmwasm_std::{Addr, BlockInfo, Decimal, Order, StdError, StdResult, Storage, Uint128};
 use cw_storage_plus::Bound;
 
-use crate::state::{Point, BLACKLIST, HISTORY, LAST_SLOPE_CHANGE, SLOPE_CHANGES};
+use crate::state::{
+    Delegation, Lock, LockToken, Point, BLACKLIST, DELEGATIONS, DELEGATORS, HISTORY,
+    LAST_SLOPE_CHANGE, LAST_TOKEN_ID, LOCKED, LOCK_MIGRATION, LOCK_TOKENS, OPERATORS, OWNER_TOKENS,
+    SLOPE_CHANGES, TOKEN_COUNT, TOKEN_HISTORY, USER_SLOPE_CHANGES,
+};
 
 /// Checks that a timestamp is within limits.
 pub(crate) fn assert_time_limits(time: u64) -> Result<(), ContractError> {
@@ -30,6 +37,15 @@ pub(crate) fn assert_not_decommissioned(config: &Config) -> Result<(), ContractE
     }
 }
 
+/// Checks that all locks were converted into lock tokens.
+pub(crate) fn assert_locks_migrated(storage: &dyn Storage) -> Result<(), ContractError> {
+    if LOCK_MIGRATION.may_load(storage)?.is_some() {
+        Err(ContractError::MigrationInProgress {})
+    } else {
+        Ok(())
+    }
+}
+
 /// Checks if the blacklist contains a specific address.
 pub(crate) fn assert_blacklist(storage: &dyn Storage, addr: &Addr) -> Result<(), ContractError> {
     let blacklist = BLACKLIST.load(storage)?;
@@ -49,6 +65,87 @@ pub(crate) fn calc_voting_power(point: &Point, period: u64) -> Uint128 {
     point.power.checked_sub(shift).unwrap_or_else(|_| Uint128::zero())
 }
 
+/// Returns the point of a lock token at the given period. Expired points have no voting power and
+/// no slope left.
+pub(crate) fn calc_token_point(point: &Point, period: u64) -> Point {
+    if point.end > period {
+        Point {
+            power: calc_voting_power(point, period),
+            start: period,
+            ..point.clone()
+        }
+    } else {
+        Point {
+            power: Uint128::zero(),
+            start: period,
+            slope: Uint128::zero(),
+            ..point.clone()
+        }
+    }
+}
+
+/// Recalculates a point at the given period by applying the scheduled slope changes since the point.
+pub(crate) fn calc_point_at_period(
+    mut point: Point,
+    scheduled_slope_changes: Vec<(u64, Uint128)>,
+    period: u64,
+) -> Point {
+    for (recalc_period, scheduled_change) in scheduled_slope_changes {
+        point = Point {
+            power: calc_voting_power(&point, recalc_period),
+            start: recalc_period,
+            slope: point.slope.saturating_sub(scheduled_change),
+            ..point
+        };
+    }
+    Point {
+        power: calc_voting_power(&point, period),
+        start: period,
+        ..point
+    }
+}
+
+/// Calculates the voting power boost of a permanent lock, which is the boost at the maximum lock time.
+pub(crate) fn calc_permanent_boost(amount: Uint128) -> Result<Uint128, ContractError> {
+    Ok(calc_coefficient(get_periods_count(MAX_LOCK_TIME)).checked_mul_uint(amount)?)
+}
+
+/// Calculates the penalty of withdrawing a lock in the current period. Only active locks pay a
+/// penalty, proportional to their remaining periods. Permanent locks pay the full penalty.
+pub(crate) fn calc_withdraw_penalty(
+    config: &Config,
+    lock: &LockToken,
+    cur_period: u64,
+) -> Result<Uint128, ContractError> {
+    let is_active = lock.end > cur_period || lock.permanent;
+    if !is_active || config.decommissioned.unwrap_or_default() {
+        return Ok(Uint128::zero());
+    }
+
+    let max_penalty = match (config.early_withdraw_penalty, &config.penalty_recipient) {
+        (Some(max_penalty), Some(_)) => max_penalty,
+        _ => return Err(ContractError::EarlyWithdrawDisabled {}),
+    };
+
+    let max_periods = get_periods_count(MAX_LOCK_TIME);
+    let remaining_periods = if lock.permanent {
+        max_periods
+    } else {
+        (lock.end - cur_period).min(max_periods)
+    };
+
+    Ok(max_penalty.checked_mul_uint(lock.amount.multiply_ratio(remaining_periods, max_periods))?)
+}
+
+/// Checks that the early withdraw penalty is at most 100%.
+pub(crate) fn assert_early_withdraw_penalty(penalty: Decimal) -> Result<(), ContractError> {
+    if penalty > Decimal::one() {
+        Err(ContractError::InvalidEarlyWithdrawPenalty {})
+    } else {
+        Ok(())
+    }
+}
+
 /// Fetches the last checkpoint in [`HISTORY`] for the given address.
 pub(crate) fn fetch_last_checkpoint(
     storage: &dyn Storage,
@@ -62,6 +159,289 @@ pub(crate) fn fetch_last_checkpoint(
         .transpose()
 }
 
+/// Fetches the checkpoint of a staker's voting power at the given period. The scheduled slope
+/// changes of the staker's lock tokens since the last checkpoint in [`HISTORY`] are applied.
+pub(crate) fn fetch_user_point(
+    storage: &dyn Storage,
+    addr: &Addr,
+    period: u64,
+) -> StdResult<Option<Point>> {
+    match fetch_last_checkpoint(storage, addr, period)? {
+        Some((_, point)) => {
+            let scheduled_slope_changes =
+                fetch_user_slope_changes(storage, addr, point.start, period)?;
+            Ok(Some(calc_point_at_period(point, scheduled_slope_changes, period)))
+        },
+        None => Ok(None),
+    }
+}
+
+/// Fetches the checkpoint of a lock token's voting power at the given period.
+pub(crate) fn fetch_token_point(
+    storage: &dyn Storage,
+    id: u64,
+    period: u64,
+) -> StdResult<Option<Point>> {
+    Ok(TOKEN_HISTORY
+        .prefix(id)
+        .range(storage, None, Some(Bound::inclusive(period)), Order::Descending)
+        .next()
+        .transpose()?
+        .map(|(_, point)| calc_token_point(&point, period)))
+}
+
+/// Fetches the last delegation in [`DELEGATIONS`] for the given address, including expired ones.
+pub(crate) fn fetch_last_delegation(
+    storage: &dyn Storage,
+    addr: &Addr,
+    period: u64,
+) -> StdResult<Option<Delegation>> {
+    Ok(DELEGATIONS
+        .prefix(addr.clone())
+        .range(storage, None, Some(Bound::inclusive(period)), Order::Descending)
+        .next()
+        .transpose()?
+        .and_then(|(_, delegation)| delegation))
+}
+
+/// Fetches the delegation of the given address that is active at the given period.
+pub(crate) fn fetch_active_delegation(
+    storage: &dyn Storage,
+    addr: &Addr,
+    period: u64,
+) -> StdResult<Option<Delegation>> {
+    Ok(fetch_last_delegation(storage, addr, period)?
+        .filter(|delegation| period < delegation.expires))
+}
+
+/// Fetches the delegators in [`DELEGATORS`] of the given delegatee at the given period. They can
+/// include expired delegations that were not removed yet.
+pub(crate) fn fetch_delegators(
+    storage: &dyn Storage,
+    delegatee: &Addr,
+    period: u64,
+) -> StdResult<Vec<Addr>> {
+    Ok(DELEGATORS
+        .prefix(delegatee.clone())
+        .range(storage, None, Some(Bound::inclusive(period)), Order::Descending)
+        .next()
+        .transpose()?
+        .map(|(_, delegators)| delegators)
+        .unwrap_or_default())
+}
+
+/// Fetches all delegations to the given delegatee that are active at the given period.
+pub(crate) fn fetch_active_delegators(
+    storage: &dyn Storage,
+    delegatee: &Addr,
+    period: u64,
+) -> StdResult<Vec<(Addr, Delegation)>> {
+    let mut delegators = vec![];
+    for delegator in fetch_delegators(storage, delegatee, period)? {
+        if let Some(delegation) = fetch_active_delegation(storage, &delegator, period)? {
+            if delegation.delegatee == *delegatee {
+                delegators.push((delegator, delegation));
+            }
+        }
+    }
+    Ok(delegators)
+}
+
+/// Adds the delegator to the delegators of the delegatee, starting with the given period.
+/// Delegators without an active delegation to the delegatee are dropped, so that only the active
+/// ones count against [`MAX_DELEGATORS`].
+pub(crate) fn add_delegator(
+    storage: &mut dyn Storage,
+    delegatee: &Addr,
+    delegator: &Addr,
+    period: u64,
+) -> Result<(), ContractError> {
+    let mut delegators: Vec<Addr> = fetch_active_delegators(storage, delegatee, period)?
+        .into_iter()
+        .map(|(addr, _)| addr)
+        .filter(|addr| addr != delegator)
+        .collect();
+    if delegators.len() >= MAX_DELEGATORS {
+        return Err(ContractError::TooManyDelegators(MAX_DELEGATORS));
+    }
+    delegators.push(delegator.clone());
+    DELEGATORS.save(storage, (delegatee.clone(), period), &delegators)?;
+    Ok(())
+}
+
+/// Removes the delegator from the delegators of the delegatee, starting with the given period.
+pub(crate) fn remove_delegator(
+    storage: &mut dyn Storage,
+    delegatee: &Addr,
+    delegator: &Addr,
+    period: u64,
+) -> StdResult<()> {
+    let mut delegators = fetch_delegators(storage, delegatee, period)?;
+    delegators.retain(|addr| addr != delegator);
+    DELEGATORS.save(storage, (delegatee.clone(), period), &delegators)
+}
+
+/// Mints a new lock token and returns its id.
+pub(crate) fn mint_lock_token(storage: &mut dyn Storage, token: &LockToken) -> StdResult<u64> {
+    let id = LAST_TOKEN_ID.may_load(storage)?.unwrap_or_default() + 1;
+    LAST_TOKEN_ID.save(storage, &id)?;
+    let count = TOKEN_COUNT.may_load(storage)?.unwrap_or_default();
+    TOKEN_COUNT.save(storage, &(count + 1))?;
+
+    LOCK_TOKENS.save(storage, id, token)?;
+    OWNER_TOKENS.save(storage, (token.owner.clone(), id), &())?;
+    Ok(id)
+}
+
+/// Removes a lock token.
+pub(crate) fn burn_lock_token(storage: &mut dyn Storage, owner: &Addr, id: u64) -> StdResult<()> {
+    LOCK_TOKENS.remove(storage, id);
+    OWNER_TOKENS.remove(storage, (owner.clone(), id));
+    let count = TOKEN_COUNT.may_load(storage)?.unwrap_or_default();
+    TOKEN_COUNT.save(storage, &count.saturating_sub(1))?;
+    Ok(())
+}
+
+/// Adds the amount of a lock token to the lock of its owner. The lock ends with the latest end of
+/// the owner's lock tokens.
+pub(crate) fn add_to_lock(
+    storage: &mut dyn Storage,
+    height: u64,
+    id: u64,
+    amount: Uint128,
+) -> StdResult<()> {
+    let token = LOCK_TOKENS.load(storage, id)?;
+    LOCKED
+        .update(storage, token.owner.clone(), height, |lock| -> StdResult<_> {
+            Ok(match lock.filter(|lock| !lock.amount.is_zero()) {
+                Some(lock) => Lock {
+                    amount: lock.amount + amount,
+                    start: lock.start,
+                    end: lock.end.max(token.end),
+                    last_extend_lock_period: lock
+                        .last_extend_lock_period
+                        .max(token.last_extend_lock_period),
+                    permanent: false,
+                },
+                None => Lock {
+                    amount,
+                    start: token.start,
+                    end: token.end,
+                    last_extend_lock_period: token.last_extend_lock_period,
+                    permanent: false,
+                },
+            })
+        })
+        .map(|_| ())
+}
+
+/// Subtracts the amount of a lock token from the lock of its owner.
+pub(crate) fn reduce_lock(
+    storage: &mut dyn Storage,
+    height: u64,
+    owner: &Addr,
+    amount: Uint128,
+) -> StdResult<()> {
+    LOCKED
+        .update(storage, owner.clone(), height, |lock| -> StdResult<_> {
+            let mut lock = lock.ok_or_else(|| StdError::generic_err("Lock not found"))?;
+            lock.amount = lock.amount.saturating_sub(amount);
+            Ok(lock)
+        })
+        .map(|_| ())
+}
+
+/// Fetches the ids of all lock tokens of the given owner.
+pub(crate) fn fetch_owner_token_ids(storage: &dyn Storage, owner: &Addr) -> StdResult<Vec<u64>> {
+    OWNER_TOKENS.prefix(owner.clone()).keys(storage, None, None, Order::Ascending).collect()
+}
+
+/// Loads the lock token `token_id` of the owner. Without a token id, the owner's only lock token is
+/// loaded.
+pub(crate) fn resolve_lock_token(
+    storage: &dyn Storage,
+    owner: &Addr,
+    token_id: Option<String>,
+) -> Result<(u64, LockToken), ContractError> {
+    if let Some(token_id) = token_id {
+        return load_owned_lock_token(storage, &token_id, owner);
+    }
+
+    let ids = OWNER_TOKENS
+        .prefix(owner.clone())
+        .keys(storage, None, None, Order::Ascending)
+        .take(2)
+        .collect::<StdResult<Vec<_>>>()?;
+    match ids.as_slice() {
+        [id] => Ok((*id, LOCK_TOKENS.load(storage, *id)?)),
+        [] => Err(ContractError::LockDoesNotExist {}),
+        _ => Err(ContractError::LockTokenRequired {}),
+    }
+}
+
+/// Checks that the sender is the owner of the lock token, an approved spender or an operator of
+/// the owner.
+pub(crate) fn assert_can_send(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+    sender: &Addr,
+    token: &LockToken,
+) -> Result<(), ContractError> {
+    if token
+        .approvals
+        .iter()
+        .any(|approval| approval.spender == *sender && !approval.expires.is_expired(block))
+    {
+        return Ok(());
+    }
+
+    assert_can_approve(storage, block, sender, &token.owner)
+}
+
+/// Checks that the sender is the owner or an operator of the owner.
+pub(crate) fn assert_can_approve(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+    sender: &Addr,
+    owner: &Addr,
+) -> Result<(), ContractError> {
+    if owner == sender {
+        return Ok(());
+    }
+
+    match OPERATORS.may_load(storage, (owner.clone(), sender.clone()))? {
+        Some(expires) if !expires.is_expired(block) => Ok(()),
+        _ => Err(ContractError::Unauthorized {}),
+    }
+}
+
+/// Loads a lock token and checks that it is owned by the given owner.
+pub(crate) fn load_owned_lock_token(
+    storage: &dyn Storage,
+    token_id: &str,
+    owner: &Addr,
+) -> Result<(u64, LockToken), ContractError> {
+    let (id, token) = load_lock_token(storage, token_id)?;
+    if token.owner != *owner {
+        return Err(ContractError::LockTokenNotOwned(token_id.to_string()));
+    }
+    Ok((id, token))
+}
+
+/// Loads a lock token by its cw721 token id.
+pub(crate) fn load_lock_token(
+    storage: &dyn Storage,
+    token_id: &str,
+) -> Result<(u64, LockToken), ContractError> {
+    let id = token_id
+        .parse::<u64>()
+        .map_err(|_| ContractError::LockTokenDoesNotExist(token_id.to_string()))?;
+    let token = LOCK_TOKENS
+        .may_load(storage, id)?
+        .ok_or_else(|| ContractError::LockTokenDoesNotExist(token_id.to_string()))?;
+    Ok((id, token))
+}
+
 /// Cancels scheduled slope change of total voting power only if the given period is in future.
 /// Removes scheduled slope change if it became zero.
 pub(crate) fn cancel_scheduled_slope(
@@ -113,6 +493,76 @@ pub(crate) fn schedule_slope_change(
     }
 }
 
+/// Schedules a slope change of a staker's voting power in the given period.
+pub(crate) fn schedule_user_slope_change(
+    storage: &mut dyn Storage,
+    addr: &Addr,
+    slope: Uint128,
+    period: u64,
+) -> StdResult<()> {
+    if !slope.is_zero() {
+        USER_SLOPE_CHANGES
+            .update(storage, (addr.clone(), period), |slope_opt| -> StdResult<Uint128> {
+                Ok(slope_opt.unwrap_or_default() + slope)
+            })
+            .map(|_| ())
+    } else {
+        Ok(())
+    }
+}
+
+/// Cancels a scheduled slope change of a staker's voting power. Removes the scheduled slope change
+/// if it became zero.
+pub(crate) fn cancel_user_slope_change(
+    storage: &mut dyn Storage,
+    addr: &Addr,
+    slope: Uint128,
+    period: u64,
+) -> StdResult<()> {
+    if let Some(old_scheduled_change) =
+        USER_SLOPE_CHANGES.may_load(storage, (addr.clone(), period))?
+    {
+        let new_slope = old_scheduled_change.saturating_sub(slope);
+        if !new_slope.is_zero() {
+            USER_SLOPE_CHANGES.save(storage, (addr.clone(), period), &new_slope)?;
+        } else {
+            USER_SLOPE_CHANGES.remove(storage, (addr.clone(), period));
+        }
+    }
+    Ok(())
+}
+
+/// Fetches all slope changes of a staker's voting power between `last_period` and `period`.
+pub(crate) fn fetch_user_slope_changes(
+    storage: &dyn Storage,
+    addr: &Addr,
+    last_period: u64,
+    period: u64,
+) -> StdResult<Vec<(u64, Uint128)>> {
+    USER_SLOPE_CHANGES
+        .prefix(addr.clone())
+        .range(
+            storage,
+            Some(Bound::exclusive(last_period)),
+            Some(Bound::inclusive(period)),
+            Order::Ascending,
+        )
+        .collect()
+}
+
+/// Fetches all slope changes of a staker's voting power after `period`. They are the ends of the
+/// staker's decaying lock tokens, so there are at most as many as periods in [`MAX_LOCK_TIME`].
+pub(crate) fn fetch_future_user_slope_changes(
+    storage: &dyn Storage,
+    addr: &Addr,
+    period: u64,
+) -> StdResult<Vec<(u64, Uint128)>> {
+    USER_SLOPE_CHANGES
+        .prefix(addr.clone())
+        .range(storage, Some(Bound::exclusive(period)), None, Order::Ascending)
+        .collect()
+}
+
 /// Fetches all slope changes between `last_slope_change` and `period`.
 pub(crate) fn fetch_slope_changes(
     storage: &dyn Storage,
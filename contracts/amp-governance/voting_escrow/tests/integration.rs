@@ -1,9 +1,9 @@
-use cosmwasm_std::{attr, coin, Addr, Fraction, StdError, Uint128};
+use cosmwasm_std::{attr, coin, Addr, Decimal, Fraction, StdError, Uint128};
 use cw20::{Logo, LogoInfo, MarketingInfoResponse};
 use cw_multi_test::{next_block, Executor};
 
 use eris::governance_helper::{get_period, MAX_LOCK_TIME, WEEK};
-use eris::voting_escrow::{ConfigResponse, ExecuteMsg, LockInfoResponse, QueryMsg};
+use eris::voting_escrow::{ConfigResponse, ExecuteMsg, LockInfoResponse, QueryMsg, MAX_DELEGATORS};
 
 use crate::test_utils::{mock_app, Helper, MULTIPLIER};
 
@@ -988,6 +988,479 @@ fn check_blacklist() {
     helper.create_lock(router_ref, "user1", 3 * WEEK, 10f32).unwrap();
 }
 
+#[test]
+fn check_delegation() {
+    let mut router = mock_app();
+    let router_ref = &mut router;
+    let owner = Addr::unchecked("owner");
+    let helper = Helper::init(router_ref, owner);
+
+    helper.mint_xastro(router_ref, "user1", 100);
+    helper.mint_xastro(router_ref, "user2", 100);
+
+    let now = router_ref.block_info().time.seconds();
+
+    // A delegation requires a lock
+    let err = helper.delegate(router_ref, "user1", "user2", 5000, now + 5 * WEEK).unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Lock does not exist");
+
+    helper.create_lock(router_ref, "user1", WEEK * 10, 100f32).unwrap();
+    helper.create_lock(router_ref, "user2", WEEK * 10, 50f32).unwrap();
+
+    let err = helper.delegate(router_ref, "user1", "user1", 5000, now + 5 * WEEK).unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Cannot delegate voting power to yourself");
+    let err = helper.delegate(router_ref, "user1", "user2", 0, now + 5 * WEEK).unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Delegated share must be between 1 and 10000 bps");
+    let err = helper.delegate(router_ref, "user1", "user2", 10001, now + 5 * WEEK).unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Delegated share must be between 1 and 10000 bps");
+    let err = helper.delegate(router_ref, "user1", "user2", 5000, now).unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "The delegation must expire in a future period");
+    let err = helper.undelegate(router_ref, "user1").unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Delegation does not exist");
+
+    let vp1 = helper.query_exact_user_vp(router_ref, "user1").unwrap();
+    let vp2 = helper.query_exact_user_vp(router_ref, "user2").unwrap();
+    let total = helper.query_exact_total_vp(router_ref).unwrap();
+
+    let res = helper.delegate(router_ref, "user1", "user2", 5000, now + 5 * WEEK).unwrap();
+    assert_eq!(res.events[1].attributes[1], attr("action", "veamp/delegate"));
+    assert_eq!(res.events[1].attributes[2], attr("delegatee", "user2"));
+
+    let delegation = helper.query_delegation(router_ref, "user1").unwrap().unwrap();
+    assert_eq!(delegation.delegatee, "user2".to_string());
+    assert_eq!(delegation.bps, 5000);
+    assert_eq!(delegation.expires, get_period(now + 5 * WEEK).unwrap());
+    assert_eq!(helper.query_delegation(router_ref, "user2").unwrap(), None);
+
+    // Half of the voting power of user1 is moved to user2, the total voting power is unchanged
+    let delegated_vp1 = helper.query_exact_user_vp(router_ref, "user1").unwrap();
+    let delegated_vp2 = helper.query_exact_user_vp(router_ref, "user2").unwrap();
+    assert_eq!(delegated_vp1, vp1 - vp1 / 2);
+    assert_eq!(delegated_vp2, vp2 + vp1 / 2);
+    assert_eq!(helper.query_exact_total_vp(router_ref).unwrap(), total);
+
+    // The lock info contains the delegation and the delegated voting power
+    let lock: LockInfoResponse = router_ref
+        .wrap()
+        .query_wasm_smart(
+            helper.voting_instance.clone(),
+            &QueryMsg::LockInfo {
+                user: "user2".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(lock.delegation, None);
+    assert_eq!(lock.delegated.len(), 1);
+    assert_eq!(lock.delegated[0].delegator, "user1".to_string());
+
+    // Delegated voting power is not delegated further
+    helper.delegate(router_ref, "user2", "user1", 10000, now + 5 * WEEK).unwrap();
+    assert_eq!(helper.query_exact_user_vp(router_ref, "user1").unwrap(), delegated_vp1 + vp2);
+    assert_eq!(helper.query_exact_user_vp(router_ref, "user2").unwrap(), vp1 / 2);
+    helper.undelegate(router_ref, "user2").unwrap();
+
+    // The delegation can't be expired before its end
+    let err = helper.expire_delegation(router_ref, "user3", "user1").unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "The delegation has not yet expired");
+
+    // Revoking the delegation restores the voting power
+    let res = helper.undelegate(router_ref, "user1").unwrap();
+    assert_eq!(res.events[1].attributes[1], attr("action", "veamp/undelegate"));
+    assert_eq!(helper.query_delegation(router_ref, "user1").unwrap(), None);
+    assert_eq!(helper.query_exact_user_vp(router_ref, "user1").unwrap(), vp1);
+    assert_eq!(helper.query_exact_user_vp(router_ref, "user2").unwrap(), vp2);
+
+    // Delegating everything until the next period
+    helper.delegate(router_ref, "user1", "user2", 10000, now + WEEK).unwrap();
+    assert_eq!(helper.query_exact_user_vp(router_ref, "user1").unwrap(), 0);
+    assert_eq!(helper.query_exact_user_vp(router_ref, "user2").unwrap(), vp1 + vp2);
+
+    // After the expiry the voting power is back to the delegator
+    router_ref.update_block(next_block);
+    router_ref.update_block(|block| block.time = block.time.plus_seconds(WEEK));
+    assert_eq!(helper.query_delegation(router_ref, "user1").unwrap(), None);
+    assert!(
+        helper.query_exact_user_vp(router_ref, "user1").unwrap()
+            > helper.query_exact_user_vp(router_ref, "user2").unwrap()
+    );
+
+    // Anyone can remove the expired delegation
+    let res = helper.expire_delegation(router_ref, "user3", "user1").unwrap();
+    assert_eq!(res.events[1].attributes[1], attr("action", "veamp/expire_delegation"));
+    let err = helper.expire_delegation(router_ref, "user3", "user1").unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Delegation does not exist");
+
+    // Blacklisted users can't receive delegations
+    helper.update_blacklist(router_ref, Some(vec!["user2".to_string()]), None).unwrap();
+    let err = helper.delegate(router_ref, "user1", "user2", 5000, now + 5 * WEEK).unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "The user2 address is blacklisted");
+}
+
+#[test]
+fn check_delegator_limit() {
+    let mut router = mock_app();
+    let router_ref = &mut router;
+    let owner = Addr::unchecked("owner");
+    let helper = Helper::init(router_ref, owner);
+
+    let now = router_ref.block_info().time.seconds();
+    let period = get_period(now).unwrap();
+    let delegators = (0..=MAX_DELEGATORS).map(|i| format!("user{}", i)).collect::<Vec<_>>();
+    for delegator in &delegators {
+        helper.mint_xastro(router_ref, delegator, 10);
+        helper.create_lock(router_ref, delegator, 10 * WEEK, 10f32).unwrap();
+    }
+
+    for delegator in &delegators[..MAX_DELEGATORS] {
+        helper.delegate(router_ref, delegator, "delegatee", 10000, now + 5 * WEEK).unwrap();
+    }
+    let err = helper
+        .delegate(router_ref, &delegators[MAX_DELEGATORS], "delegatee", 10000, now + 5 * WEEK)
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "The delegatee already has the maximum amount of 30 delegators"
+    );
+
+    // Changing an existing delegation doesn't count as a new delegator
+    helper.delegate(router_ref, &delegators[0], "delegatee", 5000, now + 5 * WEEK).unwrap();
+
+    router_ref.update_block(next_block);
+    router_ref.update_block(|block| block.time = block.time.plus_seconds(WEEK));
+    let vp_before = helper.query_user_vp_at_period(router_ref, "delegatee", period).unwrap();
+
+    // A revoked delegation frees its slot
+    helper.undelegate(router_ref, &delegators[0]).unwrap();
+    helper
+        .delegate(router_ref, &delegators[MAX_DELEGATORS], "delegatee", 10000, now + 5 * WEEK)
+        .unwrap();
+
+    // Past periods still count the revoked delegation
+    assert_eq!(helper.query_user_vp_at_period(router_ref, "delegatee", period).unwrap(), vp_before);
+    let lock: LockInfoResponse = router_ref
+        .wrap()
+        .query_wasm_smart(
+            helper.voting_instance.clone(),
+            &QueryMsg::LockInfo {
+                user: "delegatee".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(lock.delegated.len(), MAX_DELEGATORS);
+    assert!(lock.delegated.iter().all(|d| d.delegator != delegators[0]));
+
+    // Expired delegations free their slots without removing them first
+    helper.delegate(router_ref, &delegators[0], "other", 10000, now + 5 * WEEK).unwrap();
+    router_ref.update_block(|block| block.time = block.time.plus_seconds(5 * WEEK));
+    helper.delegate(router_ref, &delegators[0], "delegatee", 10000, now + 10 * WEEK).unwrap();
+}
+
+#[test]
+fn check_lock_tokens() {
+    let mut router = mock_app();
+    let router_ref = &mut router;
+    let owner = Addr::unchecked("owner");
+    let helper = Helper::init(router_ref, owner);
+
+    helper.mint_xastro(router_ref, "user1", 100);
+    helper.mint_xastro(router_ref, "user2", 100);
+    helper.mint_xastro(router_ref, "user3", 100);
+
+    let res = helper.create_lock(router_ref, "user1", WEEK * 10, 100f32).unwrap();
+    assert_eq!(res.events[1].attributes[5], attr("token_id", "1"));
+    assert_eq!(helper.query_tokens(router_ref, "user1").unwrap(), vec!["1".to_string()]);
+    assert_eq!(helper.query_token_amount(router_ref, "1").unwrap(), 100.0);
+
+    let vp = helper.query_exact_user_vp(router_ref, "user1").unwrap();
+    let total = helper.query_exact_total_vp(router_ref).unwrap();
+
+    // Splitting and merging doesn't change the voting power
+    let err = helper.split(router_ref, "user1", "1", 0f32).unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Split amount must be between zero and the lock token amount"
+    );
+    let err = helper.split(router_ref, "user1", "1", 100f32).unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Split amount must be between zero and the lock token amount"
+    );
+    let err = helper.split(router_ref, "user2", "1", 10f32).unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Lock token 1 is not owned by the sender");
+
+    helper.split(router_ref, "user1", "1", 40f32).unwrap();
+    assert_eq!(
+        helper.query_tokens(router_ref, "user1").unwrap(),
+        vec!["1".to_string(), "2".to_string()]
+    );
+    assert_eq!(helper.query_token_amount(router_ref, "1").unwrap(), 60.0);
+    assert_eq!(helper.query_token_amount(router_ref, "2").unwrap(), 40.0);
+    assert_eq!(helper.query_exact_user_vp(router_ref, "user1").unwrap(), vp);
+
+    let err = helper.merge(router_ref, "user1", "2", "2").unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Cannot merge a lock token into itself");
+    helper.merge(router_ref, "user1", "2", "1").unwrap();
+    assert_eq!(helper.query_tokens(router_ref, "user1").unwrap(), vec!["1".to_string()]);
+    assert_eq!(helper.query_token_amount(router_ref, "1").unwrap(), 100.0);
+    assert!(helper.query_token_amount(router_ref, "2").is_err());
+
+    // Merging recalculates the voting power of the merged lock token
+    let vp = helper.query_exact_user_vp(router_ref, "user1").unwrap();
+    let total = helper.query_exact_total_vp(router_ref).unwrap();
+
+    // Transferring a lock token moves its voting power
+    helper.split(router_ref, "user1", "1", 50f32).unwrap();
+    let err = helper.transfer_nft(router_ref, "user1", "user1", "3").unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Cannot transfer a lock token to yourself");
+    let res = helper.transfer_nft(router_ref, "user1", "user2", "3").unwrap();
+    assert_eq!(res.events[1].attributes[1], attr("action", "veamp/transfer_nft"));
+
+    assert_eq!(helper.query_tokens(router_ref, "user1").unwrap(), vec!["1".to_string()]);
+    assert_eq!(helper.query_tokens(router_ref, "user2").unwrap(), vec!["3".to_string()]);
+    let vp1 = helper.query_exact_user_vp(router_ref, "user1").unwrap();
+    let vp2 = helper.query_exact_user_vp(router_ref, "user2").unwrap();
+    assert_eq!(vp1 + vp2, vp);
+    assert_eq!(helper.query_exact_total_vp(router_ref).unwrap(), total);
+
+    let lock1 = helper.query_lock_info(router_ref, "user1").unwrap();
+    let lock2 = helper.query_lock_info(router_ref, "user2").unwrap();
+    assert_eq!(lock1.amount.u128(), 50 * MULTIPLIER as u128);
+    assert_eq!(lock2.amount.u128(), 50 * MULTIPLIER as u128);
+    assert_eq!(lock1.end, lock2.end);
+
+    // The recipient keeps both ends
+    helper.create_lock(router_ref, "user3", WEEK * 5, 100f32).unwrap();
+    let vp3 = helper.query_exact_user_vp(router_ref, "user3").unwrap();
+    helper.transfer_nft(router_ref, "user1", "user3", "1").unwrap();
+    assert_eq!(
+        helper.query_tokens(router_ref, "user3").unwrap(),
+        vec!["1".to_string(), "4".to_string()]
+    );
+    assert_eq!(helper.query_exact_user_vp(router_ref, "user1").unwrap(), 0);
+    assert_eq!(helper.query_exact_user_vp(router_ref, "user3").unwrap(), vp1 + vp3);
+    assert_eq!(helper.query_exact_total_vp(router_ref).unwrap(), total + vp3);
+
+    let lock3 = helper.query_lock_info(router_ref, "user3").unwrap();
+    assert_eq!(lock3.amount.u128(), 150 * MULTIPLIER as u128);
+    assert_eq!(lock3.end, lock1.end);
+    assert_eq!(lock3.ends.len(), 2);
+    assert_eq!(lock3.ends[1].end, lock1.end);
+    assert_eq!(helper.query_token_info(router_ref, "1").unwrap().end, lock1.end);
+    assert!(helper.query_token_info(router_ref, "4").unwrap().end < lock1.end);
+
+    // Only the owner, an approved spender or an operator can transfer a lock token
+    let err = helper.transfer_nft(router_ref, "user2", "user2", "1").unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+    helper.approve(router_ref, "user3", "user2", "1", None).unwrap();
+    assert_eq!(helper.query_owner_of(router_ref, "1").unwrap().approvals.len(), 1);
+    helper.transfer_nft(router_ref, "user2", "user2", "1").unwrap();
+    let owner_of = helper.query_owner_of(router_ref, "1").unwrap();
+    assert_eq!(owner_of.owner, "user2");
+    assert!(owner_of.approvals.is_empty());
+
+    helper.approve_all(router_ref, "user3", "user1").unwrap();
+    helper.transfer_nft(router_ref, "user1", "user2", "4").unwrap();
+    assert_eq!(helper.query_exact_user_vp(router_ref, "user3").unwrap(), 0);
+    assert_eq!(helper.query_exact_user_vp(router_ref, "user2").unwrap(), vp1 + vp2 + vp3);
+    assert_eq!(helper.query_exact_total_vp(router_ref).unwrap(), total + vp3);
+
+    // The merged lock token ends with the later end
+    helper.merge(router_ref, "user2", "3", "4").unwrap();
+    assert_eq!(helper.query_token_info(router_ref, "4").unwrap().end, lock1.end);
+    assert_eq!(helper.query_token_amount(router_ref, "4").unwrap(), 150.0);
+    assert!(helper.query_exact_user_vp(router_ref, "user2").unwrap() > vp1 + vp2 + vp3);
+    let lock2 = helper.query_lock_info(router_ref, "user2").unwrap();
+    assert_eq!(lock2.ends.len(), 1);
+    assert_eq!(lock2.end, lock1.end);
+
+    // The voting power of the lock tokens decays until their end
+    router_ref.update_block(next_block);
+    router_ref.update_block(|block| block.time = block.time.plus_seconds(WEEK * 10));
+    assert_eq!(helper.query_user_vp(router_ref, "user2").unwrap(), 200.0);
+
+    let err = helper.withdraw(router_ref, "user2").unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Multiple lock tokens exist, the token id is required"
+    );
+    helper.withdraw_token(router_ref, "user2", Some("1")).unwrap();
+    assert_eq!(helper.query_user_vp(router_ref, "user2").unwrap(), 150.0);
+    helper.withdraw_token(router_ref, "user2", Some("4")).unwrap();
+    helper.check_xastro_balance(router_ref, "user2", 300);
+    assert_eq!(helper.query_tokens(router_ref, "user2").unwrap(), Vec::<String>::new());
+    assert_eq!(helper.query_user_vp(router_ref, "user2").unwrap(), 0.0);
+}
+
+#[test]
+fn check_permanent_lock() {
+    let mut router = mock_app();
+    let router_ref = &mut router;
+    let owner = Addr::unchecked("owner");
+    let helper = Helper::init(router_ref, owner);
+
+    helper.mint_xastro(router_ref, "user1", 200);
+    helper.mint_xastro(router_ref, "user2", 100);
+
+    let err = helper.lock_permanent(router_ref, "user1").unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Lock does not exist");
+
+    helper.create_lock(router_ref, "user1", WEEK * 10, 100f32).unwrap();
+    helper.create_lock(router_ref, "user2", WEEK * 10, 100f32).unwrap();
+
+    let err = helper.unlock_permanent(router_ref, "user1").unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "The lock is not permanent");
+
+    // A permanent lock has the maximum boost as fixed voting power
+    let res = helper.lock_permanent(router_ref, "user1").unwrap();
+    assert_eq!(res.events[1].attributes[1], attr("action", "veamp/lock_permanent"));
+    assert_eq!(helper.query_user_vp(router_ref, "user1").unwrap(), 1000.0);
+    assert_eq!(
+        helper.query_exact_total_vp(router_ref).unwrap(),
+        helper.query_exact_user_vp(router_ref, "user1").unwrap()
+            + helper.query_exact_user_vp(router_ref, "user2").unwrap()
+    );
+
+    let err = helper.lock_permanent(router_ref, "user1").unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "The lock is permanent");
+    let err = helper.extend_lock_time(router_ref, "user1", WEEK * 10).unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "The lock is permanent");
+
+    let lock: LockInfoResponse = router_ref
+        .wrap()
+        .query_wasm_smart(
+            helper.voting_instance.clone(),
+            &QueryMsg::LockInfo {
+                user: "user1".to_string(),
+            },
+        )
+        .unwrap();
+    let cur_period = get_period(router_ref.block_info().time.seconds()).unwrap();
+    assert!(lock.permanent);
+    assert_eq!(lock.end, cur_period + MAX_LOCK_TIME / WEEK);
+    assert_eq!(lock.slope, Uint128::zero());
+    assert_eq!(lock.voting_power, Uint128::zero());
+
+    // The voting power does not decay and the lock can't be withdrawn
+    router_ref.update_block(next_block);
+    router_ref.update_block(|block| block.time = block.time.plus_seconds(WEEK * 200));
+    assert_eq!(helper.query_user_vp(router_ref, "user1").unwrap(), 1000.0);
+    assert_eq!(helper.query_user_vp(router_ref, "user2").unwrap(), 100.0);
+    assert_eq!(helper.query_total_vp(router_ref).unwrap(), 1100.0);
+    let err = helper.withdraw(router_ref, "user1").unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "The lock time has not yet expired");
+
+    // Deposits into a permanent lock receive the maximum boost
+    helper.extend_lock_amount(router_ref, "user1", 100f32).unwrap();
+    assert_eq!(helper.query_user_vp(router_ref, "user1").unwrap(), 2000.0);
+    assert_eq!(helper.query_total_vp(router_ref).unwrap(), 2100.0);
+
+    // Unlocking starts the decay over the maximum lock time
+    let res = helper.unlock_permanent(router_ref, "user1").unwrap();
+    assert_eq!(res.events[1].attributes[1], attr("action", "veamp/unlock_permanent"));
+    let vp = helper.query_user_vp(router_ref, "user1").unwrap();
+    assert!(vp <= 2000.0 && vp > 1999.0);
+    assert_eq!(
+        helper.query_exact_total_vp(router_ref).unwrap(),
+        helper.query_exact_user_vp(router_ref, "user1").unwrap()
+            + helper.query_exact_user_vp(router_ref, "user2").unwrap()
+    );
+
+    router_ref.update_block(next_block);
+    router_ref.update_block(|block| block.time = block.time.plus_seconds(MAX_LOCK_TIME / 2));
+    let vp = helper.query_user_vp(router_ref, "user1").unwrap();
+    assert!(vp < 1200.0 && vp > 1000.0);
+
+    router_ref.update_block(next_block);
+    router_ref.update_block(|block| block.time = block.time.plus_seconds(MAX_LOCK_TIME / 2));
+    assert_eq!(helper.query_user_vp(router_ref, "user1").unwrap(), 200.0);
+    assert_eq!(helper.query_total_vp(router_ref).unwrap(), 300.0);
+
+    helper.withdraw(router_ref, "user1").unwrap();
+    helper.check_xastro_balance(router_ref, "user1", 200);
+}
+
+#[test]
+fn check_early_withdraw() {
+    let mut router = mock_app();
+    let router_ref = &mut router;
+    let owner = Addr::unchecked("owner");
+    let helper = Helper::init(router_ref, owner);
+
+    helper.mint_xastro(router_ref, "user1", 100);
+    helper.mint_xastro(router_ref, "user2", 100);
+    helper.mint_xastro(router_ref, "user3", 100);
+
+    helper.create_lock(router_ref, "user1", MAX_LOCK_TIME, 100f32).unwrap();
+    helper.create_lock(router_ref, "user2", WEEK * 52, 100f32).unwrap();
+    helper.create_lock(router_ref, "user3", WEEK * 10, 100f32).unwrap();
+
+    let err = helper.early_withdraw(router_ref, "user1").unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Early withdrawals are disabled");
+    let err = helper.simulate_early_withdraw(router_ref, "user1").unwrap_err();
+    assert!(err.to_string().contains("Early withdrawals are disabled"));
+
+    let err = helper
+        .update_early_withdraw(router_ref, "user1", Decimal::percent(50), "treasury")
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Unauthorized");
+    let err = helper
+        .update_early_withdraw(router_ref, "owner", Decimal::percent(150), "treasury")
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Early withdraw penalty must be at most 100%");
+    helper.update_early_withdraw(router_ref, "owner", Decimal::percent(50), "treasury").unwrap();
+
+    let config: ConfigResponse =
+        router_ref.wrap().query_wasm_smart(&helper.voting_instance, &QueryMsg::Config {}).unwrap();
+    assert_eq!(config.early_withdraw_penalty, Some(Decimal::percent(50)));
+    assert_eq!(config.penalty_recipient, Some(Addr::unchecked("treasury")));
+
+    // The full penalty is paid at the maximum lock time
+    let res = helper.simulate_early_withdraw(router_ref, "user1").unwrap();
+    assert_eq!(res.penalty.u128(), 50 * MULTIPLIER as u128);
+    assert_eq!(res.amount.u128(), 50 * MULTIPLIER as u128);
+
+    // The penalty is proportional to the remaining lock time
+    let res = helper.simulate_early_withdraw(router_ref, "user2").unwrap();
+    assert_eq!(res.penalty.u128(), 25 * MULTIPLIER as u128);
+    assert_eq!(res.amount.u128(), 75 * MULTIPLIER as u128);
+
+    let err = helper.withdraw(router_ref, "user2").unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "The lock time has not yet expired");
+
+    let total_vp = helper.query_exact_total_vp(router_ref).unwrap();
+    let user2_vp = helper.query_exact_user_vp(router_ref, "user2").unwrap();
+    let res = helper.early_withdraw(router_ref, "user2").unwrap();
+    assert_eq!(res.events[1].attributes[1], attr("action", "veamp/early_withdraw"));
+    assert_eq!(res.events[1].attributes[2], attr("penalty", (25 * MULTIPLIER).to_string()));
+    helper.check_xastro_balance(router_ref, "user2", 75);
+    helper.check_xastro_balance(router_ref, "treasury", 25);
+    assert_eq!(helper.query_exact_user_vp(router_ref, "user2").unwrap(), 0);
+    assert_eq!(helper.query_exact_total_vp(router_ref).unwrap(), total_vp - user2_vp);
+
+    let err = helper.early_withdraw(router_ref, "user2").unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Lock does not exist");
+
+    // Expired locks are withdrawn without a penalty
+    router_ref.update_block(next_block);
+    router_ref.update_block(|block| block.time = block.time.plus_seconds(WEEK * 10));
+    let res = helper.simulate_early_withdraw(router_ref, "user3").unwrap();
+    assert_eq!(res.penalty, Uint128::zero());
+    helper.early_withdraw(router_ref, "user3").unwrap();
+    helper.check_xastro_balance(router_ref, "user3", 100);
+    helper.check_xastro_balance(router_ref, "treasury", 25);
+
+    // A zero penalty disables early withdrawals
+    helper.update_early_withdraw(router_ref, "owner", Decimal::zero(), "treasury").unwrap();
+    let err = helper.early_withdraw(router_ref, "user1").unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Early withdrawals are disabled");
+
+    // Decommissioned locks are withdrawn without a penalty
+    helper.update_decommissioned(router_ref, "owner", Some(true)).unwrap();
+    helper.early_withdraw(router_ref, "user1").unwrap();
+    helper.check_xastro_balance(router_ref, "user1", 100);
+    assert_eq!(helper.query_exact_total_vp(router_ref).unwrap(), 0);
+}
+
 #[test]
 fn check_residual() {
     let mut router = mock_app();
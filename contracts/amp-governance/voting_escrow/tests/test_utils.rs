@@ -1,12 +1,13 @@
 use anyhow::Result;
 use cosmwasm_std::testing::{mock_env, MockApi, MockStorage};
-use cosmwasm_std::{coin, Addr, StdResult, Timestamp, Uint128};
-use cw20::Logo;
+use cosmwasm_std::{coin, Addr, Binary, Decimal, StdResult, Timestamp, Uint128};
+use cw20::{Expiration, Logo};
 use cw_multi_test::{App, AppBuilder, AppResponse, BankKeeper, ContractWrapper, Executor};
 use eris::governance_helper::EPOCH_START;
 use eris::voting_escrow::{
-    BlacklistedVotersResponse, ExecuteMsg, InstantiateMsg, QueryMsg, UpdateMarketingInfo,
-    VotingPowerResponse,
+    BlacklistedVotersResponse, DelegationResponse, EarlyWithdrawResponse, ExecuteMsg,
+    InstantiateMsg, LockInfoResponse, LockTokenResponse, NftInfoResponse, OwnerOfResponse,
+    QueryMsg, TokensResponse, UpdateMarketingInfo, VotingPowerResponse,
 };
 
 pub const MULTIPLIER: u64 = 1000000;
@@ -148,6 +149,7 @@ impl Helper {
             self.voting_instance.clone(),
             &ExecuteMsg::ExtendLockAmount {
                 extend_to_min_periods: extend_to_min,
+                token_id: None,
             },
             &[coin(amount.into(), self.stake.clone())],
         )
@@ -166,27 +168,269 @@ impl Helper {
             self.voting_instance.clone(),
             &ExecuteMsg::DepositFor {
                 user: to.to_string(),
+                token_id: None,
             },
             &[coin(amount.into(), self.stake.clone())],
         )
     }
 
     pub fn extend_lock_time(&self, router: &mut App, user: &str, time: u64) -> Result<AppResponse> {
+        self.extend_lock_time_token(router, user, time, None)
+    }
+
+    pub fn extend_lock_time_token(
+        &self,
+        router: &mut App,
+        user: &str,
+        time: u64,
+        token_id: Option<&str>,
+    ) -> Result<AppResponse> {
         router.execute_contract(
             Addr::unchecked(user),
             self.voting_instance.clone(),
             &ExecuteMsg::ExtendLockTime {
                 time,
+                token_id: token_id.map(|id| id.to_string()),
             },
             &[],
         )
     }
 
     pub fn withdraw(&self, router: &mut App, user: &str) -> Result<AppResponse> {
+        self.withdraw_token(router, user, None)
+    }
+
+    pub fn withdraw_token(
+        &self,
+        router: &mut App,
+        user: &str,
+        token_id: Option<&str>,
+    ) -> Result<AppResponse> {
+        router.execute_contract(
+            Addr::unchecked(user),
+            self.voting_instance.clone(),
+            &ExecuteMsg::Withdraw {
+                token_id: token_id.map(|id| id.to_string()),
+            },
+            &[],
+        )
+    }
+
+    pub fn early_withdraw(&self, router: &mut App, user: &str) -> Result<AppResponse> {
+        router.execute_contract(
+            Addr::unchecked(user),
+            self.voting_instance.clone(),
+            &ExecuteMsg::EarlyWithdraw {
+                token_id: None,
+            },
+            &[],
+        )
+    }
+
+    pub fn update_early_withdraw(
+        &self,
+        router: &mut App,
+        sender: &str,
+        penalty: Decimal,
+        recipient: &str,
+    ) -> Result<AppResponse> {
+        router.execute_contract(
+            Addr::unchecked(sender),
+            self.voting_instance.clone(),
+            &ExecuteMsg::UpdateConfig {
+                new_guardian: None,
+                push_update_contracts: None,
+                decommissioned: None,
+                early_withdraw_penalty: Some(penalty),
+                penalty_recipient: Some(recipient.to_string()),
+            },
+            &[],
+        )
+    }
+
+    pub fn simulate_early_withdraw(
+        &self,
+        router: &mut App,
+        user: &str,
+    ) -> StdResult<EarlyWithdrawResponse> {
+        router.wrap().query_wasm_smart(
+            self.voting_instance.clone(),
+            &QueryMsg::SimulateEarlyWithdraw {
+                user: user.to_string(),
+                token_id: None,
+            },
+        )
+    }
+
+    pub fn delegate(
+        &self,
+        router: &mut App,
+        user: &str,
+        delegatee: &str,
+        bps: u16,
+        expires: u64,
+    ) -> Result<AppResponse> {
+        router.execute_contract(
+            Addr::unchecked(user),
+            self.voting_instance.clone(),
+            &ExecuteMsg::Delegate {
+                delegatee: delegatee.to_string(),
+                bps,
+                expires,
+            },
+            &[],
+        )
+    }
+
+    pub fn undelegate(&self, router: &mut App, user: &str) -> Result<AppResponse> {
         router.execute_contract(
             Addr::unchecked(user),
             self.voting_instance.clone(),
-            &ExecuteMsg::Withdraw {},
+            &ExecuteMsg::Undelegate {},
+            &[],
+        )
+    }
+
+    pub fn expire_delegation(
+        &self,
+        router: &mut App,
+        sender: &str,
+        delegator: &str,
+    ) -> Result<AppResponse> {
+        router.execute_contract(
+            Addr::unchecked(sender),
+            self.voting_instance.clone(),
+            &ExecuteMsg::ExpireDelegation {
+                delegator: delegator.to_string(),
+            },
+            &[],
+        )
+    }
+
+    pub fn transfer_nft(
+        &self,
+        router: &mut App,
+        user: &str,
+        recipient: &str,
+        token_id: &str,
+    ) -> Result<AppResponse> {
+        router.execute_contract(
+            Addr::unchecked(user),
+            self.voting_instance.clone(),
+            &ExecuteMsg::TransferNft {
+                recipient: recipient.to_string(),
+                token_id: token_id.to_string(),
+            },
+            &[],
+        )
+    }
+
+    pub fn send_nft(
+        &self,
+        router: &mut App,
+        user: &str,
+        contract: &str,
+        token_id: &str,
+    ) -> Result<AppResponse> {
+        router.execute_contract(
+            Addr::unchecked(user),
+            self.voting_instance.clone(),
+            &ExecuteMsg::SendNft {
+                contract: contract.to_string(),
+                token_id: token_id.to_string(),
+                msg: Binary::default(),
+            },
+            &[],
+        )
+    }
+
+    pub fn approve(
+        &self,
+        router: &mut App,
+        user: &str,
+        spender: &str,
+        token_id: &str,
+        expires: Option<Expiration>,
+    ) -> Result<AppResponse> {
+        router.execute_contract(
+            Addr::unchecked(user),
+            self.voting_instance.clone(),
+            &ExecuteMsg::Approve {
+                spender: spender.to_string(),
+                token_id: token_id.to_string(),
+                expires,
+            },
+            &[],
+        )
+    }
+
+    pub fn approve_all(&self, router: &mut App, user: &str, operator: &str) -> Result<AppResponse> {
+        router.execute_contract(
+            Addr::unchecked(user),
+            self.voting_instance.clone(),
+            &ExecuteMsg::ApproveAll {
+                operator: operator.to_string(),
+                expires: None,
+            },
+            &[],
+        )
+    }
+
+    pub fn merge(
+        &self,
+        router: &mut App,
+        user: &str,
+        token_id: &str,
+        into_token_id: &str,
+    ) -> Result<AppResponse> {
+        router.execute_contract(
+            Addr::unchecked(user),
+            self.voting_instance.clone(),
+            &ExecuteMsg::Merge {
+                token_id: token_id.to_string(),
+                into_token_id: into_token_id.to_string(),
+            },
+            &[],
+        )
+    }
+
+    pub fn split(
+        &self,
+        router: &mut App,
+        user: &str,
+        token_id: &str,
+        amount: f32,
+    ) -> Result<AppResponse> {
+        let amount = (amount * MULTIPLIER as f32) as u128;
+        router.execute_contract(
+            Addr::unchecked(user),
+            self.voting_instance.clone(),
+            &ExecuteMsg::Split {
+                token_id: token_id.to_string(),
+                amount: Uint128::new(amount),
+            },
+            &[],
+        )
+    }
+
+    pub fn lock_permanent(&self, router: &mut App, user: &str) -> Result<AppResponse> {
+        router.execute_contract(
+            Addr::unchecked(user),
+            self.voting_instance.clone(),
+            &ExecuteMsg::LockPermanent {
+                token_id: None,
+            },
+            &[],
+        )
+    }
+
+    pub fn unlock_permanent(&self, router: &mut App, user: &str) -> Result<AppResponse> {
+        router.execute_contract(
+            Addr::unchecked(user),
+            self.voting_instance.clone(),
+            &ExecuteMsg::UnlockPermanent {
+                token_id: None,
+            },
             &[],
         )
     }
@@ -221,6 +465,8 @@ impl Helper {
                 new_guardian: None,
                 push_update_contracts: None,
                 decommissioned,
+                early_withdraw_penalty: None,
+                penalty_recipient: None,
             },
             &[],
         )
@@ -281,6 +527,73 @@ impl Helper {
             .map(|vp: VotingPowerResponse| vp.vamp.u128() as f32 / MULTIPLIER as f32)
     }
 
+    pub fn query_lock_info(&self, router: &mut App, user: &str) -> StdResult<LockInfoResponse> {
+        router.wrap().query_wasm_smart(
+            self.voting_instance.clone(),
+            &QueryMsg::LockInfo {
+                user: user.to_string(),
+            },
+        )
+    }
+
+    pub fn query_delegation(
+        &self,
+        router: &mut App,
+        user: &str,
+    ) -> StdResult<Option<DelegationResponse>> {
+        router.wrap().query_wasm_smart(
+            self.voting_instance.clone(),
+            &QueryMsg::Delegation {
+                user: user.to_string(),
+            },
+        )
+    }
+
+    pub fn query_tokens(&self, router: &mut App, owner: &str) -> StdResult<Vec<String>> {
+        router
+            .wrap()
+            .query_wasm_smart(
+                self.voting_instance.clone(),
+                &QueryMsg::Tokens {
+                    owner: owner.to_string(),
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .map(|res: TokensResponse| res.tokens)
+    }
+
+    pub fn query_token_amount(&self, router: &mut App, token_id: &str) -> StdResult<f32> {
+        self.query_token_info(router, token_id)
+            .map(|token| token.amount.u128() as f32 / MULTIPLIER as f32)
+    }
+
+    pub fn query_token_info(
+        &self,
+        router: &mut App,
+        token_id: &str,
+    ) -> StdResult<LockTokenResponse> {
+        router
+            .wrap()
+            .query_wasm_smart(
+                self.voting_instance.clone(),
+                &QueryMsg::NftInfo {
+                    token_id: token_id.to_string(),
+                },
+            )
+            .map(|res: NftInfoResponse| res.extension)
+    }
+
+    pub fn query_owner_of(&self, router: &mut App, token_id: &str) -> StdResult<OwnerOfResponse> {
+        router.wrap().query_wasm_smart(
+            self.voting_instance.clone(),
+            &QueryMsg::OwnerOf {
+                token_id: token_id.to_string(),
+                include_expired: None,
+            },
+        )
+    }
+
     pub fn query_total_vp(&self, router: &mut App) -> StdResult<f32> {
         router
             .wrap()
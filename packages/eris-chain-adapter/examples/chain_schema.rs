@@ -0,0 +1,34 @@
+use std::env::current_dir;
+use std::fs::create_dir_all;
+
+use cosmwasm_schema::{export_schema_with_title, schema_for};
+
+/// Writes the schemas of the chain specific types of every chain into schema/<chain>.
+macro_rules! export_chain_schema {
+    ($chain:ident) => {{
+        let mut out_dir = current_dir().unwrap();
+        out_dir.push("schema");
+        out_dir.push(stringify!($chain));
+        create_dir_all(&out_dir).unwrap();
+
+        use eris_chain_adapter::$chain::*;
+        export_schema_with_title(&schema_for!(DenomType), &out_dir, "DenomType");
+        export_schema_with_title(&schema_for!(StageType), &out_dir, "StageType");
+        export_schema_with_title(&schema_for!(WithdrawType), &out_dir, "WithdrawType");
+        export_schema_with_title(
+            &schema_for!(HubChainConfigInput),
+            &out_dir,
+            "HubChainConfigInput",
+        );
+    }};
+}
+
+fn main() {
+    export_chain_schema!(injective);
+    export_chain_schema!(kujira);
+    export_chain_schema!(nibiru);
+    export_chain_schema!(osmosis);
+    export_chain_schema!(sei);
+    export_chain_schema!(terra);
+    export_chain_schema!(whitewhale);
+}
@@ -0,0 +1,69 @@
+use cosmwasm_std::DepsMut;
+use cosmwasm_std::Env;
+use cosmwasm_std::StdError;
+use cosmwasm_std::StdResult;
+use cosmwasm_std::Uint128;
+use std::collections::HashMap;
+
+use eris_chain_shared::chain_trait::ChainInterface;
+use eris_nibiru::chain::Chain;
+use eris_nibiru::types::get_asset;
+
+pub use eris_nibiru::types::AssetExt;
+pub use eris_nibiru::types::AssetInfoExt;
+
+use eris_nibiru::types::CoinType;
+pub use eris_nibiru::types::CustomMsgType;
+pub use eris_nibiru::types::CustomQueryType;
+pub use eris_nibiru::types::DenomType;
+pub use eris_nibiru::types::HubChainConfig;
+pub use eris_nibiru::types::HubChainConfigInput;
+pub use eris_nibiru::types::StageType;
+pub use eris_nibiru::types::WithdrawType;
+
+pub const CHAIN_TYPE: &str = "migaloo";
+
+/// creates the denom type of a native coin
+pub fn native_denom(denom: impl Into<String>) -> DenomType {
+    DenomType::native(denom)
+}
+
+#[inline(always)]
+pub fn chain(
+    env: &Env,
+) -> impl ChainInterface<CustomMsgType, DenomType, WithdrawType, StageType, HubChainConfig> {
+    Chain {
+        contract: env.contract.address.clone(),
+    }
+}
+
+#[inline(always)]
+pub fn test_chain_config() -> HubChainConfigInput {
+    HubChainConfigInput {}
+}
+
+/// queries all balances and converts it to a hashmap
+pub fn get_balances_hashmap<F>(
+    deps: &DepsMut<CustomQueryType>,
+    env: Env,
+    get_denoms: F,
+) -> StdResult<HashMap<String, Uint128>>
+where
+    F: FnOnce() -> Vec<DenomType>,
+{
+    let balances: HashMap<_, _> = get_denoms()
+        .into_iter()
+        .map(|denom| {
+            let balance = denom
+                .query_balance(&deps.querier, env.contract.address.clone())
+                .map_err(|e| StdError::generic_err(e.to_string()))?;
+
+            Ok(get_asset(denom, balance))
+        })
+        .collect::<StdResult<Vec<CoinType>>>()?
+        .into_iter()
+        .map(|element| (element.info.to_string(), element.amount))
+        .collect();
+
+    Ok(balances)
+}
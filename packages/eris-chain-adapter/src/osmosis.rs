@@ -0,0 +1,52 @@
+use cosmwasm_std::DepsMut;
+use cosmwasm_std::Env;
+use cosmwasm_std::StdResult;
+use cosmwasm_std::Uint128;
+use std::collections::HashMap;
+
+use eris_chain_shared::chain_trait::ChainInterface;
+use eris_osmosis::chain::OsmosisChain;
+
+pub use eris_osmosis::types::CustomMsgType;
+pub use eris_osmosis::types::CustomQueryType;
+pub use eris_osmosis::types::DenomType;
+pub use eris_osmosis::types::HubChainConfig;
+pub use eris_osmosis::types::HubChainConfigInput;
+pub use eris_osmosis::types::StageType;
+pub use eris_osmosis::types::WithdrawType;
+
+pub const CHAIN_TYPE: &str = "osmosis";
+
+/// creates the denom type of a native coin
+pub fn native_denom(denom: impl Into<String>) -> DenomType {
+    denom.into()
+}
+
+#[inline(always)]
+pub fn chain(
+    env: &Env,
+) -> impl ChainInterface<CustomMsgType, DenomType, WithdrawType, StageType, HubChainConfig> {
+    OsmosisChain {
+        contract: env.contract.address.clone(),
+    }
+}
+
+#[inline(always)]
+pub fn test_chain_config() -> HubChainConfigInput {
+    HubChainConfigInput {}
+}
+
+/// queries all balances and converts it to a hashmap
+pub fn get_balances_hashmap<F>(
+    deps: &DepsMut<CustomQueryType>,
+    env: Env,
+    _get_denoms: F,
+) -> StdResult<HashMap<String, Uint128>>
+where
+    F: FnOnce() -> Vec<DenomType>,
+{
+    let balances = deps.querier.query_all_balances(env.contract.address)?;
+    let balances: HashMap<_, _> =
+        balances.into_iter().map(|item| (item.denom.clone(), item.amount)).collect();
+    Ok(balances)
+}
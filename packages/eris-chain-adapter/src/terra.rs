@@ -0,0 +1,73 @@
+use cosmwasm_std::DepsMut;
+use cosmwasm_std::Env;
+use cosmwasm_std::StdError;
+use cosmwasm_std::StdResult;
+use cosmwasm_std::Uint128;
+use std::collections::HashMap;
+
+use eris_chain_shared::chain_trait::ChainInterface;
+
+use eris_terra::chain::Chain;
+pub use eris_terra::types::get_asset;
+pub use eris_terra::types::AssetExt;
+pub use eris_terra::types::AssetInfoExt;
+pub use eris_terra::types::CoinType;
+pub use eris_terra::types::CustomMsgType;
+pub use eris_terra::types::CustomQueryType;
+pub use eris_terra::types::DenomType;
+pub use eris_terra::types::HubChainConfig;
+pub use eris_terra::types::HubChainConfigInput;
+pub use eris_terra::types::MantaMsg;
+pub use eris_terra::types::MantaSwap;
+pub use eris_terra::types::MultiSwapRouterType;
+pub use eris_terra::types::StageType;
+pub use eris_terra::types::WithdrawType;
+
+pub const CHAIN_TYPE: &str = "terra";
+
+/// creates the denom type of a native coin
+pub fn native_denom(denom: impl Into<String>) -> DenomType {
+    DenomType::NativeToken {
+        denom: denom.into(),
+    }
+}
+
+#[inline(always)]
+pub fn chain(
+    env: &Env,
+) -> impl ChainInterface<CustomMsgType, DenomType, WithdrawType, StageType, HubChainConfig> {
+    Chain {
+        contract: env.contract.address.clone(),
+    }
+}
+
+/// queries all balances and converts it to a hashmap
+pub fn get_balances_hashmap<F>(
+    deps: &DepsMut<CustomQueryType>,
+    env: Env,
+    get_denoms: F,
+) -> StdResult<HashMap<String, Uint128>>
+where
+    F: FnOnce() -> Vec<DenomType>,
+{
+    let balances: HashMap<_, _> = get_denoms()
+        .into_iter()
+        .map(|denom| {
+            let balance = denom
+                .query_pool(&deps.querier.into_empty(), env.contract.address.clone())
+                .map_err(|e| StdError::generic_err(e.to_string()))?;
+
+            Ok(get_asset(denom, balance))
+        })
+        .collect::<StdResult<Vec<CoinType>>>()?
+        .into_iter()
+        .map(|element| (element.info.to_string(), element.amount))
+        .collect();
+
+    Ok(balances)
+}
+
+#[inline(always)]
+pub fn test_chain_config() -> HubChainConfigInput {
+    HubChainConfigInput {}
+}
@@ -0,0 +1,164 @@
+use cosmwasm_std::testing::{mock_env, MockQuerier, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{Addr, Empty, QuerierWrapper, Uint128};
+use eris_chain_shared::chain_trait::{ChainInterface, DenomMetadata};
+use eris_osmosis::chain::compute_join_pool_shares;
+
+fn metadata() -> DenomMetadata {
+    DenomMetadata {
+        name: "Eris Amplified Token".to_string(),
+        symbol: "ampTOKEN".to_string(),
+        display: "amptoken".to_string(),
+        exponent: 6,
+        description: "Amplified token".to_string(),
+    }
+}
+
+/// Checks the token messages every chain needs to support and returns whether denom metadata
+/// can be set on the chain.
+fn assert_token_msgs<TCustom, TDenomType, TWithdrawType, TStageType, THubChainConfig>(
+    chain: impl ChainInterface<TCustom, TDenomType, TWithdrawType, TStageType, THubChainConfig>,
+    prefix: &str,
+) -> bool {
+    let full_denom = chain.get_token_denom(MOCK_CONTRACT_ADDR, "stake".to_string());
+    assert_eq!(full_denom, format!("{0}/{1}/stake", prefix, MOCK_CONTRACT_ADDR));
+
+    chain.create_denom_msg(full_denom.clone(), "stake".to_string());
+    chain.create_burn_msg(full_denom.clone(), Uint128::new(100));
+
+    let mint_msgs =
+        chain.create_mint_msgs(full_denom.clone(), Uint128::new(100), Addr::unchecked("user"));
+    assert!(!mint_msgs.is_empty());
+
+    chain.create_set_metadata_msg(full_denom, metadata()).is_some()
+}
+
+#[test]
+fn all_chains_create_token_msgs() {
+    let env = mock_env();
+
+    assert!(assert_token_msgs(crate::injective::chain(&env), "factory"));
+    assert!(!assert_token_msgs(crate::kujira::chain(&env), "factory"));
+    assert!(assert_token_msgs(crate::nibiru::chain(&env), "tf"));
+    assert!(assert_token_msgs(crate::osmosis::chain(&env), "factory"));
+    assert!(!assert_token_msgs(crate::sei::chain(&env), "factory"));
+    assert!(!assert_token_msgs(crate::terra::chain(&env), "factory"));
+    assert!(assert_token_msgs(crate::whitewhale::chain(&env), "factory"));
+}
+
+#[test]
+fn all_chains_provide_liquidity() {
+    let env = mock_env();
+    let mock_querier = MockQuerier::<Empty>::new(&[]);
+    let querier = QuerierWrapper::new(&mock_querier);
+    let amount = Uint128::new(100);
+
+    let msgs = crate::whitewhale::chain(&env)
+        .create_provide_liquidity_msg(
+            &querier,
+            || Ok(crate::whitewhale::HubChainConfig {}),
+            crate::whitewhale::WithdrawType::dex("pool"),
+            vec![
+                (crate::whitewhale::DenomType::native("ua"), amount),
+                (crate::whitewhale::DenomType::cw20(Addr::unchecked("token")), amount),
+            ],
+            None,
+        )
+        .unwrap();
+    // cw20 allowance + provide
+    assert_eq!(msgs.len(), 2);
+
+    let msgs = crate::terra::chain(&env)
+        .create_provide_liquidity_msg(
+            &querier,
+            || Ok(crate::terra::HubChainConfig {}),
+            crate::terra::WithdrawType::dex("pool"),
+            vec![
+                (
+                    crate::terra::DenomType::NativeToken {
+                        denom: "ua".to_string(),
+                    },
+                    amount,
+                ),
+                (
+                    crate::terra::DenomType::NativeToken {
+                        denom: "ub".to_string(),
+                    },
+                    amount,
+                ),
+            ],
+            None,
+        )
+        .unwrap();
+    assert_eq!(msgs.len(), 1);
+
+    let msgs = crate::kujira::chain(&env)
+        .create_provide_liquidity_msg(
+            &querier,
+            || Ok(crate::kujira::HubChainConfig {}),
+            crate::kujira::WithdrawType::bow("bow"),
+            vec![("ua".into(), amount), ("ub".into(), amount)],
+            None,
+        )
+        .unwrap();
+    assert_eq!(msgs.len(), 1);
+
+    crate::kujira::chain(&env)
+        .create_provide_liquidity_msg(
+            &querier,
+            || Ok(crate::kujira::HubChainConfig {}),
+            crate::kujira::WithdrawType::bw("bw"),
+            vec![("ua".into(), amount)],
+            None,
+        )
+        .unwrap_err();
+
+    // osmosis needs the pool reserves to join the pool
+    crate::osmosis::chain(&env)
+        .create_provide_liquidity_msg(
+            &querier,
+            || Ok(crate::osmosis::HubChainConfig {}),
+            crate::osmosis::WithdrawType::Withdraw {
+                pool_id: 1,
+                token_out_mins: vec![],
+            },
+            vec![("ua".to_string(), amount), ("ub".to_string(), amount)],
+            None,
+        )
+        .unwrap_err();
+}
+
+#[test]
+fn osmosis_join_pool_shares() {
+    let reserves =
+        vec![("ua".to_string(), Uint128::new(1000)), ("ub".to_string(), Uint128::new(4000))];
+
+    // the asset with the smallest share of its reserve limits the shares
+    let shares = compute_join_pool_shares(
+        &reserves,
+        Uint128::new(500),
+        &[("ua".to_string(), Uint128::new(100)), ("ub".to_string(), Uint128::new(200))],
+    )
+    .unwrap();
+    assert_eq!(shares, Uint128::new(25));
+
+    let shares = compute_join_pool_shares(
+        &reserves,
+        Uint128::new(500),
+        &[("ua".to_string(), Uint128::new(100)), ("ub".to_string(), Uint128::new(400))],
+    )
+    .unwrap();
+    assert_eq!(shares, Uint128::new(50));
+
+    compute_join_pool_shares(
+        &reserves,
+        Uint128::new(500),
+        &[("ua".to_string(), Uint128::new(100))],
+    )
+    .unwrap_err();
+    compute_join_pool_shares(
+        &reserves,
+        Uint128::new(500),
+        &[("ua".to_string(), Uint128::new(1)), ("ub".to_string(), Uint128::new(1))],
+    )
+    .unwrap_err();
+}
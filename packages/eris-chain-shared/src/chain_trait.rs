@@ -1,4 +1,5 @@
-use cosmwasm_std::{Addr, Api, CosmosMsg, Decimal, StdResult, Uint128};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Api, CosmosMsg, Decimal, QuerierWrapper, StdError, StdResult, Uint128};
 
 pub trait ChainInterface<TCustom, TDenomType, TWithdrawType, TStageType, THubChainConfig> {
     fn get_token_denom(&self, contract_addr: impl Into<String>, sub_denom: String) -> String {
@@ -16,6 +17,16 @@ pub trait ChainInterface<TCustom, TDenomType, TWithdrawType, TStageType, THubCha
 
     fn create_burn_msg(&self, full_denom: String, amount: Uint128) -> CosmosMsg<TCustom>;
 
+    /// Sets the bank metadata of a denom created by the contract.
+    /// Returns None, if the chain does not support setting denom metadata.
+    fn create_set_metadata_msg(
+        &self,
+        _full_denom: String,
+        _metadata: DenomMetadata,
+    ) -> Option<CosmosMsg<TCustom>> {
+        None
+    }
+
     fn create_withdraw_msg<F>(
         &self,
         get_chain_config: F,
@@ -26,6 +37,36 @@ pub trait ChainInterface<TCustom, TDenomType, TWithdrawType, TStageType, THubCha
     where
         F: FnOnce() -> StdResult<THubChainConfig>;
 
+    /// Provides the assets as liquidity to the pool. LP tokens are received by the contract.
+    /// Depending on the DEX, this can be multiple messages (e.g. cw20 allowances + deposit).
+    /// The querier is used by DEXes that need the pool reserves to compute the expected shares.
+    fn create_provide_liquidity_msg<F>(
+        &self,
+        querier: &QuerierWrapper,
+        get_chain_config: F,
+        pool: TWithdrawType,
+        assets: Vec<(TDenomType, Uint128)>,
+        slippage_tolerance: Option<Decimal>,
+    ) -> StdResult<Vec<CosmosMsg<TCustom>>>
+    where
+        F: FnOnce() -> StdResult<THubChainConfig>;
+
+    /// Withdraws the liquidity of the LP tokens from the pool.
+    /// Fails, if the pool does not support withdrawing liquidity.
+    fn create_withdraw_liquidity_msg<F>(
+        &self,
+        get_chain_config: F,
+        pool: TWithdrawType,
+        lp_denom: TDenomType,
+        amount: Uint128,
+    ) -> StdResult<CosmosMsg<TCustom>>
+    where
+        F: FnOnce() -> StdResult<THubChainConfig>,
+    {
+        self.create_withdraw_msg(get_chain_config, pool, lp_denom, amount)?
+            .ok_or_else(|| StdError::generic_err("withdrawing liquidity not supported"))
+    }
+
     fn create_single_stage_swap_msgs<F>(
         &self,
         get_chain_config: F,
@@ -37,8 +78,65 @@ pub trait ChainInterface<TCustom, TDenomType, TWithdrawType, TStageType, THubCha
     ) -> StdResult<CosmosMsg<TCustom>>
     where
         F: FnOnce() -> StdResult<THubChainConfig>;
+
+    /// Simulates the swap of a stage and returns the belief price (offer per ask) of the swap.
+    /// Returns None, if the stage type does not need a simulated price to limit its slippage.
+    fn simulate_belief_price(
+        &self,
+        _querier: &QuerierWrapper,
+        _stage_type: &TStageType,
+        _denom: &TDenomType,
+        _amount: Uint128,
+    ) -> StdResult<Option<Decimal>> {
+        Ok(None)
+    }
 }
 
 pub trait Validateable<T> {
     fn validate(&self, api: &dyn Api) -> StdResult<T>;
 }
+
+/// Bank metadata of a denom created by the contract. The base denom always has exponent 0.
+#[cw_serde]
+pub struct DenomMetadata {
+    /// Name of the token, e.g. "Eris Amplified WHALE"
+    pub name: String,
+    /// Symbol shown by wallets, e.g. "ampWHALE"
+    pub symbol: String,
+    /// Denom unit that is displayed to users, e.g. "ampwhale"
+    pub display: String,
+    /// Exponent of the display denom unit relative to the base denom
+    pub exponent: u32,
+    pub description: String,
+}
+
+impl DenomMetadata {
+    /// Validates the metadata of `full_denom` like the bank module does, so that setting it does
+    /// not fail on chain.
+    pub fn validate(&self, full_denom: &str) -> StdResult<()> {
+        if self.name.trim().is_empty() || self.symbol.trim().is_empty() {
+            return Err(StdError::generic_err("name and symbol can't be blank"));
+        }
+        validate_denom(full_denom)?;
+        validate_denom(&self.display)?;
+        if self.display == full_denom {
+            return Err(StdError::generic_err("display denom must differ from the base denom"));
+        }
+        if self.exponent == 0 {
+            return Err(StdError::generic_err("exponent of the display denom can't be zero"));
+        }
+        Ok(())
+    }
+}
+
+/// Checks the denom format of the bank module: `[a-zA-Z][a-zA-Z0-9/:._-]{2,127}`
+pub fn validate_denom(denom: &str) -> StdResult<()> {
+    let mut chars = denom.chars();
+    let valid_start = chars.next().is_some_and(|c| c.is_ascii_alphabetic());
+    let valid_rest = chars.all(|c| c.is_ascii_alphanumeric() || "/:._-".contains(c));
+
+    if !valid_start || !valid_rest || denom.len() < 3 || denom.len() > 128 {
+        return Err(StdError::generic_err(format!("invalid denom: {0}", denom)));
+    }
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use cosmwasm_std::{Binary, CosmosMsg};
+use prost::Message;
+
+use crate::chain_trait::DenomMetadata;
+
+/// DenomUnit represents a struct that describes a given denomination unit of the basic token.
+#[derive(
+    Clone,
+    PartialEq,
+    Eq,
+    ::prost::Message,
+    serde::Serialize,
+    serde::Deserialize,
+    schemars::JsonSchema,
+)]
+pub struct DenomUnit {
+    #[prost(string, tag = "1")]
+    pub denom: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "2")]
+    pub exponent: u32,
+    #[prost(string, repeated, tag = "3")]
+    pub aliases: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+
+/// Metadata represents a struct that describes a basic token (cosmos.bank.v1beta1.Metadata).
+#[derive(
+    Clone,
+    PartialEq,
+    Eq,
+    ::prost::Message,
+    serde::Serialize,
+    serde::Deserialize,
+    schemars::JsonSchema,
+)]
+pub struct Metadata {
+    #[prost(string, tag = "1")]
+    pub description: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "2")]
+    pub denom_units: ::prost::alloc::vec::Vec<DenomUnit>,
+    #[prost(string, tag = "3")]
+    pub base: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub display: ::prost::alloc::string::String,
+    #[prost(string, tag = "5")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag = "6")]
+    pub symbol: ::prost::alloc::string::String,
+    #[prost(string, tag = "7")]
+    pub uri: ::prost::alloc::string::String,
+    #[prost(string, tag = "8")]
+    pub uri_hash: ::prost::alloc::string::String,
+}
+
+/// MsgSetDenomMetadata of the tokenfactory modules. Osmosis, Migaloo, Injective and Nibiru all use
+/// the same message layout, only the type url differs.
+#[derive(
+    Clone,
+    PartialEq,
+    Eq,
+    ::prost::Message,
+    serde::Serialize,
+    serde::Deserialize,
+    schemars::JsonSchema,
+)]
+pub struct MsgSetDenomMetadata {
+    #[prost(string, tag = "1")]
+    pub sender: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub metadata: ::core::option::Option<Metadata>,
+}
+
+impl DenomMetadata {
+    /// Bank metadata of `full_denom` with the base denom unit (exponent 0) and the display unit.
+    pub fn to_bank_metadata(self, full_denom: String) -> Metadata {
+        Metadata {
+            description: self.description,
+            denom_units: vec![
+                DenomUnit {
+                    denom: full_denom.clone(),
+                    exponent: 0,
+                    aliases: vec![],
+                },
+                DenomUnit {
+                    denom: self.display.clone(),
+                    exponent: self.exponent,
+                    aliases: vec![],
+                },
+            ],
+            base: full_denom,
+            display: self.display,
+            name: self.name,
+            symbol: self.symbol,
+            uri: String::new(),
+            uri_hash: String::new(),
+        }
+    }
+}
+
+/// Creates the tokenfactory message setting the bank metadata of `full_denom`.
+pub fn create_set_metadata_msg<T>(
+    type_url: &str,
+    sender: String,
+    full_denom: String,
+    metadata: DenomMetadata,
+) -> CosmosMsg<T> {
+    let msg = MsgSetDenomMetadata {
+        sender,
+        metadata: Some(metadata.to_bank_metadata(full_denom)),
+    };
+
+    CosmosMsg::Stargate {
+        type_url: type_url.to_string(),
+        value: Binary(msg.encode_to_vec()),
+    }
+}
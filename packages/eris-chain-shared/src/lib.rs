@@ -1,4 +1,6 @@
 pub mod chain_trait;
 
 pub mod alliance_query;
+pub mod denom_metadata;
+pub mod pair;
 pub mod test_trait;
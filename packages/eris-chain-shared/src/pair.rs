@@ -0,0 +1,75 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    coin, to_json_binary, Addr, Coin, CosmosMsg, Decimal, StdResult, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+
+/// Asset info of terraswap-like pairs (White Whale, Astroport)
+#[cw_serde]
+pub enum PairAssetInfo {
+    Token {
+        contract_addr: String,
+    },
+    NativeToken {
+        denom: String,
+    },
+}
+
+#[cw_serde]
+pub struct PairAsset {
+    pub info: PairAssetInfo,
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+enum PairExecuteMsg {
+    /// Provides liquidity to the pool. Also accepted by Astroport pairs, as auto_stake is optional.
+    ProvideLiquidity {
+        assets: Vec<PairAsset>,
+        slippage_tolerance: Option<Decimal>,
+        receiver: Option<String>,
+    },
+}
+
+/// Provides liquidity to a terraswap-like pair. Cw20 tokens are approved to the pair first,
+/// native tokens are sent with the deposit.
+pub fn create_pair_provide_liquidity_msgs<T>(
+    pair: &Addr,
+    assets: Vec<PairAsset>,
+    slippage_tolerance: Option<Decimal>,
+) -> StdResult<Vec<CosmosMsg<T>>> {
+    let mut msgs = vec![];
+    let mut funds: Vec<Coin> = vec![];
+
+    for asset in &assets {
+        match &asset.info {
+            PairAssetInfo::Token {
+                contract_addr,
+            } => msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract_addr.clone(),
+                funds: vec![],
+                msg: to_json_binary(&Cw20ExecuteMsg::IncreaseAllowance {
+                    spender: pair.to_string(),
+                    amount: asset.amount,
+                    expires: None,
+                })?,
+            })),
+            PairAssetInfo::NativeToken {
+                denom,
+            } => funds.push(coin(asset.amount.u128(), denom)),
+        }
+    }
+
+    funds.sort_by(|a, b| a.denom.cmp(&b.denom));
+    msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: pair.to_string(),
+        funds,
+        msg: to_json_binary(&PairExecuteMsg::ProvideLiquidity {
+            assets,
+            slippage_tolerance,
+            receiver: None,
+        })?,
+    }));
+
+    Ok(msgs)
+}
@@ -132,3 +132,8 @@ pub struct MsgBurn {
 )]
 #[proto_message(type_url = "/injective.tokenfactory.v1beta1.MsgBurnResponse")]
 pub struct MsgBurnResponse {}
+
+/// Type url of the MsgSetDenomMetadata of the tokenfactory module.
+/// The message is created by `eris_chain_shared::denom_metadata::create_set_metadata_msg`.
+pub const MSG_SET_DENOM_METADATA_TYPE_URL: &str =
+    "/injective.tokenfactory.v1beta1.MsgSetDenomMetadata";
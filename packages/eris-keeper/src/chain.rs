@@ -0,0 +1,27 @@
+use cosmwasm_std::Uint128;
+use eris::alliance_lst::{ExecuteMsg, QueryMsg};
+use serde::de::DeserializeOwned;
+
+use crate::error::KeeperResult;
+
+/// Access to the chain the hub is deployed on. Implemented by the operator tooling on top of an
+/// RPC / LCD client, and by a mock in the unit tests.
+pub trait KeeperChain {
+    /// Time of the latest block, in seconds
+    fn block_time(&self) -> KeeperResult<u64>;
+
+    /// Smart query against the hub contract
+    fn query_hub<T: DeserializeOwned>(&self, hub: &str, msg: &QueryMsg) -> KeeperResult<T>;
+
+    /// Amount of `denom` the `delegator` has delegated to `validator` on the alliance module.
+    /// Returns zero if there is no delegation.
+    fn query_alliance_delegation(
+        &self,
+        delegator: &str,
+        validator: &str,
+        denom: &str,
+    ) -> KeeperResult<Uint128>;
+
+    /// Executes `msg` on the hub contract in its own transaction, returns the tx hash
+    fn broadcast(&mut self, hub: &str, msg: &ExecuteMsg) -> KeeperResult<String>;
+}
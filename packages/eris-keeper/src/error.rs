@@ -0,0 +1,30 @@
+use cosmwasm_std::{StdError, Uint128};
+use thiserror::Error;
+
+pub type KeeperResult<T> = Result<T, KeeperError>;
+
+/// This enum describes keeper errors
+#[derive(Error, Debug, PartialEq)]
+pub enum KeeperError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Chain error: {0}")]
+    Chain(String),
+
+    #[error("Broadcast of {action} failed: {reason}, already executed: {executed:?}")]
+    Broadcast {
+        action: String,
+        reason: String,
+        /// Actions with their tx hashes that were executed before the failure
+        executed: Vec<(String, String)>,
+    },
+
+    #[error(
+        "Delegations dropped from {expected} to {actual}, too much to be reported by CheckSlashing"
+    )]
+    BigSlash {
+        expected: Uint128,
+        actual: Uint128,
+    },
+}
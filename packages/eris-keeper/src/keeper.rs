@@ -0,0 +1,266 @@
+use std::collections::HashMap;
+
+use cosmwasm_std::{Decimal, Uint128};
+use eris::alliance_lst::{ConfigResponse, ExecuteMsg, QueryMsg};
+use eris::governance_helper::get_period;
+use eris::hub::{
+    Batch, DelegationStrategy, DelegationsResponse, ExchangeRatesResponse, PendingBatch,
+    StateResponse, WantedDelegationsResponse,
+};
+
+use crate::chain::KeeperChain;
+use crate::error::{KeeperError, KeeperResult};
+
+/// Batches requested per `PreviousBatches` query, equal to the max limit of the hub
+const BATCH_PAGE_LIMIT: u32 = 30;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeeperConfig {
+    /// Address of the alliance-lst hub
+    pub hub: String,
+    /// Min time between two harvests, in seconds
+    pub harvest_interval: u64,
+    /// Rebalancing is only due if a validator exceeds its goal by at least this amount. Also sent
+    /// as `min_redelegation`, so that the hub skips smaller redelegations.
+    pub min_redelegation: Uint128,
+    /// Max difference between the bonded amount of the hub and its actual delegations that is not
+    /// reported as slashing
+    pub slashing_tolerance: Uint128,
+    /// Max share of the bonded amount a slashing may remove to be reported by `CheckSlashing`,
+    /// bigger drops are returned as error and need to be handled manually. The hub itself rejects
+    /// drops of more than 5%.
+    pub max_slashing: Decimal,
+}
+
+/// Computes the maintenance messages that are due on the hub, in the order they should be
+/// executed. `CheckSlashing` comes first, as the other actions rely on the bonded amount.
+pub fn due_messages(
+    chain: &impl KeeperChain,
+    config: &KeeperConfig,
+) -> KeeperResult<Vec<ExecuteMsg>> {
+    let now = chain.block_time()?;
+    let hub_config: ConfigResponse = chain.query_hub(&config.hub, &QueryMsg::Config {})?;
+    let state: StateResponse = chain.query_hub(&config.hub, &QueryMsg::State {})?;
+    let delegations: DelegationsResponse =
+        chain.query_hub(&config.hub, &QueryMsg::Delegations {})?;
+
+    let mut msgs = vec![];
+
+    if let Some(msg) = check_slashing_msg(chain, config, &hub_config.utoken, &state, &delegations)?
+    {
+        msgs.push(msg);
+    }
+
+    if is_reconcile_due(chain, config, now)? {
+        msgs.push(ExecuteMsg::Reconcile {});
+    }
+
+    if is_harvest_due(chain, config, &state, now)? {
+        msgs.push(ExecuteMsg::Harvest {
+            validators: None,
+            withdrawals: None,
+            stages: None,
+        });
+    }
+
+    let pending_batch: PendingBatch = chain.query_hub(&config.hub, &QueryMsg::PendingBatch {})?;
+    if is_submit_batch_due(&pending_batch, now) {
+        msgs.push(ExecuteMsg::SubmitBatch {
+            undelegations: None,
+        });
+    }
+
+    let wanted: WantedDelegationsResponse =
+        chain.query_hub(&config.hub, &QueryMsg::WantedDelegations {})?;
+    if is_tune_due(&hub_config.delegation_strategy, &wanted, now)? {
+        // the wanted delegations change with the tuning, rebalancing is checked in the next run
+        msgs.push(ExecuteMsg::TuneDelegations {});
+    } else if is_rebalance_due(config, &delegations, &wanted) {
+        msgs.push(ExecuteMsg::Rebalance {
+            min_redelegation: Some(config.min_redelegation),
+        });
+    }
+
+    Ok(msgs)
+}
+
+/// Broadcasts all due messages, each in its own transaction. Returns the executed actions with
+/// their tx hashes and stops at the first failed broadcast. The actions executed before the
+/// failure are part of the error.
+pub fn run(
+    chain: &mut impl KeeperChain,
+    config: &KeeperConfig,
+) -> KeeperResult<Vec<(String, String)>> {
+    let msgs = due_messages(chain, config)?;
+
+    let mut executed = vec![];
+    for msg in msgs.iter() {
+        let action = action(msg).to_string();
+        match chain.broadcast(&config.hub, msg) {
+            Ok(tx_hash) => executed.push((action, tx_hash)),
+            Err(err) => {
+                return Err(KeeperError::Broadcast {
+                    action,
+                    reason: err.to_string(),
+                    executed,
+                })
+            },
+        }
+    }
+
+    Ok(executed)
+}
+
+/// Name of the maintenance action, used for logging
+pub fn action(msg: &ExecuteMsg) -> &'static str {
+    match msg {
+        ExecuteMsg::Harvest {
+            ..
+        } => "harvest",
+        ExecuteMsg::TuneDelegations {} => "tune_delegations",
+        ExecuteMsg::Rebalance {
+            ..
+        } => "rebalance",
+        ExecuteMsg::Reconcile {} => "reconcile",
+        ExecuteMsg::CheckSlashing {
+            ..
+        } => "check_slashing",
+        ExecuteMsg::SubmitBatch {
+            ..
+        } => "submit_batch",
+        _ => "other",
+    }
+}
+
+/// Compares the delegations recorded by the hub with the ones on the alliance module. The hub
+/// requires an entry for every recorded validator, so validators without a delegation left are
+/// reported with zero.
+fn check_slashing_msg(
+    chain: &impl KeeperChain,
+    config: &KeeperConfig,
+    utoken: &str,
+    state: &StateResponse,
+    delegations: &DelegationsResponse,
+) -> KeeperResult<Option<ExecuteMsg>> {
+    let current = delegations
+        .delegations
+        .iter()
+        .map(|(validator, _)| {
+            let amount = chain.query_alliance_delegation(&config.hub, validator, utoken)?;
+            Ok((validator.clone(), amount))
+        })
+        .collect::<KeeperResult<Vec<(String, Uint128)>>>()?;
+
+    let actual: Uint128 = current.iter().map(|(_, amount)| *amount).sum();
+    let expected = state.total_utoken;
+
+    if actual + config.slashing_tolerance >= expected {
+        return Ok(None);
+    }
+
+    if actual < expected.saturating_sub(expected * config.max_slashing) {
+        return Err(KeeperError::BigSlash {
+            expected,
+            actual,
+        });
+    }
+
+    Ok(Some(ExecuteMsg::CheckSlashing {
+        state_total_utoken_bonded: expected,
+        delegations: current,
+    }))
+}
+
+fn is_reconcile_due(
+    chain: &impl KeeperChain,
+    config: &KeeperConfig,
+    now: u64,
+) -> KeeperResult<bool> {
+    let mut start_after = None;
+    loop {
+        let batches: Vec<Batch> = chain.query_hub(
+            &config.hub,
+            &QueryMsg::PreviousBatches {
+                start_after,
+                limit: Some(BATCH_PAGE_LIMIT),
+            },
+        )?;
+
+        if batches.iter().any(|batch| !batch.reconciled && now > batch.est_unbond_end_time) {
+            return Ok(true);
+        }
+
+        match batches.last() {
+            Some(last) if batches.len() == BATCH_PAGE_LIMIT as usize => start_after = Some(last.id),
+            _ => return Ok(false),
+        }
+    }
+}
+
+fn is_harvest_due(
+    chain: &impl KeeperChain,
+    config: &KeeperConfig,
+    state: &StateResponse,
+    now: u64,
+) -> KeeperResult<bool> {
+    if state.total_utoken.is_zero() {
+        return Ok(false);
+    }
+
+    // every harvest stores the exchange rate, the latest one is returned first
+    let rates: ExchangeRatesResponse = chain.query_hub(
+        &config.hub,
+        &QueryMsg::ExchangeRates {
+            start_after: None,
+            limit: Some(1),
+        },
+    )?;
+
+    Ok(match rates.exchange_rates.first() {
+        Some((last_harvest, _)) => now >= last_harvest + config.harvest_interval,
+        None => true,
+    })
+}
+
+fn is_submit_batch_due(pending_batch: &PendingBatch, now: u64) -> bool {
+    !pending_batch.ustake_to_burn.is_zero() && now >= pending_batch.est_unbond_start_time
+}
+
+/// Gauges change every period, defined shares only need to be applied once.
+fn is_tune_due(
+    strategy: &DelegationStrategy,
+    wanted: &WantedDelegationsResponse,
+    now: u64,
+) -> KeeperResult<bool> {
+    Ok(match (strategy, wanted.tune_time_period) {
+        (DelegationStrategy::Uniform, _) => false,
+        (_, None) => true,
+        (
+            DelegationStrategy::Gauges {
+                ..
+            },
+            Some((_, period)),
+        ) => period < get_period(now)?,
+        (
+            DelegationStrategy::Defined {
+                ..
+            },
+            Some(_),
+        ) => false,
+    })
+}
+
+fn is_rebalance_due(
+    config: &KeeperConfig,
+    delegations: &DelegationsResponse,
+    wanted: &WantedDelegationsResponse,
+) -> bool {
+    let wanted: HashMap<&String, Uint128> =
+        wanted.delegations.iter().map(|(validator, amount)| (validator, *amount)).collect();
+    let min_redelegation = config.min_redelegation.max(Uint128::one());
+
+    delegations.delegations.iter().any(|(validator, amount)| {
+        let goal = wanted.get(validator).copied().unwrap_or_default();
+        amount.saturating_sub(goal) >= min_redelegation
+    })
+}
@@ -0,0 +1,14 @@
+//! Offline keeper for the alliance-lst hub. Decides which maintenance messages (`Harvest`,
+//! `SubmitBatch`, `Reconcile`, `TuneDelegations`, `Rebalance` and `CheckSlashing`) are due, based
+//! on the queries of the hub itself, and broadcasts them through a [`chain::KeeperChain`].
+
+pub mod chain;
+pub mod error;
+pub mod keeper;
+
+pub use chain::KeeperChain;
+pub use error::{KeeperError, KeeperResult};
+pub use keeper::{due_messages, run, KeeperConfig};
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,431 @@
+use std::collections::HashMap;
+
+use cosmwasm_std::{from_json, to_json_binary, Addr, Decimal, StdError, StdResult, Uint128};
+use eris::alliance_lst::{ConfigResponse, ExecuteMsg, QueryMsg};
+use eris::governance_helper::{EPOCH_START, WEEK};
+use eris::hub::{
+    Batch, DelegationStrategy, DelegationsResponse, ExchangeRatesResponse, FeeConfig, PendingBatch,
+    StateResponse, WantedDelegationsResponse,
+};
+use serde::de::DeserializeOwned;
+
+use crate::chain::KeeperChain;
+use crate::error::{KeeperError, KeeperResult};
+use crate::keeper::{due_messages, run, KeeperConfig};
+
+const HUB: &str = "hub";
+const UTOKEN: &str = "ibc/alliance";
+const NOW: u64 = EPOCH_START + 10 * WEEK + 100;
+
+struct MockChain {
+    time: u64,
+    strategy: DelegationStrategy,
+    state: StateResponse,
+    pending_batch: PendingBatch,
+    batches: Vec<Batch>,
+    exchange_rates: Vec<(u64, Decimal)>,
+    wanted: WantedDelegationsResponse,
+    /// delegations recorded by the hub
+    delegations: Vec<(String, Uint128)>,
+    /// delegations on the alliance module
+    chain_delegations: HashMap<String, Uint128>,
+    broadcasted: Vec<ExecuteMsg>,
+    /// broadcasts fail once this many messages have been broadcasted
+    broadcast_limit: Option<usize>,
+}
+
+impl MockChain {
+    /// A hub with 300 bonded across three validators, where nothing is due
+    fn new() -> Self {
+        let delegations = vec![
+            ("val1".to_string(), Uint128::new(100)),
+            ("val2".to_string(), Uint128::new(100)),
+            ("val3".to_string(), Uint128::new(100)),
+        ];
+
+        MockChain {
+            time: NOW,
+            strategy: DelegationStrategy::Uniform,
+            state: StateResponse {
+                total_ustake: Uint128::new(300),
+                total_utoken: Uint128::new(300),
+                exchange_rate: Decimal::one(),
+                unlocked_coins: vec![],
+                unbonding: Uint128::zero(),
+                available: Uint128::zero(),
+                tvl_utoken: Uint128::new(300),
+            },
+            pending_batch: PendingBatch {
+                id: 1,
+                ustake_to_burn: Uint128::zero(),
+                est_unbond_start_time: NOW + 100,
+            },
+            batches: vec![],
+            exchange_rates: vec![(NOW - 100, Decimal::one())],
+            wanted: WantedDelegationsResponse {
+                tune_time_period: None,
+                delegations: delegations.clone(),
+            },
+            chain_delegations: delegations.iter().cloned().collect(),
+            delegations,
+            broadcasted: vec![],
+            broadcast_limit: None,
+        }
+    }
+
+    fn config(&self) -> ConfigResponse {
+        ConfigResponse {
+            owner: "owner".to_string(),
+            new_owner: None,
+            utoken: UTOKEN.to_string(),
+            stake_token: "factory/hub/arbALLIANCE".to_string(),
+            epoch_period: 259200,
+            unbond_period: 1814400,
+            validators: self.delegations.iter().map(|(validator, _)| validator.clone()).collect(),
+            fee_config: FeeConfig {
+                protocol_fee_contract: Addr::unchecked("fee"),
+                protocol_reward_fee: Decimal::percent(1),
+            },
+            operator: "operator".to_string(),
+            vote_operator: None,
+            stages_preset: vec![],
+            withdrawals_preset: vec![],
+            allow_donations: false,
+            delegation_strategy: self.strategy.clone(),
+            validator_proxy: "proxy".to_string(),
+            undelegation_tolerance_bps: 0,
+            guardian: None,
+            timelock_delay: 0,
+            priority_unbond_fee_bps: 0,
+            priority_unbond_min_fee: Uint128::zero(),
+            claim_contracts: vec![],
+            tune_tip: None,
+            tune_tip_budget: Uint128::zero(),
+        }
+    }
+
+    fn query(&self, msg: &QueryMsg) -> StdResult<cosmwasm_std::Binary> {
+        match msg {
+            QueryMsg::Config {} => to_json_binary(&self.config()),
+            QueryMsg::State {} => to_json_binary(&self.state),
+            QueryMsg::PendingBatch {} => to_json_binary(&self.pending_batch),
+            QueryMsg::PreviousBatches {
+                start_after,
+                limit,
+            } => to_json_binary(
+                &self
+                    .batches
+                    .iter()
+                    .filter(|batch| start_after.is_none_or(|id| batch.id > id))
+                    .take(limit.unwrap_or(10).min(30) as usize)
+                    .cloned()
+                    .collect::<Vec<Batch>>(),
+            ),
+            QueryMsg::ExchangeRates {
+                limit,
+                ..
+            } => to_json_binary(&ExchangeRatesResponse {
+                exchange_rates: self
+                    .exchange_rates
+                    .iter()
+                    .rev()
+                    .take(limit.unwrap_or(10) as usize)
+                    .cloned()
+                    .collect(),
+                apr: None,
+            }),
+            QueryMsg::WantedDelegations {} => to_json_binary(&self.wanted),
+            QueryMsg::Delegations {} => to_json_binary(&DelegationsResponse {
+                delegations: self.delegations.clone(),
+            }),
+            _ => Err(StdError::generic_err("unexpected query")),
+        }
+    }
+}
+
+impl KeeperChain for MockChain {
+    fn block_time(&self) -> KeeperResult<u64> {
+        Ok(self.time)
+    }
+
+    fn query_hub<T: DeserializeOwned>(&self, hub: &str, msg: &QueryMsg) -> KeeperResult<T> {
+        assert_eq!(hub, HUB);
+        Ok(from_json(&self.query(msg)?)?)
+    }
+
+    fn query_alliance_delegation(
+        &self,
+        delegator: &str,
+        validator: &str,
+        denom: &str,
+    ) -> KeeperResult<Uint128> {
+        assert_eq!(delegator, HUB);
+        assert_eq!(denom, UTOKEN);
+        Ok(self.chain_delegations.get(validator).copied().unwrap_or_default())
+    }
+
+    fn broadcast(&mut self, hub: &str, msg: &ExecuteMsg) -> KeeperResult<String> {
+        assert_eq!(hub, HUB);
+        if self.broadcast_limit.is_some_and(|limit| self.broadcasted.len() >= limit) {
+            return Err(KeeperError::Chain("out of gas".to_string()));
+        }
+        self.broadcasted.push(msg.clone());
+        Ok(format!("TX{}", self.broadcasted.len()))
+    }
+}
+
+fn keeper_config() -> KeeperConfig {
+    KeeperConfig {
+        hub: HUB.to_string(),
+        harvest_interval: 3600,
+        min_redelegation: Uint128::new(10),
+        slashing_tolerance: Uint128::new(1),
+        max_slashing: Decimal::percent(5),
+    }
+}
+
+fn batch(id: u64, reconciled: bool, est_unbond_end_time: u64) -> Batch {
+    Batch {
+        id,
+        reconciled,
+        total_shares: Uint128::new(100),
+        utoken_unclaimed: Uint128::new(100),
+        est_unbond_end_time,
+    }
+}
+
+#[test]
+fn nothing_due() {
+    let chain = MockChain::new();
+    assert_eq!(due_messages(&chain, &keeper_config()).unwrap(), vec![]);
+}
+
+#[test]
+fn harvest_due_after_interval() {
+    let mut chain = MockChain::new();
+    chain.exchange_rates = vec![(NOW - 7200, Decimal::one()), (NOW - 3000, Decimal::one())];
+    assert_eq!(due_messages(&chain, &keeper_config()).unwrap(), vec![]);
+
+    chain.time = NOW + 600;
+    assert_eq!(
+        due_messages(&chain, &keeper_config()).unwrap(),
+        vec![ExecuteMsg::Harvest {
+            validators: None,
+            withdrawals: None,
+            stages: None,
+        }]
+    );
+
+    // never harvested
+    chain.exchange_rates = vec![];
+    assert_eq!(due_messages(&chain, &keeper_config()).unwrap().len(), 1);
+
+    // nothing bonded, nothing to harvest
+    chain.state.total_utoken = Uint128::zero();
+    chain.delegations = vec![];
+    assert_eq!(due_messages(&chain, &keeper_config()).unwrap(), vec![]);
+}
+
+#[test]
+fn submit_batch_due_after_start_time() {
+    let mut chain = MockChain::new();
+    chain.pending_batch.ustake_to_burn = Uint128::new(50);
+    assert_eq!(due_messages(&chain, &keeper_config()).unwrap(), vec![]);
+
+    chain.time = NOW + 100;
+    assert_eq!(
+        due_messages(&chain, &keeper_config()).unwrap(),
+        vec![ExecuteMsg::SubmitBatch {
+            undelegations: None,
+        }]
+    );
+
+    // empty batches are not submitted
+    chain.pending_batch.ustake_to_burn = Uint128::zero();
+    assert_eq!(due_messages(&chain, &keeper_config()).unwrap(), vec![]);
+}
+
+#[test]
+fn reconcile_due_for_finished_batches() {
+    let mut chain = MockChain::new();
+    // more batches than a single page, only the last one is still waiting for reconciliation
+    chain.batches = (1..=40).map(|id| batch(id, true, NOW - 1000)).collect();
+    chain.batches.push(batch(41, false, NOW));
+    assert_eq!(due_messages(&chain, &keeper_config()).unwrap(), vec![]);
+
+    chain.time = NOW + 1;
+    assert_eq!(due_messages(&chain, &keeper_config()).unwrap(), vec![ExecuteMsg::Reconcile {}]);
+}
+
+#[test]
+fn check_slashing_reports_current_delegations() {
+    let mut chain = MockChain::new();
+    // within tolerance
+    chain.chain_delegations.insert("val1".to_string(), Uint128::new(99));
+    assert_eq!(due_messages(&chain, &keeper_config()).unwrap(), vec![]);
+
+    // val3 has no delegation left on the chain
+    chain.chain_delegations.insert("val1".to_string(), Uint128::new(95));
+    chain.chain_delegations.remove("val3");
+    chain.state.total_utoken = Uint128::new(200);
+    chain.delegations.truncate(2);
+    chain.delegations.push(("val3".to_string(), Uint128::zero()));
+    chain.wanted.delegations = chain.delegations.clone();
+    chain.pending_batch.ustake_to_burn = Uint128::new(50);
+    chain.time = NOW + 100;
+
+    // slashing is checked before the batch is submitted
+    assert_eq!(
+        due_messages(&chain, &keeper_config()).unwrap(),
+        vec![
+            ExecuteMsg::CheckSlashing {
+                state_total_utoken_bonded: Uint128::new(200),
+                delegations: vec![
+                    ("val1".to_string(), Uint128::new(95)),
+                    ("val2".to_string(), Uint128::new(100)),
+                    ("val3".to_string(), Uint128::zero()),
+                ],
+            },
+            ExecuteMsg::SubmitBatch {
+                undelegations: None,
+            },
+        ]
+    );
+}
+
+#[test]
+fn big_slash_is_reported_as_error() {
+    let mut chain = MockChain::new();
+    chain.chain_delegations.insert("val1".to_string(), Uint128::new(80));
+
+    let err = due_messages(&chain, &keeper_config()).unwrap_err();
+    assert_eq!(
+        err,
+        KeeperError::BigSlash {
+            expected: Uint128::new(300),
+            actual: Uint128::new(280),
+        }
+    );
+}
+
+#[test]
+fn max_slashing_is_configurable() {
+    let mut chain = MockChain::new();
+    chain.chain_delegations.insert("val1".to_string(), Uint128::new(80));
+
+    let config = KeeperConfig {
+        max_slashing: Decimal::percent(10),
+        ..keeper_config()
+    };
+    assert_eq!(
+        due_messages(&chain, &config).unwrap(),
+        vec![ExecuteMsg::CheckSlashing {
+            state_total_utoken_bonded: Uint128::new(300),
+            delegations: vec![
+                ("val1".to_string(), Uint128::new(80)),
+                ("val2".to_string(), Uint128::new(100)),
+                ("val3".to_string(), Uint128::new(100)),
+            ],
+        }]
+    );
+}
+
+#[test]
+fn tune_due_for_gauges_in_new_period() {
+    let mut chain = MockChain::new();
+    chain.strategy = DelegationStrategy::Gauges {
+        amp_gauges: "amp_gauges".to_string(),
+        emp_gauges: None,
+        amp_factor_bps: 10000,
+        min_delegation_bps: 0,
+        max_delegation_bps: 10000,
+        validator_count: 3,
+    };
+    chain.wanted.tune_time_period = Some((NOW - WEEK, 9));
+    assert_eq!(
+        due_messages(&chain, &keeper_config()).unwrap(),
+        vec![ExecuteMsg::TuneDelegations {}]
+    );
+
+    chain.wanted.tune_time_period = Some((NOW - 10, 10));
+    assert_eq!(due_messages(&chain, &keeper_config()).unwrap(), vec![]);
+
+    // defined shares are tuned once
+    chain.strategy = DelegationStrategy::Defined {
+        shares_bps: vec![("val1".to_string(), 10000)],
+    };
+    chain.wanted.tune_time_period = None;
+    assert_eq!(
+        due_messages(&chain, &keeper_config()).unwrap(),
+        vec![ExecuteMsg::TuneDelegations {}]
+    );
+    chain.wanted.tune_time_period = Some((NOW - WEEK, 9));
+    assert_eq!(due_messages(&chain, &keeper_config()).unwrap(), vec![]);
+}
+
+#[test]
+fn rebalance_due_above_min_redelegation() {
+    let mut chain = MockChain::new();
+    chain.wanted.delegations = vec![
+        ("val1".to_string(), Uint128::new(91)),
+        ("val2".to_string(), Uint128::new(100)),
+        ("val3".to_string(), Uint128::new(109)),
+    ];
+    assert_eq!(due_messages(&chain, &keeper_config()).unwrap(), vec![]);
+
+    // val1 is no longer wanted
+    chain.wanted.delegations =
+        vec![("val2".to_string(), Uint128::new(150)), ("val3".to_string(), Uint128::new(150))];
+    assert_eq!(
+        due_messages(&chain, &keeper_config()).unwrap(),
+        vec![ExecuteMsg::Rebalance {
+            min_redelegation: Some(Uint128::new(10)),
+        }]
+    );
+}
+
+#[test]
+fn run_broadcasts_due_messages() {
+    let mut chain = MockChain::new();
+    chain.exchange_rates = vec![];
+    chain.batches = vec![batch(1, false, NOW - 1)];
+
+    let executed = run(&mut chain, &keeper_config()).unwrap();
+    assert_eq!(
+        executed,
+        vec![
+            ("reconcile".to_string(), "TX1".to_string()),
+            ("harvest".to_string(), "TX2".to_string())
+        ]
+    );
+    assert_eq!(chain.broadcasted.len(), 2);
+
+    chain.broadcast_limit = Some(2);
+    let err = run(&mut chain, &keeper_config()).unwrap_err();
+    assert_eq!(
+        err,
+        KeeperError::Broadcast {
+            action: "reconcile".to_string(),
+            reason: "Chain error: out of gas".to_string(),
+            executed: vec![],
+        }
+    );
+}
+
+#[test]
+fn run_returns_executed_actions_on_failure() {
+    let mut chain = MockChain::new();
+    chain.exchange_rates = vec![];
+    chain.batches = vec![batch(1, false, NOW - 1)];
+    chain.broadcast_limit = Some(1);
+
+    let err = run(&mut chain, &keeper_config()).unwrap_err();
+    assert_eq!(
+        err,
+        KeeperError::Broadcast {
+            action: "harvest".to_string(),
+            reason: "Chain error: out of gas".to_string(),
+            executed: vec![("reconcile".to_string(), "TX1".to_string())],
+        }
+    );
+}
@@ -1,9 +1,12 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{to_json_binary, Addr, Coin, CosmosMsg, StdResult, Uint128, WasmMsg};
+use cosmwasm_std::{to_json_binary, Addr, Coin, CosmosMsg, Decimal, StdResult, Uint128, WasmMsg};
 use kujira::{denom::Denom, msg::KujiraMsg};
 
 #[cw_serde]
 pub enum BowExecuteMsg {
+    Deposit {
+        max_slippage: Option<Decimal>,
+    },
     Withdraw {},
 }
 
@@ -11,6 +14,29 @@ pub enum BowExecuteMsg {
 pub struct BowVault(pub Addr);
 
 impl BowVault {
+    pub fn deposit_msg(
+        &self,
+        assets: Vec<(Denom, Uint128)>,
+        max_slippage: Option<Decimal>,
+    ) -> StdResult<CosmosMsg<KujiraMsg>> {
+        let mut funds: Vec<Coin> = assets
+            .into_iter()
+            .map(|(denom, amount)| Coin {
+                amount,
+                denom: denom.to_string(),
+            })
+            .collect();
+        funds.sort_by(|a, b| a.denom.cmp(&b.denom));
+
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.0.to_string(),
+            funds,
+            msg: to_json_binary(&BowExecuteMsg::Deposit {
+                max_slippage,
+            })?,
+        }))
+    }
+
     pub fn withdraw_msg(&self, denom: Denom, amount: Uint128) -> StdResult<CosmosMsg<KujiraMsg>> {
         Ok(CosmosMsg::Wasm(WasmMsg::Execute {
             contract_addr: self.0.to_string(),
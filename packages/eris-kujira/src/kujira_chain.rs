@@ -1,4 +1,4 @@
-use cosmwasm_std::{coin, Addr, CosmosMsg, Decimal, StdResult, Uint128};
+use cosmwasm_std::{coin, Addr, CosmosMsg, Decimal, QuerierWrapper, StdError, StdResult, Uint128};
 use eris_chain_shared::chain_trait::ChainInterface;
 use kujira::msg::DenomMsg;
 
@@ -61,6 +61,27 @@ impl ChainInterface<CustomMsgType, DenomType, WithdrawType, StageType, HubChainC
         }
     }
 
+    fn create_provide_liquidity_msg<F>(
+        &self,
+        _querier: &QuerierWrapper,
+        _get_chain_config: F,
+        pool: WithdrawType,
+        assets: Vec<(DenomType, Uint128)>,
+        slippage_tolerance: Option<Decimal>,
+    ) -> StdResult<Vec<CosmosMsg<CustomMsgType>>>
+    where
+        F: FnOnce() -> StdResult<HubChainConfig>,
+    {
+        match pool {
+            WithdrawType::BlackWhale {
+                ..
+            } => Err(StdError::generic_err("providing liquidity to BlackWhale not supported")),
+            WithdrawType::Bow {
+                addr,
+            } => Ok(vec![BowVault(addr).deposit_msg(assets, slippage_tolerance)?]),
+        }
+    }
+
     fn create_single_stage_swap_msgs<F>(
         &self,
         _get_chain_config: F,
@@ -1,9 +1,10 @@
-use cosmwasm_std::{Addr, CosmosMsg, Decimal, StdResult, Uint128};
-use eris_chain_shared::chain_trait::ChainInterface;
+use cosmwasm_std::{Addr, CosmosMsg, Decimal, QuerierWrapper, StdResult, Uint128};
+use eris_chain_shared::chain_trait::{ChainInterface, DenomMetadata};
+use eris_chain_shared::denom_metadata::create_set_metadata_msg;
 
 use crate::{
     adapters::whitewhaledex::WhiteWhalePair,
-    denom::{MsgBurn, MsgCreateDenom, MsgMint},
+    denom::{MsgBurn, MsgCreateDenom, MsgMint, MSG_SET_DENOM_METADATA_TYPE_URL},
     types::{CustomMsgType, DenomType, HubChainConfig, StageType, WithdrawType},
 };
 
@@ -54,6 +55,19 @@ impl ChainInterface<CustomMsgType, DenomType, WithdrawType, StageType, HubChainC
         .into()
     }
 
+    fn create_set_metadata_msg(
+        &self,
+        full_denom: String,
+        metadata: DenomMetadata,
+    ) -> Option<CosmosMsg<CustomMsgType>> {
+        Some(create_set_metadata_msg(
+            MSG_SET_DENOM_METADATA_TYPE_URL,
+            self.contract.to_string(),
+            full_denom,
+            metadata,
+        ))
+    }
+
     fn create_withdraw_msg<F>(
         &self,
         _get_chain_config: F,
@@ -71,6 +85,24 @@ impl ChainInterface<CustomMsgType, DenomType, WithdrawType, StageType, HubChainC
         }
     }
 
+    fn create_provide_liquidity_msg<F>(
+        &self,
+        _querier: &QuerierWrapper,
+        _get_chain_config: F,
+        pool: WithdrawType,
+        assets: Vec<(DenomType, Uint128)>,
+        slippage_tolerance: Option<Decimal>,
+    ) -> StdResult<Vec<CosmosMsg<CustomMsgType>>>
+    where
+        F: FnOnce() -> StdResult<HubChainConfig>,
+    {
+        match pool {
+            WithdrawType::Dex {
+                addr,
+            } => WhiteWhalePair(addr).provide_liquidity_msg(assets, slippage_tolerance),
+        }
+    }
+
     fn create_single_stage_swap_msgs<F>(
         &self,
         _get_chain_config: F,
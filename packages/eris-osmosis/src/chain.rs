@@ -1,15 +1,20 @@
 use std::ops::Div;
+use std::str::FromStr;
 
-use cosmwasm_std::{coins, StdError};
+use cosmwasm_std::{coins, QuerierWrapper, StdError};
 use cosmwasm_std::{Addr, CosmosMsg, Decimal, StdResult, Uint128};
-use eris_chain_shared::chain_trait::ChainInterface;
+use eris_chain_shared::chain_trait::{ChainInterface, DenomMetadata};
+use eris_chain_shared::denom_metadata::create_set_metadata_msg;
 use osmosis_std::types::cosmos::base::v1beta1::Coin;
+use osmosis_std::types::osmosis::gamm::v1beta1::GammQuerier;
 use osmosis_std::types::osmosis::gamm::v1beta1::MsgExitPool;
+use osmosis_std::types::osmosis::gamm::v1beta1::MsgJoinPool;
 use osmosis_std::types::osmosis::gamm::v1beta1::MsgSwapExactAmountIn;
 use osmosis_std::types::osmosis::poolmanager::v1beta1::SwapAmountInRoute;
 use osmosis_std::types::osmosis::tokenfactory::v1beta1::MsgBurn;
 use osmosis_std::types::osmosis::tokenfactory::v1beta1::MsgCreateDenom;
 use osmosis_std::types::osmosis::tokenfactory::v1beta1::MsgMint;
+use osmosis_std::types::osmosis::tokenfactory::v1beta1::MsgSetDenomMetadata;
 
 use crate::types::{CustomMsgType, DenomType, HubChainConfig, StageType, WithdrawType};
 
@@ -64,6 +69,19 @@ impl ChainInterface<CustomMsgType, DenomType, WithdrawType, StageType, HubChainC
         .into()
     }
 
+    fn create_set_metadata_msg(
+        &self,
+        full_denom: String,
+        metadata: DenomMetadata,
+    ) -> Option<CosmosMsg<CustomMsgType>> {
+        Some(create_set_metadata_msg(
+            MsgSetDenomMetadata::TYPE_URL,
+            self.contract.to_string(),
+            full_denom,
+            metadata,
+        ))
+    }
+
     fn create_withdraw_msg<F>(
         &self,
         _get_chain_config: F,
@@ -96,6 +114,63 @@ impl ChainInterface<CustomMsgType, DenomType, WithdrawType, StageType, HubChainC
         }
     }
 
+    fn create_provide_liquidity_msg<F>(
+        &self,
+        querier: &QuerierWrapper,
+        _get_chain_config: F,
+        pool: WithdrawType,
+        assets: Vec<(DenomType, Uint128)>,
+        slippage_tolerance: Option<Decimal>,
+    ) -> StdResult<Vec<CosmosMsg<CustomMsgType>>>
+    where
+        F: FnOnce() -> StdResult<HubChainConfig>,
+    {
+        // the shares are computed from the current reserves, the pool can't move in between
+        if slippage_tolerance.is_some() {
+            return Err(StdError::generic_err("slippage_tolerance not supported"));
+        }
+
+        match pool {
+            WithdrawType::Withdraw {
+                pool_id,
+                ..
+            } => {
+                let gamm = GammQuerier::new(querier);
+                let total_shares = gamm
+                    .total_shares(pool_id)?
+                    .total_shares
+                    .ok_or_else(|| StdError::generic_err("pool has no shares"))?;
+                let reserves = gamm
+                    .total_pool_liquidity(pool_id)?
+                    .liquidity
+                    .iter()
+                    .map(|reserve| Ok((reserve.denom.clone(), parse_amount(reserve)?)))
+                    .collect::<StdResult<Vec<_>>>()?;
+
+                let share_out_amount =
+                    compute_join_pool_shares(&reserves, parse_amount(&total_shares)?, &assets)?;
+
+                // the pool takes at most the provided assets, otherwise the join fails
+                let mut token_in_maxs = assets
+                    .into_iter()
+                    .map(|(denom, amount)| Coin {
+                        denom,
+                        amount: amount.to_string(),
+                    })
+                    .collect::<Vec<_>>();
+                token_in_maxs.sort_by(|a, b| a.denom.cmp(&b.denom));
+
+                Ok(vec![MsgJoinPool {
+                    sender: self.contract.to_string(),
+                    pool_id,
+                    share_out_amount: share_out_amount.to_string(),
+                    token_in_maxs,
+                }
+                .into()])
+            },
+        }
+    }
+
     fn create_single_stage_swap_msgs<F>(
         &self,
         _get_chain_config: F,
@@ -145,3 +220,35 @@ impl ChainInterface<CustomMsgType, DenomType, WithdrawType, StageType, HubChainC
         }
     }
 }
+
+fn parse_amount(coin: &Coin) -> StdResult<Uint128> {
+    Uint128::from_str(&coin.amount)
+}
+
+/// Shares received for joining the pool without a swap: the provided asset with the smallest share
+/// of its reserve limits the shares, the surplus of the other assets is not used.
+pub fn compute_join_pool_shares(
+    reserves: &[(DenomType, Uint128)],
+    total_shares: Uint128,
+    assets: &[(DenomType, Uint128)],
+) -> StdResult<Uint128> {
+    let mut share_out_amount: Option<Uint128> = None;
+
+    for (reserve_denom, reserve_amount) in reserves {
+        let provided = assets
+            .iter()
+            .find(|(denom, _)| denom == reserve_denom)
+            .map(|(_, amount)| *amount)
+            .unwrap_or_default();
+        if reserve_amount.is_zero() {
+            return Err(StdError::generic_err(format!("pool has no {0} reserve", reserve_denom)));
+        }
+        let shares = provided.multiply_ratio(total_shares, *reserve_amount);
+        share_out_amount = Some(share_out_amount.map_or(shares, |current| current.min(shares)));
+    }
+
+    match share_out_amount {
+        Some(share_out_amount) if !share_out_amount.is_zero() => Ok(share_out_amount),
+        _ => Err(StdError::generic_err("provided assets don't receive any pool shares")),
+    }
+}
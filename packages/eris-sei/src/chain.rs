@@ -1,4 +1,4 @@
-use cosmwasm_std::{coin, coins, Coin, StdError};
+use cosmwasm_std::{coin, coins, Coin, QuerierWrapper, StdError};
 use cosmwasm_std::{Addr, CosmosMsg, Decimal, StdResult, Uint128};
 use eris_chain_shared::chain_trait::ChainInterface;
 use sei_cosmwasm::SeiMsg;
@@ -58,6 +58,20 @@ impl ChainInterface<CustomMsgType, DenomType, WithdrawType, StageType, HubChainC
         Err(StdError::generic_err("not supported"))
     }
 
+    fn create_provide_liquidity_msg<F>(
+        &self,
+        _querier: &QuerierWrapper,
+        _get_chain_config: F,
+        _pool: WithdrawType,
+        _assets: Vec<(DenomType, Uint128)>,
+        _slippage_tolerance: Option<Decimal>,
+    ) -> StdResult<Vec<CosmosMsg<CustomMsgType>>>
+    where
+        F: FnOnce() -> StdResult<HubChainConfig>,
+    {
+        Err(StdError::generic_err("not supported"))
+    }
+
     fn create_single_stage_swap_msgs<F>(
         &self,
         _get_chain_config: F,
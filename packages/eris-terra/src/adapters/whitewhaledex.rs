@@ -1,6 +1,7 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{coins, to_json_binary, Addr, CosmosMsg, Decimal, StdResult, Uint128, WasmMsg};
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use eris_chain_shared::pair::{create_pair_provide_liquidity_msgs, PairAsset, PairAssetInfo};
 
 use crate::types::{CustomMsgType, DenomType};
 
@@ -31,6 +32,12 @@ pub enum ExecuteMsg {
         max_spread: Option<Decimal>,
         to: Option<String>,
     },
+    /// Provides liquidity to the pool. Also accepted by Astroport pairs, as auto_stake is optional.
+    ProvideLiquidity {
+        assets: Vec<Asset>,
+        slippage_tolerance: Option<Decimal>,
+        receiver: Option<String>,
+    },
     /// Withdraws liquidity
     WithdrawLiquidity {},
 }
@@ -120,4 +127,31 @@ impl WhiteWhalePair {
             })),
         }
     }
+
+    pub fn provide_liquidity_msg(
+        &self,
+        assets: Vec<(DenomType, Uint128)>,
+        slippage_tolerance: Option<Decimal>,
+    ) -> StdResult<Vec<CosmosMsg<CustomMsgType>>> {
+        let assets = assets
+            .into_iter()
+            .map(|(denom, amount)| PairAsset {
+                info: match denom {
+                    astroport::asset::AssetInfo::Token {
+                        contract_addr,
+                    } => PairAssetInfo::Token {
+                        contract_addr: contract_addr.to_string(),
+                    },
+                    astroport::asset::AssetInfo::NativeToken {
+                        denom,
+                    } => PairAssetInfo::NativeToken {
+                        denom,
+                    },
+                },
+                amount,
+            })
+            .collect();
+
+        create_pair_provide_liquidity_msgs(&self.0, assets, slippage_tolerance)
+    }
 }
@@ -1,5 +1,6 @@
 use cosmwasm_std::{
-    coins, to_json_binary, Addr, CosmosMsg, Decimal, StdError, StdResult, Uint128, WasmMsg,
+    coins, to_json_binary, Addr, CosmosMsg, Decimal, QuerierWrapper, StdError, StdResult, Uint128,
+    WasmMsg,
 };
 use eris_chain_shared::chain_trait::ChainInterface;
 
@@ -70,6 +71,24 @@ impl ChainInterface<CustomMsgType, DenomType, WithdrawType, StageType, HubChainC
         }
     }
 
+    fn create_provide_liquidity_msg<F>(
+        &self,
+        _querier: &QuerierWrapper,
+        _get_chain_config: F,
+        pool: WithdrawType,
+        assets: Vec<(DenomType, Uint128)>,
+        slippage_tolerance: Option<Decimal>,
+    ) -> StdResult<Vec<CosmosMsg<CustomMsgType>>>
+    where
+        F: FnOnce() -> StdResult<HubChainConfig>,
+    {
+        match pool {
+            WithdrawType::Dex {
+                addr,
+            } => WhiteWhalePair(addr).provide_liquidity_msg(assets, slippage_tolerance),
+        }
+    }
+
     fn create_single_stage_swap_msgs<F>(
         &self,
         _get_chain_config: F,
@@ -19,7 +19,10 @@ use eris::arb_vault::LsdConfig;
 // use eris::arb_vault::LsdConfig;
 use eris_chain_adapter::types::{test_chain_config, CustomMsgType, CustomQueryType};
 
-use crate::{arb_contract, modules::types::UsedCustomModule};
+use crate::{
+    arb_contract,
+    modules::{stargate::StargateModule, types::UsedCustomModule},
+};
 
 pub const MULTIPLIER: u64 = 1_000_000;
 
@@ -60,6 +63,8 @@ pub struct BaseErisTestPackage {
     pub voting_escrow: ContractInfoWrapper,
     pub emp_gauges: ContractInfoWrapper,
     pub amp_gauges: ContractInfoWrapper,
+    pub amp_bribes: ContractInfoWrapper,
+    pub lp_gauges: ContractInfoWrapper,
     pub prop_gauges: ContractInfoWrapper,
     // pub amp_lp: ContractInfoWrapper,
 
@@ -92,6 +97,20 @@ pub type CustomApp = App<
     FailingModule<GovMsg, Empty, Empty>,
 >;
 
+/// App routing the stargate messages to the mocked alliance and tokenfactory modules.
+pub type AllianceApp = App<
+    BankKeeper,
+    MockApi,
+    MockStorage,
+    UsedCustomModule,
+    WasmKeeper<CustomMsgType, CustomQueryType>,
+    StakeKeeper,
+    DistributionKeeper,
+    FailingModule<IbcMsg, IbcQuery, Empty>,
+    FailingModule<GovMsg, Empty, Empty>,
+    StargateModule,
+>;
+
 impl BaseErisTestPackage {
     pub fn init_all(router: &mut CustomApp, msg: BaseErisTestInitMessage) -> Self {
         let mut base_pack = BaseErisTestPackage {
@@ -103,6 +122,8 @@ impl BaseErisTestPackage {
             // amp_lp: None.into(),
             emp_gauges: None.into(),
             amp_gauges: None.into(),
+            amp_bribes: None.into(),
+            lp_gauges: None.into(),
             amp_token: None.into(),
             prop_gauges: None.into(),
             arb_vault: None.into(),
@@ -119,6 +140,8 @@ impl BaseErisTestPackage {
         base_pack.init_voting_escrow(router, msg.owner.clone());
         base_pack.init_emp_gauges(router, msg.owner.clone());
         base_pack.init_amp_gauges(router, msg.owner.clone());
+        base_pack.init_amp_bribes(router, msg.owner.clone());
+        base_pack.init_lp_gauges(router, msg.owner.clone());
 
         base_pack.init_not_supported(router, msg.owner.clone());
 
@@ -312,6 +335,60 @@ impl BaseErisTestPackage {
         .into()
     }
 
+    fn init_amp_bribes(&mut self, router: &mut CustomApp, owner: Addr) {
+        let contract = Box::new(ContractWrapper::new_with_empty(
+            eris_gov_amp_bribes::contract::execute,
+            eris_gov_amp_bribes::contract::instantiate,
+            eris_gov_amp_bribes::contract::query,
+        ));
+
+        let code_id = router.store_code(contract);
+
+        let msg = eris::amp_bribes::InstantiateMsg {
+            owner: owner.to_string(),
+            amp_gauges_addr: self.amp_gauges.get_address_string(),
+            claim_periods: 4,
+            allowed_denoms: vec!["uluna".to_string()],
+        };
+
+        let instance = router
+            .instantiate_contract(code_id, owner, &msg, &[], String::from("amp-bribes"), None)
+            .unwrap();
+
+        self.amp_bribes = Some(ContractInfo {
+            address: instance,
+            code_id,
+        })
+        .into()
+    }
+
+    fn init_lp_gauges(&mut self, router: &mut CustomApp, owner: Addr) {
+        let contract = Box::new(ContractWrapper::new_with_empty(
+            eris_gov_lp_gauges::contract::execute,
+            eris_gov_lp_gauges::contract::instantiate,
+            eris_gov_lp_gauges::contract::query,
+        ));
+
+        let code_id = router.store_code(contract);
+
+        let msg = eris::lp_gauges::InstantiateMsg {
+            owner: owner.to_string(),
+            escrow_addr: self.voting_escrow.get_address_string(),
+            targets: vec!["lp1".to_string(), "lp2".to_string(), "lp3".to_string()],
+            targets_limit: 2,
+        };
+
+        let instance = router
+            .instantiate_contract(code_id, owner, &msg, &[], String::from("lp-gauges"), None)
+            .unwrap();
+
+        self.lp_gauges = Some(ContractInfo {
+            address: instance,
+            code_id,
+        })
+        .into()
+    }
+
     #[cfg(not(feature = "X-sei-X"))]
     fn init_prop_gauges(&mut self, router: &mut CustomApp, owner: Addr) {
         let contract = Box::new(ContractWrapper::new(
@@ -328,6 +405,8 @@ impl BaseErisTestPackage {
             escrow_addr: self.voting_escrow.get_address_string(),
             quorum_bps: 500,
             use_weighted_vote: false,
+            sync_buffer_s: 3600,
+            alliance_lst_addr: None,
         };
 
         let instance = router
@@ -693,7 +772,13 @@ impl BaseErisTestPackage {
                 self.voting_escrow.get_address(),
                 &eris::voting_escrow::ExecuteMsg::UpdateConfig {
                     new_guardian: None,
-                    push_update_contracts: Some(vec![self.amp_gauges.get_address_string()]),
+                    push_update_contracts: Some(vec![
+                        self.amp_gauges.get_address_string(),
+                        self.lp_gauges.get_address_string(),
+                    ]),
+                    decommissioned: None,
+                    early_withdraw_penalty: None,
+                    penalty_recipient: None,
                 },
                 &[],
             )
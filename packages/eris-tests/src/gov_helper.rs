@@ -2,7 +2,9 @@ use std::str::FromStr;
 
 use anyhow::Result;
 use astroport::asset::native_asset;
-use cosmwasm_std::{coin, coins, Addr, Delegation, FullDelegation, StdResult, Uint128, VoteOption};
+use cosmwasm_std::{
+    coin, coins, Addr, Coin, Delegation, FullDelegation, StdResult, Uint128, VoteOption,
+};
 use cw_multi_test::{AppResponse, Executor};
 use eris::{
     arb_vault::{LsdConfig, LsdType},
@@ -131,6 +133,36 @@ impl EscrowHelper {
         )
     }
 
+    pub fn emp_query_grant(
+        &self,
+        router_ref: &mut CustomApp,
+        id: u64,
+    ) -> StdResult<eris::emp_gauges::EmpGrantResponse> {
+        router_ref.wrap().query_wasm_smart(
+            self.base.emp_gauges.get_address_string(),
+            &eris::emp_gauges::QueryMsg::EmpGrant {
+                id,
+            },
+        )
+    }
+
+    pub fn emp_query_grants(
+        &self,
+        router_ref: &mut CustomApp,
+        validator_addr: impl Into<String>,
+        period: Option<u64>,
+    ) -> StdResult<Vec<eris::emp_gauges::EmpGrantResponse>> {
+        router_ref.wrap().query_wasm_smart(
+            self.base.emp_gauges.get_address_string(),
+            &eris::emp_gauges::QueryMsg::EmpGrants {
+                validator_addr: validator_addr.into(),
+                period,
+                start_after: None,
+                limit: None,
+            },
+        )
+    }
+
     pub fn amp_tune(&self, router_ref: &mut CustomApp) -> Result<AppResponse> {
         router_ref.execute_contract(
             self.owner.clone(),
@@ -207,6 +239,19 @@ impl EscrowHelper {
         )
     }
 
+    pub fn amp_query_user_info(
+        &self,
+        router_ref: &mut CustomApp,
+        user: impl Into<String>,
+    ) -> StdResult<eris::amp_gauges::UserInfoResponse> {
+        router_ref.wrap().query_wasm_smart(
+            self.base.amp_gauges.get_address_string(),
+            &eris::amp_gauges::QueryMsg::UserInfo {
+                user: user.into(),
+            },
+        )
+    }
+
     pub fn amp_query_tune_info(
         &self,
         router_ref: &mut CustomApp,
@@ -217,6 +262,140 @@ impl EscrowHelper {
         )
     }
 
+    pub fn bribe_execute_sender(
+        &self,
+        router_ref: &mut CustomApp,
+        execute: eris::amp_bribes::ExecuteMsg,
+        sender: impl Into<String>,
+        funds: &[Coin],
+    ) -> Result<AppResponse> {
+        router_ref.execute_contract(
+            Addr::unchecked(sender),
+            self.base.amp_bribes.get_address(),
+            &execute,
+            funds,
+        )
+    }
+
+    pub fn bribe_query_info(
+        &self,
+        router_ref: &mut CustomApp,
+        validator_addr: impl Into<String>,
+        period: u64,
+    ) -> StdResult<eris::amp_bribes::BribeInfoResponse> {
+        router_ref.wrap().query_wasm_smart(
+            self.base.amp_bribes.get_address_string(),
+            &eris::amp_bribes::QueryMsg::BribeInfo {
+                validator_addr: validator_addr.into(),
+                period,
+            },
+        )
+    }
+
+    pub fn bribe_query_deposit(
+        &self,
+        router_ref: &mut CustomApp,
+        user: impl Into<String>,
+        validator_addr: impl Into<String>,
+        period: u64,
+    ) -> StdResult<Vec<Coin>> {
+        router_ref.wrap().query_wasm_smart(
+            self.base.amp_bribes.get_address_string(),
+            &eris::amp_bribes::QueryMsg::Deposit {
+                user: user.into(),
+                validator_addr: validator_addr.into(),
+                period,
+            },
+        )
+    }
+
+    pub fn bribe_query_claimable(
+        &self,
+        router_ref: &mut CustomApp,
+        user: impl Into<String>,
+        validator_addr: impl Into<String>,
+        period: u64,
+    ) -> StdResult<eris::amp_bribes::ClaimableResponse> {
+        router_ref.wrap().query_wasm_smart(
+            self.base.amp_bribes.get_address_string(),
+            &eris::amp_bribes::QueryMsg::Claimable {
+                user: user.into(),
+                validator_addr: validator_addr.into(),
+                period,
+            },
+        )
+    }
+
+    pub fn lp_execute_sender(
+        &self,
+        router_ref: &mut CustomApp,
+        execute: eris::lp_gauges::ExecuteMsg,
+        sender: impl Into<String>,
+    ) -> Result<AppResponse> {
+        router_ref.execute_contract(
+            Addr::unchecked(sender),
+            self.base.lp_gauges.get_address(),
+            &execute,
+            &[],
+        )
+    }
+
+    pub fn lp_vote(
+        &self,
+        router_ref: &mut CustomApp,
+        user: impl Into<String>,
+        votes: Vec<(String, u16)>,
+    ) -> Result<AppResponse> {
+        self.lp_execute_sender(
+            router_ref,
+            eris::lp_gauges::ExecuteMsg::Vote {
+                votes,
+            },
+            user,
+        )
+    }
+
+    pub fn lp_tune(&self, router_ref: &mut CustomApp) -> Result<AppResponse> {
+        self.lp_execute_sender(
+            router_ref,
+            eris::lp_gauges::ExecuteMsg::TuneTargets {},
+            self.owner.clone(),
+        )
+    }
+
+    pub fn lp_query_config(
+        &self,
+        router_ref: &mut CustomApp,
+    ) -> StdResult<eris::lp_gauges::ConfigResponse> {
+        router_ref.wrap().query_wasm_smart(
+            self.base.lp_gauges.get_address_string(),
+            &eris::lp_gauges::QueryMsg::Config {},
+        )
+    }
+
+    pub fn lp_query_user_info(
+        &self,
+        router_ref: &mut CustomApp,
+        user: impl Into<String>,
+    ) -> StdResult<eris::lp_gauges::UserInfoResponse> {
+        router_ref.wrap().query_wasm_smart(
+            self.base.lp_gauges.get_address_string(),
+            &eris::lp_gauges::QueryMsg::UserInfo {
+                user: user.into(),
+            },
+        )
+    }
+
+    pub fn lp_query_tune_info(
+        &self,
+        router_ref: &mut CustomApp,
+    ) -> StdResult<eris::lp_gauges::GaugeInfoResponse> {
+        router_ref.wrap().query_wasm_smart(
+            self.base.lp_gauges.get_address_string(),
+            &eris::lp_gauges::QueryMsg::TuneInfo {},
+        )
+    }
+
     pub fn mint_amp_token(&self, router_ref: &mut CustomApp, to: String, amount: u128) {
         router_ref
             .sudo(cw_multi_test::SudoMsg::Bank(cw_multi_test::BankSudo::Mint {
@@ -280,6 +459,7 @@ impl EscrowHelper {
             self.base.voting_escrow.get_address(),
             &eris::voting_escrow::ExecuteMsg::ExtendLockAmount {
                 extend_to_min_periods,
+                token_id: None,
             },
             &[coin(amount, self.base.amp_token.get_address_string())],
         )
@@ -300,6 +480,7 @@ impl EscrowHelper {
             self.base.voting_escrow.get_address(),
             &eris::voting_escrow::ExecuteMsg::DepositFor {
                 user: user.into(),
+                token_id: None,
             },
             &[coin(amount, self.base.amp_token.get_address_string())],
         )
@@ -315,6 +496,7 @@ impl EscrowHelper {
             router_ref,
             eris::voting_escrow::ExecuteMsg::ExtendLockTime {
                 time: periods * WEEK,
+                token_id: None,
             },
             Addr::unchecked(sender),
         )
@@ -327,7 +509,9 @@ impl EscrowHelper {
     ) -> Result<AppResponse> {
         self.ve_execute_sender(
             router_ref,
-            eris::voting_escrow::ExecuteMsg::Withdraw {},
+            eris::voting_escrow::ExecuteMsg::Withdraw {
+                token_id: None,
+            },
             Addr::unchecked(sender),
         )
     }
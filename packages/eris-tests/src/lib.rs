@@ -5,14 +5,17 @@ pub mod base;
 mod custom_gov;
 pub mod model;
 pub mod modules;
+pub mod whitewhale_pair;
 use std::str::FromStr;
 
 use anyhow::{Error, Ok, Result};
-use base::CustomApp;
+use base::{AllianceApp, CustomApp};
 use cosmwasm_std::testing::{mock_env, MockApi, MockStorage};
-use cosmwasm_std::{coin, Addr, Attribute, BlockInfo, Decimal, Timestamp, Validator};
+use cosmwasm_std::{coin, Addr, Attribute, BlockInfo, Coin, Decimal, Timestamp, Validator};
 use cw_multi_test::{AppResponse, BankKeeper, BasicAppBuilder, StakeKeeper, StakingInfo};
 use eris::governance_helper::{get_period, EPOCH_START, WEEK};
+use modules::alliance::{AllianceDelegationResponse, AllianceModule};
+use modules::stargate::StargateModule;
 use modules::types::init_custom;
 
 #[allow(clippy::all)]
@@ -108,6 +111,81 @@ pub fn mock_app_validators(
         })
 }
 
+/// App with the mocked alliance and tokenfactory modules. `users` are funded with the coins.
+pub fn mock_alliance_app(alliance: AllianceModule, users: Vec<(&str, Vec<Coin>)>) -> AllianceApp {
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(EPOCH_START);
+
+    BasicAppBuilder::new_custom()
+        .with_api(MockApi::default())
+        .with_block(env.block)
+        .with_bank(BankKeeper::new())
+        .with_storage(MockStorage::new())
+        .with_staking(StakeKeeper::new())
+        .with_custom(init_custom())
+        .with_stargate(StargateModule {
+            alliance,
+            ..StargateModule::default()
+        })
+        .build(|router, _, storage| {
+            for (user, coins) in users {
+                router.bank.init_balance(storage, &Addr::unchecked(user), coins).unwrap();
+            }
+        })
+}
+
+pub trait AllianceAppExtension {
+    /// Moves the block time forward and pays out completed alliance unbondings.
+    fn alliance_next_block(&mut self, seconds: u64) -> Result<AppResponse>;
+    fn alliance_slash(&mut self, validator: &str, fraction: Decimal) -> Result<()>;
+    fn alliance_apply_take_rate(&mut self) -> Result<()>;
+    fn alliance_delegation(
+        &self,
+        denom: &str,
+        delegator: &str,
+        validator: &str,
+    ) -> Result<AllianceDelegationResponse>;
+}
+
+impl AllianceAppExtension for AllianceApp {
+    fn alliance_next_block(&mut self, seconds: u64) -> Result<AppResponse> {
+        self.update_block(|block| {
+            block.time = block.time.plus_seconds(seconds);
+            block.height += 1
+        });
+
+        let block = self.block_info();
+        self.init_modules(|router, api, storage| {
+            router.stargate.alliance.end_block(api, storage, &*router, &block)
+        })
+    }
+
+    fn alliance_slash(&mut self, validator: &str, fraction: Decimal) -> Result<()> {
+        let block = self.block_info();
+        self.init_modules(|router, _, storage| {
+            router.stargate.alliance.slash(storage, &block, validator, fraction)
+        })
+    }
+
+    fn alliance_apply_take_rate(&mut self) -> Result<()> {
+        let block = self.block_info();
+        self.init_modules(|router, _, storage| {
+            router.stargate.alliance.apply_take_rate(storage, &block)
+        })
+    }
+
+    fn alliance_delegation(
+        &self,
+        denom: &str,
+        delegator: &str,
+        validator: &str,
+    ) -> Result<AllianceDelegationResponse> {
+        self.read_module(|router, _, storage| {
+            router.stargate.alliance.query_delegation(storage, denom, delegator, validator)
+        })
+    }
+}
+
 pub trait CustomAppExtension {
     fn next_block(&mut self, time: u64);
     fn next_period(&mut self, periods: u64);
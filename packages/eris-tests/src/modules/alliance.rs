@@ -0,0 +1,566 @@
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use anyhow::{bail, Result as AnyResult};
+use cosmwasm_schema::{cw_serde, schemars, serde};
+use cosmwasm_std::{
+    attr, to_json_binary, Addr, Api, BankMsg, Binary, BlockInfo, Coin, Decimal, Empty, Event,
+    Order, Querier, StdResult, Storage, Uint128,
+};
+use cw_multi_test::{
+    AppResponse, BankSudo, CosmosRouter, Module, Stargate, StargateMsg, StargateQuery, SudoMsg,
+};
+use cw_storage_plus::{Item, Map};
+use eris_chain_shared::alliance_query::AllianceQuery;
+use terra_proto_rs::{
+    alliance::alliance::{MsgClaimDelegationRewards, MsgDelegate, MsgRedelegate, MsgUndelegate},
+    cosmos::base::v1beta1::Coin as ProtoCoin,
+    prost::Message,
+};
+
+/// Account holding the delegated and unbonding alliance assets.
+pub const ALLIANCE_MODULE_ADDR: &str = "alliance_module";
+
+const YEAR: u64 = 365 * 24 * 60 * 60;
+
+/// (delegator, validator, denom) -> delegation
+const DELEGATIONS: Map<(&str, &str, &str), AllianceDelegation> = Map::new("alliance_delegations");
+const UNBONDINGS: Item<Vec<AllianceUnbonding>> = Item::new("alliance_unbondings");
+
+#[cw_serde]
+pub struct AllianceDelegation {
+    pub amount: Uint128,
+    /// Rewards accrued but not yet claimed
+    pub pending_rewards: Uint128,
+    pub last_accrual: u64,
+}
+
+#[cw_serde]
+pub struct AllianceUnbonding {
+    pub delegator: String,
+    pub validator: String,
+    pub amount: Coin,
+    pub completion_time: u64,
+}
+
+/// Same format as the response of the Terra alliance bindings.
+#[cw_serde]
+pub struct AllianceDelegationResponse {
+    pub denom: String,
+    pub delegator: String,
+    pub validator: String,
+    pub amount: Coin,
+}
+
+/// Stand-in for the alliance module, handling the `/alliance.alliance.Msg*` stargate messages.
+/// Unbondings are paid out by `end_block`, as cw-multi-test has no end blocker.
+pub struct AllianceModule {
+    /// Denom in which the rewards are paid
+    pub reward_denom: String,
+    /// Yearly rewards per delegated token
+    pub reward_apr: Decimal,
+    /// Share of the delegations taken by `apply_take_rate`
+    pub take_rate: Decimal,
+    pub unbonding_time: u64,
+}
+
+impl Default for AllianceModule {
+    fn default() -> Self {
+        AllianceModule {
+            reward_denom: "uluna".to_string(),
+            reward_apr: Decimal::percent(10),
+            take_rate: Decimal::zero(),
+            unbonding_time: 1814400,
+        }
+    }
+}
+
+impl AllianceModule {
+    pub fn set_reward_apr(&mut self, reward_apr: Decimal) {
+        self.reward_apr = reward_apr;
+    }
+
+    pub fn set_take_rate(&mut self, take_rate: Decimal) {
+        self.take_rate = take_rate;
+    }
+
+    pub fn query_delegation(
+        &self,
+        storage: &dyn Storage,
+        denom: &str,
+        delegator: &str,
+        validator: &str,
+    ) -> AnyResult<AllianceDelegationResponse> {
+        query_delegation(storage, denom, delegator, validator)
+    }
+
+    /// Rewards that would be received when claiming the delegation rewards.
+    pub fn query_rewards(
+        &self,
+        storage: &dyn Storage,
+        block: &BlockInfo,
+        denom: &str,
+        delegator: &str,
+        validator: &str,
+    ) -> AnyResult<Coin> {
+        let mut delegation = DELEGATIONS.load(storage, (delegator, validator, denom))?;
+        self.accrue(&mut delegation, block);
+        Ok(Coin::new(delegation.pending_rewards.u128(), self.reward_denom.clone()))
+    }
+
+    pub fn query_unbondings(&self, storage: &dyn Storage) -> StdResult<Vec<AllianceUnbonding>> {
+        Ok(UNBONDINGS.may_load(storage)?.unwrap_or_default())
+    }
+
+    /// Pays out all unbondings that are completed at the block time.
+    pub fn end_block<ExecC, QueryC>(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        block: &BlockInfo,
+    ) -> AnyResult<AppResponse> {
+        let (completed, pending): (Vec<_>, Vec<_>) = UNBONDINGS
+            .may_load(storage)?
+            .unwrap_or_default()
+            .into_iter()
+            .partition(|unbonding| unbonding.completion_time <= block.time.seconds());
+        UNBONDINGS.save(storage, &pending)?;
+
+        let mut events = vec![];
+        for unbonding in completed {
+            if !unbonding.amount.amount.is_zero() {
+                router.execute(
+                    api,
+                    storage,
+                    block,
+                    Addr::unchecked(ALLIANCE_MODULE_ADDR),
+                    BankMsg::Send {
+                        to_address: unbonding.delegator.clone(),
+                        amount: vec![unbonding.amount.clone()],
+                    }
+                    .into(),
+                )?;
+            }
+            events.push(Event::new("alliance_complete_unbonding").add_attributes(vec![
+                attr("delegator", unbonding.delegator),
+                attr("validator", unbonding.validator),
+                attr("amount", unbonding.amount.to_string()),
+            ]));
+        }
+
+        Ok(AppResponse {
+            events,
+            data: None,
+        })
+    }
+
+    /// Slashes the delegations and unbondings of the validator by the fraction.
+    pub fn slash(
+        &self,
+        storage: &mut dyn Storage,
+        block: &BlockInfo,
+        validator: &str,
+        fraction: Decimal,
+    ) -> AnyResult<()> {
+        self.update_all_delegations(storage, block, Some(validator), |delegation| {
+            delegation.amount -= delegation.amount * fraction;
+        })?;
+
+        let mut unbondings = UNBONDINGS.may_load(storage)?.unwrap_or_default();
+        for unbonding in unbondings.iter_mut().filter(|u| u.validator == validator) {
+            unbonding.amount.amount -= unbonding.amount.amount * fraction;
+        }
+        UNBONDINGS.save(storage, &unbondings)?;
+        Ok(())
+    }
+
+    /// Takes the take rate from all delegations, like the chain does every take rate interval.
+    pub fn apply_take_rate(&self, storage: &mut dyn Storage, block: &BlockInfo) -> AnyResult<()> {
+        let take_rate = self.take_rate;
+        self.update_all_delegations(storage, block, None, |delegation| {
+            delegation.amount -= delegation.amount * take_rate;
+        })
+    }
+
+    fn update_all_delegations(
+        &self,
+        storage: &mut dyn Storage,
+        block: &BlockInfo,
+        validator: Option<&str>,
+        update: impl Fn(&mut AllianceDelegation),
+    ) -> AnyResult<()> {
+        let delegations = DELEGATIONS
+            .range(storage, None, None, Order::Ascending)
+            .filter(|item| match (item, validator) {
+                (Ok(((_, val, _), _)), Some(validator)) => val == validator,
+                _ => true,
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        for ((delegator, validator, denom), mut delegation) in delegations {
+            // rewards until now are based on the previous amount
+            self.accrue(&mut delegation, block);
+            update(&mut delegation);
+            DELEGATIONS.save(storage, (&delegator, &validator, &denom), &delegation)?;
+        }
+        Ok(())
+    }
+
+    fn accrue(&self, delegation: &mut AllianceDelegation, block: &BlockInfo) {
+        let elapsed = block.time.seconds().saturating_sub(delegation.last_accrual);
+        delegation.pending_rewards +=
+            delegation.amount * self.reward_apr * Decimal::from_ratio(elapsed, YEAR);
+        delegation.last_accrual = block.time.seconds();
+    }
+
+    fn load_delegation(
+        &self,
+        storage: &dyn Storage,
+        block: &BlockInfo,
+        key: (&str, &str, &str),
+    ) -> AnyResult<AllianceDelegation> {
+        let mut delegation = DELEGATIONS.may_load(storage, key)?.unwrap_or(AllianceDelegation {
+            amount: Uint128::zero(),
+            pending_rewards: Uint128::zero(),
+            last_accrual: block.time.seconds(),
+        });
+        self.accrue(&mut delegation, block);
+        Ok(delegation)
+    }
+
+    fn remove_delegation(
+        &self,
+        storage: &mut dyn Storage,
+        block: &BlockInfo,
+        key: (&str, &str, &str),
+        amount: Uint128,
+    ) -> AnyResult<()> {
+        let mut delegation = self.load_delegation(storage, block, key)?;
+        if delegation.amount < amount {
+            bail!(
+                "insufficient delegation: {0}{1} to {2}, requested {3}",
+                delegation.amount,
+                key.2,
+                key.1,
+                amount
+            );
+        }
+        delegation.amount -= amount;
+        DELEGATIONS.save(storage, key, &delegation)?;
+        Ok(())
+    }
+
+    fn add_delegation(
+        &self,
+        storage: &mut dyn Storage,
+        block: &BlockInfo,
+        key: (&str, &str, &str),
+        amount: Uint128,
+    ) -> AnyResult<()> {
+        let mut delegation = self.load_delegation(storage, block, key)?;
+        delegation.amount += amount;
+        DELEGATIONS.save(storage, key, &delegation)?;
+        Ok(())
+    }
+}
+
+impl Module for AllianceModule {
+    type ExecT = StargateMsg;
+
+    type QueryT = StargateQuery;
+
+    type SudoT = Empty;
+
+    fn execute<ExecC, QueryC>(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        block: &BlockInfo,
+        sender: Addr,
+        msg: Self::ExecT,
+    ) -> AnyResult<AppResponse>
+    where
+        ExecC: std::fmt::Debug
+            + Clone
+            + PartialEq
+            + schemars::JsonSchema
+            + serde::de::DeserializeOwned
+            + 'static,
+        QueryC: cosmwasm_std::CustomQuery + serde::de::DeserializeOwned + 'static,
+    {
+        let event = match msg.type_url.as_str() {
+            "/alliance.alliance.MsgDelegate" => {
+                let msg = MsgDelegate::decode(msg.value.as_slice())?;
+                check_sender(&sender, &msg.delegator_address)?;
+                let amount = to_coin(msg.amount)?;
+
+                router.execute(
+                    api,
+                    storage,
+                    block,
+                    sender,
+                    BankMsg::Send {
+                        to_address: ALLIANCE_MODULE_ADDR.to_string(),
+                        amount: vec![amount.clone()],
+                    }
+                    .into(),
+                )?;
+                self.add_delegation(
+                    storage,
+                    block,
+                    (&msg.delegator_address, &msg.validator_address, &amount.denom),
+                    amount.amount,
+                )?;
+
+                Event::new("alliance_delegate").add_attributes(vec![
+                    attr("delegator", msg.delegator_address),
+                    attr("validator", msg.validator_address),
+                    attr("amount", amount.to_string()),
+                ])
+            },
+            "/alliance.alliance.MsgUndelegate" => {
+                let msg = MsgUndelegate::decode(msg.value.as_slice())?;
+                check_sender(&sender, &msg.delegator_address)?;
+                let amount = to_coin(msg.amount)?;
+
+                self.remove_delegation(
+                    storage,
+                    block,
+                    (&msg.delegator_address, &msg.validator_address, &amount.denom),
+                    amount.amount,
+                )?;
+                let completion_time = block.time.seconds() + self.unbonding_time;
+                let mut unbondings = UNBONDINGS.may_load(storage)?.unwrap_or_default();
+                unbondings.push(AllianceUnbonding {
+                    delegator: msg.delegator_address.clone(),
+                    validator: msg.validator_address.clone(),
+                    amount: amount.clone(),
+                    completion_time,
+                });
+                UNBONDINGS.save(storage, &unbondings)?;
+
+                Event::new("alliance_undelegate").add_attributes(vec![
+                    attr("delegator", msg.delegator_address),
+                    attr("validator", msg.validator_address),
+                    attr("amount", amount.to_string()),
+                    attr("completion_time", completion_time.to_string()),
+                ])
+            },
+            "/alliance.alliance.MsgRedelegate" => {
+                let msg = MsgRedelegate::decode(msg.value.as_slice())?;
+                check_sender(&sender, &msg.delegator_address)?;
+                let amount = to_coin(msg.amount)?;
+
+                self.remove_delegation(
+                    storage,
+                    block,
+                    (&msg.delegator_address, &msg.validator_src_address, &amount.denom),
+                    amount.amount,
+                )?;
+                self.add_delegation(
+                    storage,
+                    block,
+                    (&msg.delegator_address, &msg.validator_dst_address, &amount.denom),
+                    amount.amount,
+                )?;
+
+                Event::new("alliance_redelegate").add_attributes(vec![
+                    attr("delegator", msg.delegator_address),
+                    attr("source_validator", msg.validator_src_address),
+                    attr("destination_validator", msg.validator_dst_address),
+                    attr("amount", amount.to_string()),
+                ])
+            },
+            "/alliance.alliance.MsgClaimDelegationRewards" => {
+                let msg = MsgClaimDelegationRewards::decode(msg.value.as_slice())?;
+                check_sender(&sender, &msg.delegator_address)?;
+                let key = (
+                    msg.delegator_address.as_str(),
+                    msg.validator_address.as_str(),
+                    msg.denom.as_str(),
+                );
+
+                let mut delegation = self.load_delegation(storage, block, key)?;
+                let rewards = delegation.pending_rewards;
+                delegation.pending_rewards = Uint128::zero();
+                DELEGATIONS.save(storage, key, &delegation)?;
+
+                if !rewards.is_zero() {
+                    router.sudo(
+                        api,
+                        storage,
+                        block,
+                        SudoMsg::Bank(BankSudo::Mint {
+                            to_address: msg.delegator_address.clone(),
+                            amount: vec![Coin::new(rewards.u128(), self.reward_denom.clone())],
+                        }),
+                    )?;
+                }
+
+                Event::new("alliance_claim_delegation_rewards").add_attributes(vec![
+                    attr("delegator", msg.delegator_address),
+                    attr("validator", msg.validator_address),
+                    attr("denom", msg.denom),
+                    attr(
+                        "rewards",
+                        Coin::new(rewards.u128(), self.reward_denom.clone()).to_string(),
+                    ),
+                ])
+            },
+            type_url => bail!("Unexpected stargate message: {}", type_url),
+        };
+
+        Ok(AppResponse {
+            events: vec![event],
+            data: None,
+        })
+    }
+
+    fn sudo<ExecC, QueryC>(
+        &self,
+        _api: &dyn Api,
+        _storage: &mut dyn Storage,
+        _router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        _block: &BlockInfo,
+        _msg: Self::SudoT,
+    ) -> AnyResult<AppResponse>
+    where
+        ExecC: std::fmt::Debug
+            + Clone
+            + PartialEq
+            + schemars::JsonSchema
+            + serde::de::DeserializeOwned
+            + 'static,
+        QueryC: cosmwasm_std::CustomQuery + serde::de::DeserializeOwned + 'static,
+    {
+        bail!("sudo not implemented for AllianceModule")
+    }
+
+    fn query(
+        &self,
+        _api: &dyn Api,
+        _storage: &dyn Storage,
+        _querier: &dyn Querier,
+        _block: &BlockInfo,
+        request: Self::QueryT,
+    ) -> AnyResult<Binary> {
+        bail!("Unexpected stargate query: {}", request.path)
+    }
+}
+
+impl Stargate for AllianceModule {}
+
+/// Custom module answering `AllianceQuery::Delegation` from the delegations of the
+/// `AllianceModule`. Custom messages are not supported.
+pub struct AllianceQueryModule<ExecT>(PhantomData<ExecT>);
+
+impl<ExecT> Default for AllianceQueryModule<ExecT> {
+    fn default() -> Self {
+        AllianceQueryModule(PhantomData)
+    }
+}
+
+impl<ExecT> Module for AllianceQueryModule<ExecT>
+where
+    ExecT: std::fmt::Debug,
+{
+    type ExecT = ExecT;
+
+    type QueryT = AllianceQuery;
+
+    type SudoT = Empty;
+
+    fn execute<ExecC, QueryC>(
+        &self,
+        _api: &dyn Api,
+        _storage: &mut dyn Storage,
+        _router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        _block: &BlockInfo,
+        _sender: Addr,
+        msg: Self::ExecT,
+    ) -> AnyResult<AppResponse>
+    where
+        ExecC: std::fmt::Debug
+            + Clone
+            + PartialEq
+            + schemars::JsonSchema
+            + serde::de::DeserializeOwned
+            + 'static,
+        QueryC: cosmwasm_std::CustomQuery + serde::de::DeserializeOwned + 'static,
+    {
+        bail!("Unexpected custom exec msg {:?}", msg)
+    }
+
+    fn sudo<ExecC, QueryC>(
+        &self,
+        _api: &dyn Api,
+        _storage: &mut dyn Storage,
+        _router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        _block: &BlockInfo,
+        _msg: Self::SudoT,
+    ) -> AnyResult<AppResponse>
+    where
+        ExecC: std::fmt::Debug
+            + Clone
+            + PartialEq
+            + schemars::JsonSchema
+            + serde::de::DeserializeOwned
+            + 'static,
+        QueryC: cosmwasm_std::CustomQuery + serde::de::DeserializeOwned + 'static,
+    {
+        bail!("sudo not implemented for AllianceQueryModule")
+    }
+
+    fn query(
+        &self,
+        _api: &dyn Api,
+        storage: &dyn Storage,
+        _querier: &dyn Querier,
+        _block: &BlockInfo,
+        request: Self::QueryT,
+    ) -> AnyResult<Binary> {
+        match request {
+            AllianceQuery::Delegation {
+                denom,
+                delegator,
+                validator,
+            } => Ok(to_json_binary(&query_delegation(storage, &denom, &delegator, &validator)?)?),
+        }
+    }
+}
+
+fn query_delegation(
+    storage: &dyn Storage,
+    denom: &str,
+    delegator: &str,
+    validator: &str,
+) -> AnyResult<AllianceDelegationResponse> {
+    match DELEGATIONS.may_load(storage, (delegator, validator, denom))? {
+        Some(delegation) if !delegation.amount.is_zero() => Ok(AllianceDelegationResponse {
+            denom: denom.to_string(),
+            delegator: delegator.to_string(),
+            validator: validator.to_string(),
+            amount: Coin::new(delegation.amount.u128(), denom),
+        }),
+        _ => bail!("alliance delegation not found"),
+    }
+}
+
+fn check_sender(sender: &Addr, delegator: &str) -> AnyResult<()> {
+    if sender.as_str() != delegator {
+        bail!("sender {} is not the delegator {}", sender, delegator);
+    }
+    Ok(())
+}
+
+fn to_coin(coin: Option<ProtoCoin>) -> AnyResult<Coin> {
+    match coin {
+        Some(coin) => Ok(Coin {
+            amount: Uint128::from_str(&coin.amount)?,
+            denom: coin.denom,
+        }),
+        None => bail!("missing amount"),
+    }
+}
@@ -1,3 +1,7 @@
+pub mod alliance;
+pub mod stargate;
+pub mod token_factory;
+
 #[cfg(feature = "X-injective-X")]
 pub mod injective;
 
@@ -24,10 +28,11 @@ pub mod types {
 
 #[cfg(feature = "X-terra-X")]
 pub mod types {
-    use cosmwasm_std::Empty;
-    use cw_multi_test::FailingModule;
+    use eris_chain_adapter::types::CustomMsgType;
 
-    pub type UsedCustomModule = FailingModule<Empty, Empty, Empty>;
+    use super::alliance::AllianceQueryModule;
+
+    pub type UsedCustomModule = AllianceQueryModule<CustomMsgType>;
 
     pub fn init_custom() -> UsedCustomModule {
         UsedCustomModule::default()
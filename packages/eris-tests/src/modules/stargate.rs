@@ -0,0 +1,82 @@
+use anyhow::{bail, Result as AnyResult};
+use cosmwasm_schema::{schemars, serde};
+use cosmwasm_std::{Addr, Api, Binary, BlockInfo, Empty, Querier, Storage};
+use cw_multi_test::{AppResponse, CosmosRouter, Module, Stargate, StargateMsg, StargateQuery};
+
+use super::alliance::AllianceModule;
+use super::token_factory::TokenFactoryModule;
+
+/// Routes the stargate messages to the mocked chain modules by their type url.
+#[derive(Default)]
+pub struct StargateModule {
+    pub alliance: AllianceModule,
+    pub token_factory: TokenFactoryModule,
+}
+
+impl Module for StargateModule {
+    type ExecT = StargateMsg;
+
+    type QueryT = StargateQuery;
+
+    type SudoT = Empty;
+
+    fn execute<ExecC, QueryC>(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        block: &BlockInfo,
+        sender: Addr,
+        msg: Self::ExecT,
+    ) -> AnyResult<AppResponse>
+    where
+        ExecC: std::fmt::Debug
+            + Clone
+            + PartialEq
+            + schemars::JsonSchema
+            + serde::de::DeserializeOwned
+            + 'static,
+        QueryC: cosmwasm_std::CustomQuery + serde::de::DeserializeOwned + 'static,
+    {
+        if msg.type_url.starts_with("/alliance.") {
+            self.alliance.execute(api, storage, router, block, sender, msg)
+        } else if msg.type_url.starts_with("/osmosis.tokenfactory.") {
+            self.token_factory.execute(api, storage, router, block, sender, msg)
+        } else {
+            bail!("Unexpected stargate message: {}", msg.type_url)
+        }
+    }
+
+    fn sudo<ExecC, QueryC>(
+        &self,
+        _api: &dyn Api,
+        _storage: &mut dyn Storage,
+        _router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        _block: &BlockInfo,
+        _msg: Self::SudoT,
+    ) -> AnyResult<AppResponse>
+    where
+        ExecC: std::fmt::Debug
+            + Clone
+            + PartialEq
+            + schemars::JsonSchema
+            + serde::de::DeserializeOwned
+            + 'static,
+        QueryC: cosmwasm_std::CustomQuery + serde::de::DeserializeOwned + 'static,
+    {
+        bail!("sudo not implemented for StargateModule")
+    }
+
+    fn query(
+        &self,
+        _api: &dyn Api,
+        _storage: &dyn Storage,
+        _querier: &dyn Querier,
+        _block: &BlockInfo,
+        request: Self::QueryT,
+    ) -> AnyResult<Binary> {
+        bail!("Unexpected stargate query: {}", request.path)
+    }
+}
+
+impl Stargate for StargateModule {}
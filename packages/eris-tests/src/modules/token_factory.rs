@@ -0,0 +1,227 @@
+use std::str::FromStr;
+
+use anyhow::{bail, Result as AnyResult};
+use cosmwasm_schema::{schemars, serde};
+use cosmwasm_std::{
+    attr, Addr, Api, BankMsg, Binary, BlockInfo, Coin, Empty, Event, Querier, Storage, Uint128,
+};
+use cw_multi_test::{
+    AppResponse, BankSudo, CosmosRouter, Module, Stargate, StargateMsg, StargateQuery, SudoMsg,
+};
+use cw_storage_plus::Map;
+use eris_chain_shared::denom_metadata::{Metadata, MsgSetDenomMetadata};
+use eris_whitewhale::denom::{
+    Coin as ProtoCoin, MsgBurn, MsgCreateDenom, MsgCreateDenomResponse, MsgMint,
+};
+use prost::Message;
+
+/// denom -> admin
+const DENOM_ADMINS: Map<&str, Addr> = Map::new("tokenfactory_admins");
+const DENOM_METADATA: Map<&str, Metadata> = Map::new("tokenfactory_metadata");
+
+/// Stand-in for the tokenfactory module, handling the `/osmosis.tokenfactory.v1beta1.Msg*`
+/// stargate messages in the format used by White Whale.
+#[derive(Default)]
+pub struct TokenFactoryModule {}
+
+impl TokenFactoryModule {
+    pub fn query_admin(&self, storage: &dyn Storage, denom: &str) -> AnyResult<Option<Addr>> {
+        Ok(DENOM_ADMINS.may_load(storage, denom)?)
+    }
+
+    pub fn query_metadata(
+        &self,
+        storage: &dyn Storage,
+        denom: &str,
+    ) -> AnyResult<Option<Metadata>> {
+        Ok(DENOM_METADATA.may_load(storage, denom)?)
+    }
+}
+
+impl Module for TokenFactoryModule {
+    type ExecT = StargateMsg;
+
+    type QueryT = StargateQuery;
+
+    type SudoT = Empty;
+
+    fn execute<ExecC, QueryC>(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        block: &BlockInfo,
+        sender: Addr,
+        msg: Self::ExecT,
+    ) -> AnyResult<AppResponse>
+    where
+        ExecC: std::fmt::Debug
+            + Clone
+            + PartialEq
+            + schemars::JsonSchema
+            + serde::de::DeserializeOwned
+            + 'static,
+        QueryC: cosmwasm_std::CustomQuery + serde::de::DeserializeOwned + 'static,
+    {
+        match msg.type_url.as_str() {
+            "/osmosis.tokenfactory.v1beta1.MsgCreateDenom" => {
+                let msg = MsgCreateDenom::decode(msg.value.as_slice())?;
+                check_sender(&sender, &msg.sender)?;
+
+                let denom = format!("factory/{0}/{1}", msg.sender, msg.subdenom);
+                if DENOM_ADMINS.has(storage, &denom) {
+                    bail!("denom {} already exists", denom);
+                }
+                DENOM_ADMINS.save(storage, &denom, &sender)?;
+
+                Ok(AppResponse {
+                    events: vec![Event::new("create_denom").add_attributes(vec![
+                        attr("creator", msg.sender),
+                        attr("new_token_denom", denom.clone()),
+                    ])],
+                    data: Some(Binary::from(
+                        MsgCreateDenomResponse {
+                            new_token_denom: denom,
+                        }
+                        .encode_to_vec(),
+                    )),
+                })
+            },
+            "/osmosis.tokenfactory.v1beta1.MsgMint" => {
+                let msg = MsgMint::decode(msg.value.as_slice())?;
+                check_sender(&sender, &msg.sender)?;
+                let amount = to_coin(msg.amount)?;
+                check_admin(storage, &sender, &amount.denom)?;
+
+                // the White Whale tokenfactory allows minting to any address
+                let recipient = if msg.mint_to_address.is_empty() {
+                    msg.sender
+                } else {
+                    msg.mint_to_address
+                };
+
+                router.sudo(
+                    api,
+                    storage,
+                    block,
+                    SudoMsg::Bank(BankSudo::Mint {
+                        to_address: recipient.clone(),
+                        amount: vec![amount.clone()],
+                    }),
+                )?;
+
+                Ok(AppResponse {
+                    events: vec![Event::new("tf_mint").add_attributes(vec![
+                        attr("mint_to_address", recipient),
+                        attr("amount", amount.to_string()),
+                    ])],
+                    data: None,
+                })
+            },
+            "/osmosis.tokenfactory.v1beta1.MsgBurn" => {
+                let msg = MsgBurn::decode(msg.value.as_slice())?;
+                check_sender(&sender, &msg.sender)?;
+                let amount = to_coin(msg.amount)?;
+                check_admin(storage, &sender, &amount.denom)?;
+
+                if !msg.burn_from_address.is_empty() && msg.burn_from_address != msg.sender {
+                    bail!("burning from other addresses is not supported");
+                }
+
+                router.execute(
+                    api,
+                    storage,
+                    block,
+                    sender,
+                    BankMsg::Burn {
+                        amount: vec![amount.clone()],
+                    }
+                    .into(),
+                )?;
+
+                Ok(AppResponse {
+                    events: vec![Event::new("tf_burn").add_attributes(vec![
+                        attr("burn_from_address", msg.sender),
+                        attr("amount", amount.to_string()),
+                    ])],
+                    data: None,
+                })
+            },
+            "/osmosis.tokenfactory.v1beta1.MsgSetDenomMetadata" => {
+                let msg = MsgSetDenomMetadata::decode(msg.value.as_slice())?;
+                check_sender(&sender, &msg.sender)?;
+                let metadata = match msg.metadata {
+                    Some(metadata) => metadata,
+                    None => bail!("missing metadata"),
+                };
+                check_admin(storage, &sender, &metadata.base)?;
+                DENOM_METADATA.save(storage, &metadata.base, &metadata)?;
+
+                Ok(AppResponse {
+                    events: vec![Event::new("set_denom_metadata")
+                        .add_attributes(vec![attr("denom", metadata.base)])],
+                    data: None,
+                })
+            },
+            type_url => bail!("Unexpected stargate message: {}", type_url),
+        }
+    }
+
+    fn sudo<ExecC, QueryC>(
+        &self,
+        _api: &dyn Api,
+        _storage: &mut dyn Storage,
+        _router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        _block: &BlockInfo,
+        _msg: Self::SudoT,
+    ) -> AnyResult<AppResponse>
+    where
+        ExecC: std::fmt::Debug
+            + Clone
+            + PartialEq
+            + schemars::JsonSchema
+            + serde::de::DeserializeOwned
+            + 'static,
+        QueryC: cosmwasm_std::CustomQuery + serde::de::DeserializeOwned + 'static,
+    {
+        bail!("sudo not implemented for TokenFactoryModule")
+    }
+
+    fn query(
+        &self,
+        _api: &dyn Api,
+        _storage: &dyn Storage,
+        _querier: &dyn Querier,
+        _block: &BlockInfo,
+        request: Self::QueryT,
+    ) -> AnyResult<Binary> {
+        bail!("Unexpected stargate query: {}", request.path)
+    }
+}
+
+impl Stargate for TokenFactoryModule {}
+
+fn check_sender(sender: &Addr, msg_sender: &str) -> AnyResult<()> {
+    if sender.as_str() != msg_sender {
+        bail!("sender {} does not match the message sender {}", sender, msg_sender);
+    }
+    Ok(())
+}
+
+fn check_admin(storage: &dyn Storage, sender: &Addr, denom: &str) -> AnyResult<()> {
+    match DENOM_ADMINS.may_load(storage, denom)? {
+        Some(admin) if admin == *sender => Ok(()),
+        Some(_) => bail!("unauthorized: {} is not the admin of {}", sender, denom),
+        None => bail!("denom {} does not exist", denom),
+    }
+}
+
+fn to_coin(coin: Option<ProtoCoin>) -> AnyResult<Coin> {
+    match coin {
+        Some(coin) => Ok(Coin {
+            amount: Uint128::from_str(&coin.amount)?,
+            denom: coin.denom,
+        }),
+        None => bail!("missing amount"),
+    }
+}
@@ -0,0 +1,747 @@
+//! Mock of a White Whale pool supporting constant product and stableswap pools.
+//! LP shares are tokenfactory denoms created by the pair, like on Migaloo.
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    attr, from_json, to_json_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Decimal, Deps,
+    DepsMut, Env, Isqrt, MessageInfo, QuerierWrapper, Response, StdError, StdResult, Uint128,
+    Uint256, WasmMsg,
+};
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg};
+use cw_multi_test::{Contract, ContractWrapper};
+use cw_storage_plus::Item;
+use eris_chain_adapter::types::{CustomMsgType, CustomQueryType};
+use eris_whitewhale::adapters::whitewhaledex::{Asset, AssetInfo, Cw20HookMsg, ExecuteMsg};
+use eris_whitewhale::denom::{Coin as ProtoCoin, MsgBurn, MsgCreateDenom, MsgMint};
+
+pub type ContractResult = Result<Response, StdError>;
+
+const LP_SUBDENOM: &str = "uLP";
+/// Max spread used if a swap does not specify it
+const DEFAULT_MAX_SPREAD: u64 = 1;
+const MAX_ITERATIONS: usize = 256;
+
+#[cw_serde]
+pub enum PoolType {
+    ConstantProduct,
+    StableSwap {
+        amp: u64,
+    },
+}
+
+/// All fees are taken from the ask asset. The swap fee stays in the pool for the LPs,
+/// the protocol fee is kept aside in the pair and the burn fee is burned.
+#[cw_serde]
+pub struct PoolFees {
+    pub protocol_fee: Decimal,
+    pub swap_fee: Decimal,
+    pub burn_fee: Decimal,
+}
+
+impl PoolFees {
+    fn total(&self) -> Decimal {
+        self.protocol_fee + self.swap_fee + self.burn_fee
+    }
+}
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub asset_infos: [AssetInfo; 2],
+    pub pool_type: PoolType,
+    pub pool_fees: PoolFees,
+}
+
+#[cw_serde]
+pub enum QueryMsg {
+    Pair {},
+    Pool {},
+    Simulation {
+        offer_asset: Asset,
+    },
+    ReverseSimulation {
+        ask_asset: Asset,
+    },
+    ProtocolFees {},
+}
+
+#[cw_serde]
+pub struct PairInfo {
+    pub asset_infos: [AssetInfo; 2],
+    pub liquidity_token: String,
+    pub pool_type: PoolType,
+    pub pool_fees: PoolFees,
+}
+
+#[cw_serde]
+pub struct PoolResponse {
+    pub assets: Vec<Asset>,
+    pub total_share: Uint128,
+}
+
+#[cw_serde]
+pub struct SimulationResponse {
+    pub return_amount: Uint128,
+    pub spread_amount: Uint128,
+    pub swap_fee_amount: Uint128,
+    pub protocol_fee_amount: Uint128,
+    pub burn_fee_amount: Uint128,
+}
+
+#[cw_serde]
+pub struct ReverseSimulationResponse {
+    pub offer_amount: Uint128,
+    pub spread_amount: Uint128,
+    pub swap_fee_amount: Uint128,
+    pub protocol_fee_amount: Uint128,
+    pub burn_fee_amount: Uint128,
+}
+
+const PAIR_INFO: Item<PairInfo> = Item::new("pair_info");
+const TOTAL_SHARE: Item<Uint128> = Item::new("total_share");
+/// protocol fees collected per asset, in the order of the asset infos
+const PROTOCOL_FEES: Item<[Uint128; 2]> = Item::new("protocol_fees");
+
+pub fn whitewhale_pair_contract() -> Box<dyn Contract<CustomMsgType, CustomQueryType>> {
+    Box::new(ContractWrapper::new_with_empty(execute, instantiate, query))
+}
+
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> ContractResult {
+    if msg.asset_infos[0] == msg.asset_infos[1] {
+        return Err(StdError::generic_err("assets of the pair must be different"));
+    }
+
+    let liquidity_token = format!("factory/{0}/{1}", env.contract.address, LP_SUBDENOM);
+    PAIR_INFO.save(
+        deps.storage,
+        &PairInfo {
+            asset_infos: msg.asset_infos,
+            liquidity_token,
+            pool_type: msg.pool_type,
+            pool_fees: msg.pool_fees,
+        },
+    )?;
+    TOTAL_SHARE.save(deps.storage, &Uint128::zero())?;
+    PROTOCOL_FEES.save(deps.storage, &[Uint128::zero(), Uint128::zero()])?;
+
+    Ok(Response::new().add_message(MsgCreateDenom {
+        sender: env.contract.address.to_string(),
+        subdenom: LP_SUBDENOM.to_string(),
+    }))
+}
+
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> ContractResult {
+    match msg {
+        ExecuteMsg::Receive(cw20_msg) => receive(deps, env, info, cw20_msg),
+        ExecuteMsg::Swap {
+            offer_asset,
+            belief_price,
+            max_spread,
+            to,
+        } => {
+            let denom = match &offer_asset.info {
+                AssetInfo::NativeToken {
+                    denom,
+                } => denom,
+                AssetInfo::Token {
+                    ..
+                } => return Err(StdError::generic_err("cw20 tokens are swapped with Receive")),
+            };
+            if info.funds.len() != 1
+                || info.funds[0].denom != *denom
+                || info.funds[0].amount != offer_asset.amount
+            {
+                return Err(StdError::generic_err(
+                    "native token balance mismatch between the argument and the transferred",
+                ));
+            }
+
+            let to = to.map(|to| deps.api.addr_validate(&to)).transpose()?;
+            swap(deps, env, info.sender, offer_asset, belief_price, max_spread, to)
+        },
+        ExecuteMsg::ProvideLiquidity {
+            assets,
+            slippage_tolerance,
+            receiver,
+        } => provide_liquidity(deps, env, info, assets, slippage_tolerance, receiver),
+        ExecuteMsg::WithdrawLiquidity {} => withdraw_liquidity(deps, env, info),
+    }
+}
+
+fn receive(deps: DepsMut, env: Env, info: MessageInfo, cw20_msg: Cw20ReceiveMsg) -> ContractResult {
+    let offer_asset = Asset {
+        info: AssetInfo::Token {
+            contract_addr: info.sender.to_string(),
+        },
+        amount: cw20_msg.amount,
+    };
+
+    match from_json(&cw20_msg.msg)? {
+        Cw20HookMsg::Swap {
+            belief_price,
+            max_spread,
+            to,
+        } => {
+            let sender = deps.api.addr_validate(&cw20_msg.sender)?;
+            let to = to.map(|to| deps.api.addr_validate(&to)).transpose()?;
+            swap(deps, env, sender, offer_asset, belief_price, max_spread, to)
+        },
+        Cw20HookMsg::WithdrawLiquidity {} => {
+            Err(StdError::generic_err("liquidity tokens are tokenfactory denoms"))
+        },
+    }
+}
+
+fn swap(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    offer_asset: Asset,
+    belief_price: Option<Decimal>,
+    max_spread: Option<Decimal>,
+    to: Option<Addr>,
+) -> ContractResult {
+    let pair_info = PAIR_INFO.load(deps.storage)?;
+    let offer_index = asset_index(&pair_info, &offer_asset.info)?;
+    let ask_index = 1 - offer_index;
+
+    // the offer asset has already been transferred to the pair
+    let pools = query_pools(deps.as_ref(), &env, &pair_info)?;
+    let offer_pool = pools[offer_index].amount.checked_sub(offer_asset.amount)?;
+    let ask_pool = pools[ask_index].amount;
+
+    let swap = compute_swap(&pair_info, offer_pool, ask_pool, offer_asset.amount)?;
+    assert_max_spread(
+        belief_price,
+        max_spread,
+        offer_asset.amount,
+        swap.return_amount,
+        swap.spread_amount,
+    )?;
+
+    let ask_info = pair_info.asset_infos[ask_index].clone();
+    let receiver = to.unwrap_or_else(|| sender.clone());
+
+    let mut messages = vec![];
+    if !swap.return_amount.is_zero() {
+        messages.push(transfer_msg(&ask_info, swap.return_amount, &receiver)?);
+    }
+    if !swap.burn_fee_amount.is_zero() {
+        messages.push(burn_msg(&ask_info, swap.burn_fee_amount)?);
+    }
+
+    PROTOCOL_FEES.update(deps.storage, |mut fees| -> StdResult<_> {
+        fees[ask_index] += swap.protocol_fee_amount;
+        Ok(fees)
+    })?;
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "swap"),
+        attr("sender", sender),
+        attr("receiver", receiver),
+        attr("offer_asset", asset_label(&offer_asset.info)),
+        attr("ask_asset", asset_label(&ask_info)),
+        attr("offer_amount", offer_asset.amount),
+        attr("return_amount", swap.return_amount),
+        attr("spread_amount", swap.spread_amount),
+        attr("swap_fee_amount", swap.swap_fee_amount),
+        attr("protocol_fee_amount", swap.protocol_fee_amount),
+        attr("burn_fee_amount", swap.burn_fee_amount),
+    ]))
+}
+
+fn provide_liquidity(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    assets: Vec<Asset>,
+    slippage_tolerance: Option<Decimal>,
+    receiver: Option<String>,
+) -> ContractResult {
+    let pair_info = PAIR_INFO.load(deps.storage)?;
+    if assets.len() != 2 {
+        return Err(StdError::generic_err("both assets of the pair must be provided"));
+    }
+
+    let mut deposits = [Uint128::zero(); 2];
+    for asset in assets.iter() {
+        deposits[asset_index(&pair_info, &asset.info)?] = asset.amount;
+    }
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    for (info_index, asset_info) in pair_info.asset_infos.iter().enumerate() {
+        match asset_info {
+            AssetInfo::NativeToken {
+                denom,
+            } => {
+                let sent = info
+                    .funds
+                    .iter()
+                    .find(|coin| coin.denom == *denom)
+                    .map(|coin| coin.amount)
+                    .unwrap_or_default();
+                if sent != deposits[info_index] {
+                    return Err(StdError::generic_err(
+                        "native token balance mismatch between the argument and the transferred",
+                    ));
+                }
+            },
+            AssetInfo::Token {
+                contract_addr,
+            } => {
+                if !deposits[info_index].is_zero() {
+                    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                        contract_addr: contract_addr.clone(),
+                        msg: to_json_binary(&Cw20ExecuteMsg::TransferFrom {
+                            owner: info.sender.to_string(),
+                            recipient: env.contract.address.to_string(),
+                            amount: deposits[info_index],
+                        })?,
+                        funds: vec![],
+                    }));
+                }
+            },
+        }
+    }
+
+    // native deposits are already part of the balances of the pair
+    let mut pools =
+        query_pools(deps.as_ref(), &env, &pair_info)?.map(|asset| asset.amount).to_vec();
+    for (index, asset_info) in pair_info.asset_infos.iter().enumerate() {
+        if let AssetInfo::NativeToken {
+            ..
+        } = asset_info
+        {
+            pools[index] = pools[index].checked_sub(deposits[index])?;
+        }
+    }
+
+    let total_share = TOTAL_SHARE.load(deps.storage)?;
+    let share: Uint128 = if total_share.is_zero() {
+        if deposits.iter().any(|deposit| deposit.is_zero()) {
+            return Err(StdError::generic_err("initial liquidity requires both assets"));
+        }
+        match pair_info.pool_type {
+            PoolType::ConstantProduct => {
+                Uint256::from(deposits[0]).checked_mul(deposits[1].into())?.isqrt().try_into()?
+            },
+            PoolType::StableSwap {
+                amp,
+            } => compute_d(amp, deposits[0], deposits[1])?.try_into()?,
+        }
+    } else {
+        assert_slippage_tolerance(slippage_tolerance, &deposits, &pools)?;
+        match pair_info.pool_type {
+            PoolType::ConstantProduct => std::cmp::min(
+                deposits[0].multiply_ratio(total_share, pools[0]),
+                deposits[1].multiply_ratio(total_share, pools[1]),
+            ),
+            PoolType::StableSwap {
+                amp,
+            } => {
+                let d_before = compute_d(amp, pools[0], pools[1])?;
+                let d_after = compute_d(amp, pools[0] + deposits[0], pools[1] + deposits[1])?;
+                let share = Uint256::from(total_share).multiply_ratio(d_after - d_before, d_before);
+                share.try_into()?
+            },
+        }
+    };
+
+    if share.is_zero() {
+        return Err(StdError::generic_err("provided liquidity is too small"));
+    }
+
+    let receiver = receiver.unwrap_or_else(|| info.sender.to_string());
+    deps.api.addr_validate(&receiver)?;
+    TOTAL_SHARE.save(deps.storage, &(total_share + share))?;
+
+    messages.push(
+        MsgMint {
+            sender: env.contract.address.to_string(),
+            amount: Some(ProtoCoin {
+                denom: pair_info.liquidity_token,
+                amount: share.to_string(),
+            }),
+            mint_to_address: receiver.clone(),
+        }
+        .into(),
+    );
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "provide_liquidity"),
+        attr("sender", info.sender),
+        attr("receiver", receiver),
+        attr("assets", format!("{0}, {1}", deposits[0], deposits[1])),
+        attr("share", share),
+    ]))
+}
+
+fn withdraw_liquidity(deps: DepsMut, env: Env, info: MessageInfo) -> ContractResult {
+    let pair_info = PAIR_INFO.load(deps.storage)?;
+    let amount = match info.funds.as_slice() {
+        [coin] if coin.denom == pair_info.liquidity_token => coin.amount,
+        _ => return Err(StdError::generic_err("only liquidity tokens can be withdrawn")),
+    };
+
+    let total_share = TOTAL_SHARE.load(deps.storage)?;
+    let pools = query_pools(deps.as_ref(), &env, &pair_info)?;
+
+    let mut messages = vec![];
+    let mut refunds = vec![];
+    for pool in pools {
+        let refund = pool.amount.multiply_ratio(amount, total_share);
+        if !refund.is_zero() {
+            messages.push(transfer_msg(&pool.info, refund, &info.sender)?);
+        }
+        refunds.push(format!("{0}{1}", refund, asset_label(&pool.info)));
+    }
+
+    TOTAL_SHARE.save(deps.storage, &(total_share - amount))?;
+    messages.push(
+        MsgBurn {
+            sender: env.contract.address.to_string(),
+            amount: Some(ProtoCoin {
+                denom: pair_info.liquidity_token,
+                amount: amount.to_string(),
+            }),
+            burn_from_address: env.contract.address.to_string(),
+        }
+        .into(),
+    );
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "withdraw_liquidity"),
+        attr("sender", info.sender),
+        attr("withdrawn_share", amount),
+        attr("refund_assets", refunds.join(", ")),
+    ]))
+}
+
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    let pair_info = PAIR_INFO.load(deps.storage)?;
+    match msg {
+        QueryMsg::Pair {} => to_json_binary(&pair_info),
+        QueryMsg::Pool {} => to_json_binary(&PoolResponse {
+            assets: query_pools(deps, &env, &pair_info)?.to_vec(),
+            total_share: TOTAL_SHARE.load(deps.storage)?,
+        }),
+        QueryMsg::Simulation {
+            offer_asset,
+        } => {
+            let offer_index = asset_index(&pair_info, &offer_asset.info)?;
+            let pools = query_pools(deps, &env, &pair_info)?;
+            let swap = compute_swap(
+                &pair_info,
+                pools[offer_index].amount,
+                pools[1 - offer_index].amount,
+                offer_asset.amount,
+            )?;
+            to_json_binary(&swap)
+        },
+        QueryMsg::ReverseSimulation {
+            ask_asset,
+        } => {
+            let ask_index = asset_index(&pair_info, &ask_asset.info)?;
+            let pools = query_pools(deps, &env, &pair_info)?;
+            to_json_binary(&compute_reverse_swap(
+                &pair_info,
+                pools[1 - ask_index].amount,
+                pools[ask_index].amount,
+                ask_asset.amount,
+            )?)
+        },
+        QueryMsg::ProtocolFees {} => {
+            let fees = PROTOCOL_FEES.load(deps.storage)?;
+            to_json_binary(
+                &pair_info
+                    .asset_infos
+                    .iter()
+                    .zip(fees)
+                    .map(|(info, amount)| Asset {
+                        info: info.clone(),
+                        amount,
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        },
+    }
+}
+
+fn asset_index(pair_info: &PairInfo, info: &AssetInfo) -> StdResult<usize> {
+    pair_info.asset_infos.iter().position(|asset_info| asset_info == info).ok_or_else(|| {
+        StdError::generic_err(format!("asset {} is not part of the pair", asset_label(info)))
+    })
+}
+
+fn asset_label(info: &AssetInfo) -> String {
+    match info {
+        AssetInfo::NativeToken {
+            denom,
+        } => denom.clone(),
+        AssetInfo::Token {
+            contract_addr,
+        } => contract_addr.clone(),
+    }
+}
+
+/// Balances of the pair without the collected protocol fees.
+fn query_pools(deps: Deps, env: &Env, pair_info: &PairInfo) -> StdResult<[Asset; 2]> {
+    let fees = PROTOCOL_FEES.load(deps.storage)?;
+    let pool = |index: usize| -> StdResult<Asset> {
+        let info = pair_info.asset_infos[index].clone();
+        let balance = query_balance(&deps.querier, &info, &env.contract.address)?;
+        Ok(Asset {
+            info,
+            amount: balance.checked_sub(fees[index])?,
+        })
+    };
+    Ok([pool(0)?, pool(1)?])
+}
+
+fn query_balance(querier: &QuerierWrapper, info: &AssetInfo, addr: &Addr) -> StdResult<Uint128> {
+    match info {
+        AssetInfo::NativeToken {
+            denom,
+        } => Ok(querier.query_balance(addr, denom)?.amount),
+        AssetInfo::Token {
+            contract_addr,
+        } => {
+            let response: BalanceResponse = querier.query_wasm_smart(
+                contract_addr,
+                &Cw20QueryMsg::Balance {
+                    address: addr.to_string(),
+                },
+            )?;
+            Ok(response.balance)
+        },
+    }
+}
+
+fn transfer_msg(info: &AssetInfo, amount: Uint128, recipient: &Addr) -> StdResult<CosmosMsg> {
+    match info {
+        AssetInfo::NativeToken {
+            denom,
+        } => Ok(CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![Coin::new(amount.u128(), denom)],
+        })),
+        AssetInfo::Token {
+            contract_addr,
+        } => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.clone(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        })),
+    }
+}
+
+fn burn_msg(info: &AssetInfo, amount: Uint128) -> StdResult<CosmosMsg> {
+    match info {
+        AssetInfo::NativeToken {
+            denom,
+        } => Ok(CosmosMsg::Bank(BankMsg::Burn {
+            amount: vec![Coin::new(amount.u128(), denom)],
+        })),
+        AssetInfo::Token {
+            contract_addr,
+        } => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.clone(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Burn {
+                amount,
+            })?,
+            funds: vec![],
+        })),
+    }
+}
+
+fn assert_max_spread(
+    belief_price: Option<Decimal>,
+    max_spread: Option<Decimal>,
+    offer_amount: Uint128,
+    return_amount: Uint128,
+    spread_amount: Uint128,
+) -> StdResult<()> {
+    let max_spread = max_spread.unwrap_or_else(|| Decimal::percent(DEFAULT_MAX_SPREAD));
+    let exceeded = match belief_price {
+        Some(belief_price) => {
+            let expected_return = offer_amount * belief_price.inv().unwrap_or_default();
+            let spread = expected_return.saturating_sub(return_amount);
+            return_amount < expected_return
+                && Decimal::from_ratio(spread, expected_return) > max_spread
+        },
+        None => Decimal::from_ratio(spread_amount, return_amount + spread_amount) > max_spread,
+    };
+
+    if exceeded {
+        return Err(StdError::generic_err("Operation exceeds max spread limit"));
+    }
+    Ok(())
+}
+
+fn assert_slippage_tolerance(
+    slippage_tolerance: Option<Decimal>,
+    deposits: &[Uint128; 2],
+    pools: &[Uint128],
+) -> StdResult<()> {
+    let slippage_tolerance = match slippage_tolerance {
+        Some(slippage_tolerance) => slippage_tolerance,
+        None => return Ok(()),
+    };
+    if deposits.iter().any(|deposit| deposit.is_zero()) {
+        return Err(StdError::generic_err("slippage tolerance requires both assets"));
+    }
+
+    let one_minus_tolerance = Decimal::one() - slippage_tolerance;
+    let deposit_ratio = Decimal::from_ratio(deposits[0], deposits[1]);
+    let pool_ratio = Decimal::from_ratio(pools[0], pools[1]);
+    if deposit_ratio * one_minus_tolerance > pool_ratio
+        || pool_ratio * one_minus_tolerance > deposit_ratio
+    {
+        return Err(StdError::generic_err("Operation exceeds max slippage tolerance"));
+    }
+    Ok(())
+}
+
+fn compute_swap(
+    pair_info: &PairInfo,
+    offer_pool: Uint128,
+    ask_pool: Uint128,
+    offer_amount: Uint128,
+) -> StdResult<SimulationResponse> {
+    if offer_pool.is_zero() || ask_pool.is_zero() {
+        return Err(StdError::generic_err("pool is empty"));
+    }
+
+    let (return_amount, spread_amount) = match pair_info.pool_type {
+        PoolType::ConstantProduct => {
+            let cp = Uint256::from(offer_pool) * Uint256::from(ask_pool);
+            let new_ask_pool: Uint128 =
+                cp.checked_div(Uint256::from(offer_pool + offer_amount))?.try_into()?;
+            let return_amount = ask_pool - new_ask_pool;
+            let spread_amount =
+                offer_amount.multiply_ratio(ask_pool, offer_pool).saturating_sub(return_amount);
+            (return_amount, spread_amount)
+        },
+        PoolType::StableSwap {
+            amp,
+        } => {
+            let d = compute_d(amp, offer_pool, ask_pool)?;
+            let new_ask_pool: Uint128 = compute_y(amp, offer_pool + offer_amount, d)?.try_into()?;
+            // round against the user
+            let return_amount =
+                ask_pool.saturating_sub(new_ask_pool).saturating_sub(Uint128::one());
+            // stable assets are expected to trade 1:1
+            (return_amount, offer_amount.saturating_sub(return_amount))
+        },
+    };
+
+    let fees = &pair_info.pool_fees;
+    let swap_fee_amount = return_amount * fees.swap_fee;
+    let protocol_fee_amount = return_amount * fees.protocol_fee;
+    let burn_fee_amount = return_amount * fees.burn_fee;
+
+    Ok(SimulationResponse {
+        return_amount: return_amount - swap_fee_amount - protocol_fee_amount - burn_fee_amount,
+        spread_amount,
+        swap_fee_amount,
+        protocol_fee_amount,
+        burn_fee_amount,
+    })
+}
+
+fn compute_reverse_swap(
+    pair_info: &PairInfo,
+    offer_pool: Uint128,
+    ask_pool: Uint128,
+    ask_amount: Uint128,
+) -> StdResult<ReverseSimulationResponse> {
+    if offer_pool.is_zero() || ask_pool.is_zero() {
+        return Err(StdError::generic_err("pool is empty"));
+    }
+
+    let fees = &pair_info.pool_fees;
+    let one_minus_fees = Decimal::one() - fees.total();
+    let before_fees = ask_amount.multiply_ratio(Decimal::one().atomics(), one_minus_fees.atomics());
+    if before_fees >= ask_pool {
+        return Err(StdError::generic_err("ask amount exceeds the pool"));
+    }
+
+    let (offer_amount, spread_amount) = match pair_info.pool_type {
+        PoolType::ConstantProduct => {
+            let cp = Uint256::from(offer_pool) * Uint256::from(ask_pool);
+            let new_offer_pool: Uint128 =
+                cp.checked_div(Uint256::from(ask_pool - before_fees))?.try_into()?;
+            let offer_amount = new_offer_pool - offer_pool + Uint128::one();
+            let spread_amount =
+                offer_amount.multiply_ratio(ask_pool, offer_pool).saturating_sub(before_fees);
+            (offer_amount, spread_amount)
+        },
+        PoolType::StableSwap {
+            amp,
+        } => {
+            let d = compute_d(amp, offer_pool, ask_pool)?;
+            let new_offer_pool: Uint128 = compute_y(amp, ask_pool - before_fees, d)?.try_into()?;
+            let offer_amount = new_offer_pool - offer_pool + Uint128::one();
+            (offer_amount, offer_amount.saturating_sub(before_fees))
+        },
+    };
+
+    Ok(ReverseSimulationResponse {
+        offer_amount,
+        spread_amount,
+        swap_fee_amount: before_fees * fees.swap_fee,
+        protocol_fee_amount: before_fees * fees.protocol_fee,
+        burn_fee_amount: before_fees * fees.burn_fee,
+    })
+}
+
+/// Computes the stableswap invariant D of a pool with two assets.
+fn compute_d(amp: u64, x: Uint128, y: Uint128) -> StdResult<Uint256> {
+    if x.is_zero() || y.is_zero() {
+        return Err(StdError::generic_err("pool is empty"));
+    }
+
+    let x = Uint256::from(x);
+    let y = Uint256::from(y);
+    let two = Uint256::from(2u8);
+    let sum = x + y;
+    let ann = Uint256::from(amp * 4);
+
+    let mut d = sum;
+    for _ in 0..MAX_ITERATIONS {
+        let d_p = d * d / (x * two) * d / (y * two);
+        let previous = d;
+        d = (ann * sum + d_p * two) * d / ((ann - Uint256::one()) * d + d_p * Uint256::from(3u8));
+        if d.abs_diff(previous) <= Uint256::one() {
+            return Ok(d);
+        }
+    }
+    Err(StdError::generic_err("stableswap invariant did not converge"))
+}
+
+/// Computes the balance of the other asset, so that the pool keeps the invariant D.
+fn compute_y(amp: u64, x: Uint128, d: Uint256) -> StdResult<Uint256> {
+    let x = Uint256::from(x);
+    let two = Uint256::from(2u8);
+    let ann = Uint256::from(amp * 4);
+
+    let c = d * d / (x * two) * d / (ann * two);
+    let b = x + d / ann;
+
+    let mut y = d;
+    for _ in 0..MAX_ITERATIONS {
+        let previous = y;
+        y = (y * y + c) / (y * two + b).checked_sub(d)?;
+        if y.abs_diff(previous) <= Uint256::one() {
+            return Ok(y);
+        }
+    }
+    Err(StdError::generic_err("stableswap balance did not converge"))
+}
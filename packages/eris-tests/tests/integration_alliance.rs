@@ -0,0 +1,189 @@
+use cosmwasm_std::{coin, Addr, Binary, CosmosMsg, Decimal, Uint128};
+use cw_multi_test::Executor;
+use eris_tests::base::AllianceApp;
+use eris_tests::modules::alliance::AllianceModule;
+use eris_tests::{mock_alliance_app, AllianceAppExtension};
+use terra_proto_rs::{
+    alliance::alliance::{MsgClaimDelegationRewards, MsgDelegate, MsgRedelegate, MsgUndelegate},
+    cosmos::base::v1beta1::Coin,
+    prost::Message,
+};
+
+const ALLIANCE_DENOM: &str = "ibc/alliance";
+const YEAR: u64 = 365 * 24 * 60 * 60;
+
+fn alliance_app() -> AllianceApp {
+    mock_alliance_app(
+        AllianceModule {
+            reward_denom: "uluna".to_string(),
+            reward_apr: Decimal::percent(10),
+            take_rate: Decimal::percent(1),
+            unbonding_time: 100,
+        },
+        vec![("user", vec![coin(1000_000000, ALLIANCE_DENOM)])],
+    )
+}
+
+fn proto_coin(amount: u128) -> Option<Coin> {
+    Some(Coin {
+        denom: ALLIANCE_DENOM.to_string(),
+        amount: amount.to_string(),
+    })
+}
+
+fn stargate<T>(type_url: &str, msg: impl Message) -> CosmosMsg<T> {
+    CosmosMsg::Stargate {
+        type_url: type_url.to_string(),
+        value: Binary::from(msg.encode_to_vec()),
+    }
+}
+
+fn delegate(app: &mut AllianceApp, validator: &str, amount: u128) -> anyhow::Result<()> {
+    app.execute(
+        Addr::unchecked("user"),
+        stargate(
+            "/alliance.alliance.MsgDelegate",
+            MsgDelegate {
+                delegator_address: "user".to_string(),
+                validator_address: validator.to_string(),
+                amount: proto_coin(amount),
+            },
+        ),
+    )?;
+    Ok(())
+}
+
+fn undelegate(app: &mut AllianceApp, validator: &str, amount: u128) -> anyhow::Result<()> {
+    app.execute(
+        Addr::unchecked("user"),
+        stargate(
+            "/alliance.alliance.MsgUndelegate",
+            MsgUndelegate {
+                delegator_address: "user".to_string(),
+                validator_address: validator.to_string(),
+                amount: proto_coin(amount),
+            },
+        ),
+    )?;
+    Ok(())
+}
+
+fn claim(app: &mut AllianceApp, validator: &str) -> anyhow::Result<()> {
+    app.execute(
+        Addr::unchecked("user"),
+        stargate(
+            "/alliance.alliance.MsgClaimDelegationRewards",
+            MsgClaimDelegationRewards {
+                delegator_address: "user".to_string(),
+                validator_address: validator.to_string(),
+                denom: ALLIANCE_DENOM.to_string(),
+            },
+        ),
+    )?;
+    Ok(())
+}
+
+fn balance(app: &AllianceApp, denom: &str) -> Uint128 {
+    app.wrap().query_balance("user", denom).unwrap().amount
+}
+
+fn delegated(app: &AllianceApp, validator: &str) -> Option<Uint128> {
+    app.alliance_delegation(ALLIANCE_DENOM, "user", validator).ok().map(|d| d.amount.amount)
+}
+
+#[test]
+fn alliance_delegate_claim_undelegate() {
+    let mut app = alliance_app();
+
+    delegate(&mut app, "val1", 100_000000).unwrap();
+    assert_eq!(delegated(&app, "val1"), Some(Uint128::new(100_000000)));
+    assert_eq!(balance(&app, ALLIANCE_DENOM), Uint128::new(900_000000));
+
+    // rewards accrue in the reward denom
+    app.alliance_next_block(YEAR).unwrap();
+    claim(&mut app, "val1").unwrap();
+    assert_eq!(balance(&app, "uluna"), Uint128::new(10_000000));
+
+    // claiming again without time passing does not pay anything
+    claim(&mut app, "val1").unwrap();
+    assert_eq!(balance(&app, "uluna"), Uint128::new(10_000000));
+
+    let err = undelegate(&mut app, "val1", 200_000000).unwrap_err();
+    assert!(err.root_cause().to_string().starts_with("insufficient delegation"));
+
+    undelegate(&mut app, "val1", 40_000000).unwrap();
+    assert_eq!(delegated(&app, "val1"), Some(Uint128::new(60_000000)));
+
+    // unbonding has not matured yet
+    app.alliance_next_block(99).unwrap();
+    assert_eq!(balance(&app, ALLIANCE_DENOM), Uint128::new(900_000000));
+
+    app.alliance_next_block(1).unwrap();
+    assert_eq!(balance(&app, ALLIANCE_DENOM), Uint128::new(940_000000));
+
+    undelegate(&mut app, "val1", 60_000000).unwrap();
+    assert_eq!(delegated(&app, "val1"), None);
+}
+
+#[test]
+fn alliance_redelegate() {
+    let mut app = alliance_app();
+    delegate(&mut app, "val1", 100_000000).unwrap();
+
+    app.execute(
+        Addr::unchecked("user"),
+        stargate(
+            "/alliance.alliance.MsgRedelegate",
+            MsgRedelegate {
+                delegator_address: "user".to_string(),
+                validator_src_address: "val1".to_string(),
+                validator_dst_address: "val2".to_string(),
+                amount: proto_coin(30_000000),
+            },
+        ),
+    )
+    .unwrap();
+
+    assert_eq!(delegated(&app, "val1"), Some(Uint128::new(70_000000)));
+    assert_eq!(delegated(&app, "val2"), Some(Uint128::new(30_000000)));
+}
+
+#[test]
+fn alliance_slash_and_take_rate() {
+    let mut app = alliance_app();
+    delegate(&mut app, "val1", 100_000000).unwrap();
+    delegate(&mut app, "val2", 100_000000).unwrap();
+    undelegate(&mut app, "val1", 50_000000).unwrap();
+
+    app.alliance_slash("val1", Decimal::percent(10)).unwrap();
+    assert_eq!(delegated(&app, "val1"), Some(Uint128::new(45_000000)));
+    assert_eq!(delegated(&app, "val2"), Some(Uint128::new(100_000000)));
+
+    app.alliance_apply_take_rate().unwrap();
+    assert_eq!(delegated(&app, "val1"), Some(Uint128::new(44_550000)));
+    assert_eq!(delegated(&app, "val2"), Some(Uint128::new(99_000000)));
+
+    // the unbonding is slashed as well
+    app.alliance_next_block(100).unwrap();
+    assert_eq!(balance(&app, ALLIANCE_DENOM), Uint128::new(845_000000));
+}
+
+#[test]
+fn alliance_only_delegator_can_send() {
+    let mut app = alliance_app();
+
+    let err = app
+        .execute(
+            Addr::unchecked("other"),
+            stargate(
+                "/alliance.alliance.MsgDelegate",
+                MsgDelegate {
+                    delegator_address: "user".to_string(),
+                    validator_address: "val1".to_string(),
+                    amount: proto_coin(100),
+                },
+            ),
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "sender other is not the delegator user");
+}
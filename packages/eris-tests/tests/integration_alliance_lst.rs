@@ -0,0 +1,288 @@
+use cosmwasm_std::{
+    coin, Addr, Binary, Decimal, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError,
+    StdResult, Uint128,
+};
+use cw_multi_test::{Contract, ContractWrapper, Executor};
+use cw_storage_plus::Item;
+use eris::alliance_lst::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use eris::hub::StateResponse;
+use eris_chain_adapter::types::{CustomMsgType, CustomQueryType};
+use eris_tests::base::AllianceApp;
+use eris_tests::modules::alliance::AllianceModule;
+use eris_tests::whitewhale_pair::{
+    whitewhale_pair_contract, InstantiateMsg as PairInstantiateMsg, PairInfo, PoolFees, PoolType,
+    QueryMsg as PairQueryMsg,
+};
+use eris_tests::{mock_alliance_app, AllianceAppExtension};
+use eris_whitewhale::adapters::whitewhaledex::{Asset, AssetInfo, ExecuteMsg as PairExecuteMsg};
+
+const WHALE: &str = "uwhale";
+const BTC: &str = "ibc/btc";
+const VALIDATORS: [&str; 2] = ["val1", "val2"];
+const EPOCH_PERIOD: u64 = 259200;
+const UNBOND_PERIOD: u64 = 1814400;
+const YEAR: u64 = 365 * 24 * 60 * 60;
+
+/// Stores the validators in the item read by the hub, like the validator proxy contract.
+fn validator_proxy_contract() -> Box<dyn Contract<CustomMsgType, CustomQueryType>> {
+    Box::new(ContractWrapper::new_with_empty(
+        |_: DepsMut, _: Env, _: MessageInfo, _: Empty| -> StdResult<Response> {
+            Ok(Response::new())
+        },
+        |deps: DepsMut, _: Env, _: MessageInfo, validators: Vec<String>| -> StdResult<Response> {
+            Item::<Vec<String>>::new("validators").save(deps.storage, &validators)?;
+            Ok(Response::new())
+        },
+        |_: Deps, _: Env, _: Empty| -> StdResult<Binary> {
+            Err(StdError::generic_err("not supported"))
+        },
+    ))
+}
+
+fn hub_contract() -> Box<dyn Contract<CustomMsgType, CustomQueryType>> {
+    Box::new(ContractWrapper::new_with_empty(
+        eris_alliance_lst::contract::execute,
+        eris_alliance_lst::contract::instantiate,
+        eris_alliance_lst::contract::query,
+    ))
+}
+
+fn native(denom: &str) -> AssetInfo {
+    AssetInfo::NativeToken {
+        denom: denom.to_string(),
+    }
+}
+
+fn provide_liquidity(app: &mut AllianceApp, pair: &Addr, sender: &str, amount: u128) {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        pair.clone(),
+        &PairExecuteMsg::ProvideLiquidity {
+            assets: vec![
+                Asset {
+                    info: native(WHALE),
+                    amount: Uint128::new(amount),
+                },
+                Asset {
+                    info: native(BTC),
+                    amount: Uint128::new(amount),
+                },
+            ],
+            slippage_tolerance: None,
+            receiver: None,
+        },
+        &[coin(amount, BTC), coin(amount, WHALE)],
+    )
+    .unwrap();
+}
+
+struct Setup {
+    app: AllianceApp,
+    hub: Addr,
+    lp_denom: String,
+    stake_denom: String,
+}
+
+fn setup() -> Setup {
+    let mut app = mock_alliance_app(
+        AllianceModule {
+            reward_denom: WHALE.to_string(),
+            reward_apr: Decimal::percent(10),
+            take_rate: Decimal::zero(),
+            unbonding_time: UNBOND_PERIOD,
+        },
+        vec![
+            ("provider", vec![coin(1_000_000_000, WHALE), coin(1_000_000_000, BTC)]),
+            ("user", vec![coin(100_000_000, WHALE), coin(100_000_000, BTC)]),
+        ],
+    );
+
+    let pair_code = app.store_code(whitewhale_pair_contract());
+    let pair = app
+        .instantiate_contract(
+            pair_code,
+            Addr::unchecked("owner"),
+            &PairInstantiateMsg {
+                asset_infos: [native(WHALE), native(BTC)],
+                pool_type: PoolType::ConstantProduct,
+                pool_fees: PoolFees {
+                    protocol_fee: Decimal::permille(1),
+                    swap_fee: Decimal::permille(3),
+                    burn_fee: Decimal::zero(),
+                },
+            },
+            &[],
+            "pair",
+            None,
+        )
+        .unwrap();
+    let pair_info: PairInfo = app.wrap().query_wasm_smart(&pair, &PairQueryMsg::Pair {}).unwrap();
+
+    provide_liquidity(&mut app, &pair, "provider", 1_000_000_000);
+    provide_liquidity(&mut app, &pair, "user", 100_000_000);
+
+    let proxy_code = app.store_code(validator_proxy_contract());
+    let proxy = app
+        .instantiate_contract(
+            proxy_code,
+            Addr::unchecked("owner"),
+            &VALIDATORS.map(String::from).to_vec(),
+            &[],
+            "validator_proxy",
+            None,
+        )
+        .unwrap();
+
+    let hub_code = app.store_code(hub_contract());
+    let hub = app
+        .instantiate_contract(
+            hub_code,
+            Addr::unchecked("owner"),
+            &InstantiateMsg {
+                owner: "owner".to_string(),
+                operator: "operator".to_string(),
+                utoken: pair_info.liquidity_token.clone(),
+                denom: "ampLP".to_string(),
+                epoch_period: EPOCH_PERIOD,
+                unbond_period: UNBOND_PERIOD,
+                validator_proxy: proxy.to_string(),
+                whale_btc_pool: pair.to_string(),
+                btc_denom: BTC.to_string(),
+                whale_denom: WHALE.to_string(),
+                protocol_fee_contract: "fee".to_string(),
+                protocol_reward_fee: Decimal::percent(1),
+                delegation_strategy: None,
+                denom_metadata: None,
+                guardian: "guardian".to_string(),
+            },
+            &[],
+            "hub",
+            None,
+        )
+        .unwrap();
+
+    Setup {
+        stake_denom: format!("factory/{0}/ampLP", hub),
+        app,
+        hub,
+        lp_denom: pair_info.liquidity_token,
+    }
+}
+
+fn balance(app: &AllianceApp, addr: impl Into<String>, denom: &str) -> Uint128 {
+    app.wrap().query_balance(addr, denom).unwrap().amount
+}
+
+fn hub_state(setup: &Setup) -> StateResponse {
+    setup.app.wrap().query_wasm_smart(&setup.hub, &QueryMsg::State {}).unwrap()
+}
+
+/// Sum of the delegations of the hub tracked by the alliance module
+fn delegated(setup: &Setup) -> Uint128 {
+    VALIDATORS
+        .iter()
+        .filter_map(|validator| {
+            setup
+                .app
+                .alliance_delegation(&setup.lp_denom, setup.hub.as_str(), validator)
+                .ok()
+                .map(|d| d.amount.amount)
+        })
+        .sum()
+}
+
+#[test]
+fn bond_harvest_unbond_withdraw() {
+    let mut setup = setup();
+    let user = Addr::unchecked("user");
+    let bonded = balance(&setup.app, "user", &setup.lp_denom);
+    assert_eq!(bonded, Uint128::new(100_000_000));
+
+    // bonding delegates the LP tokens through the alliance module
+    setup
+        .app
+        .execute_contract(
+            user.clone(),
+            setup.hub.clone(),
+            &ExecuteMsg::Bond {
+                receiver: None,
+            },
+            &[coin(bonded.u128(), &setup.lp_denom)],
+        )
+        .unwrap();
+
+    assert_eq!(balance(&setup.app, "user", &setup.stake_denom), bonded);
+    assert_eq!(delegated(&setup), bonded);
+    let state = hub_state(&setup);
+    assert_eq!(state.total_ustake, bonded);
+    assert_eq!(state.total_utoken, bonded);
+
+    // harvesting claims the WHALE rewards, provides them as liquidity and delegates the LP tokens
+    setup.app.alliance_next_block(YEAR).unwrap();
+    setup
+        .app
+        .execute_contract(
+            Addr::unchecked("anyone"),
+            setup.hub.clone(),
+            &ExecuteMsg::Harvest {
+                validators: None,
+                withdrawals: None,
+                stages: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    let state = hub_state(&setup);
+    assert!(state.total_utoken > bonded);
+    assert!(state.exchange_rate > Decimal::one());
+    assert_eq!(state.total_ustake, bonded);
+    assert_eq!(delegated(&setup), state.total_utoken);
+    assert!(!balance(&setup.app, "fee", &setup.lp_denom).is_zero());
+    let total_utoken = state.total_utoken;
+
+    // the batch is submitted with the unbond request, as the epoch has already passed
+    let unbonded = bonded.multiply_ratio(1u128, 2u128);
+    let utoken_unbonded = total_utoken.multiply_ratio(unbonded, bonded);
+    setup
+        .app
+        .execute_contract(
+            user.clone(),
+            setup.hub.clone(),
+            &ExecuteMsg::QueueUnbond {
+                receiver: None,
+            },
+            &[coin(unbonded.u128(), &setup.stake_denom)],
+        )
+        .unwrap();
+
+    assert_eq!(delegated(&setup), total_utoken - utoken_unbonded);
+    assert_eq!(balance(&setup.app, "user", &setup.stake_denom), bonded - unbonded);
+    assert!(balance(&setup.app, setup.hub.as_str(), &setup.lp_denom).is_zero());
+
+    // the unbonding is paid out by the alliance module once it matured
+    setup.app.alliance_next_block(UNBOND_PERIOD + 1).unwrap();
+    assert_eq!(balance(&setup.app, setup.hub.as_str(), &setup.lp_denom), utoken_unbonded);
+
+    setup
+        .app
+        .execute_contract(user.clone(), setup.hub.clone(), &ExecuteMsg::Reconcile {}, &[])
+        .unwrap();
+    setup
+        .app
+        .execute_contract(
+            user,
+            setup.hub.clone(),
+            &ExecuteMsg::WithdrawUnbonded {
+                receiver: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    assert_eq!(balance(&setup.app, "user", &setup.lp_denom), utoken_unbonded);
+    let state = hub_state(&setup);
+    assert_eq!(state.total_ustake, bonded - unbonded);
+    assert_eq!(state.total_utoken, total_utoken - utoken_unbonded);
+    assert_eq!(delegated(&setup), state.total_utoken);
+}
@@ -0,0 +1,179 @@
+use cosmwasm_std::{coin, coins, Uint128};
+use eris::amp_bribes::ExecuteMsg;
+use eris::governance_helper::WEEK;
+use eris_tests::gov_helper::EscrowHelper;
+use eris_tests::{mock_app, CustomAppExtension};
+
+#[test]
+fn integration_bribes() {
+    let mut router = mock_app();
+    let helper = EscrowHelper::init(&mut router, false);
+
+    helper.ve_lock_lp(&mut router, "user1", 100000, 104 * WEEK).unwrap();
+    helper.ve_lock_lp(&mut router, "user2", 100000, 104 * WEEK).unwrap();
+
+    let period = router.block_period() + 1;
+    let deposit = |validator_addr: &str, period: u64| ExecuteMsg::DepositBribe {
+        validator_addr: validator_addr.to_string(),
+        period,
+    };
+    let claim = |validator_addr: &str| ExecuteMsg::Claim {
+        validator_addr: validator_addr.to_string(),
+        period,
+    };
+
+    let err = helper
+        .bribe_execute_sender(
+            &mut router,
+            deposit("val1", period - 1),
+            "fake",
+            &coins(100, "uluna"),
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Bribes can only be deposited for future periods");
+    let err = helper
+        .bribe_execute_sender(&mut router, deposit("valX", period), "fake", &coins(100, "uluna"))
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Invalid validator address: valX");
+    let err =
+        helper.bribe_execute_sender(&mut router, deposit("val1", period), "fake", &[]).unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "No funds sent");
+    let err = helper
+        .bribe_execute_sender(&mut router, deposit("val1", period), "fake", &coins(100, "uspam"))
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Denom uspam can not be deposited as a bribe");
+
+    let update_config = |claim_periods: u64| ExecuteMsg::UpdateConfig {
+        claim_periods: Some(claim_periods),
+        allowed_denoms: None,
+    };
+    let err = helper
+        .bribe_execute_sender(&mut router, update_config(0), helper.owner.clone(), &[])
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "The claim periods must be at least 1");
+    helper.bribe_execute_sender(&mut router, update_config(4), helper.owner.clone(), &[]).unwrap();
+
+    helper
+        .bribe_execute_sender(&mut router, deposit("val1", period), "fake", &coins(1000, "uluna"))
+        .unwrap();
+    helper
+        .bribe_execute_sender(&mut router, deposit("val2", period), "fake", &coins(500, "uluna"))
+        .unwrap();
+
+    helper.amp_vote(&mut router, "user1", vec![("val1".to_string(), 10000)]).unwrap();
+    helper
+        .amp_vote(
+            &mut router,
+            "user2",
+            vec![("val1".to_string(), 5000), ("val3".to_string(), 5000)],
+        )
+        .unwrap();
+
+    let err = helper.bribe_execute_sender(&mut router, claim("val1"), "user1", &[]).unwrap_err();
+    assert_eq!(err.root_cause().to_string(), format!("Period {0} was not tuned yet", period));
+
+    router.next_period(1);
+    helper.amp_tune(&mut router).unwrap();
+
+    // user1 voted with twice the vAMP of user2 for val1
+    let claimable1 = helper.bribe_query_claimable(&mut router, "user1", "val1", period).unwrap();
+    let claimable2 = helper.bribe_query_claimable(&mut router, "user2", "val1", period).unwrap();
+    assert_eq!(claimable1.funds, vec![coin(666, "uluna")]);
+    assert_eq!(claimable2.funds, vec![coin(333, "uluna")]);
+    assert!(claimable1.user_vamp + claimable2.user_vamp <= claimable1.total_vamp);
+
+    helper.bribe_execute_sender(&mut router, claim("val1"), "user1", &[]).unwrap();
+    let balance = router.wrap().query_balance("user1", "uluna").unwrap();
+    assert_eq!(balance.amount, Uint128::new(1000_000666));
+
+    let err = helper.bribe_execute_sender(&mut router, claim("val1"), "user1", &[]).unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Bribes already claimed");
+    let err = helper.bribe_execute_sender(&mut router, claim("val1"), "user3", &[]).unwrap_err();
+    assert!(err.root_cause().to_string().contains("User not found"));
+
+    // changing the vote does not change the votes of a past period
+    helper.amp_vote(&mut router, "user2", vec![("val3".to_string(), 10000)]).unwrap();
+    router.next_period(1);
+    helper.amp_tune(&mut router).unwrap();
+    let claimable2 = helper.bribe_query_claimable(&mut router, "user2", "val1", period).unwrap();
+    assert_eq!(claimable2.funds, vec![coin(333, "uluna")]);
+
+    // nobody voted for val2, so the deposit can be refunded
+    let refund = |validator_addr: &str| ExecuteMsg::Refund {
+        validator_addr: validator_addr.to_string(),
+        period,
+    };
+    let err = helper.bribe_execute_sender(&mut router, refund("val1"), "fake", &[]).unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Deposits can only be refunded if nobody voted for the validator"
+    );
+    let err = helper.bribe_execute_sender(&mut router, refund("val2"), "user1", &[]).unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "No deposit found");
+    let balance_before = router.wrap().query_balance("fake", "uluna").unwrap();
+    helper.bribe_execute_sender(&mut router, refund("val2"), "fake", &[]).unwrap();
+    let balance = router.wrap().query_balance("fake", "uluna").unwrap();
+    assert_eq!(balance.amount - balance_before.amount, Uint128::new(500));
+
+    // unclaimed bribes are rolled over after the claim periods
+    let rollover = ExecuteMsg::Rollover {
+        validator_addr: "val1".to_string(),
+        period,
+    };
+    let err = helper.bribe_execute_sender(&mut router, rollover.clone(), "user3", &[]).unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        format!("The claim periods of period {0} did not end yet", period)
+    );
+
+    router.next_period(3);
+    let err = helper.bribe_execute_sender(&mut router, claim("val1"), "user2", &[]).unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        format!("The claim periods of period {0} ended", period)
+    );
+
+    helper.bribe_execute_sender(&mut router, rollover.clone(), "user3", &[]).unwrap();
+    let info = helper.bribe_query_info(&mut router, "val1", period).unwrap();
+    assert!(info.rolled_over);
+    let next_period = router.block_period() + 1;
+    let info = helper.bribe_query_info(&mut router, "val1", next_period).unwrap();
+    assert_eq!(info.funds, vec![coin(334, "uluna")]);
+
+    let err = helper.bribe_execute_sender(&mut router, rollover, "user3", &[]).unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "The bribes were rolled over to a later period");
+
+    // the rolled over funds stay refundable by the depositor, if nobody votes for val1 anymore
+    let deposit = helper.bribe_query_deposit(&mut router, "fake", "val1", next_period).unwrap();
+    assert_eq!(deposit, vec![coin(334, "uluna")]);
+
+    helper.amp_vote(&mut router, "user1", vec![("val3".to_string(), 10000)]).unwrap();
+    router.next_period(1);
+    helper.amp_tune(&mut router).unwrap();
+
+    let balance_before = router.wrap().query_balance("fake", "uluna").unwrap();
+    helper
+        .bribe_execute_sender(
+            &mut router,
+            ExecuteMsg::Refund {
+                validator_addr: "val1".to_string(),
+                period: next_period,
+            },
+            "fake",
+            &[],
+        )
+        .unwrap();
+    let balance = router.wrap().query_balance("fake", "uluna").unwrap();
+    assert_eq!(balance.amount - balance_before.amount, Uint128::new(334));
+
+    // bribes of validators without votes stay refundable
+    let rollover = ExecuteMsg::Rollover {
+        validator_addr: "val2".to_string(),
+        period,
+    };
+    let err = helper.bribe_execute_sender(&mut router, rollover, "user3", &[]).unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Bribes of validators without votes can only be refunded"
+    );
+}
@@ -1,4 +1,4 @@
-use cosmwasm_std::{attr, StdResult, Uint128};
+use cosmwasm_std::{attr, coin, coins, Addr, StdResult, Uint128};
 use eris::governance_helper::WEEK;
 use eris_tests::gov_helper::EscrowHelper;
 use eris_tests::{mock_app, CustomAppExtension, EventChecker};
@@ -19,6 +19,7 @@ fn integration_update_configs() -> StdResult<()> {
             &mut router,
             ExecuteMsg::UpdateConfig {
                 validators_limit: Some(40),
+                keeper_tip: None,
             },
             "user",
         )
@@ -31,6 +32,7 @@ fn integration_update_configs() -> StdResult<()> {
             &mut router,
             ExecuteMsg::UpdateConfig {
                 validators_limit: Some(40),
+                keeper_tip: None,
             },
         )
         .unwrap();
@@ -41,6 +43,70 @@ fn integration_update_configs() -> StdResult<()> {
     Ok(())
 }
 
+#[test]
+fn integration_keeper_tune() -> StdResult<()> {
+    let mut router = mock_app();
+    let helper = EscrowHelper::init(&mut router, false);
+
+    helper.ve_lock_lp(&mut router, "user1", 100000, 3 * WEEK).unwrap();
+    helper.amp_vote(&mut router, "user1", vec![("val1".to_string(), 10000)]).unwrap();
+
+    helper
+        .amp_execute(
+            &mut router,
+            ExecuteMsg::UpdateConfig {
+                validators_limit: None,
+                keeper_tip: Some(coin(100, "uluna")),
+            },
+        )
+        .unwrap();
+    router
+        .send_tokens(
+            Addr::unchecked("fake"),
+            helper.base.amp_gauges.get_address(),
+            &coins(150, "uluna"),
+        )
+        .unwrap();
+
+    // the gauges were tuned in the period of the instantiation
+    let err =
+        helper.amp_execute_sender(&mut router, ExecuteMsg::TuneVamp {}, "keeper").unwrap_err();
+    assert_eq!(
+        format!("Tuning already happened in period {}", router.block_period()),
+        err.root_cause().to_string()
+    );
+
+    router.next_period(1);
+    let result = helper.amp_execute_sender(&mut router, ExecuteMsg::TuneVamp {}, "keeper").unwrap();
+    result.assert_attribute("wasm", attr("keeper_tip", "true")).unwrap();
+    let balance = router.wrap().query_balance("keeper", "uluna")?;
+    assert_eq!(balance.amount, Uint128::new(100));
+    let info = helper.amp_query_tune_info(&mut router).unwrap();
+    assert_eq!(info.vamp_points, vec![("val1".to_string(), Uint128::new(125959))]);
+
+    // only once per period for keepers, the owner can still tune at any time
+    let err =
+        helper.amp_execute_sender(&mut router, ExecuteMsg::TuneVamp {}, "keeper").unwrap_err();
+    assert_eq!(
+        format!("Tuning already happened in period {}", router.block_period()),
+        err.root_cause().to_string()
+    );
+    let result = helper.amp_execute(&mut router, ExecuteMsg::TuneVamp {}).unwrap();
+    result.assert_attribute("wasm", attr("keeper_tip", "false")).unwrap();
+
+    // the remaining balance does not cover the tip
+    router.next_period(1);
+    let result = helper.amp_execute_sender(&mut router, ExecuteMsg::TuneVamp {}, "keeper").unwrap();
+    result.assert_attribute("wasm", attr("keeper_tip", "false")).unwrap();
+    let balance = router.wrap().query_balance("keeper", "uluna")?;
+    assert_eq!(balance.amount, Uint128::new(100));
+
+    let config = helper.amp_query_config(&mut router).unwrap();
+    assert_eq!(config.keeper_tip, Some(coin(100, "uluna")));
+
+    Ok(())
+}
+
 #[test]
 fn integration_vote() -> StdResult<()> {
     let mut router = mock_app();
@@ -149,6 +215,58 @@ fn integration_vote() -> StdResult<()> {
     Ok(())
 }
 
+#[test]
+fn integration_vote_delegation() -> StdResult<()> {
+    let mut router = mock_app();
+    let helper = EscrowHelper::init(&mut router, false);
+
+    helper.ve_lock_lp(&mut router, "user1", 100000, 104 * WEEK).unwrap();
+    helper.ve_lock_lp(&mut router, "user2", 100000, 104 * WEEK).unwrap();
+
+    let expires = router.block_info().time.seconds() + 10 * WEEK;
+    helper
+        .ve_execute_sender(
+            &mut router,
+            eris::voting_escrow::ExecuteMsg::Delegate {
+                delegatee: "user1".to_string(),
+                bps: 10000,
+                expires,
+            },
+            Addr::unchecked("user2"),
+        )
+        .unwrap();
+
+    // the vote of user1 is also applied to the voting power delegated by user2
+    helper.amp_vote(&mut router, "user1", vec![("val1".to_string(), 10000)]).unwrap();
+    let user2 = helper.amp_query_user_info(&mut router, "user2").unwrap();
+    assert_eq!(user2.delegated_by, Some(Addr::unchecked("user1")));
+    assert_eq!(user2.votes, vec![("val1".to_string(), 10000u16)]);
+
+    router.next_period(1);
+    helper.amp_execute(&mut router, ExecuteMsg::TuneVamp {}).unwrap();
+    let info = helper.amp_query_tune_info(&mut router).unwrap();
+    assert_eq!(info.vamp_points.len(), 1);
+    assert_eq!(info.vamp_points[0].0, "val1".to_string());
+
+    // voting overrides the delegation of user2
+    helper.amp_vote(&mut router, "user2", vec![("val2".to_string(), 10000)]).unwrap();
+    let user2 = helper.amp_query_user_info(&mut router, "user2").unwrap();
+    assert_eq!(user2.delegated_by, None);
+
+    router.next_period(1);
+    helper.amp_execute(&mut router, ExecuteMsg::TuneVamp {}).unwrap();
+    let info = helper.amp_query_tune_info(&mut router).unwrap();
+    assert_eq!(info.vamp_points.len(), 2);
+    assert_eq!(info.vamp_points[0].1, info.vamp_points[1].1);
+
+    // a new vote of user1 does not change the vote of user2
+    helper.amp_vote(&mut router, "user1", vec![("val3".to_string(), 10000)]).unwrap();
+    let user2 = helper.amp_query_user_info(&mut router, "user2").unwrap();
+    assert_eq!(user2.votes, vec![("val2".to_string(), 10000u16)]);
+
+    Ok(())
+}
+
 #[test]
 fn integration_update_vote_extend_locktime() -> StdResult<()> {
     let mut router = mock_app();
@@ -4,7 +4,7 @@ use eris_tests::{mock_app, CustomAppExtension, EventChecker};
 use std::vec;
 
 use eris::emp_gauges::{
-    ConfigResponse, EmpInfo, ExecuteMsg, GaugeInfoResponse, VotedValidatorInfoResponse,
+    ConfigResponse, EmpCategory, EmpInfo, ExecuteMsg, GaugeInfoResponse, VotedValidatorInfoResponse,
 };
 
 #[test]
@@ -20,6 +20,7 @@ fn integration_update_configs() {
             &mut router,
             ExecuteMsg::UpdateConfig {
                 validators_limit: Some(40),
+                keeper_tip: None,
             },
             "user",
         )
@@ -32,6 +33,7 @@ fn integration_update_configs() {
             &mut router,
             ExecuteMsg::UpdateConfig {
                 validators_limit: Some(40),
+                keeper_tip: None,
             },
         )
         .unwrap();
@@ -54,6 +56,8 @@ fn integration_add_points() -> StdResult<()> {
                     vec![EmpInfo {
                         decaying_period: Some(3),
                         umerit_points: Uint128::new(1000000),
+                        category: None,
+                        reason: None,
                     }],
                 )],
             },
@@ -73,10 +77,14 @@ fn integration_add_points() -> StdResult<()> {
                             EmpInfo {
                                 decaying_period: Some(2 * 4), // 2 months
                                 umerit_points: Uint128::new(2000000),
+                                category: None,
+                                reason: None,
                             },
                             EmpInfo {
                                 decaying_period: None,
                                 umerit_points: Uint128::new(1000000),
+                                category: None,
+                                reason: None,
                             },
                         ],
                     ),
@@ -86,10 +94,14 @@ fn integration_add_points() -> StdResult<()> {
                             EmpInfo {
                                 decaying_period: Some(2 * 4), // 2 months
                                 umerit_points: Uint128::new(1000000),
+                                category: None,
+                                reason: None,
                             },
                             EmpInfo {
                                 decaying_period: None,
                                 umerit_points: Uint128::new(2000000),
+                                category: None,
+                                reason: None,
                             },
                         ],
                     ),
@@ -120,6 +132,8 @@ fn integration_add_points() -> StdResult<()> {
                         vec![EmpInfo {
                             decaying_period: Some(4), // 1 months
                             umerit_points: Uint128::new(1000000),
+                            category: None,
+                            reason: None,
                         }],
                     ),
                     (
@@ -127,6 +141,8 @@ fn integration_add_points() -> StdResult<()> {
                         vec![EmpInfo {
                             decaying_period: Some(4), // 1 months
                             umerit_points: Uint128::new(1000000),
+                            category: None,
+                            reason: None,
                         }],
                     ),
                     (
@@ -134,6 +150,8 @@ fn integration_add_points() -> StdResult<()> {
                         vec![EmpInfo {
                             decaying_period: None,
                             umerit_points: Uint128::new(500000),
+                            category: None,
+                            reason: None,
                         }],
                     ),
                 ],
@@ -269,10 +287,14 @@ fn integration_check_kick_holders_works() -> StdResult<()> {
                             EmpInfo {
                                 decaying_period: Some(2 * 4), // 2 months
                                 umerit_points: Uint128::new(2000000),
+                                category: None,
+                                reason: None,
                             },
                             EmpInfo {
                                 decaying_period: None,
                                 umerit_points: Uint128::new(1000000),
+                                category: None,
+                                reason: None,
                             },
                         ],
                     ),
@@ -282,10 +304,14 @@ fn integration_check_kick_holders_works() -> StdResult<()> {
                             EmpInfo {
                                 decaying_period: Some(2 * 4), // 2 months
                                 umerit_points: Uint128::new(1000000),
+                                category: None,
+                                reason: None,
                             },
                             EmpInfo {
                                 decaying_period: None,
                                 umerit_points: Uint128::new(2000000),
+                                category: None,
+                                reason: None,
                             },
                         ],
                     ),
@@ -321,6 +347,8 @@ fn integration_add_points_later() -> StdResult<()> {
                     vec![EmpInfo {
                         decaying_period: None,
                         umerit_points: Uint128::new(1000000),
+                        category: None,
+                        reason: None,
                     }],
                 )],
             },
@@ -339,6 +367,8 @@ fn integration_add_points_later() -> StdResult<()> {
                     vec![EmpInfo {
                         decaying_period: None,
                         umerit_points: Uint128::new(2000000),
+                        category: None,
+                        reason: None,
                     }],
                 )],
             },
@@ -355,6 +385,8 @@ fn integration_add_points_later() -> StdResult<()> {
                     vec![EmpInfo {
                         decaying_period: None,
                         umerit_points: Uint128::new(2000000),
+                        category: None,
+                        reason: None,
                     }],
                 )],
             },
@@ -365,6 +397,127 @@ fn integration_add_points_later() -> StdResult<()> {
     Ok(())
 }
 
+#[test]
+fn integration_remove_emps() -> StdResult<()> {
+    let mut router = mock_app();
+    let helper = EscrowHelper::init(&mut router, false);
+
+    helper
+        .emp_execute(
+            &mut router,
+            ExecuteMsg::AddEmps {
+                emps: vec![
+                    (
+                        "val1".to_string(),
+                        vec![
+                            EmpInfo {
+                                decaying_period: Some(2 * 4), // 2 months
+                                umerit_points: Uint128::new(2000000),
+                                category: Some(EmpCategory::Uptime),
+                                reason: Some("uptime".to_string()),
+                            },
+                            EmpInfo {
+                                decaying_period: None,
+                                umerit_points: Uint128::new(1000000),
+                                category: Some(EmpCategory::CommunityWork),
+                                reason: None,
+                            },
+                        ],
+                    ),
+                    (
+                        "val2".to_string(),
+                        vec![EmpInfo {
+                            decaying_period: None,
+                            umerit_points: Uint128::new(500000),
+                            category: Some(EmpCategory::GovernanceParticipation),
+                            reason: None,
+                        }],
+                    ),
+                ],
+            },
+        )
+        .unwrap();
+
+    let grant_period = router.block_period();
+    let grants = helper.emp_query_grants(&mut router, "val1", None).unwrap();
+    assert_eq!(grants.iter().map(|grant| grant.id).collect::<Vec<_>>(), vec![1, 2]);
+    assert_eq!(grants[0].period, grant_period);
+    assert_eq!(grants[0].emp.category, Some(EmpCategory::Uptime));
+    assert_eq!(grants[0].emp.reason, Some("uptime".to_string()));
+    let grants = helper.emp_query_grants(&mut router, "val2", Some(grant_period)).unwrap();
+    assert_eq!(grants.iter().map(|grant| grant.id).collect::<Vec<_>>(), vec![3]);
+    let grants = helper.emp_query_grants(&mut router, "val2", Some(grant_period + 1)).unwrap();
+    assert_eq!(grants, vec![]);
+
+    router.next_period(4);
+
+    let err = helper
+        .emp_execute_sender(
+            &mut router,
+            ExecuteMsg::RemoveEmps {
+                ids: vec![1],
+            },
+            "user",
+        )
+        .unwrap_err();
+    assert_eq!("Generic error: unauthorized", err.root_cause().to_string());
+
+    // half of the decaying points are left
+    let result = helper
+        .emp_execute(
+            &mut router,
+            ExecuteMsg::RemoveEmps {
+                ids: vec![1],
+            },
+        )
+        .unwrap();
+    result.assert_attribute("wasm", attr("emps", "val1=1000000")).unwrap();
+    result.assert_attribute("wasm", attr("emps", "val2=500000")).unwrap();
+
+    let err = helper
+        .emp_execute(
+            &mut router,
+            ExecuteMsg::RemoveEmps {
+                ids: vec![1],
+            },
+        )
+        .unwrap_err();
+    assert_eq!("Grant 1 was already revoked", err.root_cause().to_string());
+    let err = helper
+        .emp_execute(
+            &mut router,
+            ExecuteMsg::RemoveEmps {
+                ids: vec![4],
+            },
+        )
+        .unwrap_err();
+    assert_eq!("Grant 4 not found", err.root_cause().to_string());
+
+    let result = helper
+        .emp_execute(
+            &mut router,
+            ExecuteMsg::RemoveEmps {
+                ids: vec![2],
+            },
+        )
+        .unwrap();
+    result.assert_attribute("wasm", attr("emps", "val2=500000")).unwrap();
+    result.assert_attribute("wasm", attr("emps", "val1=0")).unwrap_err();
+
+    let current_period = router.block_period();
+    let result = helper.emp_query_validator_history(&mut router, "val1", current_period).unwrap();
+    assert_eq!(result.voting_power, Uint128::zero());
+    assert_eq!(result.fixed_amount, Uint128::zero());
+
+    // the history of the grants stays available
+    let grant = helper.emp_query_grant(&mut router, 1).unwrap();
+    assert_eq!(grant.revoked_period, Some(current_period));
+    let result = helper.emp_query_validator_history(&mut router, "val1", grant_period).unwrap();
+    assert_eq!(result.fixed_amount, Uint128::new(1000000));
+
+    Ok(())
+}
+
 #[test]
 fn integration_check_update_owner() {
     let mut router = mock_app();
@@ -0,0 +1,136 @@
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use eris::governance_helper::WEEK;
+use eris::lp_gauges::ExecuteMsg;
+use eris_tests::gov_helper::EscrowHelper;
+use eris_tests::{mock_app, CustomAppExtension};
+
+#[test]
+fn integration_lp_targets() {
+    let mut router = mock_app();
+    let helper = EscrowHelper::init(&mut router, false);
+
+    let config = helper.lp_query_config(&mut router).unwrap();
+    assert_eq!(
+        config.targets,
+        vec![Addr::unchecked("lp1"), Addr::unchecked("lp2"), Addr::unchecked("lp3")]
+    );
+    assert_eq!(config.targets_limit, 2);
+
+    let update_targets = ExecuteMsg::UpdateTargets {
+        add: Some(vec!["lp4".to_string()]),
+        remove: Some(vec!["lp3".to_string()]),
+    };
+    let err = helper.lp_execute_sender(&mut router, update_targets.clone(), "user1").unwrap_err();
+    assert_eq!("Generic error: unauthorized", err.root_cause().to_string());
+
+    helper.lp_execute_sender(&mut router, update_targets, "owner").unwrap();
+    let config = helper.lp_query_config(&mut router).unwrap();
+    assert_eq!(
+        config.targets,
+        vec![Addr::unchecked("lp1"), Addr::unchecked("lp2"), Addr::unchecked("lp4")]
+    );
+
+    helper.ve_lock_lp(&mut router, "user1", 100000, 3 * WEEK).unwrap();
+    let err = helper.lp_vote(&mut router, "user1", vec![("lp3".to_string(), 10000)]).unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Invalid target address: lp3");
+    let err = helper
+        .lp_vote(&mut router, "user1", vec![("lp1".to_string(), 5000), ("lp1".to_string(), 5000)])
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Votes contain duplicated target addresses");
+}
+
+#[test]
+fn integration_lp_tune() {
+    let mut router = mock_app();
+    let helper = EscrowHelper::init(&mut router, false);
+
+    helper.ve_lock_lp(&mut router, "user1", 100000, 3 * WEEK).unwrap();
+    helper.ve_lock_lp(&mut router, "user2", 100000, 3 * WEEK).unwrap();
+
+    let err = helper.lp_tune(&mut router).unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "There are no targets to tune");
+
+    helper.lp_vote(&mut router, "user1", vec![("lp1".to_string(), 10000)]).unwrap();
+    helper
+        .lp_vote(&mut router, "user2", vec![("lp2".to_string(), 6000), ("lp3".to_string(), 4000)])
+        .unwrap();
+
+    // votes are applied in the next period
+    router.next_period(1);
+    helper.lp_tune(&mut router).unwrap();
+
+    // only the top 2 targets receive a weight
+    let info = helper.lp_query_tune_info(&mut router).unwrap();
+    let targets = info.target_points.iter().map(|(addr, _)| addr.as_str()).collect::<Vec<_>>();
+    assert_eq!(targets, vec!["lp1", "lp2"]);
+    let total: Uint128 = info.target_points.iter().map(|(_, vamp)| *vamp).sum();
+    assert_eq!(
+        info.weights,
+        vec![
+            ("lp1".to_string(), Decimal::from_ratio(info.target_points[0].1, total)),
+            ("lp2".to_string(), Decimal::from_ratio(info.target_points[1].1, total)),
+        ]
+    );
+    assert!(info.weights[0].1 > info.weights[1].1);
+
+    // removed targets are no longer tuned
+    helper
+        .lp_execute_sender(
+            &mut router,
+            ExecuteMsg::UpdateTargets {
+                add: None,
+                remove: Some(vec!["lp1".to_string()]),
+            },
+            "owner",
+        )
+        .unwrap();
+    helper.lp_tune(&mut router).unwrap();
+    let info = helper.lp_query_tune_info(&mut router).unwrap();
+    let targets = info.weights.iter().map(|(addr, _)| addr.as_str()).collect::<Vec<_>>();
+    assert_eq!(targets, vec!["lp2", "lp3"]);
+}
+
+#[test]
+fn integration_lp_vote_delegation() {
+    let mut router = mock_app();
+    let helper = EscrowHelper::init(&mut router, false);
+
+    helper.ve_lock_lp(&mut router, "user1", 100000, 104 * WEEK).unwrap();
+    helper.ve_lock_lp(&mut router, "user2", 100000, 104 * WEEK).unwrap();
+
+    let expires = router.block_info().time.seconds() + 10 * WEEK;
+    helper
+        .ve_execute_sender(
+            &mut router,
+            eris::voting_escrow::ExecuteMsg::Delegate {
+                delegatee: "user1".to_string(),
+                bps: 10000,
+                expires,
+            },
+            Addr::unchecked("user2"),
+        )
+        .unwrap();
+
+    // the vote of user1 is also applied to the voting power delegated by user2
+    helper.lp_vote(&mut router, "user1", vec![("lp1".to_string(), 10000)]).unwrap();
+    let user2 = helper.lp_query_user_info(&mut router, "user2").unwrap();
+    assert_eq!(user2.delegated_by, Some(Addr::unchecked("user1")));
+    assert_eq!(user2.votes, vec![("lp1".to_string(), 10000u16)]);
+
+    router.next_period(1);
+    helper.lp_tune(&mut router).unwrap();
+    let info = helper.lp_query_tune_info(&mut router).unwrap();
+    assert_eq!(info.target_points.len(), 1);
+    assert_eq!(info.target_points[0].0, "lp1".to_string());
+
+    // voting overrides the delegation of user2
+    helper.lp_vote(&mut router, "user2", vec![("lp2".to_string(), 10000)]).unwrap();
+    let user2 = helper.lp_query_user_info(&mut router, "user2").unwrap();
+    assert_eq!(user2.delegated_by, None);
+
+    router.next_period(1);
+    helper.lp_tune(&mut router).unwrap();
+    let info = helper.lp_query_tune_info(&mut router).unwrap();
+    assert_eq!(info.target_points.len(), 2);
+    assert_eq!(info.target_points[0].1, info.target_points[1].1);
+}
@@ -19,6 +19,8 @@ fn integration_update_configs() -> StdResult<()> {
             ExecuteMsg::UpdateConfig {
                 quorum_bps: Some(100u16),
                 use_weighted_vote: None,
+                sync_buffer_s: None,
+                alliance_lst_addr: None,
             },
             "user",
         )
@@ -32,6 +34,8 @@ fn integration_update_configs() -> StdResult<()> {
             ExecuteMsg::UpdateConfig {
                 quorum_bps: Some(100u16),
                 use_weighted_vote: None,
+                sync_buffer_s: None,
+                alliance_lst_addr: None,
             },
         )
         .unwrap();
@@ -0,0 +1,280 @@
+use cosmwasm_std::{coin, coins, Addr, Binary, CosmosMsg, Decimal, Uint128};
+use cw_multi_test::Executor;
+use eris_tests::base::AllianceApp;
+use eris_tests::mock_alliance_app;
+use eris_tests::modules::alliance::AllianceModule;
+use eris_tests::whitewhale_pair::{
+    whitewhale_pair_contract, InstantiateMsg, PairInfo, PoolFees, PoolResponse, PoolType, QueryMsg,
+    ReverseSimulationResponse, SimulationResponse,
+};
+use eris_whitewhale::adapters::whitewhaledex::{Asset, AssetInfo, ExecuteMsg};
+use eris_whitewhale::denom::{Coin as ProtoCoin, MsgMint};
+
+const WHALE: &str = "uwhale";
+const BTC: &str = "ibc/btc";
+
+fn native(denom: &str) -> AssetInfo {
+    AssetInfo::NativeToken {
+        denom: denom.to_string(),
+    }
+}
+
+fn asset(denom: &str, amount: u128) -> Asset {
+    Asset {
+        info: native(denom),
+        amount: Uint128::new(amount),
+    }
+}
+
+fn init_pair(pool_type: PoolType) -> (AllianceApp, Addr, String) {
+    let mut app = mock_alliance_app(
+        AllianceModule::default(),
+        vec![
+            ("provider", vec![coin(10_000_000, WHALE), coin(10_000_000, BTC)]),
+            ("trader", vec![coin(10_000_000, WHALE), coin(10_000_000, BTC)]),
+        ],
+    );
+
+    let code_id = app.store_code(whitewhale_pair_contract());
+    let pair = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("owner"),
+            &InstantiateMsg {
+                asset_infos: [native(WHALE), native(BTC)],
+                pool_type,
+                pool_fees: PoolFees {
+                    protocol_fee: Decimal::permille(1),
+                    swap_fee: Decimal::permille(3),
+                    burn_fee: Decimal::zero(),
+                },
+            },
+            &[],
+            "pair",
+            None,
+        )
+        .unwrap();
+
+    let pair_info: PairInfo = app.wrap().query_wasm_smart(&pair, &QueryMsg::Pair {}).unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("provider"),
+        pair.clone(),
+        &ExecuteMsg::ProvideLiquidity {
+            assets: vec![asset(WHALE, 1_000_000), asset(BTC, 1_000_000)],
+            slippage_tolerance: None,
+            receiver: None,
+        },
+        &[coin(1_000_000, BTC), coin(1_000_000, WHALE)],
+    )
+    .unwrap();
+
+    (app, pair, pair_info.liquidity_token)
+}
+
+fn swap_msg(amount: u128, max_spread: Option<Decimal>) -> ExecuteMsg {
+    ExecuteMsg::Swap {
+        offer_asset: asset(WHALE, amount),
+        belief_price: None,
+        max_spread,
+        to: None,
+    }
+}
+
+fn balance(app: &AllianceApp, addr: &str, denom: &str) -> Uint128 {
+    app.wrap().query_balance(addr, denom).unwrap().amount
+}
+
+#[test]
+fn constant_product_swap_and_liquidity() {
+    let (mut app, pair, lp_denom) = init_pair(PoolType::ConstantProduct);
+    assert_eq!(lp_denom, format!("factory/{0}/uLP", pair));
+    assert_eq!(balance(&app, "provider", &lp_denom), Uint128::new(1_000_000));
+
+    let simulation: SimulationResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &pair,
+            &QueryMsg::Simulation {
+                offer_asset: asset(WHALE, 10_000),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        simulation,
+        SimulationResponse {
+            return_amount: Uint128::new(9863),
+            spread_amount: Uint128::new(99),
+            swap_fee_amount: Uint128::new(29),
+            protocol_fee_amount: Uint128::new(9),
+            burn_fee_amount: Uint128::zero(),
+        }
+    );
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("trader"),
+            pair.clone(),
+            &swap_msg(10_000, Some(Decimal::permille(5))),
+            &coins(10_000, WHALE),
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Generic error: Operation exceeds max spread limit");
+
+    app.execute_contract(
+        Addr::unchecked("trader"),
+        pair.clone(),
+        &swap_msg(10_000, None),
+        &coins(10_000, WHALE),
+    )
+    .unwrap();
+    assert_eq!(balance(&app, "trader", BTC), Uint128::new(10_009_863));
+
+    // protocol fees are not part of the pool
+    let pool: PoolResponse = app.wrap().query_wasm_smart(&pair, &QueryMsg::Pool {}).unwrap();
+    assert_eq!(pool.assets, vec![asset(WHALE, 1_010_000), asset(BTC, 990_128)]);
+    assert_eq!(pool.total_share, Uint128::new(1_000_000));
+
+    // the reverse simulation offers enough to receive the asked amount
+    let reverse: ReverseSimulationResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &pair,
+            &QueryMsg::ReverseSimulation {
+                ask_asset: asset(BTC, 9863),
+            },
+        )
+        .unwrap();
+    let simulation: SimulationResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &pair,
+            &QueryMsg::Simulation {
+                offer_asset: asset(WHALE, reverse.offer_amount.u128()),
+            },
+        )
+        .unwrap();
+    assert!(simulation.return_amount >= Uint128::new(9863));
+
+    app.execute_contract(
+        Addr::unchecked("provider"),
+        pair.clone(),
+        &ExecuteMsg::WithdrawLiquidity {},
+        &coins(500_000, &lp_denom),
+    )
+    .unwrap();
+    assert_eq!(balance(&app, "provider", &lp_denom), Uint128::new(500_000));
+    assert_eq!(balance(&app, "provider", WHALE), Uint128::new(9_505_000));
+    assert_eq!(balance(&app, "provider", BTC), Uint128::new(9_495_064));
+    // withdrawn liquidity tokens are burned
+    assert_eq!(balance(&app, pair.as_str(), &lp_denom), Uint128::zero());
+}
+
+#[test]
+fn provide_liquidity_checks_slippage() {
+    let (mut app, pair, lp_denom) = init_pair(PoolType::ConstantProduct);
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("trader"),
+            pair.clone(),
+            &ExecuteMsg::ProvideLiquidity {
+                assets: vec![asset(WHALE, 100_000), asset(BTC, 50_000)],
+                slippage_tolerance: Some(Decimal::percent(1)),
+                receiver: None,
+            },
+            &[coin(50_000, BTC), coin(100_000, WHALE)],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Operation exceeds max slippage tolerance"
+    );
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("trader"),
+            pair.clone(),
+            &ExecuteMsg::ProvideLiquidity {
+                assets: vec![asset(WHALE, 100_000), asset(BTC, 100_000)],
+                slippage_tolerance: None,
+                receiver: None,
+            },
+            &[coin(100_000, WHALE)],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: native token balance mismatch between the argument and the transferred"
+    );
+
+    app.execute_contract(
+        Addr::unchecked("trader"),
+        pair,
+        &ExecuteMsg::ProvideLiquidity {
+            assets: vec![asset(WHALE, 100_000), asset(BTC, 100_000)],
+            slippage_tolerance: Some(Decimal::percent(1)),
+            receiver: Some("receiver".to_string()),
+        },
+        &[coin(100_000, BTC), coin(100_000, WHALE)],
+    )
+    .unwrap();
+    assert_eq!(balance(&app, "receiver", &lp_denom), Uint128::new(100_000));
+}
+
+#[test]
+fn stableswap_swaps_close_to_peg() {
+    let (mut app, pair, _) = init_pair(PoolType::StableSwap {
+        amp: 100,
+    });
+
+    let simulation: SimulationResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &pair,
+            &QueryMsg::Simulation {
+                offer_asset: asset(WHALE, 10_000),
+            },
+        )
+        .unwrap();
+    // much less spread than the constant product pool
+    assert!(simulation.spread_amount < Uint128::new(10));
+    assert_eq!(simulation.swap_fee_amount, Uint128::new(29));
+
+    app.execute_contract(
+        Addr::unchecked("trader"),
+        pair,
+        &swap_msg(10_000, None),
+        &coins(10_000, WHALE),
+    )
+    .unwrap();
+    assert_eq!(balance(&app, "trader", BTC), Uint128::new(10_000_000) + simulation.return_amount);
+}
+
+#[test]
+fn tokenfactory_only_admin_can_mint() {
+    let (mut app, pair, lp_denom) = init_pair(PoolType::ConstantProduct);
+
+    let mint = |sender: &str| -> CosmosMsg {
+        CosmosMsg::Stargate {
+            type_url: "/osmosis.tokenfactory.v1beta1.MsgMint".to_string(),
+            value: Binary::from(prost::Message::encode_to_vec(&MsgMint {
+                sender: sender.to_string(),
+                amount: Some(ProtoCoin {
+                    denom: lp_denom.clone(),
+                    amount: "100".to_string(),
+                }),
+                mint_to_address: "trader".to_string(),
+            })),
+        }
+    };
+
+    let err = app.execute(Addr::unchecked("trader"), mint("trader")).unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        format!("unauthorized: trader is not the admin of {}", lp_denom)
+    );
+
+    app.execute(pair.clone(), mint(pair.as_str())).unwrap();
+    assert_eq!(balance(&app, "trader", &lp_denom), Uint128::new(100));
+}
@@ -1 +1,2 @@
 pub mod whitewhaledex;
+pub mod whitewhalerouter;
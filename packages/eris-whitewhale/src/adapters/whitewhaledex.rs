@@ -3,6 +3,7 @@ use cosmwasm_std::{
     coins, to_json_binary, Addr, CosmosMsg, Decimal, StdError, StdResult, Uint128, WasmMsg,
 };
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use eris_chain_shared::pair::{create_pair_provide_liquidity_msgs, PairAsset, PairAssetInfo};
 
 use crate::whitewhale_types::{CustomMsgType, DenomType};
 
@@ -33,6 +34,12 @@ pub enum ExecuteMsg {
         max_spread: Option<Decimal>,
         to: Option<String>,
     },
+    /// Provides liquidity to the pool
+    ProvideLiquidity {
+        assets: Vec<Asset>,
+        slippage_tolerance: Option<Decimal>,
+        receiver: Option<String>,
+    },
     /// Withdraws liquidity
     WithdrawLiquidity {},
 }
@@ -116,4 +123,35 @@ impl WhiteWhalePair {
             _ => Err(StdError::generic_err("WhiteWhalePair.withdraw_msg: not supported")),
         }
     }
+
+    pub fn provide_liquidity_msg(
+        &self,
+        assets: Vec<(DenomType, Uint128)>,
+        slippage_tolerance: Option<Decimal>,
+    ) -> StdResult<Vec<CosmosMsg<CustomMsgType>>> {
+        let assets = assets
+            .into_iter()
+            .map(|(denom, amount)| {
+                let info = match denom {
+                    cw_asset::AssetInfoBase::Cw20(cw20) => PairAssetInfo::Token {
+                        contract_addr: cw20.to_string(),
+                    },
+                    cw_asset::AssetInfoBase::Native(native) => PairAssetInfo::NativeToken {
+                        denom: native,
+                    },
+                    _ => {
+                        return Err(StdError::generic_err(
+                            "WhiteWhalePair.provide_liquidity_msg: not supported",
+                        ))
+                    },
+                };
+                Ok(PairAsset {
+                    info,
+                    amount,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        create_pair_provide_liquidity_msgs(&self.0, assets, slippage_tolerance)
+    }
 }
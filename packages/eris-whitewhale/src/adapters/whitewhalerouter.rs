@@ -0,0 +1,160 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    coins, to_json_binary, Addr, CosmosMsg, Decimal, QuerierWrapper, StdError, StdResult, Uint128,
+    WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+
+use crate::adapters::whitewhaledex::AssetInfo;
+use crate::whitewhale_types::{CustomMsgType, DenomType};
+
+#[cw_serde]
+pub enum SwapOperation {
+    TerraSwap {
+        offer_asset_info: AssetInfo,
+        ask_asset_info: AssetInfo,
+    },
+}
+
+impl SwapOperation {
+    pub fn offer_asset_info(&self) -> &AssetInfo {
+        match self {
+            SwapOperation::TerraSwap {
+                offer_asset_info,
+                ..
+            } => offer_asset_info,
+        }
+    }
+
+    pub fn ask_asset_info(&self) -> &AssetInfo {
+        match self {
+            SwapOperation::TerraSwap {
+                ask_asset_info,
+                ..
+            } => ask_asset_info,
+        }
+    }
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Execute multiple swap operations, the last ask asset must be at least minimum_receive
+    ExecuteSwapOperations {
+        operations: Vec<SwapOperation>,
+        minimum_receive: Option<Uint128>,
+        to: Option<String>,
+        max_spread: Option<Decimal>,
+    },
+}
+
+#[cw_serde]
+pub enum Cw20HookMsg {
+    ExecuteSwapOperations {
+        operations: Vec<SwapOperation>,
+        minimum_receive: Option<Uint128>,
+        to: Option<String>,
+        max_spread: Option<Decimal>,
+    },
+}
+
+#[cw_serde]
+pub enum QueryMsg {
+    SimulateSwapOperations {
+        offer_amount: Uint128,
+        operations: Vec<SwapOperation>,
+    },
+}
+
+#[cw_serde]
+pub struct SimulateSwapOperationsResponse {
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct WhiteWhaleRouter(pub Addr);
+
+impl WhiteWhaleRouter {
+    /// Checks that the operations are a connected route starting with the offered denom.
+    pub fn validate_operations(denom: &DenomType, operations: &[SwapOperation]) -> StdResult<()> {
+        let offer = match denom {
+            cw_asset::AssetInfoBase::Cw20(cw20) => AssetInfo::Token {
+                contract_addr: cw20.to_string(),
+            },
+            cw_asset::AssetInfoBase::Native(native) => AssetInfo::NativeToken {
+                denom: native.to_string(),
+            },
+            _ => return Err(StdError::generic_err("WhiteWhaleRouter: not supported")),
+        };
+
+        let mut current = &offer;
+        for operation in operations {
+            if operation.offer_asset_info() != current {
+                return Err(StdError::generic_err(
+                    "WhiteWhaleRouter: operations are not a route for the offered denom",
+                ));
+            }
+            current = operation.ask_asset_info();
+        }
+
+        if operations.is_empty() || current == &offer {
+            return Err(StdError::generic_err("WhiteWhaleRouter: invalid operations"));
+        }
+
+        Ok(())
+    }
+
+    pub fn simulate(
+        &self,
+        querier: &QuerierWrapper,
+        offer_amount: Uint128,
+        operations: Vec<SwapOperation>,
+    ) -> StdResult<Uint128> {
+        let response: SimulateSwapOperationsResponse = querier.query_wasm_smart(
+            self.0.to_string(),
+            &QueryMsg::SimulateSwapOperations {
+                offer_amount,
+                operations,
+            },
+        )?;
+        Ok(response.amount)
+    }
+
+    pub fn swap_msg(
+        &self,
+        denom: DenomType,
+        amount: Uint128,
+        operations: Vec<SwapOperation>,
+        minimum_receive: Option<Uint128>,
+        max_spread: Option<Decimal>,
+    ) -> StdResult<CosmosMsg<CustomMsgType>> {
+        Self::validate_operations(&denom, &operations)?;
+
+        match denom {
+            cw_asset::AssetInfoBase::Cw20(cw20) => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: cw20.to_string(),
+                funds: vec![],
+                msg: to_json_binary(&Cw20ExecuteMsg::Send {
+                    contract: self.0.to_string(),
+                    amount,
+                    msg: to_json_binary(&Cw20HookMsg::ExecuteSwapOperations {
+                        operations,
+                        minimum_receive,
+                        to: None,
+                        max_spread,
+                    })?,
+                })?,
+            })),
+            cw_asset::AssetInfoBase::Native(native) => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: self.0.to_string(),
+                funds: coins(amount.u128(), native),
+                msg: to_json_binary(&ExecuteMsg::ExecuteSwapOperations {
+                    operations,
+                    minimum_receive,
+                    to: None,
+                    max_spread,
+                })?,
+            })),
+            _ => Err(StdError::generic_err("WhiteWhaleRouter.swap_msg: not supported")),
+        }
+    }
+}
@@ -1,9 +1,12 @@
-use cosmwasm_std::{Addr, CosmosMsg, Decimal, StdResult, Uint128};
-use eris_chain_shared::chain_trait::ChainInterface;
+use cosmwasm_std::{
+    Addr, CosmosMsg, Decimal, Fraction, QuerierWrapper, StdError, StdResult, Uint128,
+};
+use eris_chain_shared::chain_trait::{ChainInterface, DenomMetadata};
+use eris_chain_shared::denom_metadata::create_set_metadata_msg;
 
 use crate::{
-    adapters::whitewhaledex::WhiteWhalePair,
-    denom::{MsgBurn, MsgCreateDenom, MsgMint},
+    adapters::{whitewhaledex::WhiteWhalePair, whitewhalerouter::WhiteWhaleRouter},
+    denom::{MsgBurn, MsgCreateDenom, MsgMint, MSG_SET_DENOM_METADATA_TYPE_URL},
     whitewhale_types::{CustomMsgType, DenomType, HubChainConfig, StageType, WithdrawType},
 };
 
@@ -51,6 +54,19 @@ impl ChainInterface<CustomMsgType, DenomType, WithdrawType, StageType, HubChainC
         .into()
     }
 
+    fn create_set_metadata_msg(
+        &self,
+        full_denom: String,
+        metadata: DenomMetadata,
+    ) -> Option<CosmosMsg<CustomMsgType>> {
+        Some(create_set_metadata_msg(
+            MSG_SET_DENOM_METADATA_TYPE_URL,
+            self.contract.to_string(),
+            full_denom,
+            metadata,
+        ))
+    }
+
     fn create_withdraw_msg<F>(
         &self,
         _get_chain_config: F,
@@ -68,6 +84,24 @@ impl ChainInterface<CustomMsgType, DenomType, WithdrawType, StageType, HubChainC
         }
     }
 
+    fn create_provide_liquidity_msg<F>(
+        &self,
+        _querier: &QuerierWrapper,
+        _get_chain_config: F,
+        pool: WithdrawType,
+        assets: Vec<(DenomType, Uint128)>,
+        slippage_tolerance: Option<Decimal>,
+    ) -> StdResult<Vec<CosmosMsg<CustomMsgType>>>
+    where
+        F: FnOnce() -> StdResult<HubChainConfig>,
+    {
+        match pool {
+            WithdrawType::Dex {
+                addr,
+            } => WhiteWhalePair(addr).provide_liquidity_msg(assets, slippage_tolerance),
+        }
+    }
+
     fn create_single_stage_swap_msgs<F>(
         &self,
         _get_chain_config: F,
@@ -84,6 +118,121 @@ impl ChainInterface<CustomMsgType, DenomType, WithdrawType, StageType, HubChainC
             StageType::Dex {
                 addr,
             } => WhiteWhalePair(addr).swap_msg(denom, amount, belief_price, Some(max_spread)),
+            StageType::Router {
+                addr,
+                operations,
+            } => {
+                // the spread of the whole route is limited by the minimum receive amount
+                let minimum_receive = belief_price
+                    .map(|belief_price| -> StdResult<Uint128> {
+                        let expected = amount
+                            * belief_price.inv().ok_or_else(|| {
+                                StdError::generic_err("belief_price can't be zero")
+                            })?;
+                        Ok(expected * Decimal::one().checked_sub(max_spread)?)
+                    })
+                    .transpose()?;
+
+                WhiteWhaleRouter(addr).swap_msg(
+                    denom,
+                    amount,
+                    operations,
+                    minimum_receive,
+                    Some(max_spread),
+                )
+            },
         }
     }
+
+    fn simulate_belief_price(
+        &self,
+        querier: &QuerierWrapper,
+        stage_type: &StageType,
+        _denom: &DenomType,
+        amount: Uint128,
+    ) -> StdResult<Option<Decimal>> {
+        match stage_type {
+            // pair swaps are limited by the max spread to the current pool price
+            StageType::Dex {
+                ..
+            } => Ok(None),
+            StageType::Router {
+                addr,
+                operations,
+            } => {
+                let return_amount =
+                    WhiteWhaleRouter(addr.clone()).simulate(querier, amount, operations.clone())?;
+                if return_amount.is_zero() {
+                    return Err(StdError::generic_err("simulated swap returns nothing"));
+                }
+                Ok(Some(Decimal::from_ratio(amount, return_amount)))
+            },
+        }
+    }
+}
+
+#[test]
+pub fn test_router_swap_msg() {
+    use crate::adapters::whitewhaledex::AssetInfo;
+    use crate::adapters::whitewhalerouter::{ExecuteMsg, SwapOperation};
+    use cosmwasm_std::{coins, to_json_binary, WasmMsg};
+
+    let chain = WhiteWhaleChain {
+        contract: Addr::unchecked("contract"),
+    };
+    let native = |denom: &str| AssetInfo::NativeToken {
+        denom: denom.to_string(),
+    };
+    let operations = vec![
+        SwapOperation::TerraSwap {
+            offer_asset_info: native("uwhale"),
+            ask_asset_info: native("uusdc"),
+        },
+        SwapOperation::TerraSwap {
+            offer_asset_info: native("uusdc"),
+            ask_asset_info: native("ubtc"),
+        },
+    ];
+
+    let msg = chain
+        .create_single_stage_swap_msgs(
+            || Ok(HubChainConfig {}),
+            StageType::router("router", operations.clone()),
+            DenomType::native("uwhale"),
+            Uint128::new(1000),
+            Some(Decimal::percent(200)),
+            Decimal::percent(10),
+        )
+        .unwrap();
+
+    assert_eq!(
+        msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "router".to_string(),
+            funds: coins(1000, "uwhale"),
+            msg: to_json_binary(&ExecuteMsg::ExecuteSwapOperations {
+                operations: operations.clone(),
+                minimum_receive: Some(Uint128::new(450)),
+                to: None,
+                max_spread: Some(Decimal::percent(10)),
+            })
+            .unwrap(),
+        })
+    );
+
+    // the route has to start with the offered denom
+    let err = chain
+        .create_single_stage_swap_msgs(
+            || Ok(HubChainConfig {}),
+            StageType::router("router", operations),
+            DenomType::native("uusdc"),
+            Uint128::new(1000),
+            None,
+            Decimal::percent(10),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("WhiteWhaleRouter: operations are not a route for the offered denom")
+    );
 }
@@ -3,6 +3,8 @@ use cosmwasm_std::{Addr, Api, CosmosMsg, Empty, StdError, StdResult, Uint128};
 use cw_asset::{Asset, AssetInfo};
 use eris_chain_shared::chain_trait::Validateable;
 
+use crate::adapters::whitewhalerouter::SwapOperation;
+
 #[cw_serde]
 pub enum WithdrawType {
     Dex {
@@ -23,6 +25,11 @@ pub enum StageType {
     Dex {
         addr: Addr,
     },
+    /// Multi-hop swap through the White Whale pool router
+    Router {
+        addr: Addr,
+        operations: Vec<SwapOperation>,
+    },
 }
 
 impl StageType {
@@ -31,6 +38,13 @@ impl StageType {
             addr: Addr::unchecked(addr),
         }
     }
+
+    pub fn router(addr: &str, operations: Vec<SwapOperation>) -> Self {
+        Self::Router {
+            addr: Addr::unchecked(addr),
+            operations,
+        }
+    }
 }
 
 pub type DenomType = AssetInfo;
@@ -1,3 +1,4 @@
+pub mod alliance_lst;
 pub mod asset;
 pub mod compounder;
 pub mod factory;
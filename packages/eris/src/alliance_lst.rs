@@ -1,6 +1,7 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Decimal, Uint128};
+use cosmwasm_std::{Addr, Coin, Decimal, Uint128, VoteOption};
 use eris_chain_adapter::types::{DenomType, WithdrawType};
+use eris_chain_shared::chain_trait::DenomMetadata;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -52,9 +53,15 @@ pub struct InstantiateMsg {
     pub protocol_reward_fee: Decimal, // "1 is 100%, 0.05 is 5%"
     /// Strategy how delegations should be handled
     pub delegation_strategy: Option<DelegationStrategy>,
+    /// Bank metadata of the liquid staking token, if the chain supports setting it
+    #[serde(default)]
+    pub denom_metadata: Option<DenomMetadata>,
+    /// Account who can veto pending config changes
+    pub guardian: String,
 }
 
 #[cw_serde]
+#[allow(clippy::large_enum_variant)]
 pub enum ExecuteMsg {
     /// Bond specified amount of Token
     Bond {
@@ -82,7 +89,12 @@ pub enum ExecuteMsg {
         stages: Option<Vec<Vec<SingleSwapConfig>>>,
     },
 
+    /// Updates the delegation goal. Anyone can tune once per period and receives the keeper tip,
+    /// the owner can tune at any time. Gauges that were not tuned in the current period are tuned
+    /// first, so that the goal reflects the latest closed period.
     TuneDelegations {},
+    /// Adds the sent tip denom to the budget the keeper tips are paid from
+    FundTuneTip {},
     /// Use redelegations to balance the amounts of Token delegated to validators
     Rebalance {
         min_redelegation: Option<Uint128>,
@@ -101,6 +113,16 @@ pub enum ExecuteMsg {
     SubmitBatch {
         undelegations: Option<Vec<Undelegation>>,
     },
+    /// Vote on a proposal (only allowed by the vote_operator)
+    Vote {
+        proposal_id: u64,
+        vote: VoteOption,
+    },
+    /// Vote on a proposal weighted (only allowed by the vote_operator)
+    VoteWeighted {
+        proposal_id: u64,
+        votes: Vec<(Decimal, VoteOption)>,
+    },
     /// Callbacks; can only be invoked by the contract itself
     Callback(CallbackMsg),
 
@@ -112,6 +134,8 @@ pub enum ExecuteMsg {
         protocol_reward_fee: Option<Decimal>, // "1 is 100%, 0.05 is 5%"
         /// Sets a new operator
         operator: Option<String>,
+        /// Update the vote_operator
+        vote_operator: Option<String>,
         /// Sets the stages preset
         stages_preset: Option<Vec<Vec<SingleSwapConfig>>>,
         /// Sets the withdrawals preset
@@ -133,6 +157,39 @@ pub enum ExecuteMsg {
         whale_denom: Option<String>,
         btc_denom: Option<String>,
         whale_btc_pool: Option<Addr>,
+
+        /// Allowed deviation from the delegation goal for undelegations provided by the operator
+        undelegation_tolerance_bps: Option<u16>,
+
+        /// Account who can veto pending config changes
+        guardian: Option<String>,
+        /// Delay until proposed config changes can be executed, in seconds
+        timelock_delay: Option<u64>,
+
+        /// Fee in stake tokens for unbonding through `PriorityUnbond`, 0 disables it
+        priority_unbond_fee_bps: Option<u16>,
+        /// Minimum fee in stake tokens for unbonding through `PriorityUnbond`
+        priority_unbond_min_fee: Option<Uint128>,
+
+        /// Contracts that can receive custom claim messages
+        claim_contracts: Option<Vec<String>>,
+
+        /// Tip paid from the tip budget to keepers for tuning the delegations, 0 disables it
+        tune_tip: Option<Coin>,
+    },
+
+    /// Updates the bank metadata of the liquid staking token; callable by the owner
+    UpdateDenomMetadata {
+        metadata: DenomMetadata,
+    },
+
+    /// Applies a pending config change after its timelock expired; callable by the owner
+    ExecuteConfigChange {
+        id: u64,
+    },
+    /// Removes a pending config change; callable by the guardian or the owner
+    VetoConfigChange {
+        id: u64,
     },
 
     /// Submit an unbonding request to the current unbonding queue; automatically invokes `unbond`
@@ -141,9 +198,22 @@ pub enum ExecuteMsg {
         receiver: Option<String>,
     },
 
+    /// Unbonds the sent stake tokens in a batch of their own that is submitted immediately instead
+    /// of waiting for the next epoch. `priority_unbond_fee_bps` of the stake tokens, but at least
+    /// `priority_unbond_min_fee`, are paid as fee for the additional unbonding entry.
+    PriorityUnbond {
+        receiver: Option<String>,
+    },
+
     // Claim possible airdrops
     Claim {
         claims: Vec<ClaimType>,
+        /// Native denoms paid out by the claims. Received utoken, stake and reward tokens are
+        /// compounded, all other denoms are sent to the protocol fee contract.
+        route_denoms: Option<Vec<String>>,
+        /// Cw20 token contracts paid out by the claims. Received cw20 tokens are sent to the
+        /// protocol fee contract.
+        route_cw20s: Option<Vec<String>>,
     },
 }
 
@@ -211,8 +281,27 @@ pub enum QueryMsg {
     #[returns(DelegationsResponse)]
     Delegations {},
 
-    #[returns(Vec<Undelegation>)]
+    /// Simulates the undelegations of the pending batch. Response: `SimulateUndelegationsResponse`
+    #[returns(SimulateUndelegationsResponse)]
     SimulateUndelegations {},
+
+    /// Open unbonding entries per validator. Response: `UnbondingEntriesResponse`
+    #[returns(UnbondingEntriesResponse)]
+    UnbondingEntries {},
+
+    /// Enumerate all config changes waiting for their timelock. Response: `Vec<PendingConfigChange>`
+    #[returns(Vec<PendingConfigChange>)]
+    PendingConfigChanges {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    /// Enumerate all batches where the operator provided the undelegations. Response: `Vec<UndelegationOverride>`
+    #[returns(Vec<UndelegationOverride>)]
+    UndelegationOverrides {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
 }
 
 #[cw_serde]
@@ -221,6 +310,137 @@ pub struct Undelegation {
     pub amount: Uint128,
 }
 
+#[cw_serde]
+pub struct SimulateUndelegationsResponse {
+    /// Undelegations that would be executed when submitting the pending batch
+    pub undelegations: Vec<Undelegation>,
+    /// Whether the pending batch can be submitted right now
+    pub submittable: bool,
+    /// Reason why the pending batch can't be submitted
+    pub reason: Option<String>,
+}
+
+/// Config fields that could redirect funds or swaps. Changing them is only applied after the
+/// timelock delay and can be vetoed by the guardian until then.
+#[cw_serde]
+#[derive(Default)]
+pub struct TimelockedConfig {
+    /// Contract address where fees are sent
+    pub protocol_fee_contract: Option<String>,
+    /// Stages that must be used by permissionless users
+    pub stages_preset: Option<Vec<Vec<SingleSwapConfig>>>,
+    /// Withdrawals that must be used by permissionless users
+    pub withdrawals_preset: Option<Vec<(WithdrawType, DenomType)>>,
+    /// Strategy how delegations should be handled
+    pub delegation_strategy: Option<DelegationStrategy>,
+    /// Validators proxy contract
+    pub validator_proxy: Option<String>,
+    /// Pool contract of Whale-BTC pair
+    pub whale_btc_pool: Option<String>,
+    /// Denom of the WHALE token that is swapped to the utoken
+    pub whale_denom: Option<String>,
+    /// Denom of the BTC token that is swapped to the utoken
+    pub btc_denom: Option<String>,
+    /// Account who can veto pending config changes
+    pub guardian: Option<String>,
+    /// Delay until proposed config changes can be executed, in seconds
+    pub timelock_delay: Option<u64>,
+    /// Contracts that can receive custom claim messages
+    pub claim_contracts: Option<Vec<String>>,
+}
+
+impl TimelockedConfig {
+    /// Names of all fields that are changed
+    pub fn changed_fields(&self) -> Vec<&'static str> {
+        let mut fields = vec![];
+        if self.protocol_fee_contract.is_some() {
+            fields.push("protocol_fee_contract");
+        }
+        if self.stages_preset.is_some() {
+            fields.push("stages_preset");
+        }
+        if self.withdrawals_preset.is_some() {
+            fields.push("withdrawals_preset");
+        }
+        if self.delegation_strategy.is_some() {
+            fields.push("delegation_strategy");
+        }
+        if self.validator_proxy.is_some() {
+            fields.push("validator_proxy");
+        }
+        if self.whale_btc_pool.is_some() {
+            fields.push("whale_btc_pool");
+        }
+        if self.whale_denom.is_some() {
+            fields.push("whale_denom");
+        }
+        if self.btc_denom.is_some() {
+            fields.push("btc_denom");
+        }
+        if self.guardian.is_some() {
+            fields.push("guardian");
+        }
+        if self.timelock_delay.is_some() {
+            fields.push("timelock_delay");
+        }
+        if self.claim_contracts.is_some() {
+            fields.push("claim_contracts");
+        }
+        fields
+    }
+}
+
+#[cw_serde]
+pub struct PendingConfigChange {
+    /// ID of the change
+    pub id: u64,
+    /// Fields to be changed
+    pub change: TimelockedConfig,
+    /// Time when the change was proposed, in seconds
+    pub proposed_at: u64,
+    /// Time from when on the change can be executed, in seconds
+    pub executable_at: u64,
+}
+
+#[cw_serde]
+pub struct UndelegationOverride {
+    /// ID of the submitted batch
+    pub batch_id: u64,
+    /// Operator who provided the undelegations
+    pub operator: Addr,
+    /// Time of the submission, in seconds
+    pub time: u64,
+    /// Undelegations provided by the operator
+    pub undelegations: Vec<Undelegation>,
+}
+
+#[cw_serde]
+pub struct UnbondingEntriesResponse {
+    /// Max amount of concurrent unbonding entries per validator
+    pub max_entries: u32,
+    /// End times of the open unbonding entries per validator
+    pub entries: Vec<(String, Vec<u64>)>,
+}
+
+/// Parameters for the migration steps, keyed by the version that introduced the step.
+/// Parameters of a step are only used when the step is executed.
+#[cw_serde]
+#[derive(Default)]
+pub struct MigrateMsg {
+    pub v1_8_0: Option<MigrateV1_8_0>,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct MigrateV1_8_0 {
+    /// Allowed deviation from the delegation goal for undelegations provided by the operator
+    pub undelegation_tolerance_bps: Option<u16>,
+    /// Account who can veto pending config changes, required if none is set yet
+    pub guardian: Option<String>,
+    /// Delay until proposed config changes can be executed, in seconds
+    pub timelock_delay: Option<u64>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct ConfigResponse {
     /// Account who can call certain privileged functions
@@ -244,6 +464,8 @@ pub struct ConfigResponse {
 
     /// Account who can call harvest
     pub operator: String,
+    /// Account who can vote on props
+    pub vote_operator: Option<String>,
     /// Stages that must be used by permissionless users
     pub stages_preset: Vec<Vec<SingleSwapConfig>>,
     /// withdrawals that must be used by permissionless users
@@ -255,4 +477,25 @@ pub struct ConfigResponse {
     pub delegation_strategy: DelegationStrategy,
 
     pub validator_proxy: String,
+
+    /// Allowed deviation from the delegation goal for undelegations provided by the operator
+    pub undelegation_tolerance_bps: u16,
+
+    /// Account who can veto pending config changes
+    pub guardian: Option<String>,
+    /// Delay until proposed config changes can be executed, in seconds
+    pub timelock_delay: u64,
+
+    /// Fee in stake tokens for unbonding through `PriorityUnbond`, 0 if it is disabled
+    pub priority_unbond_fee_bps: u16,
+    /// Minimum fee in stake tokens for unbonding through `PriorityUnbond`
+    pub priority_unbond_min_fee: Uint128,
+
+    /// Contracts that can receive custom claim messages
+    pub claim_contracts: Vec<String>,
+
+    /// Tip paid to keepers for tuning the delegations, None if it is disabled
+    pub tune_tip: Option<Coin>,
+    /// Remaining budget of the tip denom the keeper tips are paid from
+    pub tune_tip_budget: Uint128,
 }
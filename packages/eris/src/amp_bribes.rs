@@ -0,0 +1,145 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Coin, StdError, StdResult, Uint128};
+
+/// This structure describes the basic settings for creating a contract.
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// Contract owner
+    pub owner: String,
+    /// The amp gauges contract address
+    pub amp_gauges_addr: String,
+    /// Number of periods after a bribe period in which voters can claim the bribes
+    pub claim_periods: u64,
+    /// Denoms that can be deposited as bribes
+    pub allowed_denoms: Vec<String>,
+}
+
+/// This structure describes the execute messages available in the contract.
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Deposit the sent funds as a bribe for the voters of a validator in a future period
+    DepositBribe {
+        validator_addr: String,
+        period: u64,
+    },
+    /// Claim the share of the bribes of a validator in a tuned period, pro-rata to the vAMP the
+    /// user voted for the validator in that period
+    Claim {
+        validator_addr: String,
+        period: u64,
+    },
+    /// Refund the deposit of a bribe if nobody voted for the validator in a tuned period
+    Refund {
+        validator_addr: String,
+        period: u64,
+    },
+    /// Move the unclaimed bribes of a validator to the next period, after the claim periods ended.
+    /// Bribes of validators without votes can only be refunded
+    Rollover {
+        validator_addr: String,
+        period: u64,
+    },
+    UpdateConfig {
+        /// Number of periods after a bribe period in which voters can claim the bribes
+        claim_periods: Option<u64>,
+        /// Replaces the denoms that can be deposited as bribes
+        allowed_denoms: Option<Vec<String>>,
+    },
+    /// ProposeNewOwner proposes a new owner for the contract
+    ProposeNewOwner {
+        /// Newly proposed contract owner
+        new_owner: String,
+        /// The timestamp when the contract ownership change expires
+        expires_in: u64,
+    },
+    /// DropOwnershipProposal removes the latest contract ownership transfer proposal
+    DropOwnershipProposal {},
+    /// ClaimOwnership allows the newly proposed owner to claim contract ownership
+    ClaimOwnership {},
+}
+
+/// This structure describes the query messages available in the contract.
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Config returns the contract configuration
+    #[returns(ConfigResponse)]
+    Config {},
+    /// BribeInfo returns the bribes of a validator in a period
+    #[returns(BribeInfoResponse)]
+    BribeInfo {
+        validator_addr: String,
+        period: u64,
+    },
+    /// BribeInfos returns the bribes of all validators in a period
+    #[returns(Vec<(String, BribeInfoResponse)>)]
+    BribeInfos {
+        period: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Deposit returns the deposit of a user for the bribe of a validator in a period
+    #[returns(Vec<Coin>)]
+    Deposit {
+        user: String,
+        validator_addr: String,
+        period: u64,
+    },
+    /// Claimable returns the bribes a user can claim for a validator in a period
+    #[returns(ClaimableResponse)]
+    Claimable {
+        user: String,
+        validator_addr: String,
+        period: u64,
+    },
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[cw_serde]
+pub struct MigrateMsg {}
+
+/// This structure describes the parameters returned when querying for the contract configuration.
+#[cw_serde]
+pub struct ConfigResponse {
+    /// Address that's allowed to change contract parameters
+    pub owner: Addr,
+    /// The amp gauges contract address
+    pub amp_gauges_addr: Addr,
+    /// Number of periods after a bribe period in which voters can claim the bribes
+    pub claim_periods: u64,
+    /// Denoms that can be deposited as bribes
+    pub allowed_denoms: Vec<String>,
+}
+
+impl ConfigResponse {
+    pub fn assert_owner(&self, addr: &Addr) -> StdResult<()> {
+        if *addr != self.owner {
+            return Err(StdError::generic_err("unauthorized"));
+        }
+        Ok(())
+    }
+}
+
+/// This structure describes the bribes of a validator in a period.
+#[cw_serde]
+#[derive(Default)]
+pub struct BribeInfoResponse {
+    /// All funds deposited for the voters of the validator, including rolled over funds
+    pub funds: Vec<Coin>,
+    /// Funds that were claimed or refunded
+    pub claimed: Vec<Coin>,
+    /// Set when the unclaimed funds were moved to the next period
+    pub rolled_over: bool,
+}
+
+/// This structure describes the bribes a user can claim.
+#[cw_serde]
+pub struct ClaimableResponse {
+    /// The vAMP the user voted for the validator in the period
+    pub user_vamp: Uint128,
+    /// The vAMP that voted for the validator in the period
+    pub total_vamp: Uint128,
+    /// The funds the user can claim
+    pub funds: Vec<Coin>,
+}
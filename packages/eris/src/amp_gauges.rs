@@ -1,5 +1,5 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, QuerierWrapper, StdError, StdResult, Uint128};
+use cosmwasm_std::{Addr, Coin, QuerierWrapper, StdError, StdResult, Uint128};
 use eris_chain_adapter::types::CustomQueryType;
 
 use crate::voting_escrow::LockInfoResponse;
@@ -34,11 +34,14 @@ pub enum ExecuteMsg {
         lock_info: LockInfoResponse,
     },
 
-    /// TunePools transforms the latest vote distribution into alloc_points which are then applied to ASTRO generators
+    /// TunePools transforms the latest vote distribution into alloc_points which are then applied to ASTRO generators.
+    /// Anyone can tune once per period and receives the keeper tip, the owner can tune at any time.
     TuneVamp {},
     UpdateConfig {
         /// ChangeValidatorsLimit changes the max amount of validators that can be voted at once to receive delegations
         validators_limit: Option<u64>,
+        /// Sets the tip paid to keepers for permissionless tunings, a zero amount disables it
+        keeper_tip: Option<Coin>,
     },
     // Admin action to remove a user
     RemoveUser {
@@ -66,6 +69,12 @@ pub enum QueryMsg {
     UserInfo {
         user: String,
     },
+    /// UserInfoAtPeriod returns the votes of a voter that were applied in a specific period
+    #[returns(UserInfoResponse)]
+    UserInfoAtPeriod {
+        user: String,
+        period: u64,
+    },
     #[returns(UserInfosResponse)]
     UserInfos {
         start_after: Option<String>,
@@ -112,6 +121,9 @@ pub struct ConfigResponse {
     pub hub_addr: Addr,
     /// Max number of validators that can receive delegations at the same time
     pub validators_limit: u64,
+    /// Tip paid from the contract balance to keepers for the tuning once per period
+    #[serde(default)]
+    pub keeper_tip: Option<Coin>,
 }
 
 impl ConfigResponse {
@@ -169,6 +181,9 @@ pub struct UserInfoResponse {
     pub fixed_amount: Uint128,
     /// Current voting power at the current
     pub current_power: Uint128,
+    /// The delegatee whose votes are applied to the delegated voting power of the user
+    #[serde(default)]
+    pub delegated_by: Option<Addr>,
 }
 
 #[cw_serde]
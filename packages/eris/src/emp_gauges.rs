@@ -1,6 +1,6 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{
-    to_json_binary, Addr, CosmosMsg, QuerierWrapper, StdError, StdResult, Uint128, WasmMsg,
+    to_json_binary, Addr, Coin, CosmosMsg, QuerierWrapper, StdError, StdResult, Uint128, WasmMsg,
 };
 use eris_chain_adapter::types::CustomQueryType;
 
@@ -22,6 +22,20 @@ pub struct InstantiateMsg {
 pub struct EmpInfo {
     pub umerit_points: Uint128,
     pub decaying_period: Option<u64>,
+    /// Category of the grant
+    #[serde(default)]
+    pub category: Option<EmpCategory>,
+    /// Reason why the points were granted
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+#[cw_serde]
+pub enum EmpCategory {
+    Uptime,
+    CommunityWork,
+    GovernanceParticipation,
+    Other,
 }
 
 // validator->points received
@@ -34,12 +48,17 @@ pub enum ExecuteMsg {
     AddEmps {
         emps: Vec<AddEmpInfo>,
     },
+    /// Anyone can tune once per period and receives the keeper tip, the owner can tune at any time.
     TuneEmps {},
-    // RemoveEmps {
-    //     emps: Vec<AddEmpInfo>,
-    // },
+    /// Revokes the remaining points of the grants. Corrections are done by revoking a grant and
+    /// adding a new one.
+    RemoveEmps {
+        ids: Vec<u64>,
+    },
     UpdateConfig {
         validators_limit: Option<u64>,
+        /// Sets the tip paid to keepers for permissionless tunings, a zero amount disables it
+        keeper_tip: Option<Coin>,
     },
     /// ProposeNewOwner proposes a new owner for the contract
     ProposeNewOwner {
@@ -81,6 +100,19 @@ pub enum QueryMsg {
         validator_addrs: Option<Vec<String>>,
         period: Option<u64>,
     },
+    /// EmpGrant returns a single grant
+    #[returns(EmpGrantResponse)]
+    EmpGrant {
+        id: u64,
+    },
+    /// EmpGrants returns the grants of a validator (ascending order), optionally only of a single period
+    #[returns(Vec<EmpGrantResponse>)]
+    EmpGrants {
+        validator_addr: String,
+        period: Option<u64>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
 }
 
 /// This structure describes a migration message.
@@ -97,6 +129,9 @@ pub struct ConfigResponse {
     pub hub_addr: Addr,
 
     pub validators_limit: u64,
+    /// Tip paid from the contract balance to keepers for the tuning once per period
+    #[serde(default)]
+    pub keeper_tip: Option<Coin>,
 }
 
 impl ConfigResponse {
@@ -140,6 +175,18 @@ pub struct GaugeInfoResponse {
     pub emp_points: Vec<(String, Uint128)>,
 }
 
+/// This structure describes a grant of EMPs to a validator.
+#[cw_serde]
+pub struct EmpGrantResponse {
+    pub id: u64,
+    pub validator_addr: String,
+    /// Period in which the points were granted
+    pub period: u64,
+    pub emp: EmpInfo,
+    /// Period in which the grant was revoked
+    pub revoked_period: Option<u64>,
+}
+
 /// Queries user's lockup information from the voting escrow contract.
 ///
 /// * **user** staker for which we return lock position information.
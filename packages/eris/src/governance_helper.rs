@@ -1,4 +1,4 @@
-use cosmwasm_std::{StdError, StdResult, Uint128};
+use cosmwasm_std::{Addr, BankMsg, Coin, CosmosMsg, QuerierWrapper, StdError, StdResult, Uint128};
 
 /// Seconds in one week. It is intended for period number calculation.
 // mainnet: 7 * 86400
@@ -23,6 +23,30 @@ pub fn get_period(time: u64) -> StdResult<u64> {
     }
 }
 
+/// Creates the message paying the keeper tip from the balance of the contract. No tip is paid if it
+/// is not set or the remaining balance does not cover it.
+pub fn get_keeper_tip_msg(
+    querier: &QuerierWrapper,
+    contract_addr: &Addr,
+    recipient: &Addr,
+    keeper_tip: &Option<Coin>,
+) -> StdResult<Option<CosmosMsg>> {
+    let keeper_tip = match keeper_tip {
+        Some(keeper_tip) if !keeper_tip.amount.is_zero() => keeper_tip,
+        _ => return Ok(None),
+    };
+
+    let balance = querier.query_balance(contract_addr, &keeper_tip.denom)?;
+    if balance.amount < keeper_tip.amount {
+        return Ok(None);
+    }
+
+    Ok(Some(CosmosMsg::Bank(BankMsg::Send {
+        to_address: recipient.to_string(),
+        amount: vec![keeper_tip.clone()],
+    })))
+}
+
 /// converts the period to the start time of the period (EPOCH_START + period * WEEK)
 pub fn get_s_from_period(period: u64) -> u64 {
     EPOCH_START + period * WEEK
@@ -0,0 +1,799 @@
+use std::convert::TryInto;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Api, Order, StdError, StdResult, Storage, Uint128};
+use cw_storage_plus::{Bound, Map};
+
+use crate::governance_helper::{calc_voting_power, get_period};
+use crate::helpers::bps::BasicPoints;
+use crate::voting_escrow::{DelegatedVotingPower, LockEndResponse, LockInfoResponse};
+
+/// This structure describes voting parameters for a specific gauge target (e.g. a validator or an LP).
+#[cw_serde]
+#[derive(Default)]
+pub struct VotedInfo {
+    /// voting_power for this target
+    pub voting_power: Uint128,
+    /// The slope at which the amount of vAMP that voted for this target will decay
+    pub slope: Uint128,
+}
+
+/// The struct describes last user's votes parameters.
+#[cw_serde]
+#[derive(Default)]
+pub struct UserInfo {
+    pub vote_ts: u64,
+    pub voting_power: Uint128,
+    pub slope: Uint128,
+    pub lock_end: u64,
+    pub votes: Vec<(String, BasicPoints)>,
+    pub fixed_amount: Uint128,
+    /// The decaying voting power of the votes grouped by the ends of the user's lock tokens
+    #[serde(default)]
+    pub lock_ends: Vec<LockEndResponse>,
+    /// The delegatee whose votes are applied to the delegated voting power of the user
+    #[serde(default)]
+    pub delegated_by: Option<Addr>,
+}
+
+impl UserInfo {
+    /// Returns the decaying voting power of the votes grouped by their end. Votes without
+    /// `lock_ends` have a single end.
+    pub fn decaying_ends(&self) -> Vec<LockEndResponse> {
+        if !self.lock_ends.is_empty() {
+            self.lock_ends.clone()
+        } else if !self.voting_power.is_zero() {
+            vec![LockEndResponse {
+                end: self.lock_end,
+                voting_power: self.voting_power,
+                slope: self.slope,
+            }]
+        } else {
+            vec![]
+        }
+    }
+
+    /// Returns the votes with their percentage in BPS form.
+    pub fn votes_bps(&self) -> Vec<(String, u16)> {
+        self.votes.iter().map(|(target_addr, bps)| (target_addr.clone(), u16::from(*bps))).collect()
+    }
+
+    /// Returns the vAMP of the user at the specified period.
+    pub fn current_power(&self, period: u64) -> StdResult<Uint128> {
+        let user_last_vote_period = get_period(self.vote_ts).unwrap_or(period);
+        let vp_at_period = self.decaying_ends().iter().fold(Uint128::zero(), |vp, lock_end| {
+            vp + calc_voting_power(
+                lock_end.slope,
+                lock_end.voting_power,
+                user_last_vote_period,
+                period,
+            )
+        });
+        Ok(self.fixed_amount.checked_add(vp_at_period)?)
+    }
+}
+
+/// The result of updating the votes of a user after a change of their lock.
+#[derive(Debug, PartialEq)]
+pub enum VoteUpdate {
+    /// The user never voted
+    Noop,
+    /// The user has no voting power anymore, the votes are kept for a new lock
+    Removed,
+    /// The votes were applied with the new vAMP of the user
+    Changed(Uint128),
+    /// The votes of the delegatee were applied to the delegated voting power of the user
+    Delegated,
+    /// The delegation ended, so the votes of the delegatee were removed
+    Undelegated,
+}
+
+impl VoteUpdate {
+    /// Returns the action suffix used in the response of the gauge.
+    pub fn action(&self) -> &'static str {
+        match self {
+            VoteUpdate::Noop => "update_vote_noop",
+            VoteUpdate::Removed => "update_vote_removed",
+            VoteUpdate::Changed(_) => "update_vote_changed",
+            VoteUpdate::Delegated => "update_vote_delegated",
+            VoteUpdate::Undelegated => "update_vote_undelegated",
+        }
+    }
+}
+
+/// The enum defines math operations with voting power and slope.
+#[derive(Debug)]
+pub enum Operation {
+    Add,
+    Sub,
+}
+
+impl Operation {
+    pub fn calc_slope(&self, cur_slope: Uint128, slope: Uint128, bps: BasicPoints) -> Uint128 {
+        match self {
+            Operation::Add => cur_slope + bps * slope,
+            Operation::Sub => cur_slope.saturating_sub(bps * slope),
+        }
+    }
+
+    pub fn calc_voting_power(&self, cur_vp: Uint128, vp: Uint128, bps: BasicPoints) -> Uint128 {
+        match self {
+            Operation::Add => cur_vp + bps * vp,
+            Operation::Sub => cur_vp.saturating_sub(bps * vp),
+        }
+    }
+}
+
+/// Enum wraps [`VotedInfo`] so the contract can leverage storage operations efficiently.
+#[derive(Debug)]
+enum VotedInfoResult {
+    Unchanged(VotedInfo),
+    New(VotedInfo),
+}
+
+/// The storage of the slope based vote accounting of a gauge contract and the votes of its users.
+/// Every gauge keeps its own maps, so their storage keys stay unchanged.
+pub struct GaugeVotes<'a> {
+    /// HashSet based on [`Map`]. It contains all target addresses whose voting power > 0.
+    pub targets: Map<'a, &'a str, ()>,
+    /// Voting parameters per target at a specific period by key ( period -> target_addr ).
+    pub votes: Map<'a, (u64, &'a str), VotedInfo>,
+    /// Hashset based on [`Map`]. It stores null object by key ( target_addr -> period ).
+    /// It contains all periods which have saved result in `votes` for a specific target address.
+    pub periods: Map<'a, (&'a str, u64), ()>,
+    /// Slope changes for a specific target address by key ( target_addr -> period ).
+    pub slope_changes: Map<'a, (&'a str, u64), Uint128>,
+    /// Fixed vAMP for a specific target address by key ( target_addr -> period ).
+    pub fixed_vamp: Map<'a, (&'a str, u64), Uint128>,
+    /// User's voting information.
+    pub user_info: Map<'a, &'a Addr, UserInfo>,
+    /// User's voting information by key ( user -> period ), stored for the period in which the
+    /// votes are applied.
+    pub user_info_history: Map<'a, (&'a Addr, u64), UserInfo>,
+}
+
+impl<'a> GaugeVotes<'a> {
+    /// Saves the user's voting information, which is applied from the next period.
+    pub fn save_user_info(
+        &self,
+        storage: &mut dyn Storage,
+        user: &'a Addr,
+        block_period: u64,
+        user_info: &UserInfo,
+    ) -> StdResult<()> {
+        self.user_info.save(storage, user, user_info)?;
+        self.user_info_history.save(storage, (user, block_period + 1), user_info)
+    }
+
+    /// Removes the user's voting information. The user has no votes from the next period.
+    pub fn remove_user_info(
+        &self,
+        storage: &mut dyn Storage,
+        user: &'a Addr,
+        block_period: u64,
+    ) -> StdResult<()> {
+        self.user_info.remove(storage, user);
+        self.user_info_history.save(storage, (user, block_period + 1), &UserInfo::default())
+    }
+
+    /// Returns the user's voting information that was applied in the specified period.
+    pub fn user_info_at_period(
+        &self,
+        storage: &dyn Storage,
+        user: &'a Addr,
+        period: u64,
+    ) -> StdResult<Option<UserInfo>> {
+        let user_info = self
+            .user_info_history
+            .prefix(user)
+            .range(storage, None, Some(Bound::inclusive(period)), Order::Descending)
+            .next()
+            .transpose()?
+            .map(|(_, user_info)| user_info);
+
+        match user_info {
+            Some(user_info) => Ok(Some(user_info)),
+            // votes from before the history was recorded are still applied, if they were not changed since
+            None if self
+                .user_info_history
+                .prefix(user)
+                .keys(storage, None, None, Order::Ascending)
+                .next()
+                .is_none() =>
+            {
+                Ok(self
+                    .user_info
+                    .may_load(storage, user)?
+                    .filter(|user_info| get_period(user_info.vote_ts).is_ok_and(|p| p < period)))
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// Cancels changes applied by previous votes and applies the new votes of the user for the
+    /// next period. The votes are also applied to the voting power delegated to the user, unless
+    /// the delegator voted themselves.
+    #[allow(clippy::too_many_arguments)]
+    pub fn vote(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        vote_ts: u64,
+        block_period: u64,
+        user: &'a Addr,
+        votes: Vec<(String, BasicPoints)>,
+        lock_info: LockInfoResponse,
+    ) -> StdResult<()> {
+        let user_info = self.user_info.may_load(storage, user)?.unwrap_or_default();
+        self.remove_votes_of_user(storage, &user_info, block_period)?;
+
+        let delegated = lock_info.delegated.clone();
+        self.apply_votes_of_user(
+            storage,
+            vote_ts,
+            block_period,
+            user,
+            votes.clone(),
+            lock_info.voting_power,
+            lock_info,
+            None,
+        )?;
+        self.apply_delegated_votes(api, storage, vote_ts, block_period, user, &votes, delegated)
+    }
+
+    /// Re-applies the votes of a user after a change of their lock, including the votes applied
+    /// to the voting power delegated to the user.
+    pub fn update_vote(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        vote_ts: u64,
+        block_period: u64,
+        user: &'a Addr,
+        lock: LockInfoResponse,
+    ) -> StdResult<VoteUpdate> {
+        let update = if let Some(user_info) = self.user_info.may_load(storage, user)? {
+            self.remove_votes_of_user(storage, &user_info, block_period)?;
+
+            if let Some(delegatee) = user_info.delegated_by {
+                self.update_delegated_vote(storage, vote_ts, block_period, user, delegatee, &lock)?
+            } else if lock.voting_power.is_zero() && lock.fixed_amount.is_zero() {
+                let user_info = UserInfo {
+                    vote_ts,
+                    voting_power: Uint128::zero(),
+                    slope: lock.slope,
+                    lock_end: lock.end,
+                    lock_ends: vec![],
+                    fixed_amount: lock.fixed_amount,
+                    votes: user_info.votes,
+                    delegated_by: None,
+                };
+                self.save_user_info(storage, user, block_period, &user_info)?;
+
+                VoteUpdate::Removed
+            } else {
+                let vamp = lock.voting_power + lock.fixed_amount;
+                self.apply_votes_of_user(
+                    storage,
+                    vote_ts,
+                    block_period,
+                    user,
+                    user_info.votes,
+                    lock.voting_power,
+                    lock.clone(),
+                    None,
+                )?;
+
+                VoteUpdate::Changed(vamp)
+            }
+        } else {
+            VoteUpdate::Noop
+        };
+
+        // the own votes of the user also apply to the voting power delegated to them
+        if let Some(user_info) =
+            self.user_info.may_load(storage, user)?.filter(|info| info.delegated_by.is_none())
+        {
+            self.apply_delegated_votes(
+                api,
+                storage,
+                vote_ts,
+                block_period,
+                user,
+                &user_info.votes,
+                lock.delegated,
+            )?;
+        }
+
+        Ok(update)
+    }
+
+    /// Removes the user and their votes. Returns `None` if the user never voted, otherwise the
+    /// result of removing the votes, which does not prevent the removal of the user.
+    pub fn remove_user(
+        &self,
+        storage: &mut dyn Storage,
+        block_period: u64,
+        user: &'a Addr,
+    ) -> StdResult<Option<StdResult<()>>> {
+        match self.user_info.may_load(storage, user)? {
+            Some(user_info) => {
+                self.remove_user_info(storage, user, block_period)?;
+                Ok(Some(self.remove_votes_of_user(storage, &user_info, block_period)))
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// Cancels the changes applied by the votes of a user from the next period.
+    pub fn remove_votes_of_user(
+        &self,
+        storage: &mut dyn Storage,
+        user_info: &UserInfo,
+        block_period: u64,
+    ) -> StdResult<()> {
+        let user_last_vote_period = get_period(user_info.vote_ts)?;
+        let lock_ends = user_info.decaying_ends();
+
+        // Cancel changes applied by previous votes
+        user_info.votes.iter().try_for_each(|(target_addr, bps)| {
+            self.remove_fixed_vamp(
+                storage,
+                block_period + 1,
+                target_addr,
+                *bps * user_info.fixed_amount,
+            )?;
+            lock_ends.iter().filter(|lock_end| lock_end.end > block_period).try_for_each(
+                |lock_end| {
+                    // Calculate voting power before changes
+                    let old_vp_at_period = calc_voting_power(
+                        lock_end.slope,
+                        lock_end.voting_power,
+                        user_last_vote_period,
+                        block_period,
+                    );
+                    self.cancel_user_changes(
+                        storage,
+                        block_period + 1,
+                        target_addr,
+                        *bps,
+                        old_vp_at_period,
+                        lock_end.slope,
+                        lock_end.end,
+                    )
+                },
+            )
+        })
+    }
+
+    /// Applies the votes of a user for the next period and saves the user's voting information.
+    #[allow(clippy::too_many_arguments)]
+    fn apply_votes_of_user(
+        &self,
+        storage: &mut dyn Storage,
+        vote_ts: u64,
+        block_period: u64,
+        user: &'a Addr,
+        votes: Vec<(String, BasicPoints)>,
+        user_vp: Uint128,
+        lock_info: LockInfoResponse,
+        delegated_by: Option<Addr>,
+    ) -> StdResult<()> {
+        // every end of the user's lock tokens decays on its own
+        let lock_ends = lock_info.decaying_ends();
+        votes.iter().try_for_each(|(target_addr, bps)| {
+            self.add_fixed_vamp(
+                storage,
+                block_period + 1,
+                target_addr,
+                *bps * lock_info.fixed_amount,
+            )?;
+            lock_ends.iter().try_for_each(|lock_end| {
+                self.vote_for_target(
+                    storage,
+                    block_period + 1,
+                    target_addr,
+                    *bps,
+                    lock_end.voting_power,
+                    lock_end.slope,
+                    lock_end.end,
+                )
+            })
+        })?;
+        let user_info = UserInfo {
+            vote_ts,
+            voting_power: user_vp,
+            slope: lock_info.slope,
+            lock_end: lock_info.end,
+            lock_ends,
+            fixed_amount: lock_info.fixed_amount,
+            votes,
+            delegated_by,
+        };
+        self.save_user_info(storage, user, block_period, &user_info)
+    }
+
+    /// Applies the votes of a delegatee to the voting power delegated to them. Delegators that voted
+    /// themselves keep their own votes. Delegated voting power is not delegated any further.
+    #[allow(clippy::too_many_arguments)]
+    fn apply_delegated_votes(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        vote_ts: u64,
+        block_period: u64,
+        delegatee: &Addr,
+        votes: &[(String, BasicPoints)],
+        delegated: Vec<DelegatedVotingPower>,
+    ) -> StdResult<()> {
+        for delegated in delegated {
+            let delegator = api.addr_validate(&delegated.delegator)?;
+
+            if let Some(delegator_info) = self.user_info.may_load(storage, &delegator)? {
+                if delegator_info.delegated_by.is_none() {
+                    continue;
+                }
+                self.remove_votes_of_user(storage, &delegator_info, block_period)?;
+            }
+
+            self.apply_votes_of_user(
+                storage,
+                vote_ts,
+                block_period,
+                &delegator,
+                votes.to_vec(),
+                delegated.lock_info.voting_power,
+                delegated.lock_info,
+                Some(delegatee.clone()),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Re-applies the votes of the delegatee with the delegated share of the user's voting power.
+    /// The votes are removed when the delegation to the delegatee ended.
+    fn update_delegated_vote(
+        &self,
+        storage: &mut dyn Storage,
+        vote_ts: u64,
+        block_period: u64,
+        user: &'a Addr,
+        delegatee: Addr,
+        lock: &LockInfoResponse,
+    ) -> StdResult<VoteUpdate> {
+        if let Some(delegation) =
+            lock.delegation.as_ref().filter(|delegation| delegation.delegatee == delegatee.as_str())
+        {
+            let delegatee_info = self
+                .user_info
+                .may_load(storage, &delegatee)?
+                .filter(|info| info.delegated_by.is_none());
+
+            if let Some(delegatee_info) = delegatee_info {
+                let share = lock.share(delegation.bps.try_into()?);
+                self.apply_votes_of_user(
+                    storage,
+                    vote_ts,
+                    block_period,
+                    user,
+                    delegatee_info.votes,
+                    share.voting_power,
+                    share,
+                    Some(delegatee),
+                )?;
+                return Ok(VoteUpdate::Delegated);
+            }
+        }
+
+        self.remove_user_info(storage, user, block_period)?;
+        Ok(VoteUpdate::Undelegated)
+    }
+
+    /// Cancels user changes using old voting parameters for a given target.
+    /// Firstly, it removes slope change scheduled for previous lockup end period.
+    /// Secondly, it updates voting parameters for the given period, but without user's vote.
+    #[allow(clippy::too_many_arguments)]
+    pub fn cancel_user_changes(
+        &self,
+        storage: &mut dyn Storage,
+        period: u64,
+        target_addr: &'a str,
+        old_bps: BasicPoints,
+        old_vp: Uint128,
+        old_slope: Uint128,
+        old_lock_end: u64,
+    ) -> StdResult<()> {
+        // Cancel scheduled slope changes
+        let last_target_period =
+            self.fetch_last_target_period(storage, period, target_addr)?.unwrap_or(period);
+        if last_target_period < old_lock_end + 1 {
+            let end_period_key = old_lock_end + 1;
+            let old_scheduled_change =
+                self.slope_changes.load(storage, (target_addr, end_period_key))?;
+            let new_slope = old_scheduled_change.saturating_sub(old_bps * old_slope);
+            if !new_slope.is_zero() {
+                self.slope_changes.save(storage, (target_addr, end_period_key), &new_slope)?
+            } else {
+                self.slope_changes.remove(storage, (target_addr, end_period_key))
+            }
+        }
+
+        self.update_target_info(
+            storage,
+            period,
+            target_addr,
+            Some((old_bps, old_vp, old_slope, Operation::Sub)),
+        )
+        .map(|_| ())
+    }
+
+    /// Applies user's vote for a given target.
+    /// Firstly, it schedules slope change for lockup end period.
+    /// Secondly, it updates voting parameters with applied user's vote.
+    #[allow(clippy::too_many_arguments)]
+    pub fn vote_for_target(
+        &self,
+        storage: &mut dyn Storage,
+        period: u64,
+        target_addr: &'a str,
+        bps: BasicPoints,
+        vp: Uint128,
+        slope: Uint128,
+        lock_end: u64,
+    ) -> StdResult<()> {
+        // Schedule slope changes
+        self.slope_changes.update::<_, StdError>(
+            storage,
+            (target_addr, lock_end + 1),
+            |slope_opt| {
+                if let Some(saved_slope) = slope_opt {
+                    Ok(saved_slope + bps * slope)
+                } else {
+                    Ok(bps * slope)
+                }
+            },
+        )?;
+        self.update_target_info(
+            storage,
+            period,
+            target_addr,
+            Some((bps, vp, slope, Operation::Add)),
+        )
+        .map(|_| ())
+    }
+
+    pub fn add_fixed_vamp(
+        &self,
+        storage: &mut dyn Storage,
+        period: u64,
+        target_addr: &'a str,
+        vamps: Uint128,
+    ) -> StdResult<()> {
+        self.add_target_to_active(storage, target_addr)?;
+
+        let last = self.fetch_last_fixed_vamp_value(storage, period, target_addr)?;
+        let new = last.checked_add(vamps)?;
+        self.fixed_vamp.save(storage, (target_addr, period), &new)?;
+
+        Ok(())
+    }
+
+    pub fn remove_fixed_vamp(
+        &self,
+        storage: &mut dyn Storage,
+        period: u64,
+        target_addr: &'a str,
+        vamps: Uint128,
+    ) -> StdResult<()> {
+        self.add_target_to_active(storage, target_addr)?;
+
+        // always change the future period only
+        let last = self.fetch_last_fixed_vamp_value(storage, period, target_addr)?;
+        let new = last.checked_sub(vamps).map_err(|_| {
+            StdError::generic_err("remove_fixed_vamp: could not sub last with current")
+        })?;
+        self.fixed_vamp.save(storage, (target_addr, period), &new)?;
+
+        Ok(())
+    }
+
+    /// Fetches voting parameters for a given target at specific period, applies new changes, saves it in storage
+    /// and returns new voting parameters in [`VotedInfo`] object.
+    /// If there are no changes in 'changes' parameter
+    /// and voting parameters were already calculated before the function just returns [`VotedInfo`].
+    pub fn update_target_info(
+        &self,
+        storage: &mut dyn Storage,
+        period: u64,
+        target_addr: &'a str,
+        changes: Option<(BasicPoints, Uint128, Uint128, Operation)>,
+    ) -> StdResult<VotedInfo> {
+        self.add_target_to_active(storage, target_addr)?;
+        let period_key = period;
+        let target_info = match self.get_target_info_mut(storage, period, target_addr)? {
+            VotedInfoResult::Unchanged(mut target_info) | VotedInfoResult::New(mut target_info)
+                if changes.is_some() =>
+            {
+                if let Some((bps, vp, slope, op)) = changes {
+                    target_info.slope = op.calc_slope(target_info.slope, slope, bps);
+                    target_info.voting_power =
+                        op.calc_voting_power(target_info.voting_power, vp, bps);
+                }
+                self.periods.save(storage, (target_addr, period_key), &())?;
+                self.votes.save(storage, (period_key, target_addr), &target_info)?;
+                target_info
+            },
+            VotedInfoResult::New(target_info) => {
+                self.periods.save(storage, (target_addr, period_key), &())?;
+                self.votes.save(storage, (period_key, target_addr), &target_info)?;
+                target_info
+            },
+            VotedInfoResult::Unchanged(target_info) => target_info,
+        };
+
+        Ok(target_info)
+    }
+
+    fn add_target_to_active(
+        &self,
+        storage: &mut dyn Storage,
+        target_addr: &'a str,
+    ) -> Result<(), StdError> {
+        if self.targets.may_load(storage, target_addr)?.is_none() {
+            self.targets.save(storage, target_addr, &())?
+        };
+        Ok(())
+    }
+
+    /// Returns target info at specified period or calculates it. Saves intermediate results in storage.
+    fn get_target_info_mut(
+        &self,
+        storage: &mut dyn Storage,
+        period: u64,
+        target_addr: &'a str,
+    ) -> StdResult<VotedInfoResult> {
+        let target_info_result =
+            if let Some(target_info) = self.votes.may_load(storage, (period, target_addr))? {
+                VotedInfoResult::Unchanged(target_info)
+            } else {
+                let target_info_result = if let Some(mut prev_period) =
+                    self.fetch_last_target_period(storage, period, target_addr)?
+                {
+                    let mut target_info = self.votes.load(storage, (prev_period, target_addr))?;
+                    // Recalculating passed periods
+                    let scheduled_slope_changes =
+                        self.fetch_slope_changes(storage, target_addr, prev_period, period)?;
+                    for (recalc_period, scheduled_change) in scheduled_slope_changes {
+                        target_info = VotedInfo {
+                            voting_power: calc_voting_power(
+                                target_info.slope,
+                                target_info.voting_power,
+                                prev_period,
+                                recalc_period,
+                            ),
+                            slope: target_info.slope.saturating_sub(scheduled_change),
+                        };
+                        // Save intermediate result
+                        let recalc_period_key = recalc_period;
+                        self.periods.save(storage, (target_addr, recalc_period_key), &())?;
+                        self.votes.save(storage, (recalc_period_key, target_addr), &target_info)?;
+                        prev_period = recalc_period
+                    }
+
+                    VotedInfo {
+                        voting_power: calc_voting_power(
+                            target_info.slope,
+                            target_info.voting_power,
+                            prev_period,
+                            period,
+                        ),
+                        ..target_info
+                    }
+                } else {
+                    VotedInfo::default()
+                };
+
+                VotedInfoResult::New(target_info_result)
+            };
+
+        Ok(target_info_result)
+    }
+
+    /// Returns the decaying voting parameters of a target at specified period or calculates them.
+    pub fn get_target_info(
+        &self,
+        storage: &dyn Storage,
+        period: u64,
+        target_addr: &'a str,
+    ) -> StdResult<VotedInfo> {
+        let target_info =
+            if let Some(target_info) = self.votes.may_load(storage, (period, target_addr))? {
+                target_info
+            } else if let Some(mut prev_period) =
+                self.fetch_last_target_period(storage, period, target_addr)?
+            {
+                let mut target_info = self.votes.load(storage, (prev_period, target_addr))?;
+                // Recalculating passed periods
+                let scheduled_slope_changes =
+                    self.fetch_slope_changes(storage, target_addr, prev_period, period)?;
+                for (recalc_period, scheduled_change) in scheduled_slope_changes {
+                    target_info = VotedInfo {
+                        voting_power: calc_voting_power(
+                            target_info.slope,
+                            target_info.voting_power,
+                            prev_period,
+                            recalc_period,
+                        ),
+                        slope: target_info.slope.saturating_sub(scheduled_change),
+                    };
+                    prev_period = recalc_period
+                }
+
+                VotedInfo {
+                    voting_power: calc_voting_power(
+                        target_info.slope,
+                        target_info.voting_power,
+                        prev_period,
+                        period,
+                    ),
+                    ..target_info
+                }
+            } else {
+                VotedInfo::default()
+            };
+
+        Ok(target_info)
+    }
+
+    /// Fetches last period for specified target which has saved result in `votes`.
+    pub fn fetch_last_target_period(
+        &self,
+        storage: &dyn Storage,
+        period: u64,
+        target_addr: &'a str,
+    ) -> StdResult<Option<u64>> {
+        let period_opt = self
+            .periods
+            .prefix(target_addr)
+            .range(storage, None, Some(Bound::exclusive(period)), Order::Descending)
+            .next()
+            .transpose()?
+            .map(|(period, _)| period);
+        Ok(period_opt)
+    }
+
+    pub fn fetch_last_fixed_vamp_value(
+        &self,
+        storage: &dyn Storage,
+        period: u64,
+        target_addr: &'a str,
+    ) -> StdResult<Uint128> {
+        let emps_opt = self
+            .fixed_vamp
+            .prefix(target_addr)
+            .range(storage, None, Some(Bound::inclusive(period)), Order::Descending)
+            .next()
+            .transpose()?
+            .map(|(_, emps)| emps);
+        Ok(emps_opt.unwrap_or_default())
+    }
+
+    /// Fetches all slope changes between `last_period` and `period` for specific target.
+    pub fn fetch_slope_changes(
+        &self,
+        storage: &dyn Storage,
+        target_addr: &'a str,
+        last_period: u64,
+        period: u64,
+    ) -> StdResult<Vec<(u64, Uint128)>> {
+        self.slope_changes
+            .prefix(target_addr)
+            .range(
+                storage,
+                Some(Bound::exclusive(last_period)),
+                Some(Bound::inclusive(period)),
+                Order::Ascending,
+            )
+            .collect()
+    }
+}
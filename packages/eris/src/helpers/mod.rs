@@ -1,2 +1,3 @@
 pub mod bps;
+pub mod gauge_votes;
 pub mod slope;
@@ -2,7 +2,7 @@ use std::{collections::HashSet, convert::TryInto};
 
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{
-    to_json_binary, Addr, Api, Coin, CosmosMsg, Decimal, Empty, QuerierWrapper, StdError,
+    to_json_binary, Addr, Api, Binary, Coin, CosmosMsg, Decimal, Empty, QuerierWrapper, StdError,
     StdResult, Uint128, VoteOption, WasmMsg,
 };
 use eris_chain_adapter::types::{
@@ -264,6 +264,14 @@ pub enum CallbackMsg {
     },
     ProvideLiquidity {},
     HalfSwapReward {},
+    /// Routes the coins received by claims, compared to the balances before claiming
+    RouteClaimed {
+        snapshots: Vec<Coin>,
+        /// cw20 token contracts with their balance before claiming
+        cw20_snapshots: Vec<(Addr, Uint128)>,
+    },
+    /// Updates the delegation goal after the gauges were tuned
+    TuneDelegations {},
 }
 
 impl CallbackMsg {
@@ -522,7 +530,20 @@ pub struct ExchangeRatesResponse {
 
 #[cw_serde]
 pub enum ClaimType {
+    /// Sends `{"claim":{}}` to the contract
     Default(String),
+    /// Claims from a merkle airdrop contract with the proof for the amount of the hub
+    MerkleAirdrop {
+        contract: String,
+        stage: u8,
+        amount: Uint128,
+        proof: Vec<String>,
+    },
+    /// Sends an arbitrary message to a contract of the claim whitelist
+    Custom {
+        contract: String,
+        msg: Binary,
+    },
 }
 
 pub type MigrateMsg = Empty;
@@ -1,6 +1,7 @@
 // pub mod asset;
 pub mod adapters;
 pub mod alliance_lst;
+pub mod amp_bribes;
 pub mod amp_gauges;
 pub mod arb_vault;
 pub mod astroport_farm;
@@ -12,6 +13,7 @@ pub mod governance_helper;
 pub mod helper;
 pub mod helpers;
 pub mod hub;
+pub mod lp_gauges;
 pub mod prop_gauges;
 pub mod querier;
 pub mod restake_gauges;
@@ -0,0 +1,194 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Decimal, QuerierWrapper, StdError, StdResult, Uint128};
+
+use crate::voting_escrow::LockInfoResponse;
+
+/// This structure describes the basic settings for creating a contract.
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// Contract owner
+    pub owner: String,
+    /// The vAMP token contract address
+    pub escrow_addr: String,
+    /// The LP targets (alliance-lst instances or pool addresses) that can be voted for
+    pub targets: Vec<String>,
+    /// Max number of targets that can receive a weight at the same time
+    pub targets_limit: u64,
+}
+
+/// This structure describes the execute messages available in the contract.
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Vote allows a vAMP holder to cast votes on which LP targets should receive the deposits
+    Vote {
+        votes: Vec<(String, u16)>,
+    },
+
+    /// Updates the vote for a specified user. Only can be called from the escrow_addr
+    UpdateVote {
+        user: String,
+        lock_info: LockInfoResponse,
+    },
+
+    /// TuneTargets transforms the latest vote distribution into weights of the LP targets
+    TuneTargets {},
+    UpdateConfig {
+        /// Changes the max amount of targets that can receive a weight at the same time
+        targets_limit: Option<u64>,
+    },
+    /// Adds or removes LP targets that can be voted for
+    UpdateTargets {
+        add: Option<Vec<String>>,
+        remove: Option<Vec<String>>,
+    },
+    // Admin action to remove a user
+    RemoveUser {
+        user: String,
+    },
+    /// ProposeNewOwner proposes a new owner for the contract
+    ProposeNewOwner {
+        /// Newly proposed contract owner
+        new_owner: String,
+        /// The timestamp when the contract ownership change expires
+        expires_in: u64,
+    },
+    /// DropOwnershipProposal removes the latest contract ownership transfer proposal
+    DropOwnershipProposal {},
+    /// ClaimOwnership allows the newly proposed owner to claim contract ownership
+    ClaimOwnership {},
+}
+
+/// This structure describes the query messages available in the contract.
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// UserInfo returns information about a voter and the LP targets they voted for
+    #[returns(UserInfoResponse)]
+    UserInfo {
+        user: String,
+    },
+    /// UserInfoAtPeriod returns the votes of a voter that were applied in a specific period
+    #[returns(UserInfoResponse)]
+    UserInfoAtPeriod {
+        user: String,
+        period: u64,
+    },
+    #[returns(UserInfosResponse)]
+    UserInfos {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// TuneInfo returns the weights of the LP targets of the latest tuning
+    #[returns(GaugeInfoResponse)]
+    TuneInfo {},
+    /// Config returns the contract configuration
+    #[returns(ConfigResponse)]
+    Config {},
+    /// TargetInfo returns the latest voting power allocated to a specific LP target
+    #[returns(VotedTargetInfoResponse)]
+    TargetInfo {
+        target_addr: String,
+    },
+    /// TargetInfoAtPeriod returns the voting power allocated to a specific LP target at a specific period
+    #[returns(VotedTargetInfoResponse)]
+    TargetInfoAtPeriod {
+        target_addr: String,
+        period: u64,
+    },
+    /// TargetInfos returns the voting power allocated to all active LP targets
+    #[returns(Vec<(String,VotedTargetInfoResponse)>)]
+    TargetInfos {
+        target_addrs: Option<Vec<String>>,
+        period: Option<u64>,
+    },
+}
+
+/// This structure describes a migration message.
+/// We currently take no arguments for migrations.
+#[cw_serde]
+pub struct MigrateMsg {}
+
+/// This structure describes the parameters returned when querying for the contract configuration.
+#[cw_serde]
+pub struct ConfigResponse {
+    /// Address that's allowed to change contract parameters
+    pub owner: Addr,
+    /// The vAMP token contract address
+    pub escrow_addr: Addr,
+    /// The LP targets that can be voted for
+    pub targets: Vec<Addr>,
+    /// Max number of targets that can receive a weight at the same time
+    pub targets_limit: u64,
+}
+
+impl ConfigResponse {
+    pub fn assert_owner(&self, addr: &Addr) -> StdResult<()> {
+        if *addr != self.owner {
+            return Err(StdError::generic_err("unauthorized"));
+        }
+        Ok(())
+    }
+}
+
+/// This structure describes the response used to return voting information for a specific LP target.
+#[cw_serde]
+#[derive(Default)]
+pub struct VotedTargetInfoResponse {
+    /// Dynamic voting power that voted for this target
+    pub voting_power: Uint128,
+    /// fixed amount available
+    pub fixed_amount: Uint128,
+    /// The slope at which the amount of vAMP that voted for this target will decay
+    pub slope: Uint128,
+}
+
+/// This structure describes the response used to return the weights of all LP targets.
+#[cw_serde]
+#[derive(Default)]
+pub struct GaugeInfoResponse {
+    /// Last timestamp when a tuning vote happened
+    pub tune_ts: u64,
+    /// The vAMP of the LP targets
+    pub target_points: Vec<(String, Uint128)>,
+    /// The share of the LP targets, used to split deposits across the targets
+    pub weights: Vec<(String, Decimal)>,
+}
+
+/// The struct describes a response used to return a staker's vAMP lock position.
+#[cw_serde]
+#[derive(Default)]
+pub struct UserInfoResponse {
+    /// Last timestamp when the user voted
+    pub vote_ts: u64,
+    /// The user's decreasing voting power
+    pub voting_power: Uint128,
+    /// The slope at which the user's voting power decays
+    pub slope: Uint128,
+    /// Timestamp when the user's lock expires
+    pub lock_end: u64,
+    /// The vote distribution for all the LP targets the staker picked
+    pub votes: Vec<(String, u16)>,
+    /// fixed amount available
+    pub fixed_amount: Uint128,
+    /// Current voting power at the current
+    pub current_power: Uint128,
+    /// The delegatee whose votes are applied to the delegated voting power of the user
+    #[serde(default)]
+    pub delegated_by: Option<Addr>,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct UserInfosResponse {
+    pub users: Vec<(Addr, UserInfoResponse)>,
+}
+
+/// Queries the weights of the LP targets of the latest tuning.
+pub fn get_lp_gauge_weights(
+    querier: &QuerierWrapper,
+    lp_gauges_addr: impl Into<String>,
+) -> StdResult<Vec<(String, Decimal)>> {
+    let gauge: GaugeInfoResponse =
+        querier.query_wasm_smart(lp_gauges_addr, &QueryMsg::TuneInfo {})?;
+    Ok(gauge.weights)
+}
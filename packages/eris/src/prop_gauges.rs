@@ -22,6 +22,10 @@ pub struct InstantiateMsg {
     pub quorum_bps: u16,
     /// Specifies wether voting should be weighted based on VP
     pub use_weighted_vote: bool,
+    /// Seconds before the chain voting end time when synced props end
+    pub sync_buffer_s: u64,
+    /// Alliance LST contract address, which votes the same as the hub when set
+    pub alliance_lst_addr: Option<String>,
 }
 
 /// This structure describes the execute messages available in the contract.
@@ -32,12 +36,24 @@ pub enum ExecuteMsg {
         end_time_s: u64,
     },
 
+    /// Initializes a prop from a chain proposal in voting period. The end time is the voting end
+    /// time of the proposal minus the sync buffer. Can be called by anyone.
+    SyncProp {
+        proposal_id: u64,
+    },
+
     /// Vote allows a vAMP holder to cast votes on which validators should get the delegations
     Vote {
         proposal_id: u64,
         vote: VoteOption,
     },
 
+    /// Sends the result of a proposal to the hub after its end time passed and the quorum was
+    /// reached. Can be called by anyone and repeated while the proposal is still open on chain.
+    ExecuteVote {
+        proposal_id: u64,
+    },
+
     /// Updates the vote for a specified user. Only can be called from the escrow_addr
     UpdateVote {
         user: String,
@@ -50,6 +66,12 @@ pub enum ExecuteMsg {
 
         /// Updates if weighted voting is used
         use_weighted_vote: Option<bool>,
+
+        /// Updates the seconds before the chain voting end time when synced props end
+        sync_buffer_s: Option<u64>,
+
+        /// Updates the alliance LST contract address that executed votes are also sent to
+        alliance_lst_addr: Option<String>,
     },
     // Admin action to remove a user
     RemoveUser {
@@ -138,6 +160,14 @@ pub struct ConfigResponse {
 
     #[serde(default)]
     pub use_weighted_vote: bool,
+
+    /// Seconds before the chain voting end time when synced props end
+    #[serde(default)]
+    pub sync_buffer_s: u64,
+
+    /// Alliance LST contract address, which votes the same as the hub when set
+    #[serde(default)]
+    pub alliance_lst_addr: Option<Addr>,
 }
 
 impl ConfigResponse {
@@ -304,6 +334,9 @@ pub struct PropUserInfo {
     pub user: Addr,
     pub current_vote: VoteOption,
     pub vp: Uint128,
+    /// The delegatee whose vote is applied to the delegated voting power of the user
+    #[serde(default)]
+    pub delegated_by: Option<Addr>,
 }
 
 fn default_addr() -> Addr {
@@ -1,9 +1,12 @@
+use crate::helpers::bps::BasicPoints;
 use crate::voting_escrow::QueryMsg::{LockInfo, TotalVamp, TotalVampAt, UserVamp, UserVampAt};
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Decimal, QuerierWrapper, StdResult, Uint128};
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, CosmosMsg, Decimal, QuerierWrapper, StdResult, Uint128, WasmMsg,
+};
 #[allow(unused_imports)]
 use cw20::{
-    BalanceResponse, Cw20ReceiveMsg, DownloadLogoResponse, Logo, MarketingInfoResponse,
+    BalanceResponse, Cw20ReceiveMsg, DownloadLogoResponse, Expiration, Logo, MarketingInfoResponse,
     TokenInfoResponse,
 };
 use std::fmt;
@@ -17,6 +20,9 @@ pub const DEFAULT_LIMIT: u32 = 10;
 
 pub const DEFAULT_PERIODS_LIMIT: u64 = 20;
 
+/// The maximum amount of active delegators per delegatee
+pub const MAX_DELEGATORS: usize = 30;
+
 /// This structure stores marketing information for voting escrow.
 #[cw_serde]
 pub struct UpdateMarketingInfo {
@@ -49,26 +55,38 @@ pub struct InstantiateMsg {
 #[cw_serde]
 pub enum ExecuteMsg {
     /// Extend the lockup time for your staked ampLP. For an expired lock, it will always start from the current period.
+    /// `token_id` is only required if you own multiple lock tokens.
     ExtendLockTime {
         time: u64,
+        token_id: Option<String>,
     },
 
     /// Create a vAMP position and lock ampLP for `time` amount of time
     CreateLock {
         time: u64,
     },
-    /// Deposit ampLP in another user's vAMP position
+    /// Deposit ampLP in another user's vAMP position. `token_id` is only required if the user owns
+    /// multiple lock tokens.
     DepositFor {
         user: String,
+        token_id: Option<String>,
     },
     /// Add more ampLP to your vAMP position
     ExtendLockAmount {
         /// Specify that the contract should extend the lock time to the min required periods
         extend_to_min_periods: Option<bool>,
+        token_id: Option<String>,
     },
 
-    /// Withdraw ampLP from the voting escrow contract
-    Withdraw {},
+    /// Withdraw ampLP of an expired lock token from the voting escrow contract
+    Withdraw {
+        token_id: Option<String>,
+    },
+    /// Withdraw ampLP from an active lock token. A penalty proportional to the remaining lock time
+    /// is sent to the penalty recipient.
+    EarlyWithdraw {
+        token_id: Option<String>,
+    },
     /// Propose a new owner for the contract
     ProposeNewOwner {
         new_owner: String,
@@ -100,11 +118,85 @@ pub enum ExecuteMsg {
         push_update_contracts: Option<Vec<String>>,
         // allows withdrawals of tokens.
         decommissioned: Option<bool>,
+        /// Penalty of an early withdrawal at the maximum lock time. Zero disables early withdrawals.
+        early_withdraw_penalty: Option<Decimal>,
+        /// Receiver of the early withdrawal penalties
+        penalty_recipient: Option<String>,
     },
     /// Set whitelisted logo urls
     SetLogoUrlsWhitelist {
         whitelist: Vec<String>,
     },
+    /// Delegate a share of your vAMP to another address. Replaces an existing delegation.
+    Delegate {
+        delegatee: String,
+        /// Share of the voting power to delegate in bps
+        bps: u16,
+        /// Timestamp when the delegation expires, rounded down to the start of its period
+        expires: u64,
+    },
+    /// Revoke your delegation
+    Undelegate {},
+    /// Remove an expired delegation, so that the push update contracts receive the change
+    ExpireDelegation {
+        delegator: String,
+    },
+    /// Transfer a lock token together with its voting power (cw721 compatible)
+    TransferNft {
+        recipient: String,
+        token_id: String,
+    },
+    /// Transfer a lock token to a contract and trigger an action on the receiving contract
+    /// (cw721 compatible)
+    SendNft {
+        contract: String,
+        token_id: String,
+        msg: Binary,
+    },
+    /// Allow the spender to transfer a lock token (cw721 compatible)
+    Approve {
+        spender: String,
+        token_id: String,
+        expires: Option<Expiration>,
+    },
+    /// Remove a previously granted approval (cw721 compatible)
+    Revoke {
+        spender: String,
+        token_id: String,
+    },
+    /// Allow the operator to transfer all of your lock tokens (cw721 compatible)
+    ApproveAll {
+        operator: String,
+        expires: Option<Expiration>,
+    },
+    /// Remove a previously granted operator (cw721 compatible)
+    RevokeAll {
+        operator: String,
+    },
+    /// Merge the lock token `token_id` into the lock token `into_token_id`. The merged lock token
+    /// ends with the later end of both.
+    Merge {
+        token_id: String,
+        into_token_id: String,
+    },
+    /// Split `amount` of ampLP from a lock token into a new lock token
+    Split {
+        token_id: String,
+        amount: Uint128,
+    },
+    /// Keep your lock token at the maximum lock time without any decay
+    LockPermanent {
+        token_id: Option<String>,
+    },
+    /// End the permanent lock. The voting power starts to decay over the maximum lock time.
+    UnlockPermanent {
+        token_id: Option<String>,
+    },
+    /// Convert the next `limit` locks into lock tokens after a migration. Other messages are
+    /// rejected until all locks are converted.
+    MigrateLocks {
+        limit: Option<u32>,
+    },
 }
 
 #[cw_serde]
@@ -212,6 +304,61 @@ pub enum QueryMsg {
     /// Return the vAMP contract configuration
     #[returns(ConfigResponse)]
     Config {},
+    /// Return the penalty and the returned amount of an early withdrawal of the user's lock token
+    #[returns(EarlyWithdrawResponse)]
+    SimulateEarlyWithdraw {
+        user: String,
+        token_id: Option<String>,
+    },
+    /// Return the active delegation of a user
+    #[returns(Option<DelegationResponse>)]
+    Delegation {
+        user: String,
+    },
+    /// Return the owner of a lock token (cw721 compatible)
+    #[returns(OwnerOfResponse)]
+    OwnerOf {
+        token_id: String,
+        include_expired: Option<bool>,
+    },
+    /// Return the approvals of a lock token (cw721 compatible)
+    #[returns(ApprovalsResponse)]
+    Approvals {
+        token_id: String,
+        include_expired: Option<bool>,
+    },
+    /// Return the operators of an owner (cw721 compatible)
+    #[returns(OperatorsResponse)]
+    AllOperators {
+        owner: String,
+        include_expired: Option<bool>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Return the lock of a lock token (cw721 compatible)
+    #[returns(NftInfoResponse)]
+    NftInfo {
+        token_id: String,
+    },
+    /// Return the lock tokens of an owner (cw721 compatible)
+    #[returns(TokensResponse)]
+    Tokens {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Return all lock tokens (cw721 compatible)
+    #[returns(TokensResponse)]
+    AllTokens {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Return the amount of lock tokens (cw721 compatible)
+    #[returns(NumTokensResponse)]
+    NumTokens {},
+    /// Return the name and symbol of the lock tokens (cw721 compatible)
+    #[returns(NftContractInfoResponse)]
+    ContractInfo {},
 }
 
 /// This structure is used to return a user's amount of vAMP.
@@ -239,6 +386,185 @@ pub struct LockInfoResponse {
     pub fixed_amount: Uint128,
     /// includes only decreasing voting_power, it is the current voting power of the period currently queried.
     pub voting_power: Uint128,
+    /// Permanent locks have their full voting power in `fixed_amount` and do not decay.
+    /// `end` is always the maximum lock time from the current period.
+    #[serde(default)]
+    pub permanent: bool,
+
+    /// The active delegation of the user's voting power. The fields above are not reduced by it.
+    #[serde(default)]
+    pub delegation: Option<DelegationResponse>,
+    /// The voting power delegated to the user, reduced to the delegated share
+    #[serde(default)]
+    pub delegated: Vec<DelegatedVotingPower>,
+    /// The decaying voting power of the lock tokens, grouped by their end. `voting_power` and
+    /// `slope` are the sums of all ends, `end` is the latest one.
+    #[serde(default)]
+    pub ends: Vec<LockEndResponse>,
+}
+
+impl LockInfoResponse {
+    /// Returns the voting power of the lock reduced to the given share, without delegations.
+    pub fn share(&self, bps: BasicPoints) -> LockInfoResponse {
+        LockInfoResponse {
+            amount: bps * self.amount,
+            coefficient: self.coefficient,
+            start: self.start,
+            end: self.end,
+            slope: bps * self.slope,
+            fixed_amount: bps * self.fixed_amount,
+            voting_power: bps * self.voting_power,
+            permanent: self.permanent,
+            delegation: None,
+            delegated: vec![],
+            ends: self
+                .ends
+                .iter()
+                .map(|end| LockEndResponse {
+                    end: end.end,
+                    voting_power: bps * end.voting_power,
+                    slope: bps * end.slope,
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the decaying voting power grouped by its end. Responses without `ends` have a
+    /// single end.
+    pub fn decaying_ends(&self) -> Vec<LockEndResponse> {
+        if !self.ends.is_empty() {
+            self.ends.clone()
+        } else if !self.voting_power.is_zero() {
+            vec![LockEndResponse {
+                end: self.end,
+                voting_power: self.voting_power,
+                slope: self.slope,
+            }]
+        } else {
+            vec![]
+        }
+    }
+}
+
+/// This structure describes the decaying voting power of the lock tokens ending in the same period.
+#[cw_serde]
+pub struct LockEndResponse {
+    /// The period when the voting power is fully decayed
+    pub end: u64,
+    /// The current voting power
+    pub voting_power: Uint128,
+    /// Slope at which the voting power decreases over time
+    pub slope: Uint128,
+}
+
+/// This structure describes a delegation of voting power.
+#[cw_serde]
+pub struct DelegationResponse {
+    /// The address receiving the voting power
+    pub delegatee: String,
+    /// Share of the voting power that is delegated in bps
+    pub bps: u16,
+    /// The first period in which the delegation is no longer active
+    pub expires: u64,
+}
+
+/// This structure describes the voting power delegated by a single delegator.
+#[cw_serde]
+pub struct DelegatedVotingPower {
+    /// The address delegating the voting power
+    pub delegator: String,
+    /// The delegated share of the delegator's lock
+    pub lock_info: LockInfoResponse,
+}
+
+/// This structure describes an approval of a lock token or an operator of an owner.
+#[cw_serde]
+pub struct Approval {
+    /// Address that can transfer the lock token
+    pub spender: String,
+    /// When the approval expires
+    pub expires: Expiration,
+}
+
+#[cw_serde]
+pub struct OwnerOfResponse {
+    /// Owner of the lock token
+    pub owner: String,
+    /// Addresses that can transfer the lock token
+    pub approvals: Vec<Approval>,
+}
+
+#[cw_serde]
+pub struct ApprovalsResponse {
+    pub approvals: Vec<Approval>,
+}
+
+#[cw_serde]
+pub struct OperatorsResponse {
+    pub operators: Vec<Approval>,
+}
+
+#[cw_serde]
+pub struct NftInfoResponse {
+    pub token_uri: Option<String>,
+    pub extension: LockTokenResponse,
+}
+
+/// This structure describes the lock position of a lock token.
+#[cw_serde]
+pub struct LockTokenResponse {
+    /// The amount of ampLP locked in the lock token
+    pub amount: Uint128,
+    /// The period when the lock token was created
+    pub start: u64,
+    /// The period when the lock token expires
+    pub end: u64,
+    /// Permanent lock tokens are always at the maximum lock time
+    pub permanent: bool,
+    /// The current decaying voting power of the lock token
+    pub voting_power: Uint128,
+    /// The fixed voting power of the lock token
+    pub fixed_amount: Uint128,
+}
+
+#[cw_serde]
+pub struct TokensResponse {
+    pub tokens: Vec<String>,
+}
+
+#[cw_serde]
+pub struct NumTokensResponse {
+    pub count: u64,
+}
+
+#[cw_serde]
+pub struct NftContractInfoResponse {
+    pub name: String,
+    pub symbol: String,
+}
+
+/// The message sent to the receiving contract of [`ExecuteMsg::SendNft`] (cw721 compatible).
+#[cw_serde]
+pub struct Cw721ReceiveMsg {
+    /// The address that sent the lock token
+    pub sender: String,
+    pub token_id: String,
+    pub msg: Binary,
+}
+
+impl Cw721ReceiveMsg {
+    pub fn into_cosmos_msg(&self, contract_addr: &Addr) -> StdResult<CosmosMsg> {
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            msg: to_json_binary(&Cw721ReceiverExecuteMsg::ReceiveNft(self.clone()))?,
+            funds: vec![],
+        }))
+    }
+}
+
+#[cw_serde]
+enum Cw721ReceiverExecuteMsg {
+    ReceiveNft(Cw721ReceiveMsg),
 }
 
 /// This structure stores the parameters returned when querying for a contract's configuration.
@@ -256,6 +582,19 @@ pub struct ConfigResponse {
     pub push_update_contracts: Vec<String>,
     /// specifies whether the lock is decomissioned
     pub decommissioned: bool,
+    /// Penalty of an early withdrawal at the maximum lock time, if early withdrawals are enabled
+    pub early_withdraw_penalty: Option<Decimal>,
+    /// Receiver of the early withdrawal penalties
+    pub penalty_recipient: Option<Addr>,
+}
+
+/// This structure describes the result of an early withdrawal.
+#[cw_serde]
+pub struct EarlyWithdrawResponse {
+    /// The amount of ampLP returned to the user
+    pub amount: Uint128,
+    /// The amount of ampLP sent to the penalty recipient
+    pub penalty: Uint128,
 }
 
 /// This structure describes a Migration message.